@@ -1,11 +1,11 @@
 use std::ops::{Deref, DerefMut};
 
 use crate::types::{
-    ChangeKind, FragmentNumber, Guid, InlineQos, InstanceHandle, SequenceNumber, SerializedData,
-    Timestamp,
+    ChangeKind, FragmentNumber, Guid, InlineQos, InstanceHandle, LifespanQosPolicy, SequenceNumber,
+    SerializedData, Timestamp,
 };
 
-use crate::subscription::SampleStateKind;
+use crate::subscription::{InstanceStateKind, SampleStateKind, ViewStateKind};
 
 #[derive(Debug, Default, Clone)]
 pub struct CacheChangeInfos {
@@ -94,6 +94,31 @@ impl CacheChange {
     pub fn get_reception_timestamp(&self) -> Timestamp {
         self.infos.reception_timestamp
     }
+
+    /// Whether the change only marks the end of a coherent set, an alive change without payload
+    pub fn is_coherent_set_end(&self) -> bool {
+        matches!(self.infos.kind, ChangeKind::Alive)
+            && self.data.is_none()
+            && self
+                .get_inline_qos()
+                .is_some_and(|qos| qos.coherent_set.is_some())
+    }
+
+    /// Time, in milliseconds, at which the change is no longer valid according to `lifespan`
+    ///
+    /// The lifespan starts at the source timestamp, the reception one is used when the source did not provide it
+    pub fn expiration_time(&self, lifespan: LifespanQosPolicy) -> Option<i64> {
+        if lifespan.is_infinite() {
+            return None;
+        }
+        let source_timestamp = self
+            .get_emission_timestamp()
+            .unwrap_or(self.get_reception_timestamp());
+        Some(
+            source_timestamp.to_datetime().timestamp_millis()
+                + lifespan.duration.to_datetime().timestamp_millis(),
+        )
+    }
 }
 
 impl From<FragmentedCacheChange> for CacheChange {
@@ -105,10 +130,34 @@ impl From<FragmentedCacheChange> for CacheChange {
     }
 }
 
+/// Position of a change in the life of its instance, see DDS 2.2.2.5.5
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GenerationInfos {
+    /// Number of times the instance went from NOT_ALIVE_DISPOSED to ALIVE before the change was received
+    pub disposed_generation_count: i32,
+    /// Number of times the instance went from NOT_ALIVE_NO_WRITERS to ALIVE before the change was received
+    pub no_writers_generation_count: i32,
+    /// Number of changes of the same instance following this one in the returned collection
+    pub sample_rank: i32,
+    /// Generation difference with the most recent change of the same instance in the returned collection
+    pub generation_rank: i32,
+    /// Generation difference with the most recent change of the same instance received
+    pub absolute_generation_rank: i32,
+}
+
+impl GenerationInfos {
+    pub fn generation(&self) -> i32 {
+        self.disposed_generation_count + self.no_writers_generation_count
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CacheChangeContainer {
     change: CacheChange,
     readed: SampleStateKind,
+    view_state: ViewStateKind,
+    instance_state: InstanceStateKind,
+    generation: GenerationInfos,
 }
 
 impl CacheChangeContainer {
@@ -116,6 +165,9 @@ impl CacheChangeContainer {
         Self {
             change,
             readed: SampleStateKind::NotRead,
+            view_state: ViewStateKind::New,
+            instance_state: InstanceStateKind::Alive,
+            generation: GenerationInfos::default(),
         }
     }
 
@@ -123,6 +175,33 @@ impl CacheChangeContainer {
         self.readed
     }
 
+    /// View state of the change's instance, as known when the change was last accessed
+    pub fn get_view_state_kind(&self) -> ViewStateKind {
+        self.view_state
+    }
+
+    /// Instance state of the change's instance, as known when the change was last accessed
+    pub fn get_instance_state_kind(&self) -> InstanceStateKind {
+        self.instance_state
+    }
+
+    pub fn set_instance_states(
+        &mut self,
+        view_state: ViewStateKind,
+        instance_state: InstanceStateKind,
+    ) {
+        self.view_state = view_state;
+        self.instance_state = instance_state;
+    }
+
+    pub fn get_generation_infos(&self) -> GenerationInfos {
+        self.generation
+    }
+
+    pub fn get_generation_infos_mut(&mut self) -> &mut GenerationInfos {
+        &mut self.generation
+    }
+
     pub fn mark_read(&mut self) {
         self.readed = SampleStateKind::Read;
     }
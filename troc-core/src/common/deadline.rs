@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::{Effect, Effects, TickId},
+    types::{DeadlineQosPolicy, InstanceHandle},
+};
+
+/// Missed deadlines count, as reported by the OFFERED_DEADLINE_MISSED and REQUESTED_DEADLINE_MISSED statuses
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DeadlineMissedStatus {
+    /// Cumulative number of missed deadlines
+    pub total_count: u32,
+    /// Number of missed deadlines since the last notification
+    pub total_count_change: u32,
+    /// Last instance for which a deadline was missed
+    pub last_instance_handle: InstanceHandle,
+}
+
+/// Track the last update of each alive instance and detect the ones not updated within the deadline period
+///
+/// A period of zero means the deadline is infinite, nothing is tracked then
+#[derive(Debug, Default)]
+pub struct DeadlineTracker {
+    period: Option<i64>,
+    last_updates: HashMap<InstanceHandle, i64>,
+    status: DeadlineMissedStatus,
+    /// Expiration of the pending check, a tick expiring before it was scheduled for a longer period and is ignored
+    next_check: Option<i64>,
+}
+
+impl DeadlineTracker {
+    pub fn new(deadline: DeadlineQosPolicy) -> Self {
+        let period = deadline.period.to_datetime().timestamp_millis();
+        Self {
+            period: (period > 0).then_some(period),
+            ..Default::default()
+        }
+    }
+
+    /// Change the period of the deadline at `now`, the tracked instances are kept
+    ///
+    /// A shorter period schedules a check at the first deadline it makes expire before the pending check, which is then
+    /// ignored
+    pub fn set_deadline(&mut self, effects: &mut Effects, deadline: DeadlineQosPolicy, now: i64) {
+        let period = deadline.period.to_datetime().timestamp_millis();
+        self.period = (period > 0).then_some(period);
+        let Some(period) = self.period else {
+            self.last_updates.clear();
+            return;
+        };
+
+        let Some(first_expiration) = self.last_updates.values().map(|last| last + period).min()
+        else {
+            return;
+        };
+        if self
+            .next_check
+            .is_some_and(|next_check| first_expiration < next_check)
+        {
+            self.schedule(effects, (first_expiration - now).max(1), now);
+        }
+    }
+
+    /// Record an update of `instance` at `now`, scheduling a deadline check if none is pending
+    pub fn update(&mut self, effects: &mut Effects, instance: InstanceHandle, now: i64) {
+        let Some(period) = self.period else {
+            return;
+        };
+
+        self.last_updates.insert(instance, now);
+        if self.next_check.is_none() {
+            self.schedule(effects, period, now);
+        }
+    }
+
+    /// Stop tracking `instance`, it is not expected to be updated anymore
+    pub fn forget(&mut self, instance: InstanceHandle) {
+        self.last_updates.remove(&instance);
+    }
+
+    /// Count every instance whose deadline expired at `now` and schedule the next check
+    ///
+    /// An [`Effect::DeadlineMissed`] is produced if at least one deadline was missed, the missed instances are returned
+    pub fn check(&mut self, effects: &mut Effects, now: i64) -> Vec<InstanceHandle> {
+        if self.next_check.is_some_and(|next_check| now < next_check) {
+            // superseded by the check of a shorter period
+            return Vec::new();
+        }
+        self.next_check = None;
+        let Some(period) = self.period else {
+            return Vec::new();
+        };
+
+        let mut missed = Vec::new();
+        for (instance, last_update) in self.last_updates.iter_mut() {
+            if now - *last_update >= period {
+                missed.push(*instance);
+                // the next period starts at the miss
+                *last_update = now;
+                self.status.total_count += 1;
+                self.status.total_count_change += 1;
+                self.status.last_instance_handle = *instance;
+            }
+        }
+
+        if self.status.total_count_change > 0 {
+            effects.push(Effect::DeadlineMissed {
+                status: self.status,
+            });
+            self.status.total_count_change = 0;
+        }
+
+        if let Some(next_expiration) = self.last_updates.values().map(|last| last + period).min() {
+            self.schedule(effects, (next_expiration - now).max(1), now);
+        }
+
+        missed
+    }
+
+    fn schedule(&mut self, effects: &mut Effects, delay: i64, now: i64) {
+        self.next_check = Some(now + delay);
+        effects.push(Effect::ScheduleTick {
+            id: TickId::Deadline,
+            delay,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use rstest::rstest;
+
+    use crate::{
+        common::{Effect, Effects, TickId},
+        types::{DeadlineQosPolicy, InstanceHandle, Timestamp},
+    };
+
+    use super::DeadlineTracker;
+
+    fn deadline(period_ms: i64) -> DeadlineQosPolicy {
+        DeadlineQosPolicy {
+            period: Timestamp::from_datetime(DateTime::from_timestamp_millis(period_ms).unwrap()),
+        }
+    }
+
+    #[rstest]
+    fn infinite_deadline_is_never_missed() {
+        let mut effects = Effects::new();
+        let mut tracker = DeadlineTracker::new(DeadlineQosPolicy::default());
+
+        tracker.update(&mut effects, InstanceHandle::default(), 0);
+        tracker.check(&mut effects, 10_000);
+
+        assert!(effects.is_empty());
+    }
+
+    #[rstest]
+    fn missed_deadline() {
+        let mut effects = Effects::new();
+        let mut tracker = DeadlineTracker::new(deadline(100));
+        let instance = InstanceHandle::new([1; 16]);
+
+        tracker.update(&mut effects, instance, 0);
+        assert!(matches!(
+            effects.pop(),
+            Some(Effect::ScheduleTick {
+                id: TickId::Deadline,
+                delay: 100
+            })
+        ));
+
+        // updated in time
+        tracker.update(&mut effects, instance, 50);
+        assert!(effects.is_empty());
+        tracker.check(&mut effects, 100);
+        assert!(matches!(
+            effects.pop(),
+            Some(Effect::ScheduleTick {
+                id: TickId::Deadline,
+                delay: 50
+            })
+        ));
+        assert!(effects.is_empty());
+
+        tracker.check(&mut effects, 150);
+        let _schedule = effects.pop();
+        let Some(Effect::DeadlineMissed { status }) = effects.pop() else {
+            panic!("deadline should have been missed");
+        };
+        assert_eq!(status.total_count, 1);
+        assert_eq!(status.total_count_change, 1);
+        assert_eq!(status.last_instance_handle, instance);
+
+        tracker.forget(instance);
+        tracker.check(&mut effects, 250);
+        assert!(effects.is_empty());
+    }
+
+    #[rstest]
+    fn single_tick_chain_after_deadline_change() {
+        let mut effects = Effects::new();
+        let mut tracker = DeadlineTracker::new(deadline(100));
+        let instance = InstanceHandle::new([1; 16]);
+
+        tracker.update(&mut effects, instance, 0);
+        assert_eq!(effects.len(), 1);
+        effects.clean();
+
+        // the tick scheduled before the change is still pending
+        tracker.set_deadline(&mut effects, DeadlineQosPolicy::default(), 5);
+        tracker.set_deadline(&mut effects, deadline(100), 5);
+        tracker.update(&mut effects, instance, 10);
+        assert!(effects.is_empty());
+
+        // the pending tick schedules the next one, the chain goes on
+        tracker.check(&mut effects, 100);
+        assert!(matches!(
+            effects.pop(),
+            Some(Effect::ScheduleTick {
+                id: TickId::Deadline,
+                delay: 10
+            })
+        ));
+        assert!(effects.is_empty());
+    }
+
+    #[rstest]
+    fn shorter_deadline_checked_at_once() {
+        let mut effects = Effects::new();
+        let mut tracker = DeadlineTracker::new(deadline(1_000));
+        let instance = InstanceHandle::new([1; 16]);
+
+        tracker.update(&mut effects, instance, 0);
+        effects.clean();
+
+        // the miss is reported at the end of the new period, not at the end of the pending one
+        tracker.set_deadline(&mut effects, deadline(100), 50);
+        assert!(matches!(
+            effects.pop(),
+            Some(Effect::ScheduleTick {
+                id: TickId::Deadline,
+                delay: 50
+            })
+        ));
+        tracker.check(&mut effects, 100);
+        let _schedule = effects.pop();
+        assert!(matches!(effects.pop(), Some(Effect::DeadlineMissed { .. })));
+
+        // a tick expiring before the pending check is a superseded one, it is ignored
+        tracker.check(&mut effects, 150);
+        assert!(effects.is_empty());
+
+        // a longer period waits for the pending check
+        tracker.set_deadline(&mut effects, deadline(1_000), 160);
+        assert!(effects.is_empty());
+    }
+}
@@ -1,4 +1,12 @@
-use crate::{ParticipantProxy, messages::Message, types::LocatorList};
+use crate::{
+    ParticipantProxy,
+    common::{
+        DeadlineMissedStatus, LivelinessChangedStatus, LivelinessLostStatus, SampleLostStatus,
+        SampleRejectedStatusKind,
+    },
+    messages::Message,
+    types::{GroupCoherentSet, GuidPrefix, LivelinessKind, LocatorList},
+};
 
 use crate::discovery::{DiscoveredReaderData, DiscoveredWriterData};
 
@@ -12,6 +20,12 @@ pub enum TickId {
     SubscriptionDetector,
     Reader,
     Writer,
+    Deadline,
+    Lifespan,
+    Liveliness,
+    ParticipantLiveliness,
+    ParticipantMessageWriter,
+    ParticipantMessageReader,
     #[default]
     Uknown,
 }
@@ -76,6 +90,10 @@ pub enum Effect {
     ParticipantMatch {
         participant_proxy: ParticipantProxy,
     },
+    /// A known remote Participant announced new discovery data
+    ParticipantUpdated {
+        participant_proxy: ParticipantProxy,
+    },
     ParticipantRemoved {
         participant_proxy: ParticipantProxy,
     },
@@ -89,8 +107,48 @@ pub enum Effect {
         local_writer_infos: DiscoveredWriterData,
         remote_reader_infos: DiscoveredReaderData,
     },
+    /// A matched remote Writer is no longer compatible with the local Reader
+    ReaderUnmatch {
+        local_reader_infos: DiscoveredReaderData,
+        remote_writer_infos: DiscoveredWriterData,
+    },
+    /// A matched remote Writer announced a new QoS, still compatible with the local Reader
+    ReaderMatchUpdated {
+        local_reader_infos: DiscoveredReaderData,
+        remote_writer_infos: DiscoveredWriterData,
+    },
+    /// A matched remote Reader is no longer compatible with the local Writer
+    WriterUnmatch {
+        local_writer_infos: DiscoveredWriterData,
+        remote_reader_infos: DiscoveredReaderData,
+    },
     ScheduleTick {
         id: TickId,
         delay: i64,
     },
+    DeadlineMissed {
+        status: DeadlineMissedStatus,
+    },
+    LivelinessLost {
+        status: LivelinessLostStatus,
+    },
+    LivelinessChanged {
+        status: LivelinessChangedStatus,
+    },
+    /// A received sample could not be kept because of the RESOURCE_LIMITS
+    SampleRejected {
+        reason: SampleRejectedStatusKind,
+    },
+    SampleLost {
+        status: SampleLostStatus,
+    },
+    /// A coherent set which is part of `group` is complete, it is presented once the group coherent set is
+    GroupCoherentSetComplete {
+        group: GroupCoherentSet,
+    },
+    /// A remote Participant asserted the liveliness of its Writers of kind `kind`
+    RemoteLivelinessAsserted {
+        participant_guid_prefix: GuidPrefix,
+        kind: LivelinessKind,
+    },
 }
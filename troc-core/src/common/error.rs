@@ -1,4 +1,7 @@
-use crate::types::Guid;
+use crate::{
+    DdsError,
+    types::{EntityId, Guid, InstanceHandle},
+};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -17,4 +20,26 @@ pub enum Error {
     RemoteWriterNotFound(Guid),
     #[error("The Writer is in BestEffort mode")]
     IsBestEffort,
+    #[error("The instance {0} has not been registered")]
+    UnknownInstance(InstanceHandle),
+    #[error("The history is full, the RESOURCE_LIMITS are reached")]
+    OutOfResources,
+    #[error("There is no coherent set in progress")]
+    NoCoherentSet,
+    #[error("The {0} policy cannot be changed once the entity is enabled")]
+    ImmutablePolicy(&'static str),
+    #[error("The local entity {0} is unknown")]
+    UnknownEntity(EntityId),
+}
+
+impl From<Error> for DdsError {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::OutOfResources => DdsError::OutOfResources,
+            Error::NoCoherentSet => DdsError::PreconditionNotMet,
+            Error::ImmutablePolicy(_) => DdsError::ImmutablePolicy,
+            Error::UnknownEntity(_) => DdsError::BadParameter,
+            e => DdsError::Error(e.to_string()),
+        }
+    }
 }
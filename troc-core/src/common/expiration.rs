@@ -0,0 +1,27 @@
+use crate::common::{Effect, Effects, TickId};
+
+/// Keep a single [`TickId::Lifespan`] tick pending, at the earliest known expiration time
+#[derive(Debug, Default)]
+pub struct ExpirationScheduler {
+    next_expiration: Option<i64>,
+}
+
+impl ExpirationScheduler {
+    /// Schedule a tick at `expiration` unless an earlier one is already pending
+    pub fn schedule(&mut self, effects: &mut Effects, now: i64, expiration: Option<i64>) {
+        let Some(expiration) = expiration else {
+            return;
+        };
+        if self
+            .next_expiration
+            .is_some_and(|next| next > now && next <= expiration)
+        {
+            return;
+        }
+        self.next_expiration = Some(expiration);
+        effects.push(Effect::ScheduleTick {
+            id: TickId::Lifespan,
+            delay: (expiration - now).max(1),
+        });
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::{Effect, Effects, TickId},
+    types::{Guid, GuidPrefix, InstanceHandle, LivelinessKind, LivelinessQosPolicy},
+};
+
+/// Times the Writer failed to assert its liveliness, as reported by the LIVELINESS_LOST status
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LivelinessLostStatus {
+    /// Cumulative number of times the liveliness was lost
+    pub total_count: u32,
+    /// Number of times the liveliness was lost since the last notification
+    pub total_count_change: u32,
+}
+
+/// Liveliness of the matched Writers, as reported by the LIVELINESS_CHANGED status
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LivelinessChangedStatus {
+    /// Number of matched Writers currently alive
+    pub alive_count: i32,
+    /// Number of matched Writers currently not alive
+    pub not_alive_count: i32,
+    /// Change of `alive_count` since the last notification
+    pub alive_count_change: i32,
+    /// Change of `not_alive_count` since the last notification
+    pub not_alive_count_change: i32,
+    /// Last Writer whose liveliness changed
+    pub last_publication_handle: InstanceHandle,
+}
+
+fn lease_of(liveliness: LivelinessQosPolicy) -> Option<i64> {
+    let lease = liveliness.lease_duration.to_datetime().timestamp_millis();
+    (lease > 0).then_some(lease)
+}
+
+/// Detect a local Writer failing to assert its liveliness within its lease duration
+///
+/// Only MANUAL_BY_PARTICIPANT and MANUAL_BY_TOPIC Writers can lose their liveliness, AUTOMATIC ones are asserted by the Participant itself
+#[derive(Debug, Default)]
+pub struct LivelinessLease {
+    kind: LivelinessKind,
+    lease: Option<i64>,
+    last_assertion: Option<i64>,
+    lost: bool,
+    status: LivelinessLostStatus,
+    tick_scheduled: bool,
+}
+
+impl LivelinessLease {
+    pub fn new(liveliness: LivelinessQosPolicy) -> Self {
+        let lease = match liveliness.kind {
+            LivelinessKind::Automatic => None,
+            _ => lease_of(liveliness),
+        };
+        Self {
+            kind: liveliness.kind,
+            lease,
+            ..Default::default()
+        }
+    }
+
+    pub fn get_kind(&self) -> LivelinessKind {
+        self.kind
+    }
+
+    /// Record a liveliness assertion at `now`, scheduling a lease check if none is pending
+    pub fn assert(&mut self, effects: &mut Effects, now: i64) {
+        let Some(lease) = self.lease else {
+            return;
+        };
+
+        self.last_assertion = Some(now);
+        self.lost = false;
+        if !self.tick_scheduled {
+            self.tick_scheduled = true;
+            effects.push(Effect::ScheduleTick {
+                id: TickId::Liveliness,
+                delay: lease,
+            });
+        }
+    }
+
+    /// Check whether the lease expired at `now`, an [`Effect::LivelinessLost`] is produced the first time it does
+    pub fn check(&mut self, effects: &mut Effects, now: i64) {
+        let (Some(lease), Some(last_assertion)) = (self.lease, self.last_assertion) else {
+            return;
+        };
+        self.tick_scheduled = false;
+
+        if self.lost {
+            return;
+        }
+
+        let expiration = last_assertion + lease;
+        if now >= expiration {
+            self.lost = true;
+            self.status.total_count += 1;
+            self.status.total_count_change += 1;
+            effects.push(Effect::LivelinessLost {
+                status: self.status,
+            });
+            self.status.total_count_change = 0;
+        } else {
+            self.tick_scheduled = true;
+            effects.push(Effect::ScheduleTick {
+                id: TickId::Liveliness,
+                delay: expiration - now,
+            });
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RemoteWriterLiveliness {
+    kind: LivelinessKind,
+    lease: Option<i64>,
+    last_assertion: i64,
+    alive: bool,
+}
+
+/// Track the lease of each matched remote Writer and report the ones becoming alive or not alive
+#[derive(Debug, Default)]
+pub struct LivelinessMonitor {
+    writers: HashMap<Guid, RemoteWriterLiveliness>,
+    status: LivelinessChangedStatus,
+    tick_scheduled: bool,
+}
+
+impl LivelinessMonitor {
+    /// Start tracking a newly matched Writer, it is considered alive from `now`
+    pub fn track(
+        &mut self,
+        effects: &mut Effects,
+        writer_guid: Guid,
+        liveliness: LivelinessQosPolicy,
+        now: i64,
+    ) {
+        let writer = RemoteWriterLiveliness {
+            kind: liveliness.kind,
+            lease: lease_of(liveliness),
+            last_assertion: now,
+            alive: true,
+        };
+
+        if let Some(previous) = self.writers.insert(writer_guid, writer) {
+            if previous.alive {
+                return;
+            }
+            self.status.not_alive_count -= 1;
+            self.status.not_alive_count_change -= 1;
+        }
+
+        self.status.alive_count += 1;
+        self.status.alive_count_change += 1;
+        self.notify(effects, writer_guid);
+        self.schedule(effects, now);
+    }
+
+    /// Stop tracking a Writer which is no longer matched, the counts change is reported with the next notification
+    pub fn untrack(&mut self, writer_guid: Guid) {
+        let Some(writer) = self.writers.remove(&writer_guid) else {
+            return;
+        };
+
+        if writer.alive {
+            self.status.alive_count -= 1;
+            self.status.alive_count_change -= 1;
+        } else {
+            self.status.not_alive_count -= 1;
+            self.status.not_alive_count_change -= 1;
+        }
+    }
+
+    /// Whether `writer_guid` is alive, a Writer which is not tracked is considered alive
+    pub fn is_alive(&self, writer_guid: Guid) -> bool {
+        self.writers
+            .get(&writer_guid)
+            .is_none_or(|writer| writer.alive)
+    }
+
+    /// Record a liveliness assertion of `writer_guid`, any message received from a Writer asserts it
+    pub fn assert_writer(&mut self, effects: &mut Effects, writer_guid: Guid, now: i64) {
+        let Some(writer) = self.writers.get_mut(&writer_guid) else {
+            return;
+        };
+
+        writer.last_assertion = now;
+        if !writer.alive {
+            writer.alive = true;
+            self.status.alive_count += 1;
+            self.status.alive_count_change += 1;
+            self.status.not_alive_count -= 1;
+            self.status.not_alive_count_change -= 1;
+            self.notify(effects, writer_guid);
+        }
+        self.schedule(effects, now);
+    }
+
+    /// Record a liveliness assertion of every Writer of the Participant `guid_prefix` whose kind is covered by `kind`
+    pub fn assert_participant(
+        &mut self,
+        effects: &mut Effects,
+        guid_prefix: GuidPrefix,
+        kind: LivelinessKind,
+        now: i64,
+    ) {
+        let asserted = self
+            .writers
+            .iter()
+            .filter(|(guid, writer)| {
+                guid.get_guid_prefix() == guid_prefix
+                    && writer.kind <= kind
+                    && writer.kind != LivelinessKind::ManualByTopic
+            })
+            .map(|(guid, _)| *guid)
+            .collect::<Vec<_>>();
+
+        for writer_guid in asserted {
+            self.assert_writer(effects, writer_guid, now);
+        }
+    }
+
+    /// Flag every Writer whose lease expired at `now` as not alive and schedule the next check
+    ///
+    /// An [`Effect::LivelinessChanged`] is produced if at least one Writer lost its liveliness
+    pub fn check(&mut self, effects: &mut Effects, now: i64) {
+        self.tick_scheduled = false;
+
+        let mut last_lost = None;
+        for (guid, writer) in self.writers.iter_mut() {
+            let Some(lease) = writer.lease else {
+                continue;
+            };
+            if writer.alive && now - writer.last_assertion >= lease {
+                writer.alive = false;
+                self.status.alive_count -= 1;
+                self.status.alive_count_change -= 1;
+                self.status.not_alive_count += 1;
+                self.status.not_alive_count_change += 1;
+                last_lost = Some(*guid);
+            }
+        }
+
+        if let Some(writer_guid) = last_lost {
+            self.notify(effects, writer_guid);
+        }
+
+        self.schedule(effects, now);
+    }
+
+    fn notify(&mut self, effects: &mut Effects, writer_guid: Guid) {
+        self.status.last_publication_handle = InstanceHandle::new(writer_guid.as_bytes());
+        effects.push(Effect::LivelinessChanged {
+            status: self.status,
+        });
+        self.status.alive_count_change = 0;
+        self.status.not_alive_count_change = 0;
+    }
+
+    fn schedule(&mut self, effects: &mut Effects, now: i64) {
+        if self.tick_scheduled {
+            return;
+        }
+
+        let next_expiration = self
+            .writers
+            .values()
+            .filter(|writer| writer.alive)
+            .filter_map(|writer| writer.lease.map(|lease| writer.last_assertion + lease))
+            .min();
+
+        if let Some(next_expiration) = next_expiration {
+            self.tick_scheduled = true;
+            effects.push(Effect::ScheduleTick {
+                id: TickId::Liveliness,
+                delay: (next_expiration - now).max(1),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use rstest::rstest;
+
+    use crate::{
+        common::{Effect, Effects, TickId},
+        types::{EntityId, Guid, GuidPrefix, LivelinessKind, LivelinessQosPolicy, Timestamp},
+    };
+
+    use super::{LivelinessLease, LivelinessMonitor};
+
+    fn liveliness(kind: LivelinessKind, lease_ms: i64) -> LivelinessQosPolicy {
+        LivelinessQosPolicy {
+            kind,
+            lease_duration: Timestamp::from_datetime(
+                DateTime::from_timestamp_millis(lease_ms).unwrap(),
+            ),
+        }
+    }
+
+    #[rstest]
+    fn automatic_liveliness_is_never_lost() {
+        let mut effects = Effects::new();
+        let mut lease = LivelinessLease::new(liveliness(LivelinessKind::Automatic, 100));
+
+        lease.assert(&mut effects, 0);
+        lease.check(&mut effects, 10_000);
+
+        assert!(effects.is_empty());
+    }
+
+    #[rstest]
+    fn liveliness_lost() {
+        let mut effects = Effects::new();
+        let mut lease = LivelinessLease::new(liveliness(LivelinessKind::ManualByTopic, 100));
+
+        lease.assert(&mut effects, 0);
+        assert!(matches!(
+            effects.pop(),
+            Some(Effect::ScheduleTick {
+                id: TickId::Liveliness,
+                delay: 100
+            })
+        ));
+
+        // asserted in time
+        lease.assert(&mut effects, 50);
+        assert!(effects.is_empty());
+        lease.check(&mut effects, 100);
+        assert!(matches!(
+            effects.pop(),
+            Some(Effect::ScheduleTick {
+                id: TickId::Liveliness,
+                delay: 50
+            })
+        ));
+
+        lease.check(&mut effects, 150);
+        let Some(Effect::LivelinessLost { status }) = effects.pop() else {
+            panic!("liveliness should have been lost");
+        };
+        assert_eq!(status.total_count, 1);
+        assert_eq!(status.total_count_change, 1);
+        assert!(effects.is_empty());
+
+        // lost only once until asserted again
+        lease.check(&mut effects, 300);
+        assert!(effects.is_empty());
+    }
+
+    #[rstest]
+    fn liveliness_changed() {
+        let mut effects = Effects::new();
+        let mut monitor = LivelinessMonitor::default();
+        let prefix = GuidPrefix([1; 12]);
+        let automatic_writer = Guid::new(prefix, EntityId::writer_with_key([0, 0, 1]));
+        let manual_writer = Guid::new(prefix, EntityId::writer_with_key([0, 0, 2]));
+
+        monitor.track(
+            &mut effects,
+            automatic_writer,
+            liveliness(LivelinessKind::Automatic, 100),
+            0,
+        );
+        let _schedule = effects.pop();
+        let Some(Effect::LivelinessChanged { status }) = effects.pop() else {
+            panic!("matched writer should be alive");
+        };
+        assert_eq!(status.alive_count, 1);
+        assert_eq!(status.alive_count_change, 1);
+
+        monitor.track(
+            &mut effects,
+            manual_writer,
+            liveliness(LivelinessKind::ManualByTopic, 100),
+            0,
+        );
+        effects.clean();
+
+        // only the AUTOMATIC writer is asserted by its participant
+        monitor.assert_participant(&mut effects, prefix, LivelinessKind::Automatic, 60);
+        assert!(effects.is_empty());
+        monitor.check(&mut effects, 100);
+        let _schedule = effects.pop();
+        let Some(Effect::LivelinessChanged { status }) = effects.pop() else {
+            panic!("manual writer should not be alive");
+        };
+        assert_eq!(status.alive_count, 1);
+        assert_eq!(status.not_alive_count, 1);
+        assert_eq!(status.alive_count_change, -1);
+        assert_eq!(status.not_alive_count_change, 1);
+
+        monitor.assert_writer(&mut effects, manual_writer, 120);
+        let Some(Effect::LivelinessChanged { status }) = effects.pop() else {
+            panic!("manual writer should be alive again");
+        };
+        assert_eq!(status.alive_count, 2);
+        assert_eq!(status.not_alive_count, 0);
+    }
+}
@@ -1,19 +1,35 @@
 mod cache_change;
 mod counter;
+mod deadline;
 mod effect;
 mod error;
+mod expiration;
+mod liveliness;
+mod ownership;
 mod qos_matcher;
 mod reader_proxy;
+mod resource_limits;
+mod time_based_filter;
 mod writer_proxy;
 
 pub use cache_change::{
-    CacheChange, CacheChangeContainer, CacheChangeInfos, FragmentedCacheChange,
+    CacheChange, CacheChangeContainer, CacheChangeInfos, FragmentedCacheChange, GenerationInfos,
 };
 pub use counter::Counter;
+pub use deadline::{DeadlineMissedStatus, DeadlineTracker};
 pub use effect::{Effect, EffectConsumption, Effects, TickId};
 pub use error::Error;
+pub use expiration::ExpirationScheduler;
+pub use liveliness::{
+    LivelinessChangedStatus, LivelinessLease, LivelinessLostStatus, LivelinessMonitor,
+};
+pub use ownership::OwnershipArbiter;
 pub use qos_matcher::QosPolicyConsistencyChecker;
 pub use reader_proxy::ReaderProxy;
+pub use resource_limits::{
+    SampleLostStatus, SampleRejectedStatusKind, check_history_limits, preallocated_changes,
+};
+pub use time_based_filter::TimeBasedFilter;
 pub use writer_proxy::WriterProxy;
 
 use crate::{messages::Message, types::LocatorList};
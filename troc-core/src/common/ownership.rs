@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use crate::types::{Guid, InstanceHandle, OwnershipQosPolicy, OwnershipStrengthQosPolicy};
+
+/// Decide, for each instance, which matched Writer owns it under EXCLUSIVE OWNERSHIP
+///
+/// The owner is the alive Writer with the highest strength, ties are resolved in favor of the lowest GUID.
+/// With SHARED OWNERSHIP every Writer is accepted.
+#[derive(Debug, Default)]
+pub struct OwnershipArbiter {
+    ownership: OwnershipQosPolicy,
+    strengths: HashMap<Guid, i32>,
+    owners: HashMap<InstanceHandle, Guid>,
+}
+
+impl OwnershipArbiter {
+    pub fn new(ownership: OwnershipQosPolicy) -> Self {
+        Self {
+            ownership,
+            ..Default::default()
+        }
+    }
+
+    /// Record the OWNERSHIP_STRENGTH of a newly matched Writer
+    pub fn track(&mut self, writer_guid: Guid, strength: OwnershipStrengthQosPolicy) {
+        self.strengths.insert(writer_guid, strength.value);
+    }
+
+    /// Forget a Writer which is no longer matched, the instances it owned have no owner anymore
+    pub fn untrack(&mut self, writer_guid: Guid) {
+        self.strengths.remove(&writer_guid);
+        self.owners.retain(|_, owner| *owner != writer_guid);
+    }
+
+    /// The owner of `instance` missed its deadline, any Writer can claim the instance again
+    pub fn release(&mut self, instance: InstanceHandle) {
+        self.owners.remove(&instance);
+    }
+
+    /// Whether a sample of `instance` from `writer_guid` must be delivered, claiming the instance if needed
+    ///
+    /// The current owner loses the instance to a stronger Writer, or to any Writer once `is_alive` reports it is no longer alive
+    pub fn accept(
+        &mut self,
+        instance: InstanceHandle,
+        writer_guid: Guid,
+        is_alive: impl Fn(Guid) -> bool,
+    ) -> bool {
+        if matches!(self.ownership, OwnershipQosPolicy::Shared) {
+            return true;
+        }
+
+        let claims = match self.owners.get(&instance) {
+            None => true,
+            Some(owner) if *owner == writer_guid => true,
+            Some(owner) if !is_alive(*owner) => true,
+            Some(owner) => {
+                let rank = |guid: &Guid| {
+                    (
+                        self.strengths.get(guid).copied().unwrap_or_default(),
+                        std::cmp::Reverse(*guid),
+                    )
+                };
+                rank(&writer_guid) > rank(owner)
+            }
+        };
+
+        if claims {
+            self.owners.insert(instance, writer_guid);
+        }
+        claims
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::types::{
+        EntityId, Guid, GuidPrefix, InstanceHandle, OwnershipQosPolicy, OwnershipStrengthQosPolicy,
+    };
+
+    use super::OwnershipArbiter;
+
+    #[rstest]
+    fn exclusive_ownership() {
+        let mut arbiter = OwnershipArbiter::new(OwnershipQosPolicy::Exclusive);
+        let instance = InstanceHandle::new([1; 16]);
+        let weak = Guid::new(GuidPrefix([1; 12]), EntityId::writer_with_key([0, 0, 1]));
+        let strong = Guid::new(GuidPrefix([2; 12]), EntityId::writer_with_key([0, 0, 1]));
+        arbiter.track(weak, OwnershipStrengthQosPolicy { value: 1 });
+        arbiter.track(strong, OwnershipStrengthQosPolicy { value: 10 });
+
+        assert!(arbiter.accept(instance, weak, |_| true));
+        // the stronger Writer takes over
+        assert!(arbiter.accept(instance, strong, |_| true));
+        assert!(!arbiter.accept(instance, weak, |_| true));
+
+        // fail over once the owner is not alive anymore
+        assert!(arbiter.accept(instance, weak, |guid| guid != strong));
+        assert!(arbiter.accept(instance, strong, |_| true));
+
+        // or once it missed its deadline
+        arbiter.release(instance);
+        assert!(arbiter.accept(instance, weak, |_| true));
+
+        arbiter.untrack(weak);
+        assert!(arbiter.accept(instance, strong, |_| true));
+    }
+}
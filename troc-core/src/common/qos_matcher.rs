@@ -1,4 +1,4 @@
-use crate::types::{DurabilityQosPolicy, InlineQos, LivelinessKind, ReliabilityQosPolicy};
+use crate::types::{InlineQos, LivelinessKind, ReliabilityQosPolicy};
 use tracing::{Level, event, instrument};
 
 use crate::DdsError;
@@ -15,6 +15,10 @@ impl QosPolicyConsistencyChecker {
         Self::check_deadline(writer_qos, reader_qos)?;
         Self::check_reliability(writer_qos, reader_qos)?;
         Self::check_liveness(writer_qos, reader_qos)?;
+        Self::check_ownership(writer_qos, reader_qos)?;
+        Self::check_partition(writer_qos, reader_qos)?;
+        Self::check_destination_order(writer_qos, reader_qos)?;
+        Self::check_presentation(writer_qos, reader_qos)?;
         Ok(())
     }
 
@@ -38,10 +42,11 @@ impl QosPolicyConsistencyChecker {
         let offered = writer_qos.durability;
         let requested = reader_qos.durability;
 
-        match (offered, requested) {
-            (DurabilityQosPolicy::Volatile, DurabilityQosPolicy::Volatile)
-            | (DurabilityQosPolicy::TransientLocal, DurabilityQosPolicy::Volatile) => Ok(()),
-            _ => Err(DdsError::InconsistentPolicy),
+        // the offered durability must be at least as persistent as the requested one
+        if offered >= requested {
+            Ok(())
+        } else {
+            Err(DdsError::InconsistentPolicy)
         }
     }
 
@@ -49,7 +54,7 @@ impl QosPolicyConsistencyChecker {
         let offered = writer_qos.deadline;
         let requested = reader_qos.deadline;
 
-        if offered <= requested {
+        if requested.is_infinite() || (!offered.is_infinite() && offered <= requested) {
             Ok(())
         } else {
             Err(DdsError::InconsistentPolicy)
@@ -84,4 +89,47 @@ impl QosPolicyConsistencyChecker {
             _ => Err(DdsError::InconsistentPolicy),
         }
     }
+
+    fn check_ownership(writer_qos: &InlineQos, reader_qos: &InlineQos) -> Result<(), DdsError> {
+        if writer_qos.ownership != reader_qos.ownership {
+            event!(Level::TRACE, "Ownership Qos doesn't match");
+            return Err(DdsError::InconsistentPolicy);
+        }
+
+        Ok(())
+    }
+
+    fn check_partition(writer_qos: &InlineQos, reader_qos: &InlineQos) -> Result<(), DdsError> {
+        if !writer_qos.partition.intersects(&reader_qos.partition) {
+            event!(Level::TRACE, "Partitions don't intersect");
+            return Err(DdsError::InconsistentPolicy);
+        }
+
+        Ok(())
+    }
+
+    fn check_destination_order(
+        writer_qos: &InlineQos,
+        reader_qos: &InlineQos,
+    ) -> Result<(), DdsError> {
+        let offered = writer_qos.destination_order;
+        let requested = reader_qos.destination_order;
+
+        // BY_SOURCE_TIMESTAMP offers at least what BY_RECEPTION_TIMESTAMP does
+        if offered >= requested {
+            Ok(())
+        } else {
+            event!(Level::TRACE, "DestinationOrder Qos doesn't match");
+            Err(DdsError::InconsistentPolicy)
+        }
+    }
+
+    fn check_presentation(writer_qos: &InlineQos, reader_qos: &InlineQos) -> Result<(), DdsError> {
+        if writer_qos.presentation.satisfies(&reader_qos.presentation) {
+            Ok(())
+        } else {
+            event!(Level::TRACE, "Presentation Qos doesn't match");
+            Err(DdsError::InconsistentPolicy)
+        }
+    }
 }
@@ -9,7 +9,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use crate::{
     messages::Message,
     types::{
-        Count, FragmentNumber, FragmentNumberSet, Locator,
+        Count, DurabilityQosPolicy, FragmentNumber, FragmentNumberSet, Locator,
         guid::{EntityId, Guid},
         locator_list::LocatorList,
         sequence_number::{SEQUENCENUMBER_INVALID, SEQUENCENUMBER_UNKNOWN, SequenceNumber},
@@ -34,6 +34,12 @@ pub struct ReaderProxy {
     #[serde(skip)]
     pub(crate) acknowledged_changes: BTreeSet<SequenceNumber>,
     pub(crate) is_active: bool,
+    /// The Reader acknowledges the changes it receives, a best-effort one is never waited for
+    #[serde(skip)]
+    pub(crate) is_reliable: bool,
+    /// A VOLATILE Reader only gets the changes written once it is matched
+    #[serde(skip)]
+    pub(crate) durability: DurabilityQosPolicy,
     #[serde(skip)]
     pub(crate) last_acknack_timestamp_ms: i64,
     #[serde(skip)]
@@ -62,6 +68,8 @@ impl ReaderProxy {
             requested_fragments: Default::default(),
             acknowledged_changes: Default::default(),
             is_active,
+            is_reliable: true,
+            durability: DurabilityQosPolicy::TransientLocal,
             last_acknack_timestamp_ms: Utc::now().timestamp_millis(),
             acknack_count: Count::default(),
             nackfrag_count: Count::default(),
@@ -86,6 +94,14 @@ impl ReaderProxy {
         self.expects_inline_qos
     }
 
+    pub fn is_reliable(&self) -> bool {
+        self.is_reliable
+    }
+
+    pub fn get_durability(&self) -> DurabilityQosPolicy {
+        self.durability
+    }
+
     pub fn can_send(&self) -> bool {
         true
     }
@@ -114,6 +130,14 @@ impl ReaderProxy {
         self.acknowledged_changes.insert(committed_seq_num);
     }
 
+    /// Highest sequence number up to which the Reader acknowledged every change
+    pub fn highest_acked_change(&self) -> SequenceNumber {
+        self.acknowledged_changes
+            .last()
+            .copied()
+            .unwrap_or(SEQUENCENUMBER_INVALID)
+    }
+
     pub fn get_acknowledged_changes(&self) -> BTreeSet<SequenceNumber> {
         self.acknowledged_changes.clone()
     }
@@ -222,6 +246,8 @@ impl Clone for ReaderProxy {
             requested_fragments: Default::default(),
             acknowledged_changes: Default::default(),
             is_active: self.is_active,
+            is_reliable: self.is_reliable,
+            durability: self.durability,
             last_acknack_timestamp_ms: Default::default(),
             acknack_count: Default::default(),
             nackfrag_count: Default::default(),
@@ -258,6 +284,8 @@ impl Debug for ReaderProxy {
             .field("requested_fragments", &self.requested_fragments)
             .field("acknowledged_changes", &self.acknowledged_changes)
             .field("is_active", &self.is_active)
+            .field("is_reliable", &self.is_reliable)
+            .field("durability", &self.durability)
             .finish()
     }
 }
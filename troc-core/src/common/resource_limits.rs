@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+
+use crate::types::InstanceHandle;
+
+/// Upper bound of the changes storage allocated upfront, the RESOURCE_LIMITS may be unlimited
+const PREALLOCATED_CHANGES: u32 = 100;
+
+/// Reason of a sample rejection, as reported by the SAMPLE_REJECTED status
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SampleRejectedStatusKind {
+    #[default]
+    NotRejected,
+    RejectedByInstancesLimit,
+    RejectedBySamplesLimit,
+    RejectedBySamplesPerInstanceLimit,
+}
+
+/// Samples the Reader never received, as reported by the SAMPLE_LOST status
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SampleLostStatus {
+    /// Cumulative number of samples lost
+    pub total_count: u32,
+    /// Number of samples lost since the last notification
+    pub total_count_change: u32,
+}
+
+/// Number of changes a history of the given KEEP_LAST `depth` or KEEP_ALL (`None`) allocates upfront
+pub fn preallocated_changes(depth: Option<u32>, max_samples: u32) -> usize {
+    depth.unwrap_or(max_samples).min(PREALLOCATED_CHANGES) as usize
+}
+
+/// Check a sample of `instance` can be added to a history holding samples of `held` instances, one item per sample,
+/// the oldest last
+///
+/// With KEEP_LAST, the oldest change is replaced once the `depth` is reached, it doesn't count then.
+/// Return whether the oldest change is replaced, the error tells which RESOURCE_LIMITS would be exceeded
+pub fn check_history_limits(
+    held: impl ExactSizeIterator<Item = InstanceHandle>,
+    instance: InstanceHandle,
+    depth: Option<u32>,
+    max_instances: u32,
+    max_samples_per_instance: u32,
+    max_samples: u32,
+) -> Result<bool, SampleRejectedStatusKind> {
+    let replaced = depth.is_some_and(|depth| held.len() == depth as usize);
+    let kept = held.len() - usize::from(replaced);
+    check_resource_limits(
+        held.take(kept),
+        instance,
+        max_instances,
+        max_samples_per_instance,
+        max_samples,
+    )?;
+    Ok(replaced)
+}
+
+/// Check a sample of `instance` can be added to a history holding samples of `held` instances, one item per sample
+///
+/// The error tells which RESOURCE_LIMITS would be exceeded
+fn check_resource_limits(
+    held: impl Iterator<Item = InstanceHandle>,
+    instance: InstanceHandle,
+    max_instances: u32,
+    max_samples_per_instance: u32,
+    max_samples: u32,
+) -> Result<(), SampleRejectedStatusKind> {
+    let mut samples = 0;
+    let mut instance_samples = 0;
+    let mut instances = HashSet::new();
+    for held_instance in held {
+        samples += 1;
+        if held_instance == instance {
+            instance_samples += 1;
+        }
+        instances.insert(held_instance);
+    }
+
+    if instance_samples == 0 && instances.len() as u32 >= max_instances {
+        Err(SampleRejectedStatusKind::RejectedByInstancesLimit)
+    } else if instance_samples >= max_samples_per_instance {
+        Err(SampleRejectedStatusKind::RejectedBySamplesPerInstanceLimit)
+    } else if samples >= max_samples {
+        Err(SampleRejectedStatusKind::RejectedBySamplesLimit)
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use crate::types::{InstanceHandle, TimeBasedFilterQosPolicy};
+
+/// Drop the samples of an instance received less than the minimum separation after the last accepted one
+///
+/// A minimum separation of zero, the default, accepts every sample
+#[derive(Debug, Default)]
+pub struct TimeBasedFilter {
+    minimum_separation: Option<i64>,
+    last_accepted: HashMap<InstanceHandle, i64>,
+    filtered_count: u32,
+}
+
+impl TimeBasedFilter {
+    pub fn new(time_based_filter: TimeBasedFilterQosPolicy) -> Self {
+        let minimum_separation = time_based_filter
+            .minimum_separation
+            .to_datetime()
+            .timestamp_millis();
+        Self {
+            minimum_separation: (minimum_separation > 0).then_some(minimum_separation),
+            ..Default::default()
+        }
+    }
+
+    /// Change the minimum separation, the last accepted samples are kept
+    pub fn set_minimum_separation(&mut self, time_based_filter: TimeBasedFilterQosPolicy) {
+        *self = Self {
+            last_accepted: std::mem::take(&mut self.last_accepted),
+            filtered_count: self.filtered_count,
+            ..Self::new(time_based_filter)
+        };
+    }
+
+    /// Whether a sample of `instance` received at `now` must be delivered, a filtered sample is counted
+    pub fn accept(&mut self, instance: InstanceHandle, now: i64) -> bool {
+        let Some(minimum_separation) = self.minimum_separation else {
+            return true;
+        };
+
+        match self.last_accepted.get(&instance) {
+            Some(last_accepted) if now - *last_accepted < minimum_separation => {
+                self.filtered_count += 1;
+                false
+            }
+            _ => {
+                self.last_accepted.insert(instance, now);
+                true
+            }
+        }
+    }
+
+    /// Stop filtering `instance`, its next sample is always accepted
+    pub fn forget(&mut self, instance: InstanceHandle) {
+        self.last_accepted.remove(&instance);
+    }
+
+    /// Number of samples dropped by the filter
+    pub fn filtered_count(&self) -> u32 {
+        self.filtered_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use rstest::rstest;
+
+    use crate::types::{InstanceHandle, TimeBasedFilterQosPolicy, Timestamp};
+
+    use super::TimeBasedFilter;
+
+    #[rstest]
+    fn minimum_separation_per_instance() {
+        let mut filter = TimeBasedFilter::new(TimeBasedFilterQosPolicy {
+            minimum_separation: Timestamp::from_datetime(
+                DateTime::from_timestamp_millis(100).unwrap(),
+            ),
+        });
+        let instance = InstanceHandle::new([1; 16]);
+        let other_instance = InstanceHandle::new([2; 16]);
+
+        assert!(filter.accept(instance, 0));
+        assert!(!filter.accept(instance, 50));
+        assert!(filter.accept(other_instance, 50));
+        assert!(filter.accept(instance, 100));
+        assert!(!filter.accept(instance, 199));
+
+        filter.forget(instance);
+        assert!(filter.accept(instance, 150));
+        assert_eq!(filter.filtered_count(), 2);
+    }
+
+    #[rstest]
+    fn no_minimum_separation_accepts_everything() {
+        let mut filter = TimeBasedFilter::new(TimeBasedFilterQosPolicy::default());
+
+        assert!(filter.accept(InstanceHandle::default(), 0));
+        assert!(filter.accept(InstanceHandle::default(), 0));
+        assert_eq!(filter.filtered_count(), 0);
+    }
+}
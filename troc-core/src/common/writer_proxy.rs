@@ -160,11 +160,14 @@ impl WriterProxy {
             .collect()
     }
 
+    /// Flag the changes below `first_available_seq_num` that were never received as not available
+    ///
+    /// Return the number of changes flagged
     pub fn lost_changes_update(
         &mut self,
         first_available_seq_num: SequenceNumber,
         changes_removed: bool,
-    ) {
+    ) -> usize {
         self.fill(first_available_seq_num);
         self.changes_from_writer_map
             .changes_from_writer
@@ -174,13 +177,14 @@ impl WriterProxy {
                     || e.status == ChangeFromWriterStatusKind::Missing
             })
             .filter(|(seq, _)| **seq < first_available_seq_num)
-            .for_each(|(_, e)| {
+            .map(|(_, e)| {
                 if changes_removed {
                     e.status = ChangeFromWriterStatusKind::NotAvailableRemoved
                 } else {
                     e.status = ChangeFromWriterStatusKind::NotAvailableUnspecified
                 }
             })
+            .count()
     }
 
     pub fn missing_changes(&self) -> Vec<SequenceNumber> {
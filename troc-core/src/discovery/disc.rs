@@ -14,8 +14,10 @@ use crate::{
         ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
         ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR, ENTITYID_SPDP_BUILTIN_PARTICIPANT_ANNOUNCER,
         ENTITYID_SPDP_BUILTIN_PARTICIPANT_DETECTOR, EntityId, Guid, GuidPrefix, InlineQos,
-        InstanceHandle, ParticipantProxy, PdpDiscoveredParticipantData, ReliabilityKind,
-        SerializedData, participant_builtin_topic_data::ParticipantBuiltinTopicData,
+        InstanceHandle, LivelinessKind, ParticipantProxy, PdpDiscoveredParticipantData,
+        ReliabilityKind, SerializedData, Timestamp,
+        participant_builtin_topic_data::ParticipantBuiltinTopicData,
+        participant_message_data::{ParticipantMessageData, ParticipantMessageKind},
     },
 };
 use binrw::Endian;
@@ -31,6 +33,9 @@ use crate::{
     subscription::SampleStateKind,
 };
 
+/// Period of the participant message Writer while no local Writer requires a shorter one
+const PARTICIPANT_MESSAGE_PERIOD: i64 = 2000;
+
 #[derive(Debug)]
 pub struct Announce {
     pub data: SerializedData,
@@ -44,6 +49,8 @@ pub struct DiscoveryConfiguration {
     pub lease_duration: Duration,
     pub metatraffic_unicast_locator_list: LocatorList,
     pub metatraffic_multicast_locator_list: LocatorList,
+    /// Locators the participant announcements are sent to, besides the multicast ones
+    pub initial_peers: LocatorList,
 }
 
 impl DiscoveryConfiguration {
@@ -59,6 +66,7 @@ impl Default for DiscoveryConfiguration {
             lease_duration: Duration::from_millis(30000),
             metatraffic_unicast_locator_list: LocatorList::default(),
             metatraffic_multicast_locator_list: LocatorList::default(),
+            initial_peers: LocatorList::default(),
         }
     }
 }
@@ -72,13 +80,19 @@ struct RemoteParticipantInfos {
 #[derive(Debug)]
 struct WriterMatchingInfos {
     disc_data: DiscoveredWriterData,
-    matches: HashSet<Guid>,
+    /// Discovery data of the matched remote Readers
+    matches: HashMap<Guid, DiscoveredReaderData>,
+    /// Remote Readers already reported as incompatible
+    mismatches: HashSet<Guid>,
 }
 
 #[derive(Debug)]
 struct ReaderMatchingInfos {
     disc_data: DiscoveredReaderData,
-    matches: HashSet<Guid>,
+    /// Discovery data advertised by the matched remote Writers
+    matches: HashMap<Guid, DiscoveredWriterData>,
+    /// Remote Writers already reported as incompatible
+    mismatches: HashSet<Guid>,
 }
 
 #[derive()]
@@ -123,10 +137,14 @@ impl DiscoveryBuilder {
             EntityId::default(),
             false,
             true,
-            config.metatraffic_unicast_locator_list.clone(),
+            config
+                .metatraffic_unicast_locator_list
+                .clone()
+                .merge(config.initial_peers.clone()),
             config.metatraffic_multicast_locator_list.clone(),
         );
-        pdp_announcer.add_proxy(pdp_announcer_proxy);
+        // the announcer has no history yet, matching its proxy produces no effect
+        pdp_announcer.add_proxy(&mut Effects::new(), pdp_announcer_proxy);
 
         let mut pdp_detector = ReaderBuilder::new(
             Guid::new(
@@ -190,6 +208,28 @@ impl DiscoveryBuilder {
         .with_tick_id(TickId::SubscriptionDetector)
         .build();
 
+        let p2p_msg_writer = WriterBuilder::new(
+            Guid::new(
+                participant_guid_prefix,
+                ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER,
+            ),
+            InlineQos::default(),
+        )
+        .reliability(ReliabilityKind::Reliable)
+        .with_tick_id(TickId::ParticipantMessageWriter)
+        .build();
+
+        let p2p_msg_reader = ReaderBuilder::new(
+            Guid::new(
+                participant_guid_prefix,
+                ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER,
+            ),
+            InlineQos::default(),
+        )
+        .reliability(ReliabilityKind::Reliable)
+        .with_tick_id(TickId::ParticipantMessageReader)
+        .build();
+
         Discovery {
             participant_guid_prefix,
             pdp_announcer,
@@ -198,6 +238,10 @@ impl DiscoveryBuilder {
             edp_pub_detector,
             edp_sub_announcer,
             edp_sub_detector,
+            p2p_msg_writer,
+            p2p_msg_reader,
+            automatic_liveliness_period: None,
+            participant_message_period: None,
             application_writers_infos: Default::default(),
             application_readers_infos: Default::default(),
             local_participant_infos: Default::default(),
@@ -217,6 +261,11 @@ pub struct Discovery {
     edp_pub_detector: Reader,
     edp_sub_announcer: Writer,
     edp_sub_detector: Reader,
+    p2p_msg_writer: Writer,
+    p2p_msg_reader: Reader,
+    automatic_liveliness_period: Option<i64>,
+    /// Period of the participant message Writer, shortened by the lease duration of the local Writers it asserts
+    participant_message_period: Option<i64>,
     application_writers_infos: HashMap<EntityId, WriterMatchingInfos>,
     application_readers_infos: HashMap<EntityId, ReaderMatchingInfos>,
     local_participant_infos: ParticipantProxy,
@@ -241,6 +290,10 @@ impl Discovery {
             delay: self.config.announcement_period,
             id: TickId::SubscriptionAnnouncer,
         });
+        effects.push(Effect::ScheduleTick {
+            delay: self.config.announcement_period,
+            id: TickId::ParticipantMessageWriter,
+        });
         Ok(())
     }
 
@@ -277,30 +330,68 @@ impl Discovery {
         let instance = InstanceHandle(key);
         let qos = writer_discovery_data.params.clone();
 
+        match qos.liveness.kind {
+            LivelinessKind::Automatic => {
+                self.update_automatic_liveliness_period(effects, qos.liveness.lease_duration);
+                self.update_participant_message_period(qos.liveness.lease_duration);
+            }
+            LivelinessKind::ManualByParticipant => {
+                self.update_participant_message_period(qos.liveness.lease_duration);
+            }
+            LivelinessKind::ManualByTopic => {}
+        }
+
         let change =
             self.edp_pub_announcer
                 .new_change(ChangeKind::Alive, Some(data), Some(qos), instance);
 
         self.edp_pub_announcer.add_change(effects, change).unwrap();
-        self.application_writers_infos.insert(
-            writer_entity_duid.get_entity_id(),
-            WriterMatchingInfos {
-                disc_data: writer_discovery_data,
-                matches: HashSet::default(),
-            },
-        );
+        // an announced Writer keeps its matches, they are re-evaluated against its new QoS
+        match self
+            .application_writers_infos
+            .entry(writer_entity_duid.get_entity_id())
+        {
+            Entry::Occupied(mut entry) => entry.get_mut().disc_data = writer_discovery_data,
+            Entry::Vacant(entry) => {
+                entry.insert(WriterMatchingInfos {
+                    disc_data: writer_discovery_data,
+                    matches: HashMap::default(),
+                    mismatches: HashSet::default(),
+                });
+            }
+        }
+        self.associate_writers(effects)?;
 
         event!(Level::DEBUG, "Writer discovery data produced");
 
         Ok(())
     }
 
+    /// Forget the local Writer `entity_id`, its disposal is announced so that the remote Readers unmatch it
     #[instrument(level = Level::TRACE, skip_all, fields())]
-    pub fn remove_publications_infos(&mut self, entity_id: EntityId) -> Result<&Effects, Error> {
-        // let _res = self
-        //     .edp_pub_announcer
-        //     .remove_change_for_instance(InstanceHandle::default());
-        unimplemented!()
+    pub fn remove_publications_infos(
+        &mut self,
+        effects: &mut Effects,
+        entity_id: EntityId,
+    ) -> Result<(), Error> {
+        let Some(writer_infos) = self.application_writers_infos.remove(&entity_id) else {
+            return Err(Error::UnknownEntity(entity_id));
+        };
+        let key = writer_infos
+            .disc_data
+            .proxy
+            .get_remote_writer_guid()
+            .as_bytes();
+        let instance = InstanceHandle(key);
+
+        self.edp_pub_announcer
+            .dispose_instance(effects, instance, None)?;
+        self.edp_pub_announcer
+            .unregister_instance(effects, instance, None)?;
+
+        event!(Level::DEBUG, "Writer discovery data disposed");
+
+        Ok(())
     }
 
     // TODO: when new local Reader is created we should try to associate it with remote Writer infos previously received
@@ -329,22 +420,72 @@ impl Discovery {
                 .new_change(ChangeKind::Alive, Some(data), Some(qos), instance);
 
         self.edp_sub_announcer.add_change(effects, change).unwrap();
-        self.application_readers_infos.insert(
-            reader_entity_guid.get_entity_id(),
-            ReaderMatchingInfos {
-                disc_data: reader_discovery_data,
-                matches: HashSet::default(),
-            },
-        );
+        // an announced Reader keeps its matches, they are re-evaluated against its new QoS
+        match self
+            .application_readers_infos
+            .entry(reader_entity_guid.get_entity_id())
+        {
+            Entry::Occupied(mut entry) => entry.get_mut().disc_data = reader_discovery_data,
+            Entry::Vacant(entry) => {
+                entry.insert(ReaderMatchingInfos {
+                    disc_data: reader_discovery_data,
+                    matches: HashMap::default(),
+                    mismatches: HashSet::default(),
+                });
+            }
+        }
+        self.associate_readers(effects)?;
 
         event!(Level::DEBUG, "Reader discovery data produced");
 
         Ok(())
     }
 
+    /// Forget the local Reader `entity_id`, its disposal is announced so that the remote Writers unmatch it
     #[instrument(level = Level::TRACE, skip_all, fields())]
-    pub fn remove_subscriptions_infos(&mut self, entity_id: EntityId) -> Result<&Effects, Error> {
-        unimplemented!()
+    pub fn remove_subscriptions_infos(
+        &mut self,
+        effects: &mut Effects,
+        entity_id: EntityId,
+    ) -> Result<(), Error> {
+        let Some(reader_infos) = self.application_readers_infos.remove(&entity_id) else {
+            return Err(Error::UnknownEntity(entity_id));
+        };
+        let key = reader_infos
+            .disc_data
+            .proxy
+            .get_remote_reader_guid()
+            .as_bytes();
+        let instance = InstanceHandle(key);
+
+        self.edp_sub_announcer
+            .dispose_instance(effects, instance, None)?;
+        self.edp_sub_announcer
+            .unregister_instance(effects, instance, None)?;
+
+        event!(Level::DEBUG, "Reader discovery data disposed");
+
+        Ok(())
+    }
+
+    /// Announce the disposal of the local Participant, the remote Participants forget it without waiting for its lease to expire
+    #[instrument(level = Level::TRACE, skip_all, fields())]
+    pub fn dispose_participant(&mut self, effects: &mut Effects) -> Result<(), Error> {
+        let key = self.local_participant_infos.get_guid().as_bytes();
+        let instance = InstanceHandle(key);
+
+        self.pdp_announcer
+            .dispose_instance(effects, instance, None)?;
+
+        event!(Level::DEBUG, "Participant disposal produced");
+
+        Ok(())
+    }
+
+    /// Assert the liveliness of every MANUAL_BY_PARTICIPANT Writer of this Participant to the remote Participants
+    #[instrument(level = Level::TRACE, skip_all, fields())]
+    pub fn assert_liveliness(&mut self, effects: &mut Effects) -> Result<(), Error> {
+        self.produce_participant_message(effects, ParticipantMessageKind::MANUAL_LIVELINESS_UPDATE)
     }
 
     #[instrument(level = Level::TRACE, skip_all, fields())]
@@ -365,7 +506,16 @@ impl Discovery {
                         break;
                     };
 
-                    let data = container.get_data().unwrap();
+                    // a disposed or unregistered Participant is forgotten without waiting for its lease to expire
+                    let Some(data) = container
+                        .get_data()
+                        .filter(|_| matches!(container.infos.kind, ChangeKind::Alive))
+                    else {
+                        let remote_participant_guid_prefix =
+                            Guid::from_bytes(container.get_instance_handle().0).get_guid_prefix();
+                        self.forget_participant(effects, remote_participant_guid_prefix);
+                        break;
+                    };
                     let pdp_participant_data =
                         PdpDiscoveredParticipantData::from_serialized_data(data.clone()).unwrap();
                     let lease_duration_ms: std::time::Duration =
@@ -389,7 +539,13 @@ impl Discovery {
                     {
                         Entry::Occupied(mut occupied_entry) => {
                             event!(Level::DEBUG, "Remote Participant discovery data updated");
+                            let participant_proxy = infos.infos.get_proxy();
+                            let is_changed =
+                                occupied_entry.get().infos.get_proxy() != participant_proxy;
                             *occupied_entry.get_mut() = infos;
+                            if is_changed {
+                                effects.push(Effect::ParticipantUpdated { participant_proxy });
+                            }
                         }
                         Entry::Vacant(vacant_entry) => {
                             event!(Level::DEBUG, "Remote Participant discovered");
@@ -401,7 +557,8 @@ impl Discovery {
                         }
                     }
 
-                    self.update_edp_endpoints(&remote_participant_proxy);
+                    self.update_edp_endpoints(effects, &remote_participant_proxy);
+                    self.update_p2p_endpoints(effects, &remote_participant_proxy);
                     break;
                 }
                 SubmessageContent::Heartbeat {
@@ -458,6 +615,29 @@ impl Discovery {
                         .unwrap();
                     break;
                 }
+                SubmessageContent::Data {
+                    writer_id: ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER,
+                    ..
+                }
+                | SubmessageContent::Heartbeat {
+                    writer_id: ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER,
+                    ..
+                } => {
+                    self.p2p_msg_reader
+                        .ingest(effects, now_ms, message)
+                        .unwrap();
+                    self.process_participant_messages(effects);
+                    break;
+                }
+                SubmessageContent::AckNack {
+                    reader_id: ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER,
+                    ..
+                } => {
+                    self.p2p_msg_writer
+                        .ingest(effects, now_ms, message)
+                        .unwrap();
+                    break;
+                }
                 _ => {
                     event!(Level::TRACE, "Unexpected submessage");
                     continue;
@@ -525,6 +705,39 @@ impl Discovery {
             TickId::SubscriptionDetector => {
                 self.edp_sub_detector.tick(effects, now_ms);
             }
+            TickId::ParticipantMessageWriter => {
+                self.p2p_msg_writer.tick(effects, now_ms);
+                effects.push(Effect::ScheduleTick {
+                    delay: self
+                        .participant_message_period
+                        .unwrap_or(PARTICIPANT_MESSAGE_PERIOD),
+                    id: TickId::ParticipantMessageWriter,
+                });
+            }
+            TickId::ParticipantMessageReader => {
+                self.p2p_msg_reader.tick(effects, now_ms);
+            }
+            TickId::Lifespan => {
+                // the expiring change may belong to any of the builtin endpoints
+                self.edp_pub_announcer.lifespan_tick(effects, now_ms);
+                self.edp_pub_detector.lifespan_tick(effects, now_ms);
+                self.edp_sub_announcer.lifespan_tick(effects, now_ms);
+                self.edp_sub_detector.lifespan_tick(effects, now_ms);
+                self.p2p_msg_writer.lifespan_tick(effects, now_ms);
+                self.p2p_msg_reader.lifespan_tick(effects, now_ms);
+            }
+            TickId::ParticipantLiveliness => {
+                self.produce_participant_message(
+                    effects,
+                    ParticipantMessageKind::AUTOMATIC_LIVELINESS_UPDATE,
+                )?;
+                if let Some(period) = self.automatic_liveliness_period {
+                    effects.push(Effect::ScheduleTick {
+                        delay: period,
+                        id: TickId::ParticipantLiveliness,
+                    });
+                }
+            }
             _ => unreachable!(),
         }
 
@@ -563,6 +776,90 @@ impl Discovery {
         Ok(())
     }
 
+    fn produce_participant_message(
+        &mut self,
+        effects: &mut Effects,
+        kind: ParticipantMessageKind,
+    ) -> Result<(), Error> {
+        let participant_message = ParticipantMessageData::new(self.participant_guid_prefix, kind);
+        let data = participant_message.into_serialized_data(Endian::Big)?;
+        let instance = participant_message.get_instance();
+
+        let change = self.p2p_msg_writer.new_change(
+            ChangeKind::Alive,
+            Some(data),
+            Some(InlineQos::default()),
+            instance,
+        );
+
+        self.p2p_msg_writer.add_change(effects, change)?;
+
+        event!(Level::DEBUG, kind = ?kind, "Participant message produced");
+
+        Ok(())
+    }
+
+    fn process_participant_messages(&mut self, effects: &mut Effects) {
+        for change in self.p2p_msg_reader.take_all_available_changes() {
+            let Some(data) = change.get_data() else {
+                continue;
+            };
+
+            let Ok(participant_message) =
+                ParticipantMessageData::from_serialized_data(data.clone())
+            else {
+                event!(Level::ERROR, "Deserialization error");
+                continue;
+            };
+
+            let Some(kind) = participant_message.kind.liveliness_kind() else {
+                event!(Level::TRACE, "Participant message of unknown kind ignored");
+                continue;
+            };
+
+            effects.push(Effect::RemoteLivelinessAsserted {
+                participant_guid_prefix: participant_message.participant_guid_prefix,
+                kind,
+            });
+        }
+    }
+
+    /// AUTOMATIC liveliness is asserted three times per lease duration of the most demanding local Writer
+    fn update_automatic_liveliness_period(
+        &mut self,
+        effects: &mut Effects,
+        lease_duration: Timestamp,
+    ) {
+        let Some(period) = liveliness_period(lease_duration) else {
+            return;
+        };
+
+        match self.automatic_liveliness_period {
+            Some(current) if current <= period => {}
+            Some(_) => self.automatic_liveliness_period = Some(period),
+            None => {
+                self.automatic_liveliness_period = Some(period);
+                effects.push(Effect::ScheduleTick {
+                    delay: period,
+                    id: TickId::ParticipantLiveliness,
+                });
+            }
+        }
+    }
+
+    /// The participant messages are repaired three times per lease duration of the most demanding
+    /// AUTOMATIC or MANUAL_BY_PARTICIPANT local Writer
+    fn update_participant_message_period(&mut self, lease_duration: Timestamp) {
+        let Some(period) = liveliness_period(lease_duration) else {
+            return;
+        };
+
+        let current = self
+            .participant_message_period
+            .unwrap_or(PARTICIPANT_MESSAGE_PERIOD);
+        self.participant_message_period = Some(current.min(period));
+    }
+
     fn remove_participant(&mut self, effects: &mut Effects, now_ms: i64) -> Result<(), Error> {
         let stale_participants = self
             .remote_participants_infos
@@ -572,35 +869,104 @@ impl Discovery {
             .collect::<Vec<_>>();
 
         for participant_guid_prefix in stale_participants {
-            let old = self
-                .remote_participants_infos
-                .remove(&participant_guid_prefix);
-            assert!(old.is_some());
+            self.forget_participant(effects, participant_guid_prefix);
+        }
 
-            self.edp_pub_announcer.remove_proxy(Guid::new(
-                participant_guid_prefix,
-                ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
-            ));
-            self.edp_sub_announcer.remove_proxy(Guid::new(
-                participant_guid_prefix,
-                ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR,
-            ));
-            self.edp_pub_detector.remove_proxy(Guid::new(
-                participant_guid_prefix,
-                ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
-            ));
-            self.edp_sub_detector.remove_proxy(Guid::new(
-                participant_guid_prefix,
-                ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
-            ));
+        Ok(())
+    }
 
-            let effect = Effect::ParticipantRemoved {
-                participant_proxy: old.unwrap().infos.get_proxy(),
-            };
-            effects.push(effect);
+    /// Forget the remote Participant `participant_guid_prefix` along with its builtin endpoints and the matches of its endpoints
+    fn forget_participant(&mut self, effects: &mut Effects, participant_guid_prefix: GuidPrefix) {
+        let Some(old) = self
+            .remote_participants_infos
+            .remove(&participant_guid_prefix)
+        else {
+            return;
+        };
+
+        self.edp_pub_announcer.remove_proxy(Guid::new(
+            participant_guid_prefix,
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
+        ));
+        self.edp_sub_announcer.remove_proxy(Guid::new(
+            participant_guid_prefix,
+            ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR,
+        ));
+        self.edp_pub_detector.remove_proxy(Guid::new(
+            participant_guid_prefix,
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+        ));
+        self.edp_sub_detector.remove_proxy(Guid::new(
+            participant_guid_prefix,
+            ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
+        ));
+        self.p2p_msg_writer.remove_proxy(Guid::new(
+            participant_guid_prefix,
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER,
+        ));
+        self.p2p_msg_reader.remove_proxy(Guid::new(
+            participant_guid_prefix,
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER,
+        ));
+
+        for reader_match_infos in self.application_readers_infos.values_mut() {
+            let remote_writers = reader_match_infos
+                .matches
+                .keys()
+                .filter(|guid| guid.get_guid_prefix() == participant_guid_prefix)
+                .copied()
+                .collect::<Vec<_>>();
+            for remote_writer_guid in remote_writers {
+                Self::unmatch_remote_writer(effects, reader_match_infos, remote_writer_guid);
+            }
+        }
+        for writer_match_infos in self.application_writers_infos.values_mut() {
+            let remote_readers = writer_match_infos
+                .matches
+                .keys()
+                .filter(|guid| guid.get_guid_prefix() == participant_guid_prefix)
+                .copied()
+                .collect::<Vec<_>>();
+            for remote_reader_guid in remote_readers {
+                Self::unmatch_remote_reader(effects, writer_match_infos, remote_reader_guid);
+            }
         }
 
-        Ok(())
+        event!(Level::DEBUG, "Remote Participant removed");
+        let effect = Effect::ParticipantRemoved {
+            participant_proxy: old.infos.get_proxy(),
+        };
+        effects.push(effect);
+    }
+
+    /// The remote Writer `remote_writer_guid` is gone, the local Reader stops matching it
+    fn unmatch_remote_writer(
+        effects: &mut Effects,
+        reader_match_infos: &mut ReaderMatchingInfos,
+        remote_writer_guid: Guid,
+    ) {
+        reader_match_infos.mismatches.remove(&remote_writer_guid);
+        if let Some(remote_writer_infos) = reader_match_infos.matches.remove(&remote_writer_guid) {
+            effects.push(Effect::ReaderUnmatch {
+                local_reader_infos: reader_match_infos.disc_data.clone(),
+                remote_writer_infos,
+            });
+        }
+    }
+
+    /// The remote Reader `remote_reader_guid` is gone, the local Writer stops matching it
+    fn unmatch_remote_reader(
+        effects: &mut Effects,
+        writer_match_infos: &mut WriterMatchingInfos,
+        remote_reader_guid: Guid,
+    ) {
+        writer_match_infos.mismatches.remove(&remote_reader_guid);
+        if let Some(remote_reader_infos) = writer_match_infos.matches.remove(&remote_reader_guid) {
+            effects.push(Effect::WriterUnmatch {
+                local_writer_infos: writer_match_infos.disc_data.clone(),
+                remote_reader_infos,
+            });
+        }
     }
 
     // TODO: when a match occurs, discovery must creates SenderWire(s) and other structures to setup the local endpoint
@@ -611,8 +977,16 @@ impl Discovery {
             .get_all_available_changes(SampleStateKind::Any);
 
         for potential_match in potential_matches {
-            let Some(data) = potential_match.get_data() else {
-                unreachable!("CacheChange Data submessage presence checked at ingestion")
+            // the remote Writer has been deleted, its key is the only thing left
+            let Some(data) = potential_match
+                .get_data()
+                .filter(|_| matches!(potential_match.infos.kind, ChangeKind::Alive))
+            else {
+                let remote_writer_guid = Guid::from_bytes(potential_match.get_instance_handle().0);
+                for reader_match_infos in self.application_readers_infos.values_mut() {
+                    Self::unmatch_remote_writer(effects, reader_match_infos, remote_writer_guid);
+                }
+                continue;
             };
 
             let Ok(disc_writer_data) = DiscoveredWriterData::from_serialized_data(data.clone())
@@ -624,29 +998,60 @@ impl Discovery {
             let remote_writer_guid = disc_writer_data.proxy.get_remote_writer_guid();
 
             for (_id, reader_match_infos) in self.application_readers_infos.iter_mut() {
-                if reader_match_infos.matches.contains(&remote_writer_guid) {
-                    continue;
-                }
-
-                if let Err(_e) = QosPolicyConsistencyChecker::check(
+                // the QoS of both sides can change, every pair is evaluated again
+                let is_compatible = QosPolicyConsistencyChecker::check(
                     &disc_writer_data.params,
                     &reader_match_infos.disc_data.params,
-                ) {
-                    let effect = Effect::ReaderMatch {
-                        success: false,
-                        local_reader_infos: reader_match_infos.disc_data.clone(),
-                        remote_writer_infos: disc_writer_data.clone(),
-                    };
-                    effects.push(effect);
+                )
+                .is_ok();
+                let is_matched = reader_match_infos.matches.contains_key(&remote_writer_guid);
+
+                match (is_compatible, is_matched) {
+                    (true, false) => {
+                        let effect = Effect::ReaderMatch {
+                            success: true,
+                            local_reader_infos: reader_match_infos.disc_data.clone(),
+                            remote_writer_infos: disc_writer_data.clone(),
+                        };
+                        effects.push(effect);
+                        reader_match_infos
+                            .matches
+                            .insert(remote_writer_guid, disc_writer_data.clone());
+                        reader_match_infos.mismatches.remove(&remote_writer_guid);
+                    }
+                    (false, true) => {
+                        let effect = Effect::ReaderUnmatch {
+                            local_reader_infos: reader_match_infos.disc_data.clone(),
+                            remote_writer_infos: disc_writer_data.clone(),
+                        };
+                        effects.push(effect);
+                        reader_match_infos.matches.remove(&remote_writer_guid);
+                    }
+                    (false, false) => {
+                        if reader_match_infos.mismatches.insert(remote_writer_guid) {
+                            let effect = Effect::ReaderMatch {
+                                success: false,
+                                local_reader_infos: reader_match_infos.disc_data.clone(),
+                                remote_writer_infos: disc_writer_data.clone(),
+                            };
+                            effects.push(effect);
+                        }
+                    }
+                    (true, true) => {
+                        let remote_writer_infos = reader_match_infos
+                            .matches
+                            .get_mut(&remote_writer_guid)
+                            .unwrap();
+                        if remote_writer_infos.params != disc_writer_data.params {
+                            *remote_writer_infos = disc_writer_data.clone();
+                            let effect = Effect::ReaderMatchUpdated {
+                                local_reader_infos: reader_match_infos.disc_data.clone(),
+                                remote_writer_infos: disc_writer_data.clone(),
+                            };
+                            effects.push(effect);
+                        }
+                    }
                 }
-
-                let effect = Effect::ReaderMatch {
-                    success: true,
-                    local_reader_infos: reader_match_infos.disc_data.clone(),
-                    remote_writer_infos: disc_writer_data.clone(),
-                };
-                effects.push(effect);
-                reader_match_infos.matches.insert(remote_writer_guid);
             }
         }
 
@@ -661,8 +1066,16 @@ impl Discovery {
             .get_all_available_changes(SampleStateKind::Any);
 
         for potential_match in potential_matches {
-            let Some(data) = potential_match.get_data() else {
-                unreachable!("CacheChange Data submessage presence checked at ingestion")
+            // the remote Reader has been deleted, its key is the only thing left
+            let Some(data) = potential_match
+                .get_data()
+                .filter(|_| matches!(potential_match.infos.kind, ChangeKind::Alive))
+            else {
+                let remote_reader_guid = Guid::from_bytes(potential_match.get_instance_handle().0);
+                for writer_match_infos in self.application_writers_infos.values_mut() {
+                    Self::unmatch_remote_reader(effects, writer_match_infos, remote_reader_guid);
+                }
+                continue;
             };
 
             let Ok(disc_reader_data) = DiscoveredReaderData::from_serialized_data(data.clone())
@@ -674,36 +1087,58 @@ impl Discovery {
             let remote_reader_guid = disc_reader_data.proxy.get_remote_reader_guid();
 
             for (_id, writer_match_infos) in self.application_writers_infos.iter_mut() {
-                if writer_match_infos.matches.contains(&remote_reader_guid) {
-                    continue;
-                }
-
-                if let Err(_e) = QosPolicyConsistencyChecker::check(
+                // the QoS of both sides can change, every pair is evaluated again
+                let is_compatible = QosPolicyConsistencyChecker::check(
                     &writer_match_infos.disc_data.params,
                     &disc_reader_data.params,
-                ) {
-                    let effect = Effect::WriterMatch {
-                        success: false,
-                        local_writer_infos: writer_match_infos.disc_data.clone(),
-                        remote_reader_infos: disc_reader_data.clone(),
-                    };
-                    effects.push(effect);
+                )
+                .is_ok();
+                let is_matched = writer_match_infos.matches.contains_key(&remote_reader_guid);
+
+                match (is_compatible, is_matched) {
+                    (true, false) => {
+                        let effect = Effect::WriterMatch {
+                            success: true,
+                            local_writer_infos: writer_match_infos.disc_data.clone(),
+                            remote_reader_infos: disc_reader_data.clone(),
+                        };
+                        effects.push(effect);
+                        writer_match_infos
+                            .matches
+                            .insert(remote_reader_guid, disc_reader_data.clone());
+                        writer_match_infos.mismatches.remove(&remote_reader_guid);
+                    }
+                    (false, true) => {
+                        let effect = Effect::WriterUnmatch {
+                            local_writer_infos: writer_match_infos.disc_data.clone(),
+                            remote_reader_infos: disc_reader_data.clone(),
+                        };
+                        effects.push(effect);
+                        writer_match_infos.matches.remove(&remote_reader_guid);
+                    }
+                    (false, false) => {
+                        if writer_match_infos.mismatches.insert(remote_reader_guid) {
+                            let effect = Effect::WriterMatch {
+                                success: false,
+                                local_writer_infos: writer_match_infos.disc_data.clone(),
+                                remote_reader_infos: disc_reader_data.clone(),
+                            };
+                            effects.push(effect);
+                        }
+                    }
+                    (true, true) => (),
                 }
-
-                let effect = Effect::WriterMatch {
-                    success: true,
-                    local_writer_infos: writer_match_infos.disc_data.clone(),
-                    remote_reader_infos: disc_reader_data.clone(),
-                };
-                effects.push(effect);
-                writer_match_infos.matches.insert(remote_reader_guid);
             }
         }
 
         Ok(())
     }
 
-    fn update_edp_endpoints(&mut self, participant_proxy: &ParticipantProxy) {
+    fn update_edp_endpoints(
+        &mut self,
+        effects: &mut Effects,
+        participant_proxy: &ParticipantProxy,
+    ) {
         let builtin_endpoints = participant_proxy.get_available_builtin_endpoints();
         let guid_prefix = participant_proxy.get_guid_prefix();
 
@@ -764,7 +1199,7 @@ impl Discovery {
                     participant_proxy.metatraffic_unicast_locator_list.clone(),
                     participant_proxy.metatraffic_multicast_locator_list.clone(),
                 );
-                endpoint.add_proxy(reader_proxy);
+                endpoint.add_proxy(effects, reader_proxy);
 
                 event!(
                     Level::TRACE,
@@ -829,7 +1264,7 @@ impl Discovery {
                     participant_proxy.metatraffic_unicast_locator_list.clone(),
                     participant_proxy.metatraffic_multicast_locator_list.clone(),
                 );
-                endpoint.add_proxy(reader_proxy);
+                endpoint.add_proxy(effects, reader_proxy);
 
                 event!(
                     Level::TRACE,
@@ -847,6 +1282,77 @@ impl Discovery {
             }
         }
     }
+
+    fn update_p2p_endpoints(
+        &mut self,
+        effects: &mut Effects,
+        participant_proxy: &ParticipantProxy,
+    ) {
+        let builtin_endpoints = participant_proxy.get_available_builtin_endpoints();
+        let guid_prefix = participant_proxy.get_guid_prefix();
+
+        let has_participant_message_writer =
+            builtin_endpoints.builtin_endpoint_participant_message_data_writer() == 1;
+        let has_participant_message_reader =
+            builtin_endpoints.builtin_endpoint_participant_message_data_reader() == 1;
+
+        if has_participant_message_writer {
+            let endpoint = &mut self.p2p_msg_reader;
+
+            let remote_participant_guid =
+                Guid::new(guid_prefix, ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER);
+
+            if !endpoint.lookup_proxy(remote_participant_guid) {
+                let writer_proxy = WriterProxy::new(
+                    remote_participant_guid,
+                    Default::default(),
+                    Default::default(),
+                    participant_proxy.metatraffic_unicast_locator_list.clone(),
+                    participant_proxy.metatraffic_multicast_locator_list.clone(),
+                );
+                endpoint.add_proxy(writer_proxy);
+
+                event!(
+                    Level::TRACE,
+                    "Participant message reader<{}> discovered Participant message writer<{}>",
+                    &endpoint.get_guid(),
+                    &remote_participant_guid
+                );
+            }
+        }
+
+        if has_participant_message_reader {
+            let endpoint = &mut self.p2p_msg_writer;
+
+            let remote_participant_guid =
+                Guid::new(guid_prefix, ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER);
+
+            if !endpoint.lookup_proxy(remote_participant_guid) {
+                let reader_proxy = ReaderProxy::new(
+                    remote_participant_guid,
+                    Default::default(),
+                    true,
+                    true,
+                    participant_proxy.metatraffic_unicast_locator_list.clone(),
+                    participant_proxy.metatraffic_multicast_locator_list.clone(),
+                );
+                endpoint.add_proxy(effects, reader_proxy);
+
+                event!(
+                    Level::TRACE,
+                    "Participant message writer<{}> discovered Participant message reader<{}>",
+                    &endpoint.get_guid(),
+                    &remote_participant_guid
+                );
+            }
+        }
+    }
+}
+
+/// A third of `lease_duration`, `None` for an infinite lease
+fn liveliness_period(lease_duration: Timestamp) -> Option<i64> {
+    let lease_duration = lease_duration.to_datetime().timestamp_millis();
+    (lease_duration > 0).then(|| (lease_duration / 3).max(1))
 }
 
 #[cfg(test)]
@@ -858,20 +1364,33 @@ mod tests {
         common::TickId,
         messages::MessageFactory,
         types::{
-            ContentNature, DomainTag, ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER, Guid,
-            GuidPrefix, Locator, LocatorList, ParticipantProxy, PdpDiscoveredParticipantData,
-            SequenceNumber, builtin_endpoint_qos::BuiltinEndpointQos,
-            builtin_endpoint_set::BuiltinEndpointSet, domain_id::DomainId, duration::Duration,
+            ContentNature, DomainTag, ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER,
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER,
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
+            ENTITYID_SPDP_BUILTIN_PARTICIPANT_ANNOUNCER,
+            ENTITYID_SPDP_BUILTIN_PARTICIPANT_DETECTOR, EntityId, Guid, GuidPrefix, InlineQos,
+            InstanceHandle, LivelinessKind, LivelinessQosPolicy, Locator, LocatorList,
+            ParticipantProxy, PartitionQosPolicy, PdpDiscoveredParticipantData, SequenceNumber,
+            StatusInfo, Timestamp,
+            builtin_endpoint_qos::BuiltinEndpointQos,
+            builtin_endpoint_set::BuiltinEndpointSet,
+            domain_id::DomainId,
+            duration::Duration,
             participant_builtin_topic_data::ParticipantBuiltinTopicData,
+            participant_message_data::{ParticipantMessageData, ParticipantMessageKind},
         },
     };
     use binrw::Endian;
     use rstest::{fixture, rstest};
 
     use crate::{
-        Effect, Effects,
-        common::tests::setup_guid_prefix,
-        discovery::{Discovery, DiscoveryBuilder, DiscoveryConfiguration},
+        Effect, Effects, ReaderProxy, WriterProxy,
+        common::{Error, tests::setup_guid_prefix},
+        discovery::{
+            DiscoveredReaderData, DiscoveredWriterData, Discovery, DiscoveryBuilder,
+            DiscoveryConfiguration,
+        },
     };
 
     #[rstest]
@@ -893,6 +1412,399 @@ mod tests {
         assert!(schedule_effect.is_some());
     }
 
+    #[rstest]
+    fn remote_liveliness_asserted(
+        #[from(setup_discovery)] mut discovery: Discovery,
+        #[from(setup_participant_infos_1)] mut remote_participant_proxy: ParticipantProxy,
+    ) {
+        let mut effects = Effects::new();
+        let remote_guid_prefix = remote_participant_proxy.get_guid_prefix();
+
+        let mut endpoint_set = BuiltinEndpointSet::new();
+        endpoint_set.set_builtin_endpoint_participant_message_data_writer(1);
+        endpoint_set.set_builtin_endpoint_participant_message_data_reader(1);
+        remote_participant_proxy.available_builtin_endpoints = endpoint_set;
+
+        let participant_disc_data = PdpDiscoveredParticipantData::new(
+            ParticipantBuiltinTopicData::default(),
+            remote_participant_proxy,
+            Duration::from(std::time::Duration::from_millis(1000)),
+        );
+        let data = participant_disc_data
+            .into_serialized_data(Endian::Little)
+            .unwrap();
+        let message = MessageFactory::new(remote_guid_prefix)
+            .message()
+            .reader(ENTITYID_SPDP_BUILTIN_PARTICIPANT_DETECTOR)
+            .writer(ENTITYID_SPDP_BUILTIN_PARTICIPANT_ANNOUNCER)
+            .data(ContentNature::Data, SequenceNumber(1), None, Some(data))
+            .build();
+        discovery.ingest(&mut effects, message, 1000).unwrap();
+        effects.clean();
+
+        let participant_message = ParticipantMessageData::new(
+            remote_guid_prefix,
+            ParticipantMessageKind::MANUAL_LIVELINESS_UPDATE,
+        );
+        let inline_qos = InlineQos {
+            key_hash: participant_message.get_instance(),
+            ..Default::default()
+        };
+        let message = MessageFactory::new(remote_guid_prefix)
+            .message()
+            .reader(ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER)
+            .writer(ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER)
+            .data(
+                ContentNature::Data,
+                SequenceNumber(1),
+                Some(inline_qos),
+                Some(
+                    participant_message
+                        .into_serialized_data(Endian::Big)
+                        .unwrap(),
+                ),
+            )
+            .build();
+        discovery.ingest(&mut effects, message, 1100).unwrap();
+
+        let Some(Effect::RemoteLivelinessAsserted {
+            participant_guid_prefix,
+            kind,
+        }) = effects.find(|e| matches!(e, Effect::RemoteLivelinessAsserted { .. }))
+        else {
+            panic!("remote liveliness should have been asserted");
+        };
+        assert_eq!(*participant_guid_prefix, remote_guid_prefix);
+        assert_eq!(*kind, LivelinessKind::ManualByParticipant);
+    }
+
+    #[rstest]
+    fn participant_message_period_follows_lease_duration(
+        #[from(setup_discovery)] mut discovery: Discovery,
+    ) {
+        let mut effects = Effects::new();
+        let guid_prefix = discovery.participant_guid_prefix;
+        let mut add_writer =
+            |id: u8, kind: LivelinessKind, lease_ms: i64, effects: &mut Effects| {
+                discovery
+                    .add_publications_infos(
+                        effects,
+                        DiscoveredWriterData {
+                            proxy: WriterProxy {
+                                remote_writer_guid: Guid::new(
+                                    guid_prefix,
+                                    EntityId::writer_no_key([0, 0, id]),
+                                ),
+                                ..Default::default()
+                            },
+                            params: InlineQos {
+                                topic_name: "topic".to_string(),
+                                liveness: LivelinessQosPolicy {
+                                    kind,
+                                    lease_duration: Timestamp::from_datetime(
+                                        chrono::DateTime::from_timestamp_millis(lease_ms).unwrap(),
+                                    ),
+                                },
+                                ..Default::default()
+                            },
+                        },
+                    )
+                    .unwrap();
+            };
+        add_writer(1, LivelinessKind::ManualByTopic, 30, &mut effects);
+        add_writer(2, LivelinessKind::ManualByParticipant, 600, &mut effects);
+        add_writer(3, LivelinessKind::Automatic, 900, &mut effects);
+        effects.clean();
+
+        discovery
+            .tick(&mut effects, 1000, TickId::ParticipantMessageWriter)
+            .unwrap();
+        let Some(Effect::ScheduleTick { delay, .. }) = effects.find(|e| {
+            matches!(
+                e,
+                Effect::ScheduleTick {
+                    id: TickId::ParticipantMessageWriter,
+                    ..
+                }
+            )
+        }) else {
+            panic!("the participant message Writer should be ticked again");
+        };
+        assert_eq!(*delay, 200);
+    }
+
+    #[rstest]
+    fn partition_change_reevaluates_matches(
+        #[from(setup_discovery)] mut discovery: Discovery,
+        #[from(setup_participant_infos_1)] mut remote_participant_proxy: ParticipantProxy,
+    ) {
+        let mut effects = Effects::new();
+        let remote_guid_prefix = remote_participant_proxy.get_guid_prefix();
+
+        let mut endpoint_set = BuiltinEndpointSet::new();
+        endpoint_set.set_disc_builtin_endpoint_publications_announcer(1);
+        remote_participant_proxy.available_builtin_endpoints = endpoint_set;
+
+        let participant_disc_data = PdpDiscoveredParticipantData::new(
+            ParticipantBuiltinTopicData::default(),
+            remote_participant_proxy,
+            Duration::from(std::time::Duration::from_millis(1000)),
+        );
+        let data = participant_disc_data
+            .into_serialized_data(Endian::Little)
+            .unwrap();
+        let message = MessageFactory::new(remote_guid_prefix)
+            .message()
+            .reader(ENTITYID_SPDP_BUILTIN_PARTICIPANT_DETECTOR)
+            .writer(ENTITYID_SPDP_BUILTIN_PARTICIPANT_ANNOUNCER)
+            .data(ContentNature::Data, SequenceNumber(1), None, Some(data))
+            .build();
+        discovery.ingest(&mut effects, message, 1000).unwrap();
+
+        let topic_qos = |partition: &str| InlineQos {
+            topic_name: "topic".to_string(),
+            partition: PartitionQosPolicy::new([partition]),
+            ..Default::default()
+        };
+        let local_reader_guid = Guid::new(
+            discovery.participant_guid_prefix,
+            EntityId::reader_no_key([0, 0, 1]),
+        );
+        discovery
+            .add_subscriptions_infos(
+                &mut effects,
+                DiscoveredReaderData {
+                    proxy: ReaderProxy {
+                        remote_reader_guid: local_reader_guid,
+                        ..Default::default()
+                    },
+                    params: topic_qos("tenant-*"),
+                },
+            )
+            .unwrap();
+        effects.clean();
+
+        // the remote Writer is announced, then announced again in another partition
+        let remote_writer_guid = Guid::new(remote_guid_prefix, EntityId::writer_no_key([0, 0, 1]));
+        let mut announce = |partition: &str, sequence: i64, effects: &mut Effects| {
+            let data = DiscoveredWriterData {
+                proxy: WriterProxy {
+                    remote_writer_guid,
+                    ..Default::default()
+                },
+                params: topic_qos(partition),
+            }
+            .into_serialized_data(Endian::Big)
+            .unwrap();
+            let message = MessageFactory::new(remote_guid_prefix)
+                .message()
+                .reader(ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR)
+                .writer(ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER)
+                .data(
+                    ContentNature::Data,
+                    SequenceNumber(sequence),
+                    Some(InlineQos {
+                        key_hash: InstanceHandle(remote_writer_guid.as_bytes()),
+                        ..Default::default()
+                    }),
+                    Some(data),
+                )
+                .build();
+            discovery.ingest(effects, message, 1100).unwrap();
+        };
+
+        announce("tenant-a", 1, &mut effects);
+        assert!(
+            effects
+                .find(|e| matches!(e, Effect::ReaderMatch { success: true, .. }))
+                .is_some()
+        );
+        effects.clean();
+
+        // still within the partitions of the Reader, the match is kept with the new QoS
+        announce("tenant-b", 2, &mut effects);
+        assert!(
+            effects
+                .find(|e| matches!(e, Effect::ReaderMatchUpdated { .. }))
+                .is_some()
+        );
+        effects.clean();
+
+        announce("other", 3, &mut effects);
+        assert!(
+            effects
+                .find(|e| matches!(e, Effect::ReaderUnmatch { .. }))
+                .is_some()
+        );
+    }
+
+    #[rstest]
+    fn remote_deletions_unmatch(
+        #[from(setup_discovery)] mut discovery: Discovery,
+        #[from(setup_participant_infos_1)] mut remote_participant_proxy: ParticipantProxy,
+    ) {
+        let mut effects = Effects::new();
+        let remote_guid_prefix = remote_participant_proxy.get_guid_prefix();
+        let remote_participant_key = InstanceHandle(remote_participant_proxy.get_guid().as_bytes());
+
+        let mut endpoint_set = BuiltinEndpointSet::new();
+        endpoint_set.set_disc_builtin_endpoint_publications_announcer(1);
+        remote_participant_proxy.available_builtin_endpoints = endpoint_set;
+
+        let participant_disc_data = PdpDiscoveredParticipantData::new(
+            ParticipantBuiltinTopicData::default(),
+            remote_participant_proxy,
+            Duration::from(std::time::Duration::from_millis(1000)),
+        );
+        let data = participant_disc_data
+            .into_serialized_data(Endian::Little)
+            .unwrap();
+        let message = MessageFactory::new(remote_guid_prefix)
+            .message()
+            .reader(ENTITYID_SPDP_BUILTIN_PARTICIPANT_DETECTOR)
+            .writer(ENTITYID_SPDP_BUILTIN_PARTICIPANT_ANNOUNCER)
+            .data(ContentNature::Data, SequenceNumber(1), None, Some(data))
+            .build();
+        discovery.ingest(&mut effects, message, 1000).unwrap();
+
+        let topic_qos = InlineQos {
+            topic_name: "topic".to_string(),
+            ..Default::default()
+        };
+        let local_reader_guid = Guid::new(
+            discovery.participant_guid_prefix,
+            EntityId::reader_no_key([0, 0, 1]),
+        );
+        discovery
+            .add_subscriptions_infos(
+                &mut effects,
+                DiscoveredReaderData {
+                    proxy: ReaderProxy {
+                        remote_reader_guid: local_reader_guid,
+                        ..Default::default()
+                    },
+                    params: topic_qos.clone(),
+                },
+            )
+            .unwrap();
+
+        let remote_writer_guid = Guid::new(remote_guid_prefix, EntityId::writer_no_key([0, 0, 1]));
+        let remote_writer_key = InstanceHandle(remote_writer_guid.as_bytes());
+        let data = DiscoveredWriterData {
+            proxy: WriterProxy {
+                remote_writer_guid,
+                ..Default::default()
+            },
+            params: topic_qos,
+        }
+        .into_serialized_data(Endian::Big)
+        .unwrap();
+        let message = MessageFactory::new(remote_guid_prefix)
+            .message()
+            .reader(ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR)
+            .writer(ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER)
+            .data(
+                ContentNature::Data,
+                SequenceNumber(1),
+                Some(InlineQos {
+                    key_hash: remote_writer_key,
+                    ..Default::default()
+                }),
+                Some(data),
+            )
+            .build();
+        discovery.ingest(&mut effects, message, 1100).unwrap();
+        assert!(
+            effects
+                .find(|e| matches!(e, Effect::ReaderMatch { success: true, .. }))
+                .is_some()
+        );
+        effects.clean();
+
+        // the deleted remote Writer only sends its key
+        let message = MessageFactory::new(remote_guid_prefix)
+            .message()
+            .reader(ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR)
+            .writer(ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER)
+            .data(
+                ContentNature::None,
+                SequenceNumber(2),
+                Some(InlineQos {
+                    key_hash: remote_writer_key,
+                    status_info: StatusInfo::new_disposed(),
+                    ..Default::default()
+                }),
+                None,
+            )
+            .build();
+        discovery.ingest(&mut effects, message, 1200).unwrap();
+        let Some(Effect::ReaderUnmatch {
+            remote_writer_infos,
+            ..
+        }) = effects.find(|e| matches!(e, Effect::ReaderUnmatch { .. }))
+        else {
+            panic!("the deleted remote Writer should have been unmatched");
+        };
+        assert_eq!(
+            remote_writer_infos.proxy.get_remote_writer_guid(),
+            remote_writer_guid
+        );
+        effects.clean();
+
+        // the disposed remote Participant is forgotten at once
+        let message = MessageFactory::new(remote_guid_prefix)
+            .message()
+            .reader(ENTITYID_SPDP_BUILTIN_PARTICIPANT_DETECTOR)
+            .writer(ENTITYID_SPDP_BUILTIN_PARTICIPANT_ANNOUNCER)
+            .data(
+                ContentNature::None,
+                SequenceNumber(2),
+                Some(InlineQos {
+                    key_hash: remote_participant_key,
+                    status_info: StatusInfo::new_disposed(),
+                    ..Default::default()
+                }),
+                None,
+            )
+            .build();
+        discovery.ingest(&mut effects, message, 1300).unwrap();
+        assert!(
+            effects
+                .find(|e| matches!(e, Effect::ParticipantRemoved { .. }))
+                .is_some()
+        );
+        assert!(discovery.remote_participants_infos.is_empty());
+    }
+
+    #[rstest]
+    fn local_writer_removal(#[from(setup_discovery)] mut discovery: Discovery) {
+        let mut effects = Effects::new();
+        let local_writer_id = EntityId::writer_no_key([0, 0, 1]);
+        discovery
+            .add_publications_infos(
+                &mut effects,
+                DiscoveredWriterData {
+                    proxy: WriterProxy {
+                        remote_writer_guid: Guid::new(
+                            discovery.participant_guid_prefix,
+                            local_writer_id,
+                        ),
+                        ..Default::default()
+                    },
+                    params: InlineQos::default(),
+                },
+            )
+            .unwrap();
+
+        discovery
+            .remove_publications_infos(&mut effects, local_writer_id)
+            .unwrap();
+        assert!(discovery.application_writers_infos.is_empty());
+
+        let res = discovery.remove_publications_infos(&mut effects, local_writer_id);
+        assert!(matches!(res, Err(Error::UnknownEntity(_))));
+    }
+
     // #[rstest]
     // fn receive_remote_participant_infos(
     //     #[from(setup_discovery)] mut discovery: Discovery,
@@ -3,7 +3,8 @@ use std::fmt::Display;
 use crate::{
     common::Error,
     types::{
-        EntityId, Guid, InlineQos, Locator, ParameterId, ParameterList, RtpsString, SerializedData,
+        EntityId, Guid, InlineQos, Locator, ParameterId, ParameterList, ReliabilityQosPolicy,
+        RtpsString, SerializedData,
     },
 };
 use binrw::Endian;
@@ -49,6 +50,7 @@ impl DiscoveredReaderData {
             .into();
 
         let params = InlineQos::from_parameter_list(parameter_list, endian);
+        let is_reliable = matches!(params.reliability, ReliabilityQosPolicy::Reliable { .. });
 
         Self {
             proxy: ReaderProxy {
@@ -58,6 +60,8 @@ impl DiscoveredReaderData {
                 unicast_locator_list,
                 multicast_locator_list,
                 is_active: true,
+                is_reliable,
+                durability: params.durability,
                 ..Default::default()
             },
             params,
@@ -8,8 +8,10 @@ mod types;
 
 pub use cdr;
 pub use common::{
-    CacheChange, CacheChangeContainer, CacheChangeInfos, Effect, Effects, IncommingMessage,
-    OutcommingMessage, ReaderProxy, TickId, WriterProxy,
+    CacheChange, CacheChangeContainer, CacheChangeInfos, DeadlineMissedStatus, Effect, Effects,
+    Error, GenerationInfos, IncommingMessage, LivelinessChangedStatus, LivelinessLostStatus,
+    OutcommingMessage, ReaderProxy, SampleLostStatus, SampleRejectedStatusKind, TickId,
+    WriterProxy,
 };
 pub use discovery::{
     Announce, DiscoveredReaderData, DiscoveredWriterData, Discovery, DiscoveryBuilder,
@@ -19,11 +21,14 @@ pub use key::{KeyCalculationError, Keyed};
 pub use messages::*;
 use pretty_hex::HexConfig;
 pub use publication::{Writer, WriterBuilder, WriterConfiguration};
-pub use subscription::{Reader, ReaderBuilder, ReaderConfiguration};
+pub use subscription::{
+    InstanceStateKind, InstanceStateMask, Reader, ReaderBuilder, ReaderConfiguration,
+    SampleStateKind, SampleStateMask, ViewStateKind, ViewStateMask,
+};
 use thiserror::Error;
 pub use types::*;
 
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum DdsError {
     #[error("Generic, unspecified error. Additional infos: {0}")]
     Error(String),
@@ -70,6 +75,12 @@ pub enum DdsError {
     return any data but there is no inherent error. "
     )]
     NoData,
+    #[error("Invalid configuration: {0}")]
+    InvalidConfiguration(String),
+    #[error("Cannot communicate through {locator}: {reason}")]
+    Transport { locator: Locator, reason: String },
+    #[error("The {actor} actor cannot process the request: {reason}")]
+    Actor { actor: &'static str, reason: String },
 }
 
 pub const K: u32 = 1024;
@@ -1,11 +1,14 @@
 use std::collections::VecDeque;
 
-use crate::types::{FragmentNumber, SequenceNumber};
+use crate::types::{FragmentNumber, InstanceHandle, LifespanQosPolicy, SequenceNumber};
 use contracts::requires;
 use thiserror::Error;
 use tracing::{Level, event};
 
-use crate::CacheChange;
+use crate::{
+    CacheChange,
+    common::{SampleRejectedStatusKind, check_history_limits, preallocated_changes},
+};
 
 #[derive(Debug)]
 pub struct WriterHistoryCacheConfiguration {
@@ -38,6 +41,8 @@ impl WriterHistoryCacheConfiguration {
 pub enum WriterHistoryCacheError {
     #[error("The Change's SequenceNumber({0}) is already in use")]
     SequenceAlreadyPresent(SequenceNumber),
+    #[error("The RESOURCE_LIMITS prevent from keeping the Change: {0:?}")]
+    OutOfResources(SampleRejectedStatusKind),
 }
 
 #[derive(Debug)]
@@ -58,8 +63,7 @@ impl WriterHistoryCache {
             max_samples_per_instance,
             max_samples,
         } = config;
-        let changes_size = config.depth.unwrap_or(max_samples) as usize;
-        let changes = VecDeque::with_capacity(changes_size);
+        let changes = VecDeque::with_capacity(preallocated_changes(depth, max_samples));
         Self {
             changes,
             depth,
@@ -70,23 +74,91 @@ impl WriterHistoryCache {
         }
     }
 
-    /// Push a new CacheChange into the ReaderHistoryCache
+    /// Push a new CacheChange into the WriterHistoryCache
+    ///
+    /// With KEEP_LAST, the oldest change is replaced once the depth is reached,
+    /// otherwise the change is refused if it exceeds the RESOURCE_LIMITS
     ///
     /// # Preconditions
     /// - change.sequence must not be already in use
     pub fn push_change(&mut self, change: CacheChange) -> Result<(), WriterHistoryCacheError> {
-        if let Some(depth) = self.depth {
-            if self.changes.len() == depth as usize {
-                let taken_change = self.changes.pop_back().expect("presence asserted");
-                self.trash.push_front(taken_change);
-            }
-            self.changes.push_front(change);
-            Ok(())
-        } else {
-            // FIXME: handle resources limit cases
-            self.changes.push_front(change);
-            Ok(())
+        if self.check_history_limits(change.get_instance_handle())? {
+            let position = self
+                .changes
+                .iter()
+                .rposition(|c| !c.is_coherent_set_end())
+                .expect("presence asserted");
+            let taken_change = self.changes.remove(position).expect("presence asserted");
+            self.trash.push_front(taken_change);
+            // the ends of coherent sets older than every kept change have nothing left to end
+            let oldest = self
+                .changes
+                .iter()
+                .filter(|c| !c.is_coherent_set_end())
+                .map(|c| c.get_sequence_number())
+                .chain([change.get_sequence_number()])
+                .min()
+                .expect("presence asserted");
+            self.changes
+                .retain(|c| !c.is_coherent_set_end() || c.get_sequence_number() > oldest);
         }
+        self.changes.push_front(change);
+        Ok(())
+    }
+
+    /// Push the end of a coherent set, it takes no room in the history and is not subject to the RESOURCE_LIMITS
+    ///
+    /// # Preconditions
+    /// - change.sequence must not be already in use
+    #[requires(change.is_coherent_set_end())]
+    pub fn push_coherent_set_end(&mut self, change: CacheChange) {
+        self.changes.push_front(change);
+    }
+
+    /// Check a change of `instance` could be pushed without exceeding the RESOURCE_LIMITS
+    pub fn check_resources(&self, instance: InstanceHandle) -> Result<(), WriterHistoryCacheError> {
+        self.check_history_limits(instance).map(|_| ())
+    }
+
+    /// Same as [`WriterHistoryCache::check_resources`], telling whether the oldest change is replaced
+    fn check_history_limits(
+        &self,
+        instance: InstanceHandle,
+    ) -> Result<bool, WriterHistoryCacheError> {
+        let held = self
+            .changes
+            .iter()
+            .filter(|c| !c.is_coherent_set_end())
+            .map(|c| c.get_instance_handle())
+            .collect::<Vec<_>>();
+        check_history_limits(
+            held.into_iter(),
+            instance,
+            self.depth,
+            self.max_instances,
+            self.max_samples_per_instance,
+            self.max_samples,
+        )
+        .map_err(WriterHistoryCacheError::OutOfResources)
+    }
+
+    /// Remove the changes whose `lifespan` has elapsed at `now`
+    ///
+    /// Return the expiration time of the next change to expire, if any
+    pub fn remove_expired_changes(&mut self, now: i64, lifespan: LifespanQosPolicy) -> Option<i64> {
+        self.changes.retain(|c| {
+            c.expiration_time(lifespan)
+                .is_none_or(|expiration| expiration > now)
+        });
+        self.changes
+            .iter()
+            .filter_map(|c| c.expiration_time(lifespan))
+            .min()
+    }
+
+    /// Remove the changes up to `sequence` included
+    pub fn remove_changes_until(&mut self, sequence: SequenceNumber) {
+        self.changes.retain(|c| c.get_sequence_number() > sequence);
     }
 
     pub fn get_change(&self, sequence: SequenceNumber) -> Option<&CacheChange> {
@@ -95,6 +167,17 @@ impl WriterHistoryCache {
             .find(|c| c.get_sequence_number() == sequence)
     }
 
+    /// Sequence numbers of the retained changes, in ascending order
+    pub fn get_sequence_numbers(&self) -> Vec<SequenceNumber> {
+        let mut sequences = self
+            .changes
+            .iter()
+            .map(|c| c.get_sequence_number())
+            .collect::<Vec<_>>();
+        sequences.sort();
+        sequences
+    }
+
     pub fn get_min_sequence(&self) -> Option<SequenceNumber> {
         self.changes.iter().map(|c| c.get_sequence_number()).min()
     }
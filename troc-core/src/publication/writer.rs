@@ -4,9 +4,10 @@ use crate::{
     common::TickId,
     messages::{Message, MessageFactory, MessageReceiver, SubmessageContent},
     types::{
-        ChangeKind, ContentNature, ENTITYID_UNKOWN, EntityId, FragmentNumber, Guid,
-        HistoryQosPolicy, InlineQos, InstanceHandle, LocatorList, ReliabilityKind, SequenceNumber,
-        SequenceNumberSet, SerializedData, Timestamp, sequence_number::SEQUENCENUMBER_UNKNOWN,
+        ChangeKind, ContentNature, DurabilityQosPolicy, ENTITYID_UNKOWN, EntityId,
+        GroupCoherentSet, Guid, HistoryQosPolicy, InlineQos, InstanceHandle, LivelinessKind,
+        LocatorList, ReliabilityKind, SequenceNumber, SequenceNumberSet, SerializedData,
+        StatusInfo, Timestamp, sequence_number::SEQUENCENUMBER_UNKNOWN,
     },
 };
 use chrono::Utc;
@@ -14,7 +15,10 @@ use tracing::{Level, event, instrument};
 
 use crate::{
     CacheChange, WriterProxy,
-    common::{CacheChangeInfos, Counter, Effect, Effects, Error, ReaderProxy},
+    common::{
+        CacheChangeInfos, Counter, DeadlineTracker, Effect, Effects, Error, ExpirationScheduler,
+        LivelinessLease, ReaderProxy,
+    },
     publication::{WriterHistoryCache, historycache::WriterHistoryCacheConfiguration},
 };
 
@@ -105,8 +109,16 @@ impl WriterBuilder {
             HistoryQosPolicy::KeepAll => None,
             HistoryQosPolicy::KeepLast { depth } => Some(depth),
         };
-        let cache_config = WriterHistoryCacheConfiguration::new(depth, 100, 100, 100);
+        let limits = qos.resource_limits;
+        let cache_config = WriterHistoryCacheConfiguration::new(
+            depth,
+            limits.max_instances,
+            limits.max_samples_per_instance,
+            limits.max_samples,
+        );
         let cache = WriterHistoryCache::new(cache_config);
+        let deadline = DeadlineTracker::new(qos.deadline);
+        let liveliness = LivelinessLease::new(qos.liveness);
         Writer {
             guid,
             qos,
@@ -114,6 +126,7 @@ impl WriterBuilder {
             message_factory: MessageFactory::new(guid.get_guid_prefix()),
             matched_readers,
             cache,
+            registered_instances: Default::default(),
             receiver: MessageReceiver::new(guid.get_guid_prefix()),
             last_change_sequence_number: SEQUENCENUMBER_UNKNOWN,
             heartbeat_counter: Counter::new(),
@@ -122,6 +135,10 @@ impl WriterBuilder {
             multicast_locator_list,
             config,
             tick_id,
+            deadline,
+            expiration: ExpirationScheduler::default(),
+            liveliness,
+            coherent_set: None,
         }
     }
 }
@@ -134,6 +151,8 @@ pub struct Writer {
     message_factory: MessageFactory,
     matched_readers: HashMap<Guid, ReaderProxy>,
     cache: WriterHistoryCache,
+    /// Source timestamp of the registration of every registered instance
+    registered_instances: HashMap<InstanceHandle, Timestamp>,
     receiver: MessageReceiver,
     last_change_sequence_number: SequenceNumber,
     heartbeat_counter: Counter,
@@ -142,6 +161,10 @@ pub struct Writer {
     multicast_locator_list: LocatorList,
     config: WriterConfiguration,
     tick_id: TickId,
+    deadline: DeadlineTracker,
+    expiration: ExpirationScheduler,
+    liveliness: LivelinessLease,
+    coherent_set: Option<SequenceNumber>,
 }
 
 impl Writer {
@@ -160,6 +183,15 @@ impl Writer {
         self.last_change_sequence_number += 1;
         let sample_size = data.as_ref().map(|d| d.size()).unwrap_or_default() as u32;
         let fragments_count = sample_size.div_ceil(self.config.fragment_size as u32) as u16;
+        // the USER_DATA, TOPIC_DATA and GROUP_DATA are announced by the discovery, not with every sample
+        let inline_qos = inline_qos.map(|qos| InlineQos {
+            key_hash: instance_handle,
+            coherent_set: self.coherent_set.or(qos.coherent_set),
+            user_data: Default::default(),
+            topic_data: Default::default(),
+            group_data: Default::default(),
+            ..qos
+        });
 
         CacheChange {
             infos: CacheChangeInfos {
@@ -178,12 +210,156 @@ impl Writer {
         }
     }
 
+    /// Keep `change` in the history and send it to the matched Readers
+    ///
+    /// Fail with [`Error::OutOfResources`] if the history can't keep `change`, its sequence number is then released
     #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), matched_readers = ?self.matched_readers))]
     pub fn add_change(&mut self, effects: &mut Effects, change: CacheChange) -> Result<(), Error> {
+        self.reserve(&change)?;
+        let now = Utc::now().timestamp_millis();
+        // writing asserts the liveliness of the Writer
+        self.liveliness.assert(effects, now);
+        if matches!(change.infos.kind, ChangeKind::Alive) {
+            let timestamp = change
+                .infos
+                .emission_timestamp
+                .unwrap_or_else(|| Timestamp::from_datetime(Utc::now()));
+            self.registered_instances
+                .entry(change.get_instance_handle())
+                .or_insert(timestamp);
+            self.deadline
+                .update(effects, change.get_instance_handle(), now);
+        }
+        let expiration = change.expiration_time(self.qos.lifespan);
+        self.expiration.schedule(effects, now, expiration);
         self.cache.push_change(change).unwrap();
         self.produce_data(self.last_change_sequence_number, effects)
     }
 
+    /// Start a coherent set, the following changes are only presented to the Readers once the set is ended
+    ///
+    /// A coherent set already in progress keeps going
+    pub fn begin_coherent_set(&mut self) {
+        self.coherent_set
+            .get_or_insert(self.last_change_sequence_number + 1);
+    }
+
+    /// End the coherent set in progress, sending the marker which lets the Readers present its changes
+    ///
+    /// The set is part of the `group` coherent set of the Publisher with GROUP access scope, if any: the marker is
+    /// then sent even if nothing was written, the Subscribers wait for the set of every DataWriter of the group
+    ///
+    /// Fail with [`Error::NoCoherentSet`] if no coherent set was begun
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id()))]
+    pub fn end_coherent_set(
+        &mut self,
+        effects: &mut Effects,
+        group: Option<GroupCoherentSet>,
+    ) -> Result<(), Error> {
+        let first = self.coherent_set.take().ok_or(Error::NoCoherentSet)?;
+        if self.last_change_sequence_number < first && group.is_none() {
+            // nothing was written during the set
+            return Ok(());
+        }
+
+        // the marker has neither payload nor key, it only carries the coherent set it ends
+        let inline_qos = InlineQos {
+            coherent_set: Some(first),
+            group_coherent_set: group,
+            ..Default::default()
+        };
+        let change = self.new_change(
+            ChangeKind::Alive,
+            None,
+            Some(inline_qos),
+            InstanceHandle::default(),
+        );
+        self.cache.push_coherent_set_end(change);
+        self.produce_data(self.last_change_sequence_number, effects)
+    }
+
+    /// Apply a new QoS at `now`, the changeable policies take effect from then on
+    ///
+    /// Fail with [`Error::ImmutablePolicy`] if a policy which cannot change once the Writer is enabled differs
+    pub fn set_qos(
+        &mut self,
+        effects: &mut Effects,
+        qos: InlineQos,
+        now: i64,
+    ) -> Result<(), Error> {
+        if let Some(policy) = self.qos.changed_immutable_policy(&qos) {
+            return Err(Error::ImmutablePolicy(policy));
+        }
+
+        self.deadline.set_deadline(effects, qos.deadline, now);
+        self.qos = qos;
+        Ok(())
+    }
+
+    /// Declare an instance the Writer is about to update, writing a sample of it does the same implicitly
+    ///
+    /// The registration keeps its source `timestamp`, now if absent, registering an instance again has no effect
+    pub fn register_instance(&mut self, instance: InstanceHandle, timestamp: Option<Timestamp>) {
+        self.registered_instances
+            .entry(instance)
+            .or_insert_with(|| timestamp.unwrap_or_else(|| Timestamp::from_datetime(Utc::now())));
+    }
+
+    /// Source timestamp of the registration of `instance`, if registered
+    pub fn get_registration_timestamp(&self, instance: InstanceHandle) -> Option<Timestamp> {
+        self.registered_instances.get(&instance).copied()
+    }
+
+    /// Notify matched Readers that this Writer will no longer update `instance`
+    ///
+    /// # Preconditions
+    /// - `instance` must have been registered
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), instance = %instance))]
+    pub fn unregister_instance(
+        &mut self,
+        effects: &mut Effects,
+        instance: InstanceHandle,
+        timestamp: Option<Timestamp>,
+    ) -> Result<(), Error> {
+        if self.registered_instances.remove(&instance).is_none() {
+            return Err(Error::UnknownInstance(instance));
+        }
+        self.deadline.forget(instance);
+
+        self.produce_instance_state_change(
+            effects,
+            instance,
+            ChangeKind::NotAliveUnregistered,
+            StatusInfo::new_unregister(),
+            timestamp,
+        )
+    }
+
+    /// Notify matched Readers that `instance` has been deleted
+    ///
+    /// # Preconditions
+    /// - `instance` must have been registered
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), instance = %instance))]
+    pub fn dispose_instance(
+        &mut self,
+        effects: &mut Effects,
+        instance: InstanceHandle,
+        timestamp: Option<Timestamp>,
+    ) -> Result<(), Error> {
+        if !self.registered_instances.contains_key(&instance) {
+            return Err(Error::UnknownInstance(instance));
+        }
+        self.deadline.forget(instance);
+
+        self.produce_instance_state_change(
+            effects,
+            instance,
+            ChangeKind::NotAliveDisposed,
+            StatusInfo::new_disposed(),
+            timestamp,
+        )
+    }
+
     /// Process an incomming message if it contains a Acknack or a NackFrag submessage
     ///
     /// Each Acknack will records positive and/or negative acknowledgments and call [`NackedDataSchedulePort::on_nacked_data_schedule`] if one was provided
@@ -238,8 +414,10 @@ impl Writer {
                     let base = writer_sn_state.get_base();
                     let set = writer_sn_state.get_set();
 
-                    proxy.acked_changes_set(base - 1);
-                    proxy.requested_changes_set(set);
+                    // an acknowledgment never goes back, the Reader may not know the irrelevant changes
+                    let acked = (base - 1).max(proxy.highest_acked_change());
+                    proxy.acked_changes_set(acked);
+                    proxy.requested_changes_set(set.into_iter().filter(|s| *s > acked).collect());
 
                     // let effect = Effect::ScheduleTick {
                     //     delay: self.config.nack_response_delay_ms,
@@ -249,6 +427,7 @@ impl Writer {
                         delay: 200,
                     };
                     effects.push(effect);
+                    self.remove_acknowledged_changes();
 
                     event!(Level::DEBUG, "ACKNACK processed");
                 }
@@ -291,10 +470,77 @@ impl Writer {
         Ok(())
     }
 
+    /// Check the DEADLINE of every registered instance, to be called when a [`TickId::Deadline`] tick expires
+    ///
+    /// An [`Effect::DeadlineMissed`] is produced for the instances not written within the offered period
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id()))]
+    pub fn deadline_tick(&mut self, effects: &mut Effects, now: i64) {
+        self.deadline.check(effects, now);
+    }
+
+    /// Assert the liveliness of this Writer, matched Readers are notified through a HEARTBEAT with the liveliness flag set
     #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), matched_readers = ?self.matched_readers))]
-    pub fn tick(&mut self, effects: &mut Effects, now: i64) {
+    pub fn assert_liveliness(&mut self, effects: &mut Effects, now: i64) {
+        self.liveliness.assert(effects, now);
+
+        if matches!(self.liveliness.get_kind(), LivelinessKind::Automatic) {
+            return;
+        }
+
+        let max = self.last_change_sequence_number;
+        let min = self.cache.get_min_sequence().unwrap_or(max + 1);
+
+        for proxy in self.matched_readers.values() {
+            let count = self.heartbeat_counter.increase();
+            let message = self
+                .message_factory
+                .message()
+                .reader(proxy.get_remote_reader_guid().get_entity_id())
+                .writer(self.guid.get_entity_id())
+                .heartbeat(true, true, min, max, count, None)
+                .build();
+
+            effects.push(Effect::Message {
+                timestamp_millis: Default::default(),
+                message,
+                locators: proxy.get_locators(),
+            });
+        }
+
+        event!(Level::DEBUG, "Liveliness asserted");
+    }
+
+    /// Record a liveliness assertion of the Participant, only MANUAL_BY_PARTICIPANT Writers are concerned
+    pub fn participant_liveliness_asserted(&mut self, effects: &mut Effects, now: i64) {
+        if matches!(
+            self.liveliness.get_kind(),
+            LivelinessKind::ManualByParticipant
+        ) {
+            self.liveliness.assert(effects, now);
+        }
+    }
+
+    /// Check the LIVELINESS lease of the Writer, to be called when a [`TickId::Liveliness`] tick expires
+    ///
+    /// An [`Effect::LivelinessLost`] is produced if the Writer didn't assert its liveliness in time
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id()))]
+    pub fn liveliness_tick(&mut self, effects: &mut Effects, now: i64) {
+        self.liveliness.check(effects, now);
+    }
+
+    /// Remove the changes whose LIFESPAN expired, to be called when a [`TickId::Lifespan`] tick expires
+    ///
+    /// Expired changes are no longer sent, a GAP replaces them if they are requested
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id()))]
+    pub fn lifespan_tick(&mut self, effects: &mut Effects, now: i64) {
+        let next_expiration = self.cache.remove_expired_changes(now, self.qos.lifespan);
+        self.expiration.schedule(effects, now, next_expiration);
+    }
+
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), matched_readers = ?self.matched_readers))]
+    pub fn tick(&mut self, effects: &mut Effects, _now: i64) {
         if matches!(self.is_reliable, ReliabilityKind::BestEffort) {
-            event!(Level::WARN, "BestEffort Writer doesn't send HEARTBEAT");
+            event!(Level::TRACE, "BestEffort Writer doesn't send HEARTBEAT");
             return;
         }
 
@@ -307,7 +553,11 @@ impl Writer {
             // HEARTBEAT production
             if proxy.unacked_changes(max_seq_in_cache) {
                 let max = self.last_change_sequence_number;
-                let min = self.cache.get_min_sequence().unwrap_or(max + 1);
+                let min = self
+                    .cache
+                    .get_min_sequence()
+                    .unwrap_or(max + 1)
+                    .max(proxy.highest_acked_change() + 1);
 
                 let count = self.heartbeat_counter.increase();
 
@@ -356,7 +606,7 @@ impl Writer {
                 while let Some(request_change_sequence) = proxy.next_requested_change() {
                     if let Some(requested_change) = self.cache.get_change(request_change_sequence) {
                         msg = msg.data(
-                            ContentNature::Data,
+                            Self::content_nature(requested_change),
                             request_change_sequence,
                             requested_change.get_inline_qos().cloned(),
                             requested_change.data.clone(),
@@ -390,7 +640,15 @@ impl Writer {
     }
 
     #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), matched_readers = ?self.matched_readers))]
-    pub fn add_proxy(&mut self, proxy: ReaderProxy) {
+    /// Match a new Reader, with a TRANSIENT_LOCAL durability it receives the changes retained by the history
+    pub fn add_proxy(&mut self, effects: &mut Effects, mut proxy: ReaderProxy) {
+        if matches!(proxy.get_durability(), DurabilityQosPolicy::Volatile) {
+            // the changes written before the match are not relevant to the Reader
+            proxy.acked_changes_set(self.last_change_sequence_number);
+            proxy.set_highest_sent_change_sn(self.last_change_sequence_number);
+        } else if matches!(self.qos.durability, DurabilityQosPolicy::TransientLocal) {
+            self.produce_history(effects, &mut proxy);
+        }
         self.matched_readers
             .insert(proxy.get_remote_reader_guid(), proxy);
     }
@@ -439,7 +697,7 @@ impl Writer {
         }
 
         msg = msg.data(
-            ContentNature::Data,
+            Self::content_nature(change),
             change.get_sequence_number(),
             change.get_inline_qos().cloned(),
             change.get_data().cloned(),
@@ -480,6 +738,147 @@ impl Writer {
         Ok(())
     }
 
+    /// Whether every matched reliable Reader acknowledged all the changes written so far
+    ///
+    /// A best-effort Writer or Reader gets no acknowledgment, its changes are always considered acknowledged
+    pub fn is_acknowledged_by_all(&self) -> bool {
+        matches!(self.is_reliable, ReliabilityKind::BestEffort)
+            || self.last_change_sequence_number == SEQUENCENUMBER_UNKNOWN
+            || self
+                .matched_readers
+                .values()
+                .filter(|p| p.is_reliable())
+                .all(|p| !p.unacked_changes(self.last_change_sequence_number))
+    }
+
+    /// Make sure the history can keep `change`, otherwise release the sequence number it was given
+    ///
+    /// With KEEP_ALL, room is made by removing the changes acknowledged by every matched Reader,
+    /// they are also removed as soon as the acknowledgments are received
+    fn reserve(&mut self, change: &CacheChange) -> Result<(), Error> {
+        let instance = change.get_instance_handle();
+        if self.cache.check_resources(instance).is_err() {
+            self.remove_acknowledged_changes();
+        }
+        if self.cache.check_resources(instance).is_ok() {
+            return Ok(());
+        }
+        if change.get_sequence_number() == self.last_change_sequence_number {
+            self.last_change_sequence_number -= 1;
+        }
+        Err(Error::OutOfResources)
+    }
+
+    /// Remove from a reliable KEEP_ALL history the changes no matched reliable Reader still needs
+    ///
+    /// A TRANSIENT_LOCAL history keeps them for the late-joining Readers
+    fn remove_acknowledged_changes(&mut self) {
+        if !matches!(self.qos.history, HistoryQosPolicy::KeepAll)
+            || !matches!(self.is_reliable, ReliabilityKind::Reliable)
+            || !matches!(self.qos.durability, DurabilityQosPolicy::Volatile)
+        {
+            return;
+        }
+        let acked = self
+            .matched_readers
+            .values()
+            .filter(|p| p.is_reliable())
+            .map(|p| p.highest_acked_change())
+            .min()
+            .unwrap_or(self.last_change_sequence_number);
+        self.cache.remove_changes_until(acked);
+
+        event!(Level::DEBUG, %acked, "Acknowledged changes removed");
+    }
+
+    /// Deliver the changes retained by the history to a late-joining Reader
+    ///
+    /// A reliable Reader gets them as requested changes, announced by an immediate HEARTBEAT,
+    /// a best-effort one gets them pushed once
+    fn produce_history(&mut self, effects: &mut Effects, proxy: &mut ReaderProxy) {
+        let retained = self.cache.get_sequence_numbers();
+        let Some(&last) = retained.last() else {
+            return;
+        };
+
+        let mut msg = self
+            .message_factory
+            .message()
+            .reader(proxy.get_remote_reader_guid().get_entity_id())
+            .writer(self.guid.get_entity_id());
+
+        match self.is_reliable {
+            ReliabilityKind::Reliable => {
+                proxy.requested_changes_set(retained);
+
+                let count = self.heartbeat_counter.increase();
+                let max = self.last_change_sequence_number;
+                let min = self.cache.get_min_sequence().unwrap_or(max + 1);
+                msg = msg.heartbeat(false, false, min, max, count, None);
+
+                event!(Level::DEBUG, "HEARTBEAT produced for late-joining Reader");
+            }
+            ReliabilityKind::BestEffort => {
+                for sequence in retained {
+                    let change = self.cache.get_change(sequence).expect("presence asserted");
+                    if self.config.should_piggyback_timestamp {
+                        msg = msg.info_timestamp(change.get_emission_timestamp());
+                    }
+                    msg = msg.data(
+                        Self::content_nature(change),
+                        sequence,
+                        change.get_inline_qos().cloned(),
+                        change.get_data().cloned(),
+                    );
+                }
+
+                event!(Level::DEBUG, "History produced for late-joining Reader");
+            }
+        }
+
+        let effect = Effect::Message {
+            timestamp_millis: Default::default(),
+            message: msg.build(),
+            locators: proxy.get_locators(),
+        };
+        effects.push(effect);
+
+        proxy.set_highest_sent_change_sn(last);
+    }
+
+    fn produce_instance_state_change(
+        &mut self,
+        effects: &mut Effects,
+        instance: InstanceHandle,
+        kind: ChangeKind,
+        status_info: StatusInfo,
+        timestamp: Option<Timestamp>,
+    ) -> Result<(), Error> {
+        let inline_qos = InlineQos {
+            status_info,
+            ..self.qos.clone()
+        };
+        let mut change = self.new_change(kind, None, Some(inline_qos), instance);
+        self.reserve(&change)?;
+        if let Some(timestamp) = timestamp {
+            change.set_emission_timestamp(timestamp);
+        }
+        let expiration = change.expiration_time(self.qos.lifespan);
+        self.expiration
+            .schedule(effects, Utc::now().timestamp_millis(), expiration);
+        self.cache.push_change(change).unwrap();
+        self.produce_data(self.last_change_sequence_number, effects)
+    }
+
+    /// A change without payload only carries its instance state through the inline QoS
+    fn content_nature(change: &CacheChange) -> ContentNature {
+        match (change.infos.kind, change.get_data()) {
+            (_, None) => ContentNature::None,
+            (ChangeKind::Alive | ChangeKind::AliveFiltered, Some(_)) => ContentNature::Data,
+            (_, Some(_)) => ContentNature::Key,
+        }
+    }
+
     fn is_myself(&self, receiver: &MessageReceiver) -> bool {
         let self_guid_prefix = self.guid.get_guid_prefix();
         let remote_guid_prefix = receiver.source_guid_prefix;
@@ -491,31 +890,26 @@ impl Writer {
         self_entity_id == entity_id || entity_id == ENTITYID_UNKOWN
     }
 
+    /// Build the `gap_start` and `gap_list` of a GAP covering `gaps`
+    ///
+    /// The consecutive sequences starting at the lowest one are covered by the `[gap_start, gap_list.base)` range,
+    /// the others by the `gap_list` set
     fn build_gap_infos(gaps: &[SequenceNumber]) -> Option<(SequenceNumber, SequenceNumberSet)> {
-        let gap_first = *gaps.first()?;
+        let mut gaps = gaps.to_vec();
+        gaps.sort();
+        gaps.dedup();
 
-        let mut gap_last = gap_first;
-        let mut gap_last_pos = 0;
-        let mut gaps_peekable_iter = gaps.iter().peekable();
-        while let Some((pos, &gap)) = gaps_peekable_iter.clone().enumerate().next() {
-            gap_last = gap;
-            gap_last_pos = pos;
-            let Some(&&next) = gaps_peekable_iter.peek() else {
-                break;
-            };
-            if next > gap + 1 {
-                break;
-            }
-        }
-        let gap_unconsecutives = gaps
+        let gap_first = *gaps.first()?;
+        let consecutive_count = gaps
             .iter()
-            .skip(gap_last_pos + 1)
-            .cloned()
-            .collect::<Vec<_>>();
+            .zip(gap_first.0..)
+            .take_while(|(gap, expected)| gap.0 == *expected)
+            .count();
+        let gap_list_base = SequenceNumber(gap_first.0 + consecutive_count as i64);
 
         Some((
             gap_first,
-            SequenceNumberSet::new(gap_last, &gap_unconsecutives),
+            SequenceNumberSet::new(gap_list_base, &gaps[consecutive_count..]),
         ))
     }
 
@@ -534,19 +928,22 @@ impl Writer {
 mod test {
     use crate::{
         common::TickId,
-        messages::{Message, MessageFactory},
+        messages::{Message, MessageFactory, SubmessageContent},
         types::{
-            ChangeKind, Count, EntityId, Guid, InlineQos, InstanceHandle, LocatorList,
-            ReliabilityKind, SequenceNumber, SequenceNumberSet, SerializedData,
+            ChangeKind, Count, DurabilityQosPolicy, EntityId, GroupDataQosPolicy, Guid,
+            HistoryQosPolicy, InlineQos, InstanceHandle, LifespanQosPolicy, LivelinessKind,
+            LivelinessQosPolicy, LocatorList, PartitionQosPolicy, ReliabilityKind,
+            ResourceLimitsQosPolicy, SequenceNumber, SequenceNumberSet, SerializedData, Timestamp,
+            TopicDataQosPolicy, UserDataQosPolicy,
         },
     };
-    use chrono::Utc;
+    use chrono::{DateTime, Utc};
     use rstest::{fixture, rstest};
 
     use crate::{
         CacheChange, Effects, ReaderProxy,
         common::{
-            Effect, EffectConsumption,
+            Effect, EffectConsumption, Error,
             tests::{setup_reader_0_guid, setup_uni_locatorlist, setup_writer_0_guid},
         },
     };
@@ -644,6 +1041,574 @@ mod test {
         // TODO: effects should contains a Message (Heartbeat) and a Message (Data)
     }
 
+    #[rstest]
+    fn dispose(#[from(setup_writer)] mut writer: Writer) {
+        let mut effects = Effects::new();
+        let instance = InstanceHandle::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+        assert!(
+            writer
+                .dispose_instance(&mut effects, instance, None)
+                .is_err()
+        );
+
+        let change = new_change_helper(&mut writer);
+        writer.add_change(&mut effects, change).unwrap();
+        effects.clean();
+
+        writer
+            .dispose_instance(&mut effects, instance, None)
+            .unwrap();
+
+        let Some(Effect::Message { message, .. }) = effects.pop() else {
+            panic!()
+        };
+        let Some(SubmessageContent::Data {
+            inline_qos: Some(inline_qos),
+            serialized_data: None,
+            ..
+        }) = message.submessages.into_iter().map(|s| s.content).last()
+        else {
+            panic!()
+        };
+        let inline_qos = InlineQos::from(inline_qos);
+        assert_eq!(inline_qos.key_hash, instance);
+        assert_eq!(inline_qos.status_info.disposed(), 1);
+    }
+
+    #[rstest]
+    fn data_qos_not_inlined(
+        #[from(setup_writer)]
+        #[with(ReliabilityKind::Reliable, InlineQos { user_data: UserDataQosPolicy::new(b"user".as_slice()), topic_data: TopicDataQosPolicy::new(b"topic".as_slice()), group_data: GroupDataQosPolicy::new(b"group".as_slice()), ..Default::default() })]
+        mut writer: Writer,
+    ) {
+        let qos = writer.qos.clone();
+        let change = writer.new_change(
+            ChangeKind::Alive,
+            Some(SerializedData::from_vec(vec![0, 1, 2, 3])),
+            Some(qos),
+            InstanceHandle::default(),
+        );
+
+        let inline_qos = change.get_inline_qos().unwrap();
+        assert!(inline_qos.user_data.value.is_empty());
+        assert!(inline_qos.topic_data.value.is_empty());
+        assert!(inline_qos.group_data.value.is_empty());
+    }
+
+    #[rstest]
+    fn lifespan(
+        #[from(setup_writer)]
+        #[with(ReliabilityKind::Reliable, InlineQos { lifespan: LifespanQosPolicy { duration: Timestamp::from_datetime(DateTime::from_timestamp_millis(100).unwrap()) }, ..Default::default() })]
+        mut writer: Writer,
+    ) {
+        let mut effects = Effects::new();
+        let qos = writer.qos.clone();
+        let change = writer.new_change(
+            ChangeKind::Alive,
+            Some(SerializedData::from_vec(vec![0, 1, 2, 3])),
+            Some(qos),
+            InstanceHandle::default(),
+        );
+        let emission = change
+            .get_emission_timestamp()
+            .unwrap()
+            .to_datetime()
+            .timestamp_millis();
+        writer.add_change(&mut effects, change).unwrap();
+        assert!(
+            effects
+                .find(|e| matches!(
+                    e,
+                    Effect::ScheduleTick {
+                        id: TickId::Lifespan,
+                        ..
+                    }
+                ))
+                .is_some()
+        );
+        effects.clean();
+
+        writer.lifespan_tick(&mut effects, emission + 50);
+        assert!(writer.cache.get_change(SequenceNumber(1)).is_some());
+        effects.clean();
+
+        // the heartbeat tick leaves the expired change to the lifespan tick
+        writer.tick(&mut effects, emission + 100);
+        assert!(writer.cache.get_change(SequenceNumber(1)).is_some());
+        effects.clean();
+
+        writer.lifespan_tick(&mut effects, emission + 100);
+        assert!(writer.cache.get_change(SequenceNumber(1)).is_none());
+        effects.clean();
+
+        // an expired change is answered with a GAP
+        let reader_proxy = writer.matched_readers.values_mut().next().unwrap();
+        reader_proxy.requested_changes_set(vec![SequenceNumber(1)]);
+        writer.tick(&mut effects, emission + 150);
+
+        let Some(Effect::Message { message, .. }) = effects.pop() else {
+            panic!()
+        };
+        assert!(
+            message
+                .submessages
+                .iter()
+                .any(|s| matches!(s.content, SubmessageContent::Gap { .. }))
+        );
+        assert!(
+            !message
+                .submessages
+                .iter()
+                .any(|s| matches!(s.content, SubmessageContent::Data { .. }))
+        );
+    }
+
+    #[rstest]
+    fn registration_timestamp(#[from(setup_writer)] mut writer: Writer) {
+        let mut effects = Effects::new();
+        let instance = InstanceHandle([1; 16]);
+        let timestamp = Timestamp::from_datetime(DateTime::from_timestamp_millis(1_000).unwrap());
+
+        writer.register_instance(instance, Some(timestamp));
+        // registering again keeps the first registration
+        writer.register_instance(instance, None);
+        assert_eq!(writer.get_registration_timestamp(instance), Some(timestamp));
+
+        writer
+            .unregister_instance(&mut effects, instance, None)
+            .unwrap();
+        assert!(writer.get_registration_timestamp(instance).is_none());
+    }
+
+    #[rstest]
+    fn gap_infos() {
+        assert!(Writer::build_gap_infos(&[]).is_none());
+
+        let (gap_start, gap_list) = Writer::build_gap_infos(&[
+            SequenceNumber(3),
+            SequenceNumber(1),
+            SequenceNumber(2),
+            SequenceNumber(6),
+            SequenceNumber(2),
+        ])
+        .unwrap();
+        assert_eq!(gap_start, SequenceNumber(1));
+        assert_eq!(gap_list.get_base(), SequenceNumber(4));
+        assert_eq!(gap_list.get_set(), vec![SequenceNumber(6)]);
+
+        let (gap_start, gap_list) = Writer::build_gap_infos(&[SequenceNumber(5)]).unwrap();
+        assert_eq!(gap_start, SequenceNumber(5));
+        assert_eq!(gap_list.get_base(), SequenceNumber(6));
+        assert!(gap_list.get_set().is_empty());
+    }
+
+    #[rstest]
+    fn liveliness(
+        #[from(setup_writer)]
+        #[with(ReliabilityKind::Reliable, InlineQos { liveness: LivelinessQosPolicy { kind: LivelinessKind::ManualByTopic, lease_duration: Timestamp::from_datetime(DateTime::from_timestamp_millis(100).unwrap()) }, ..Default::default() })]
+        mut writer: Writer,
+    ) {
+        let mut effects = Effects::new();
+
+        writer.assert_liveliness(&mut effects, 0);
+        let Some(Effect::Message { message, .. }) = effects.pop() else {
+            panic!()
+        };
+        let heartbeat = message
+            .submessages
+            .iter()
+            .find(|s| matches!(s.content, SubmessageContent::Heartbeat { .. }))
+            .unwrap();
+        assert_eq!(heartbeat.header.flags.third(), 1);
+        assert!(matches!(
+            effects.pop(),
+            Some(Effect::ScheduleTick {
+                id: TickId::Liveliness,
+                delay: 100
+            })
+        ));
+
+        writer.liveliness_tick(&mut effects, 100);
+        let Some(Effect::LivelinessLost { status }) = effects.pop() else {
+            panic!("liveliness should have been lost")
+        };
+        assert_eq!(status.total_count, 1);
+
+        // asserting the Participant doesn't concern MANUAL_BY_TOPIC Writers
+        writer.participant_liveliness_asserted(&mut effects, 150);
+        assert!(effects.is_empty());
+    }
+
+    #[rstest]
+    fn resource_limits(
+        #[from(setup_writer)]
+        #[with(ReliabilityKind::Reliable, InlineQos { history: HistoryQosPolicy::KeepAll, resource_limits: ResourceLimitsQosPolicy { max_samples: 2, max_instances: 1, max_samples_per_instance: 2 }, ..Default::default() })]
+        mut writer: Writer,
+    ) {
+        let mut effects = Effects::new();
+        for _ in 0..2 {
+            let change = new_change_helper(&mut writer);
+            writer.add_change(&mut effects, change).unwrap();
+        }
+
+        let change = new_change_helper(&mut writer);
+        assert!(matches!(
+            writer.add_change(&mut effects, change),
+            Err(Error::OutOfResources)
+        ));
+
+        // the sequence number of the refused change is given to the next one
+        let change = new_change_helper(&mut writer);
+        assert_eq!(change.get_sequence_number(), SequenceNumber(3));
+    }
+
+    #[rstest]
+    fn keep_all_acknowledged(
+        #[from(setup_writer)]
+        #[with(ReliabilityKind::Reliable, InlineQos { history: HistoryQosPolicy::KeepAll, resource_limits: ResourceLimitsQosPolicy { max_samples: 2, max_instances: 1, max_samples_per_instance: 2 }, ..Default::default() })]
+        mut writer: Writer,
+        #[from(setup_writer_0_guid)] proxy_guid: Guid,
+    ) {
+        let mut effects = Effects::new();
+        assert!(writer.is_acknowledged_by_all());
+        for _ in 0..2 {
+            let change = new_change_helper(&mut writer);
+            writer.add_change(&mut effects, change).unwrap();
+        }
+        assert!(!writer.is_acknowledged_by_all());
+
+        // the Reader received the first change only, one change can be removed
+        let ack = acknack_helper(proxy_guid, SequenceNumber(2), 1);
+        writer.ingest(&mut effects, 0, ack).unwrap();
+        let change = new_change_helper(&mut writer);
+        writer.add_change(&mut effects, change).unwrap();
+        assert_eq!(
+            writer.cache.get_sequence_numbers(),
+            vec![SequenceNumber(2), SequenceNumber(3)]
+        );
+
+        let change = new_change_helper(&mut writer);
+        assert!(matches!(
+            writer.add_change(&mut effects, change),
+            Err(Error::OutOfResources)
+        ));
+
+        let ack = acknack_helper(proxy_guid, SequenceNumber(4), 2);
+        writer.ingest(&mut effects, 0, ack).unwrap();
+        assert!(writer.is_acknowledged_by_all());
+    }
+
+    #[rstest]
+    fn acknowledged_changes_removed(
+        #[from(setup_writer)]
+        #[with(ReliabilityKind::Reliable, InlineQos { history: HistoryQosPolicy::KeepAll, ..Default::default() })]
+        mut writer: Writer,
+        #[from(setup_writer_0_guid)] proxy_guid: Guid,
+        #[from(setup_reader_0_guid)] best_effort_reader_guid: Guid,
+        #[from(setup_uni_locatorlist)] uni_locators: LocatorList,
+    ) {
+        let mut effects = Effects::new();
+        let mut best_effort_reader = ReaderProxy::new(
+            best_effort_reader_guid,
+            EntityId::default(),
+            false,
+            true,
+            uni_locators,
+            LocatorList::default(),
+        );
+        best_effort_reader.is_reliable = false;
+        writer.add_proxy(&mut effects, best_effort_reader);
+
+        for _ in 0..3 {
+            let change = new_change_helper(&mut writer);
+            writer.add_change(&mut effects, change).unwrap();
+        }
+
+        // without resource limits, the acknowledged changes are removed as soon as the ACKNACK is received
+        let ack = acknack_helper(proxy_guid, SequenceNumber(3), 1);
+        writer.ingest(&mut effects, 0, ack).unwrap();
+        assert_eq!(writer.cache.get_sequence_numbers(), vec![SequenceNumber(3)]);
+        assert!(!writer.is_acknowledged_by_all());
+
+        // the best-effort Reader never acknowledges, it is not waited for
+        let ack = acknack_helper(proxy_guid, SequenceNumber(4), 2);
+        writer.ingest(&mut effects, 0, ack).unwrap();
+        assert!(writer.cache.get_sequence_numbers().is_empty());
+        assert!(writer.is_acknowledged_by_all());
+    }
+
+    #[rstest]
+    fn transient_local_best_effort(
+        #[from(setup_writer)]
+        #[with(ReliabilityKind::BestEffort, InlineQos { durability: DurabilityQosPolicy::TransientLocal, history: HistoryQosPolicy::KeepLast { depth: 2 }, ..Default::default() })]
+        mut writer: Writer,
+        #[from(setup_reader_0_guid)] late_reader_guid: Guid,
+        #[from(setup_uni_locatorlist)] uni_locators: LocatorList,
+    ) {
+        let mut effects = Effects::new();
+        for _ in 0..3 {
+            let change = new_change_helper(&mut writer);
+            writer.add_change(&mut effects, change).unwrap();
+        }
+        effects.clean();
+
+        let late_reader = ReaderProxy::new(
+            late_reader_guid,
+            EntityId::default(),
+            false,
+            false,
+            uni_locators,
+            LocatorList::default(),
+        );
+        writer.add_proxy(&mut effects, late_reader);
+
+        // only the changes retained by the history are pushed, once
+        let Some(Effect::Message { message, .. }) = effects.pop() else {
+            panic!("history should have been pushed")
+        };
+        let sequences = message
+            .submessages
+            .iter()
+            .filter_map(|s| match s.content {
+                SubmessageContent::Data { writer_sn, .. } => Some(writer_sn),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(sequences, vec![SequenceNumber(2), SequenceNumber(3)]);
+        assert!(effects.is_empty());
+
+        writer.tick(&mut effects, Utc::now().timestamp_millis());
+        assert!(effects.is_empty());
+    }
+
+    #[rstest]
+    fn transient_local_reliable(
+        #[from(setup_writer)]
+        #[with(ReliabilityKind::Reliable, InlineQos { durability: DurabilityQosPolicy::TransientLocal, history: HistoryQosPolicy::KeepLast { depth: 2 }, ..Default::default() })]
+        mut writer: Writer,
+        #[from(setup_reader_0_guid)] late_reader_guid: Guid,
+        #[from(setup_uni_locatorlist)] uni_locators: LocatorList,
+    ) {
+        let mut effects = Effects::new();
+        for _ in 0..3 {
+            let change = new_change_helper(&mut writer);
+            writer.add_change(&mut effects, change).unwrap();
+        }
+        effects.clean();
+
+        let late_reader = ReaderProxy::new(
+            late_reader_guid,
+            EntityId::default(),
+            false,
+            true,
+            uni_locators,
+            LocatorList::default(),
+        );
+        writer.add_proxy(&mut effects, late_reader);
+
+        // the retained changes are announced right away
+        let Some(Effect::Message { message, .. }) = effects.pop() else {
+            panic!("HEARTBEAT should have been produced")
+        };
+        assert!(matches!(
+            message.submessages[..],
+            [.., ref s] if matches!(s.content, SubmessageContent::Heartbeat { first_sn: SequenceNumber(2), last_sn: SequenceNumber(3), .. })
+        ));
+        assert!(effects.is_empty());
+
+        // and delivered with the next tick, without waiting for an ACKNACK
+        writer.tick(&mut effects, Utc::now().timestamp_millis());
+        let mut delivered = Vec::new();
+        while let Some(effect) = effects.pop() {
+            if let Effect::Message { message, .. } = effect {
+                delivered.extend(message.submessages.iter().filter_map(|s| match s.content {
+                    SubmessageContent::Data { writer_sn, .. } => Some(writer_sn),
+                    _ => None,
+                }));
+            }
+        }
+        delivered.sort();
+        assert_eq!(delivered, vec![SequenceNumber(2), SequenceNumber(3)]);
+    }
+
+    #[rstest]
+    fn transient_local_volatile_reader(
+        #[from(setup_writer)]
+        #[with(ReliabilityKind::Reliable, InlineQos { durability: DurabilityQosPolicy::TransientLocal, history: HistoryQosPolicy::KeepLast { depth: 2 }, ..Default::default() })]
+        mut writer: Writer,
+        #[from(setup_writer_0_guid)] proxy_guid: Guid,
+        #[from(setup_reader_0_guid)] late_reader_guid: Guid,
+        #[from(setup_uni_locatorlist)] uni_locators: LocatorList,
+    ) {
+        let mut effects = Effects::new();
+        for _ in 0..3 {
+            let change = new_change_helper(&mut writer);
+            writer.add_change(&mut effects, change).unwrap();
+        }
+        let ack = acknack_helper(proxy_guid, SequenceNumber(4), 1);
+        writer.ingest(&mut effects, 0, ack).unwrap();
+        effects.clean();
+
+        let mut late_reader = ReaderProxy::new(
+            late_reader_guid,
+            EntityId::default(),
+            false,
+            true,
+            uni_locators,
+            LocatorList::default(),
+        );
+        late_reader.durability = DurabilityQosPolicy::Volatile;
+        writer.add_proxy(&mut effects, late_reader);
+        assert!(effects.is_empty());
+
+        // the history is neither announced nor delivered, even when the Reader asks from the start
+        let ack = acknack_helper(late_reader_guid, SequenceNumber(1), 1);
+        writer.ingest(&mut effects, 0, ack).unwrap();
+        effects.clean();
+        writer.tick(&mut effects, Utc::now().timestamp_millis());
+        assert!(
+            effects
+                .find(|e| matches!(e, Effect::Message { .. }))
+                .is_none()
+        );
+
+        // the next change is announced from its own sequence number
+        let change = new_change_helper(&mut writer);
+        writer.add_change(&mut effects, change).unwrap();
+        effects.clean();
+        writer.tick(&mut effects, Utc::now().timestamp_millis());
+        let mut first_sequences = Vec::new();
+        while let Some(effect) = effects.pop() {
+            if let Effect::Message { message, .. } = effect {
+                first_sequences.extend(message.submessages.iter().filter_map(
+                    |s| match s.content {
+                        SubmessageContent::Heartbeat { first_sn, .. } => Some(first_sn),
+                        _ => None,
+                    },
+                ));
+            }
+        }
+        assert_eq!(first_sequences, vec![SequenceNumber(4), SequenceNumber(4)]);
+    }
+
+    #[rstest]
+    fn coherent_set(#[from(setup_writer)] mut writer: Writer) {
+        let mut effects = Effects::new();
+        let instance = InstanceHandle::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+        assert!(matches!(
+            writer.end_coherent_set(&mut effects, None),
+            Err(Error::NoCoherentSet)
+        ));
+
+        writer.begin_coherent_set();
+        for _ in 0..2 {
+            let change = writer.new_change(
+                ChangeKind::Alive,
+                Some(SerializedData::from_vec(vec![0, 1, 2, 3])),
+                Some(InlineQos::default()),
+                instance,
+            );
+            assert_eq!(
+                change.get_inline_qos().unwrap().coherent_set,
+                Some(SequenceNumber(1))
+            );
+            writer.add_change(&mut effects, change).unwrap();
+        }
+        effects.clean();
+
+        writer.end_coherent_set(&mut effects, None).unwrap();
+
+        let Some(Effect::Message { message, .. }) = effects.pop() else {
+            panic!()
+        };
+        let Some(SubmessageContent::Data {
+            inline_qos: Some(inline_qos),
+            serialized_data: None,
+            ..
+        }) = message.submessages.into_iter().map(|s| s.content).last()
+        else {
+            panic!()
+        };
+        let inline_qos = InlineQos::from(inline_qos);
+        assert_eq!(inline_qos.coherent_set, Some(SequenceNumber(1)));
+
+        // the changes written after the end of the set are independent
+        let change = writer.new_change(
+            ChangeKind::Alive,
+            Some(SerializedData::from_vec(vec![0, 1, 2, 3])),
+            Some(InlineQos::default()),
+            instance,
+        );
+        assert_eq!(change.get_inline_qos().unwrap().coherent_set, None);
+    }
+
+    #[rstest]
+    fn coherent_set_end_takes_no_room(
+        #[from(setup_writer)]
+        #[with(ReliabilityKind::Reliable, InlineQos { history: HistoryQosPolicy::KeepAll, resource_limits: ResourceLimitsQosPolicy { max_samples: 2, max_instances: 1, max_samples_per_instance: 2 }, ..Default::default() })]
+        mut writer: Writer,
+    ) {
+        let mut effects = Effects::new();
+        writer.begin_coherent_set();
+        for _ in 0..2 {
+            let change = new_change_helper(&mut writer);
+            writer.add_change(&mut effects, change).unwrap();
+        }
+
+        // the history is full and the end of the set is of no instance, it is still sent
+        writer.end_coherent_set(&mut effects, None).unwrap();
+        assert_eq!(
+            writer.cache.get_sequence_numbers(),
+            vec![SequenceNumber(1), SequenceNumber(2), SequenceNumber(3)]
+        );
+        assert!(matches!(
+            writer.end_coherent_set(&mut effects, None),
+            Err(Error::NoCoherentSet)
+        ));
+    }
+
+    #[rstest]
+    fn coherent_set_end_replaced_with_its_set(
+        #[from(setup_writer)]
+        #[with(ReliabilityKind::Reliable, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 1 }, ..Default::default() })]
+        mut writer: Writer,
+    ) {
+        let mut effects = Effects::new();
+        writer.begin_coherent_set();
+        let change = new_change_helper(&mut writer);
+        writer.add_change(&mut effects, change).unwrap();
+        writer.end_coherent_set(&mut effects, None).unwrap();
+        assert_eq!(
+            writer.cache.get_sequence_numbers(),
+            vec![SequenceNumber(1), SequenceNumber(2)]
+        );
+
+        let change = new_change_helper(&mut writer);
+        writer.add_change(&mut effects, change).unwrap();
+        assert_eq!(writer.cache.get_sequence_numbers(), vec![SequenceNumber(3)]);
+    }
+
+    #[rstest]
+    fn set_qos(#[from(setup_writer)] mut writer: Writer) {
+        let qos = InlineQos {
+            partition: PartitionQosPolicy::new(["A"]),
+            ..Default::default()
+        };
+        writer.set_qos(&mut Effects::new(), qos.clone(), 0).unwrap();
+        assert_eq!(writer.qos, qos);
+
+        let qos = InlineQos {
+            history: HistoryQosPolicy::KeepAll,
+            ..qos
+        };
+        assert!(matches!(
+            writer.set_qos(&mut Effects::new(), qos, 0),
+            Err(Error::ImmutablePolicy("HISTORY"))
+        ));
+    }
+
     fn new_change_helper(writer: &mut Writer) -> CacheChange {
         writer.new_change(
             ChangeKind::Alive,
@@ -653,6 +1618,15 @@ mod test {
         )
     }
 
+    fn acknack_helper(reader_guid: Guid, base: SequenceNumber, count: i32) -> Message {
+        MessageFactory::new(reader_guid.get_guid_prefix())
+            .message()
+            .reader(reader_guid.get_entity_id())
+            .writer(EntityId::default())
+            .acknack(SequenceNumberSet::new(base, &[]), Count::new(count))
+            .build()
+    }
+
     #[fixture]
     fn setup_ack(
         #[from(setup_reader_0_guid)] reader_guid_0: Guid,
@@ -683,7 +1657,7 @@ mod test {
             .with_configuration(conf)
             .reliability(reliable)
             .build();
-        writer.add_proxy(proxy);
+        writer.add_proxy(&mut Effects::new(), proxy);
         writer
     }
 
@@ -1,15 +1,21 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 
 use crate::{
-    subscription::SampleStateKind,
-    types::{Guid, InstanceHandle, SequenceNumber},
+    subscription::{InstanceStateKind, SampleStateKind, ViewStateKind},
+    types::{
+        ChangeKind, DestinationOrderQosPolicy, GroupCoherentSet, Guid, InstanceHandle,
+        PresentationAccessScope, PresentationQosPolicy, SequenceNumber, Timestamp,
+    },
 };
 use contracts::requires;
 use thiserror::Error;
 
 use crate::{
     CacheChange,
-    common::{CacheChangeContainer, FragmentedCacheChange},
+    common::{
+        CacheChangeContainer, FragmentedCacheChange, SampleRejectedStatusKind,
+        check_history_limits, preallocated_changes,
+    },
 };
 
 #[derive(Debug)]
@@ -18,6 +24,8 @@ pub struct ReaderHistoryCacheConfiguration {
     max_instances: u32,
     max_samples_per_instance: u32,
     max_samples: u32,
+    destination_order: DestinationOrderQosPolicy,
+    presentation: PresentationQosPolicy,
 }
 
 impl ReaderHistoryCacheConfiguration {
@@ -29,12 +37,16 @@ impl ReaderHistoryCacheConfiguration {
         max_instances: u32,
         max_samples_per_instance: u32,
         max_samples: u32,
+        destination_order: DestinationOrderQosPolicy,
+        presentation: PresentationQosPolicy,
     ) -> Self {
         Self {
             depth,
             max_instances,
             max_samples_per_instance,
             max_samples,
+            destination_order,
+            presentation,
         }
     }
 }
@@ -43,6 +55,10 @@ impl ReaderHistoryCacheConfiguration {
 pub enum ReaderHistoryCacheError {
     #[error("The Change's SequenceNumber({0}) is already in use")]
     SequenceAlreadyPresent(SequenceNumber),
+    #[error("The RESOURCE_LIMITS prevent from keeping the Change: {0:?}")]
+    SampleRejected(SampleRejectedStatusKind),
+    #[error("A Change with a more recent source timestamp than {0} was already accepted")]
+    OutdatedSourceTimestamp(Timestamp),
     #[error("The FragmentedCacheChange<guid:{writer_guid}, sequence:{sequence}> is not in cache")]
     FragmentedCacheChangeAbsent {
         writer_guid: Guid,
@@ -50,14 +66,74 @@ pub enum ReaderHistoryCacheError {
     },
 }
 
+#[derive(Debug, Default, Clone)]
+struct InstanceInfos {
+    view_state: ViewStateKind,
+    instance_state: InstanceStateKind,
+    alive_writers: HashSet<Guid>,
+    disposed_generation_count: i32,
+    no_writers_generation_count: i32,
+    source_timestamp: Option<Timestamp>,
+}
+
+/// Changes of a coherent set, held back until every change of the set has been received
+#[derive(Debug, Default)]
+struct CoherentSet {
+    changes: Vec<CacheChange>,
+    received: BTreeSet<SequenceNumber>,
+    end: Option<SequenceNumber>,
+    /// Group coherent set of the set, it is only presented with the sets of the other DataWriters of the group
+    group: Option<GroupCoherentSet>,
+    is_group_reported: bool,
+    is_group_committed: bool,
+}
+
+impl CoherentSet {
+    fn is_ended(&self) -> bool {
+        self.end.is_some()
+    }
+
+    fn is_committable(&self) -> bool {
+        self.group.is_none() || self.is_group_committed
+    }
+
+    /// Whether every change of the set was received, or `skipped` because it will never be
+    fn is_complete(
+        &self,
+        first: SequenceNumber,
+        skipped: Option<&BTreeSet<SequenceNumber>>,
+    ) -> bool {
+        self.end.is_some_and(|end| {
+            (first.0..end.0).map(SequenceNumber).all(|sequence| {
+                self.received.contains(&sequence)
+                    || skipped.is_some_and(|skipped| skipped.contains(&sequence))
+            })
+        })
+    }
+}
+
+impl InstanceInfos {
+    fn generation(&self) -> i32 {
+        self.disposed_generation_count + self.no_writers_generation_count
+    }
+}
+
 #[derive(Debug)]
 pub struct ReaderHistoryCache {
     changes: VecDeque<CacheChangeContainer>,
     frag_changes: HashMap<(SequenceNumber, Guid), FragmentedCacheChange>,
+    instances: HashMap<InstanceHandle, InstanceInfos>,
     depth: Option<u32>,
     max_instances: u32,
     max_samples_per_instance: u32,
     max_samples: u32,
+    destination_order: DestinationOrderQosPolicy,
+    coherent_access: bool,
+    group_access: bool,
+    coherent_sets: HashMap<(Guid, SequenceNumber), CoherentSet>,
+    /// Changes of each Writer which will never be received, their coherent set may not be known yet
+    skipped_changes: HashMap<Guid, BTreeSet<SequenceNumber>>,
+    is_accessed: bool,
     trash: VecDeque<CacheChange>,
 }
 
@@ -68,39 +144,290 @@ impl ReaderHistoryCache {
             max_instances,
             max_samples_per_instance,
             max_samples,
+            destination_order,
+            presentation,
         } = config;
-        let changes_size = config.depth.unwrap_or(max_samples) as usize;
+        let changes_size = preallocated_changes(depth, max_samples);
         let changes = VecDeque::with_capacity(changes_size);
         let frag_changes = HashMap::with_capacity(changes_size);
         let trash = VecDeque::with_capacity(1);
         Self {
             changes,
             frag_changes,
+            instances: HashMap::default(),
             depth,
             max_instances,
             max_samples_per_instance,
             max_samples,
+            destination_order,
+            coherent_access: presentation.coherent_access,
+            group_access: presentation.coherent_access
+                && matches!(presentation.access_scope, PresentationAccessScope::Group),
+            coherent_sets: HashMap::default(),
+            skipped_changes: HashMap::default(),
+            is_accessed: false,
             trash,
         }
     }
 
     /// Push a new CacheChange into the ReaderHistoryCache
     ///
+    /// With KEEP_LAST, the oldest change is replaced once the depth is reached,
+    /// otherwise the change is rejected if it exceeds the RESOURCE_LIMITS.
+    /// With BY_SOURCE_TIMESTAMP DESTINATION_ORDER, a change older than the last one accepted for its instance is discarded.
+    /// With coherent access, a change belonging to a coherent set is held back until the set is complete
+    ///
     /// # Preconditions
     /// - change.sequence must not be already in use
     pub fn push_change(&mut self, change: CacheChange) -> Result<(), ReaderHistoryCacheError> {
-        let change = CacheChangeContainer::new(change);
-        if let Some(depth) = self.depth {
-            if self.changes.len() == depth as usize {
-                let taken_change = self.changes.pop_back().expect("presence asserted");
-                self.trash.push_front(taken_change.into_inner());
+        if self.coherent_access
+            && let Some(first) = change.get_inline_qos().and_then(|qos| qos.coherent_set)
+        {
+            let set = self
+                .coherent_sets
+                .entry((change.get_guid(), first))
+                .or_default();
+            set.received.insert(change.get_sequence_number());
+            set.changes.push(change);
+            return Ok(());
+        }
+
+        // outside of any coherent set, the earlier changes skipped belong to no set still expected
+        if !self
+            .coherent_sets
+            .keys()
+            .any(|(guid, _)| *guid == change.get_guid())
+            && let Some(skipped) = self.skipped_changes.get_mut(&change.get_guid())
+        {
+            skipped.retain(|sequence| *sequence > change.get_sequence_number());
+        }
+
+        self.insert_change(change)
+    }
+
+    fn insert_change(&mut self, change: CacheChange) -> Result<(), ReaderHistoryCacheError> {
+        if let Some(source_timestamp) = self.outdated_source_timestamp(
+            change.get_instance_handle(),
+            change.get_emission_timestamp(),
+        ) {
+            return Err(ReaderHistoryCacheError::OutdatedSourceTimestamp(
+                source_timestamp,
+            ));
+        }
+
+        let replaced = check_history_limits(
+            self.changes.iter().map(|c| c.get_instance_handle()),
+            change.get_instance_handle(),
+            self.depth,
+            self.max_instances,
+            self.max_samples_per_instance,
+            self.max_samples,
+        )
+        .map_err(ReaderHistoryCacheError::SampleRejected)?;
+
+        self.update_instance(&change);
+        let mut change = CacheChangeContainer::new(change);
+        if let Some(infos) = self.instances.get(&change.get_instance_handle()) {
+            let generation = change.get_generation_infos_mut();
+            generation.disposed_generation_count = infos.disposed_generation_count;
+            generation.no_writers_generation_count = infos.no_writers_generation_count;
+        }
+        if replaced {
+            let taken_change = self.changes.pop_back().expect("presence asserted");
+            self.trash.push_front(taken_change.into_inner());
+        }
+        self.changes.push_front(change);
+        Ok(())
+    }
+
+    /// Record a change of a coherent set which was received but filtered out, the set can still be complete
+    pub fn skip_coherent_change(
+        &mut self,
+        writer_guid: Guid,
+        first: SequenceNumber,
+        sequence: SequenceNumber,
+    ) {
+        if self.coherent_access {
+            self.coherent_sets
+                .entry((writer_guid, first))
+                .or_default()
+                .received
+                .insert(sequence);
+        }
+    }
+
+    /// Record the changes of `writer_guid` which will never be received, removed from the Writer history or lost
+    ///
+    /// The coherent sets they belong to can still be complete
+    pub fn skip_coherent_changes(
+        &mut self,
+        writer_guid: Guid,
+        sequences: impl IntoIterator<Item = SequenceNumber>,
+    ) {
+        if self.coherent_access {
+            self.skipped_changes
+                .entry(writer_guid)
+                .or_default()
+                .extend(sequences);
+        }
+    }
+
+    /// Record the end of a coherent set, signaled by the change `end`, part of the `group` coherent set if any
+    ///
+    /// The earlier sets of the same Writer which are still incomplete will never be, they are discarded
+    pub fn end_coherent_set(
+        &mut self,
+        writer_guid: Guid,
+        first: SequenceNumber,
+        end: SequenceNumber,
+        group: Option<GroupCoherentSet>,
+    ) {
+        if !self.coherent_access {
+            return;
+        }
+        let set = self.coherent_sets.entry((writer_guid, first)).or_default();
+        set.end.replace(end);
+        // with a narrower access scope, the set is presented on its own
+        set.group = group.filter(|_| self.group_access);
+
+        let skipped = self.skipped_changes.get(&writer_guid);
+        let incomplete = self
+            .coherent_sets
+            .iter()
+            .filter(|((guid, set_first), set)| {
+                *guid == writer_guid && *set_first < first && !set.is_complete(*set_first, skipped)
+            })
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        self.discard_coherent_sets(|key, _| incomplete.contains(key));
+        if let Some(skipped) = self.skipped_changes.get_mut(&writer_guid) {
+            skipped.retain(|sequence| *sequence >= first);
+        }
+    }
+
+    /// Discard the ended coherent sets of `writer_guid` which are incomplete, their missing changes will not be sent again
+    pub fn discard_incomplete_coherent_sets(&mut self, writer_guid: Guid) {
+        let skipped = self.skipped_changes.get(&writer_guid);
+        let incomplete = self
+            .coherent_sets
+            .iter()
+            .filter(|((guid, first), set)| {
+                *guid == writer_guid && set.is_ended() && !set.is_complete(*first, skipped)
+            })
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        self.discard_coherent_sets(|key, _| incomplete.contains(key));
+    }
+
+    /// Discard every coherent set of a Writer which is no longer matched
+    pub fn remove_coherent_sets(&mut self, writer_guid: Guid) {
+        self.discard_coherent_sets(|(guid, _), _| *guid == writer_guid);
+        self.skipped_changes.remove(&writer_guid);
+    }
+
+    /// Make the changes of every complete coherent set available, unless the cache is being accessed
+    ///
+    /// A set which is part of a group coherent set waits for [`ReaderHistoryCache::commit_group_coherent_set`],
+    /// the sets of a Writer are made available in order.
+    /// Return the errors of the changes which could not be kept
+    pub fn commit_coherent_sets(&mut self) -> Vec<ReaderHistoryCacheError> {
+        if self.is_accessed {
+            return Vec::new();
+        }
+
+        let mut complete = self
+            .coherent_sets
+            .iter()
+            .filter(|((guid, first), set)| {
+                set.is_committable() && set.is_complete(*first, self.skipped_changes.get(guid))
+            })
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        complete.sort();
+
+        let mut errors = Vec::new();
+        for key in complete {
+            let (writer_guid, first) = key;
+            if self
+                .coherent_sets
+                .keys()
+                .any(|(guid, set_first)| *guid == writer_guid && *set_first < first)
+            {
+                continue;
+            }
+            let mut set = self.coherent_sets.remove(&key).expect("presence asserted");
+            if let (Some(skipped), Some(end)) = (self.skipped_changes.get_mut(&key.0), set.end) {
+                skipped.retain(|sequence| *sequence >= end);
+            }
+            set.changes.sort_by_key(|c| c.get_sequence_number());
+            for change in set.changes {
+                if let Err(e) = self.insert_change(change) {
+                    errors.push(e);
+                }
             }
-            self.changes.push_front(change);
-            Ok(())
-        } else {
-            // FIXME: handle resources limit cases
-            self.changes.push_front(change);
-            Ok(())
+        }
+        errors
+    }
+
+    /// Groups of the complete coherent sets which are part of a group coherent set, each set is reported once
+    pub fn complete_group_coherent_sets(&mut self) -> Vec<GroupCoherentSet> {
+        let mut groups = Vec::new();
+        for ((guid, first), set) in self.coherent_sets.iter_mut() {
+            if let Some(group) = &set.group
+                && !set.is_group_reported
+                && set.is_complete(*first, self.skipped_changes.get(guid))
+            {
+                set.is_group_reported = true;
+                groups.push(group.clone());
+            }
+        }
+        groups
+    }
+
+    /// Whether the coherent set of `writer_guid` which is part of `group` is complete
+    pub fn is_group_coherent_set_complete(
+        &self,
+        writer_guid: Guid,
+        group: &GroupCoherentSet,
+    ) -> bool {
+        self.coherent_sets.iter().any(|((guid, first), set)| {
+            *guid == writer_guid
+                && set.group.as_ref() == Some(group)
+                && set.is_complete(*first, self.skipped_changes.get(guid))
+        })
+    }
+
+    /// Let the coherent sets which are part of `group` be made available, every set of the group is complete
+    pub fn commit_group_coherent_set(&mut self, group: &GroupCoherentSet) {
+        self.coherent_sets
+            .values_mut()
+            .filter(|set| set.group.as_ref() == Some(group))
+            .for_each(|set| set.is_group_committed = true);
+    }
+
+    /// Hold back the coherent sets completed from now on, so that the available changes stay consistent while they are accessed
+    pub fn begin_access(&mut self) {
+        self.is_accessed = true;
+    }
+
+    /// Stop holding back the complete coherent sets, they still have to be committed
+    pub fn end_access(&mut self) {
+        self.is_accessed = false;
+    }
+
+    fn discard_coherent_sets(
+        &mut self,
+        predicate: impl Fn(&(Guid, SequenceNumber), &CoherentSet) -> bool,
+    ) {
+        let discarded = self
+            .coherent_sets
+            .iter()
+            .filter(|(key, set)| predicate(key, set))
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        for key in discarded {
+            let set = self.coherent_sets.remove(&key).expect("presence asserted");
+            self.trash.extend(set.changes);
         }
     }
 
@@ -123,16 +450,83 @@ impl ReaderHistoryCache {
         writer_guid: Guid,
         sequence: SequenceNumber,
     ) -> Option<&CacheChangeContainer> {
-        if let Some(change) = self.changes.iter_mut().find(|c| {
-            c.get_guid() == writer_guid
-                && c.get_sequence_number() == sequence
-                && c.get_sample_state_kind() == SampleStateKind::NotRead
-        }) {
+        let instance = self
+            .changes
+            .iter()
+            .find(|c| {
+                c.get_guid() == writer_guid
+                    && c.get_sequence_number() == sequence
+                    && c.get_sample_state_kind() == SampleStateKind::NotRead
+            })?
+            .get_instance_handle();
+        let (view_state, instance_state) = self.get_instance_states(instance);
+        let instance_generation = self.get_instance_generation(instance);
+        self.mark_instance_viewed(instance);
+
+        let change = self
+            .changes
+            .iter_mut()
+            .find(|c| c.get_guid() == writer_guid && c.get_sequence_number() == sequence)
+            .expect("presence asserted");
+        change.set_instance_states(view_state, instance_state);
+        Self::rank_absolute_generation(change, instance_generation);
+        change.mark_read();
+        Some(&*change)
+    }
+
+    /// Return a copy of each designated change, as it was before the access, then mark them as READ
+    ///
+    /// The view state of every accessed instance becomes [`ViewStateKind::NotNew`]
+    pub fn read_changes(&mut self, keys: &[(Guid, SequenceNumber)]) -> Vec<CacheChangeContainer> {
+        let mut read_changes = Vec::with_capacity(keys.len());
+        for (writer_guid, sequence) in keys {
+            let Some(pos) = self
+                .changes
+                .iter()
+                .position(|c| c.get_guid() == *writer_guid && c.get_sequence_number() == *sequence)
+            else {
+                continue;
+            };
+            let instance = self.changes[pos].get_instance_handle();
+            let (view_state, instance_state) = self.get_instance_states(instance);
+            let instance_generation = self.get_instance_generation(instance);
+
+            let change = &mut self.changes[pos];
+            change.set_instance_states(view_state, instance_state);
+            Self::rank_absolute_generation(change, instance_generation);
+            read_changes.push(change.clone());
             change.mark_read();
-            Some(&*change)
-        } else {
-            None
         }
+        Self::rank_changes(&mut read_changes);
+
+        for change in &read_changes {
+            self.mark_instance_viewed(change.get_instance_handle());
+        }
+
+        read_changes
+    }
+
+    /// Move to the trash the changes whose lifespan, given by their Writer, has elapsed at `now`
+    ///
+    /// Return the expiration time of the next change to expire, if any
+    pub fn remove_expired_changes(&mut self, now: i64) -> Option<i64> {
+        let mut expired_instances = Vec::new();
+        let mut pos = 0;
+        while let Some(change) = self.changes.get(pos) {
+            if Self::expiration_time(change).is_some_and(|expiration| expiration <= now) {
+                let change = self.changes.remove(pos).expect("presence asserted");
+                expired_instances.push(change.get_instance_handle());
+                self.trash.push_front(change.into_inner());
+            } else {
+                pos += 1;
+            }
+        }
+
+        for instance in expired_instances {
+            self.purge_instance(instance);
+        }
+
+        self.changes.iter().filter_map(Self::expiration_time).min()
     }
 
     pub fn get_changes_by_instance(&self, instance: InstanceHandle) -> Vec<&CacheChangeContainer> {
@@ -142,6 +536,16 @@ impl ReaderHistoryCache {
             .collect()
     }
 
+    pub fn get_instance_states(
+        &self,
+        instance: InstanceHandle,
+    ) -> (ViewStateKind, InstanceStateKind) {
+        self.instances
+            .get(&instance)
+            .map(|infos| (infos.view_state, infos.instance_state))
+            .unwrap_or_default()
+    }
+
     pub fn take_change(
         &mut self,
         writer_guid: Guid,
@@ -151,7 +555,26 @@ impl ReaderHistoryCache {
             .changes
             .iter()
             .position(|c| c.get_guid() == writer_guid && c.get_sequence_number() == sequence)?;
-        self.changes.remove(pos)
+        let mut change = self.changes.remove(pos)?;
+        let instance = change.get_instance_handle();
+        let (view_state, instance_state) = self.get_instance_states(instance);
+        change.set_instance_states(view_state, instance_state);
+        Self::rank_absolute_generation(&mut change, self.get_instance_generation(instance));
+        self.mark_instance_viewed(instance);
+        self.purge_instance(instance);
+        Some(change)
+    }
+
+    /// Remove each designated change from the cache and return them
+    ///
+    /// The view state of every accessed instance becomes [`ViewStateKind::NotNew`]
+    pub fn take_changes(&mut self, keys: &[(Guid, SequenceNumber)]) -> Vec<CacheChangeContainer> {
+        let mut taken_changes: Vec<_> = keys
+            .iter()
+            .filter_map(|(writer_guid, sequence)| self.take_change(*writer_guid, *sequence))
+            .collect();
+        Self::rank_changes(&mut taken_changes);
+        taken_changes
     }
 
     pub fn mark_read(&mut self, sequence: SequenceNumber, guid: Guid) {
@@ -222,7 +645,7 @@ impl ReaderHistoryCache {
     /// # Example
     ///
     // /// ```no_run
-    /// let config = ReaderHistoryCacheConfiguration::new(Some(1), 10, 10, 10);
+    /// let config = ReaderHistoryCacheConfiguration::new(Some(1), 10, 10, 10, Default::default(), Default::default());
     /// let mut cache = ReaderHistoryCache::new(config);
     ///
     /// cache.push(CacheChange::default()).unwrap();
@@ -235,4 +658,119 @@ impl ReaderHistoryCache {
     pub fn collect_garbage(&mut self) -> Option<CacheChange> {
         self.trash.pop_back()
     }
+
+    /// The `source_timestamp` of a change of `instance_handle` if a more recent change of the instance was already accepted
+    pub fn outdated_source_timestamp(
+        &self,
+        instance_handle: InstanceHandle,
+        source_timestamp: Option<Timestamp>,
+    ) -> Option<Timestamp> {
+        if !matches!(
+            self.destination_order,
+            DestinationOrderQosPolicy::BySourceTimestamp
+        ) {
+            return None;
+        }
+        let source_timestamp = source_timestamp?;
+        let latest = self.instances.get(&instance_handle)?.source_timestamp?;
+        (source_timestamp < latest).then_some(source_timestamp)
+    }
+
+    fn update_instance(&mut self, change: &CacheChange) {
+        let writer_guid = change.get_guid();
+        let infos = self
+            .instances
+            .entry(change.get_instance_handle())
+            .or_default();
+        infos.source_timestamp = infos.source_timestamp.max(change.get_emission_timestamp());
+
+        let instance_state = match change.infos.kind {
+            ChangeKind::Alive | ChangeKind::AliveFiltered => {
+                infos.alive_writers.insert(writer_guid);
+                InstanceStateKind::Alive
+            }
+            ChangeKind::NotAliveDisposed => InstanceStateKind::NotAliveDisposed,
+            ChangeKind::NotAliveUnregistered => {
+                infos.alive_writers.remove(&writer_guid);
+                // the instance stays alive as long as another Writer still updates it
+                if infos.alive_writers.is_empty() {
+                    InstanceStateKind::NotAliveNoWriters
+                } else {
+                    infos.instance_state
+                }
+            }
+        };
+
+        // an instance coming back to life is seen as a new one, in a new generation
+        if instance_state == InstanceStateKind::Alive {
+            match infos.instance_state {
+                InstanceStateKind::Alive => (),
+                InstanceStateKind::NotAliveDisposed => {
+                    infos.view_state = ViewStateKind::New;
+                    infos.disposed_generation_count += 1;
+                }
+                InstanceStateKind::NotAliveNoWriters => {
+                    infos.view_state = ViewStateKind::New;
+                    infos.no_writers_generation_count += 1;
+                }
+            }
+        }
+        infos.instance_state = instance_state;
+    }
+
+    fn get_instance_generation(&self, instance: InstanceHandle) -> i32 {
+        self.instances
+            .get(&instance)
+            .map(InstanceInfos::generation)
+            .unwrap_or_default()
+    }
+
+    /// Set the generation distance between a change and the most recent change received for its instance
+    fn rank_absolute_generation(change: &mut CacheChangeContainer, instance_generation: i32) {
+        let generation = change.get_generation_infos_mut();
+        generation.absolute_generation_rank = instance_generation - generation.generation();
+    }
+
+    /// Set the sample and generation ranks of each change relatively to the returned collection
+    ///
+    /// The most recent change of an instance is the last one of the collection
+    fn rank_changes(changes: &mut [CacheChangeContainer]) {
+        let mut most_recents: HashMap<InstanceHandle, (i32, i32)> = HashMap::new();
+        for change in changes.iter_mut().rev() {
+            let (sample_rank, most_recent_generation) = most_recents
+                .entry(change.get_instance_handle())
+                .or_insert((0, change.get_generation_infos().generation()));
+            let generation = change.get_generation_infos_mut();
+            generation.sample_rank = *sample_rank;
+            generation.generation_rank = *most_recent_generation - generation.generation();
+            *sample_rank += 1;
+        }
+    }
+
+    fn expiration_time(change: &CacheChangeContainer) -> Option<i64> {
+        let lifespan = change.get_inline_qos()?.lifespan;
+        change.expiration_time(lifespan)
+    }
+
+    fn mark_instance_viewed(&mut self, instance: InstanceHandle) {
+        if let Some(infos) = self.instances.get_mut(&instance) {
+            infos.view_state = ViewStateKind::NotNew;
+        }
+    }
+
+    /// Forget a not alive instance once all its changes have been taken
+    fn purge_instance(&mut self, instance: InstanceHandle) {
+        let is_alive = self
+            .instances
+            .get(&instance)
+            .is_some_and(|infos| infos.instance_state == InstanceStateKind::Alive);
+        let has_changes = self
+            .changes
+            .iter()
+            .any(|c| c.get_instance_handle() == instance);
+
+        if !is_alive && !has_changes {
+            self.instances.remove(&instance);
+        }
+    }
 }
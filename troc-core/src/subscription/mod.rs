@@ -1,10 +1,12 @@
 mod change_from_writer;
 mod historycache;
 mod reader;
+mod state_mask;
 
 pub use change_from_writer::{ChangeFromWriter, ChangeFromWriterMap};
 pub use historycache::ReaderHistoryCache;
 pub use reader::{Reader, ReaderBuilder, ReaderConfiguration};
+pub use state_mask::{InstanceStateMask, SampleStateMask, ViewStateMask};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum SampleStateKind {
@@ -13,3 +15,18 @@ pub enum SampleStateKind {
     Read,
     Any,
 }
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ViewStateKind {
+    #[default]
+    New,
+    NotNew,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InstanceStateKind {
+    #[default]
+    Alive,
+    NotAliveDisposed,
+    NotAliveNoWriters,
+}
@@ -10,8 +10,9 @@ use crate::{
     },
     types::{
         ChangeCount, ChangeKind, ContentNature, Count, ENTITYID_UNKOWN, EntityId, FragmentNumber,
-        FragmentNumberSet, Guid, HistoryQosPolicy, InlineQos, ReliabilityKind, SequenceNumber,
-        SequenceNumberSet, SerializedData, SubmessageFlags,
+        GroupCoherentSet, Guid, GuidPrefix, HistoryQosPolicy, InlineQos, InstanceHandle,
+        LivelinessKind, LivelinessQosPolicy, OwnershipStrengthQosPolicy, ReliabilityKind,
+        SequenceNumber, SequenceNumberSet, SerializedData, SubmessageFlags,
     },
 };
 // use chrono::Utc;
@@ -20,9 +21,14 @@ use tracing::{Level, event, instrument};
 
 use crate::{
     CacheChange,
-    common::{CacheChangeContainer, Effect, Effects, Error, FragmentedCacheChange, WriterProxy},
+    common::{
+        CacheChangeContainer, DeadlineTracker, Effect, Effects, Error, ExpirationScheduler,
+        FragmentedCacheChange, LivelinessMonitor, OwnershipArbiter, SampleLostStatus,
+        TimeBasedFilter, WriterProxy,
+    },
     subscription::{
-        ReaderHistoryCache, SampleStateKind, historycache::ReaderHistoryCacheConfiguration,
+        InstanceStateMask, ReaderHistoryCache, SampleStateKind, SampleStateMask, ViewStateMask,
+        historycache::{ReaderHistoryCacheConfiguration, ReaderHistoryCacheError},
     },
 };
 
@@ -104,15 +110,27 @@ impl ReaderBuilder {
             HistoryQosPolicy::KeepAll => None,
             HistoryQosPolicy::KeepLast { depth } => Some(*depth),
         };
-        let cache_config = ReaderHistoryCacheConfiguration::new(depth, 100, 100, 100);
+        let limits = qos.resource_limits;
+        let cache_config = ReaderHistoryCacheConfiguration::new(
+            depth,
+            limits.max_instances,
+            limits.max_samples_per_instance,
+            limits.max_samples,
+            qos.destination_order,
+            qos.presentation,
+        );
         let cache = ReaderHistoryCache::new(cache_config);
         let receiver = MessageReceiver::new(self.guid.get_guid_prefix());
         let htb_count = Default::default();
         let htbfrag_count = Default::default();
         let message_factory = MessageFactory::new(self.guid.get_guid_prefix());
+        let deadline = DeadlineTracker::new(qos.deadline);
+        let ownership = OwnershipArbiter::new(qos.ownership);
+        let time_based_filter = TimeBasedFilter::new(qos.time_based_filter);
 
         Reader {
             guid,
+            qos,
             matched_writers,
             cache,
             receiver,
@@ -124,6 +142,12 @@ impl ReaderBuilder {
             multicast_locator_list,
             config,
             tick_id,
+            deadline,
+            expiration: ExpirationScheduler::default(),
+            liveliness: LivelinessMonitor::default(),
+            sample_lost: SampleLostStatus::default(),
+            ownership,
+            time_based_filter,
         }
     }
 }
@@ -131,6 +155,7 @@ impl ReaderBuilder {
 #[derive()]
 pub struct Reader {
     guid: Guid,
+    qos: InlineQos,
     matched_writers: HashMap<Guid, WriterProxy>,
     cache: ReaderHistoryCache,
     receiver: MessageReceiver,
@@ -142,6 +167,12 @@ pub struct Reader {
     multicast_locator_list: LocatorList,
     config: ReaderConfiguration,
     tick_id: TickId,
+    deadline: DeadlineTracker,
+    expiration: ExpirationScheduler,
+    liveliness: LivelinessMonitor,
+    sample_lost: SampleLostStatus,
+    ownership: OwnershipArbiter,
+    time_based_filter: TimeBasedFilter,
 }
 
 impl Reader {
@@ -221,6 +252,61 @@ impl Reader {
         changes
     }
 
+    /// Read up to `max_samples` available changes whose states match the given masks,
+    /// optionally restricted to a single instance
+    ///
+    /// The returned changes carry the states they had before the read, the cached ones are marked as READ
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), matched_writers = ?self.matched_writers))]
+    pub fn read_changes(
+        &mut self,
+        max_samples: usize,
+        sample_states: SampleStateMask,
+        view_states: ViewStateMask,
+        instance_states: InstanceStateMask,
+        instance: Option<InstanceHandle>,
+    ) -> Vec<CacheChangeContainer> {
+        let selected_changes = self.select_changes(
+            max_samples,
+            sample_states,
+            view_states,
+            instance_states,
+            instance,
+        );
+        self.cache.read_changes(&selected_changes)
+    }
+
+    /// Take up to `max_samples` available changes whose states match the given masks,
+    /// optionally restricted to a single instance
+    ///
+    /// The returned changes are removed from the cache
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), matched_writers = ?self.matched_writers))]
+    pub fn take_changes(
+        &mut self,
+        max_samples: usize,
+        sample_states: SampleStateMask,
+        view_states: ViewStateMask,
+        instance_states: InstanceStateMask,
+        instance: Option<InstanceHandle>,
+    ) -> Vec<CacheChangeContainer> {
+        let selected_changes = self.select_changes(
+            max_samples,
+            sample_states,
+            view_states,
+            instance_states,
+            instance,
+        );
+        let changes = self.cache.take_changes(&selected_changes);
+
+        for change in &changes {
+            // the changes of an unmatched Writer stay in the cache until they are taken or expire
+            if let Some(proxy) = self.matched_writers.get_mut(&change.get_guid()) {
+                proxy.clean(&change.get_sequence_number());
+            }
+        }
+
+        changes
+    }
+
     #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), matched_writers = ?self.matched_writers))]
     pub fn ingest(
         &mut self,
@@ -268,6 +354,7 @@ impl Reader {
 
                     self.handle_data(
                         effects,
+                        now,
                         writer_guid,
                         submessage.header.flags,
                         writer_sn,
@@ -296,6 +383,7 @@ impl Reader {
                         };
 
                     self.handle_gap(
+                        effects,
                         writer_guid,
                         gap_start,
                         gap_list,
@@ -355,6 +443,7 @@ impl Reader {
                     let writer_guid = Guid::new(self.receiver.source_guid_prefix, writer_id);
                     self.handle_datafrag(
                         effects,
+                        now,
                         writer_guid,
                         writer_sn,
                         fragment_starting_num,
@@ -452,9 +541,126 @@ impl Reader {
         Ok(())
     }
 
+    /// Check the DEADLINE of every alive instance, to be called when a [`TickId::Deadline`] tick expires
+    ///
+    /// An [`Effect::DeadlineMissed`] is produced for the instances not updated within the requested period,
+    /// their exclusive owner loses them
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id()))]
+    pub fn deadline_tick(&mut self, effects: &mut Effects, now: i64) {
+        for instance in self.deadline.check(effects, now) {
+            self.ownership.release(instance);
+        }
+    }
+
+    /// Remove the changes whose LIFESPAN expired, to be called when a [`TickId::Lifespan`] tick expires
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id()))]
+    pub fn lifespan_tick(&mut self, effects: &mut Effects, now: i64) {
+        let next_expiration = self.cache.remove_expired_changes(now);
+        self.expiration.schedule(effects, now, next_expiration);
+    }
+
+    /// Check the LIVELINESS lease of every matched Writer, to be called when a [`TickId::Liveliness`] tick expires
+    ///
+    /// An [`Effect::LivelinessChanged`] is produced if some Writers didn't assert their liveliness in time
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id()))]
+    pub fn liveliness_tick(&mut self, effects: &mut Effects, now: i64) {
+        self.liveliness.check(effects, now);
+    }
+
+    /// Start monitoring the liveliness offered by the matched Writer `writer_guid`
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), writer_guid = %writer_guid))]
+    pub fn track_liveliness(
+        &mut self,
+        effects: &mut Effects,
+        writer_guid: Guid,
+        liveliness: LivelinessQosPolicy,
+        now: i64,
+    ) {
+        self.liveliness.track(effects, writer_guid, liveliness, now);
+    }
+
+    /// Keep the available changes as they are while the application accesses them, the coherent sets completed meanwhile are held back
+    pub fn begin_access(&mut self) {
+        self.cache.begin_access();
+    }
+
+    /// Make the coherent sets completed during the access available
+    pub fn end_access(&mut self, effects: &mut Effects) {
+        self.cache.end_access();
+        self.commit_coherent_sets(effects);
+    }
+
+    /// Whether the coherent sets of the matched Writers taking part in `group` are complete,
+    /// the Writers which are not matched have nothing to send to this Reader
+    pub fn is_group_coherent_set_complete(&self, group: &GroupCoherentSet) -> bool {
+        group
+            .writers
+            .iter()
+            .filter(|writer_guid| self.matched_writers.contains_key(writer_guid))
+            .all(|writer_guid| {
+                self.cache
+                    .is_group_coherent_set_complete(*writer_guid, group)
+            })
+    }
+
+    /// Make the coherent sets of `group` available, the group coherent set is complete for every Reader of the Subscriber
+    pub fn commit_group_coherent_set(&mut self, effects: &mut Effects, group: &GroupCoherentSet) {
+        self.cache.commit_group_coherent_set(group);
+        self.commit_coherent_sets(effects);
+    }
+
+    /// Apply a new QoS at `now`, the changeable policies take effect from then on
+    ///
+    /// Fail with [`Error::ImmutablePolicy`] if a policy which cannot change once the Reader is enabled differs
+    pub fn set_qos(
+        &mut self,
+        effects: &mut Effects,
+        qos: InlineQos,
+        now: i64,
+    ) -> Result<(), Error> {
+        if let Some(policy) = self.qos.changed_immutable_policy(&qos) {
+            return Err(Error::ImmutablePolicy(policy));
+        }
+
+        self.deadline.set_deadline(effects, qos.deadline, now);
+        self.time_based_filter
+            .set_minimum_separation(qos.time_based_filter);
+        self.qos = qos;
+        Ok(())
+    }
+
+    /// Number of samples dropped because of the TIME_BASED_FILTER
+    pub fn get_filtered_sample_count(&self) -> u32 {
+        self.time_based_filter.filtered_count()
+    }
+
+    /// Record the OWNERSHIP_STRENGTH offered by the matched Writer `writer_guid`
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), writer_guid = %writer_guid))]
+    pub fn track_ownership(&mut self, writer_guid: Guid, strength: OwnershipStrengthQosPolicy) {
+        self.ownership.track(writer_guid, strength);
+    }
+
+    /// Record a liveliness assertion received from the remote Participant `guid_prefix` for its Writers of kind `kind`
+    #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), guid_prefix = %guid_prefix))]
+    pub fn participant_liveliness_asserted(
+        &mut self,
+        effects: &mut Effects,
+        guid_prefix: GuidPrefix,
+        kind: LivelinessKind,
+        now: i64,
+    ) {
+        self.liveliness
+            .assert_participant(effects, guid_prefix, kind, now);
+    }
+
     #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), matched_writers = ?self.matched_writers))]
-    pub fn tick(&mut self, effects: &mut Effects, now: i64) {
-        // TODO: check time related QoS
+    pub fn tick(&mut self, effects: &mut Effects, _now: i64) {
+        self.cleanup();
+
+        if matches!(self.is_reliable, ReliabilityKind::BestEffort) {
+            event!(Level::TRACE, "BestEffort Reader doesn't send ACKNACK");
+            return;
+        }
 
         for (_guid, proxy) in self.matched_writers.iter_mut() {
             let base = proxy.available_changes_max() + 1;
@@ -537,6 +743,9 @@ impl Reader {
     #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), matched_writers = ?self.matched_writers))]
     pub fn remove_proxy(&mut self, proxy_guid: Guid) {
         self.matched_writers.remove(&proxy_guid);
+        self.liveliness.untrack(proxy_guid);
+        self.ownership.untrack(proxy_guid);
+        self.cache.remove_coherent_sets(proxy_guid);
     }
 
     #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), matched_writers = ?self.matched_writers))]
@@ -602,12 +811,37 @@ impl Reader {
         res
     }
 
+    fn select_changes(
+        &self,
+        max_samples: usize,
+        sample_states: SampleStateMask,
+        view_states: ViewStateMask,
+        instance_states: InstanceStateMask,
+        instance: Option<InstanceHandle>,
+    ) -> Vec<(Guid, SequenceNumber)> {
+        self.iter_all_available_changes(SampleStateKind::Any)
+            .filter(|container| {
+                instance.is_none_or(|instance| container.get_instance_handle() == instance)
+            })
+            .filter(|container| {
+                let (view_state, instance_state) = self
+                    .cache
+                    .get_instance_states(container.get_instance_handle());
+                sample_states.contains(container.get_sample_state_kind())
+                    && view_states.contains(view_state)
+                    && instance_states.contains(instance_state)
+            })
+            .take(max_samples)
+            .map(|container| (container.get_guid(), container.get_sequence_number()))
+            .collect()
+    }
+
     fn cleanup(&mut self) {
         while let Some(change) = self.cache.collect_garbage() {
-            let Some(proxy) = self.matched_writers.get_mut(&change.get_guid()) else {
-                unreachable!()
-            };
-            proxy.clean(&change.get_sequence_number());
+            // the changes of an unmatched Writer stay in the cache until they are taken or expire
+            if let Some(proxy) = self.matched_writers.get_mut(&change.get_guid()) {
+                proxy.clean(&change.get_sequence_number());
+            }
         }
     }
 
@@ -623,9 +857,11 @@ impl Reader {
     }
 
     #[instrument(level = Level::DEBUG, skip_all, fields(entity_id = ?self.guid.get_entity_id(), matched_writers = ?self.matched_writers, writer_guid = %writer_guid, sequence = %change_sequence))]
+    #[allow(clippy::too_many_arguments)]
     fn handle_data(
         &mut self,
         effects: &mut Effects,
+        now: i64,
         writer_guid: Guid,
         flags: SubmessageFlags,
         change_sequence: SequenceNumber,
@@ -636,6 +872,7 @@ impl Reader {
             event!(Level::TRACE, remote_writer_guid = %writer_guid, "Matched Writer not found");
             return Ok(());
         };
+        self.liveliness.assert_writer(effects, writer_guid, now);
 
         let payload_nature = if flags.third() == 1 && flags.fourth() == 0 {
             ContentNature::Data
@@ -645,8 +882,26 @@ impl Reader {
             ContentNature::None
         };
 
+        let kind = match inline_qos.as_ref().map(|qos| qos.status_info) {
+            Some(status_info) if status_info.disposed() == 1 => ChangeKind::NotAliveDisposed,
+            Some(status_info) if status_info.unregister() == 1 => ChangeKind::NotAliveUnregistered,
+            _ => ChangeKind::Alive,
+        };
+
+        let coherent_set = inline_qos.as_ref().and_then(|qos| qos.coherent_set);
+        let group_coherent_set = inline_qos
+            .as_ref()
+            .and_then(|qos| qos.group_coherent_set.clone());
+        // an alive change without payload in a coherent set marks the end of the set
+        let is_coherent_set_end = coherent_set.is_some()
+            && matches!(kind, ChangeKind::Alive)
+            && !matches!(payload_nature, ContentNature::Data);
+
         // FIXME: temporary, should be smarter when interop will be needed
-        if !matches!(payload_nature, ContentNature::Data) {
+        if matches!(kind, ChangeKind::Alive)
+            && !matches!(payload_nature, ContentNature::Data)
+            && !is_coherent_set_end
+        {
             event!(Level::TRACE, "The payload is not Data");
             return Ok(());
         }
@@ -661,8 +916,15 @@ impl Reader {
             instance_handle = qos.key_hash;
         }
 
+        // the payload of a not alive change is at most the serialized key, never a valid sample
+        let data = if matches!(kind, ChangeKind::Alive) {
+            data
+        } else {
+            None
+        };
+
         let mut change = CacheChange::new(
-            ChangeKind::Alive,
+            kind,
             writer_guid,
             instance_handle,
             change_sequence,
@@ -680,12 +942,6 @@ impl Reader {
             change.set_emission_timestamp(emission_timestamp);
         }
 
-        if let Err(e) = self.cache.push_change(change) {
-            event!(Level::ERROR, "Error pushing data in cache");
-            // TODO: for now there are no error that could possibly be raised
-            unreachable!()
-        };
-
         let is_in_watch_all_mode = proxy.get_remote_writer_guid() == Guid::default();
 
         let expected_sequence = proxy.expected_sequence();
@@ -704,17 +960,104 @@ impl Reader {
             return Ok(());
         }
 
+        if let Some(first) = coherent_set
+            && is_coherent_set_end
+        {
+            event!(Level::TRACE, %first, "End of coherent set");
+            proxy.received_change_set(change_sequence);
+            self.cache
+                .end_coherent_set(writer_guid, first, change_sequence, group_coherent_set);
+            if matches!(self.is_reliable, ReliabilityKind::BestEffort) {
+                self.cache.discard_incomplete_coherent_sets(writer_guid);
+            }
+            self.commit_coherent_sets(effects);
+            return Ok(());
+        }
+
+        if let Some(source_timestamp) = self
+            .cache
+            .outdated_source_timestamp(instance_handle, change.get_emission_timestamp())
+        {
+            event!(
+                Level::TRACE,
+                %source_timestamp,
+                "DATA older than the last accepted change of the instance"
+            );
+            proxy.received_change_set(change_sequence);
+            if let Some(first) = coherent_set {
+                self.cache
+                    .skip_coherent_change(writer_guid, first, change_sequence);
+            }
+            return Ok(());
+        }
+
+        let liveliness = &self.liveliness;
+        if !self.ownership.accept(instance_handle, writer_guid, |guid| {
+            liveliness.is_alive(guid)
+        }) {
+            event!(Level::TRACE, "DATA from a Writer not owning the instance");
+            proxy.received_change_set(change_sequence);
+            if let Some(first) = coherent_set {
+                self.cache
+                    .skip_coherent_change(writer_guid, first, change_sequence);
+            }
+            return Ok(());
+        }
+
+        if matches!(kind, ChangeKind::Alive) {
+            if !self.time_based_filter.accept(instance_handle, now) {
+                event!(Level::TRACE, "DATA filtered by the TIME_BASED_FILTER");
+                proxy.received_change_set(change_sequence);
+                if let Some(first) = coherent_set {
+                    self.cache
+                        .skip_coherent_change(writer_guid, first, change_sequence);
+                }
+                return Ok(());
+            }
+        } else {
+            self.time_based_filter.forget(instance_handle);
+        }
+
+        if matches!(kind, ChangeKind::Alive) {
+            self.deadline.update(effects, instance_handle, now);
+        } else {
+            self.deadline.forget(instance_handle);
+        }
+
+        let expiration = change
+            .get_inline_qos()
+            .and_then(|qos| change.expiration_time(qos.lifespan));
+        self.expiration.schedule(effects, now, expiration);
+
+        match self.cache.push_change(change) {
+            Err(ReaderHistoryCacheError::SampleRejected(reason)) => {
+                event!(Level::DEBUG, ?reason, "DATA rejected");
+                effects.push(Effect::SampleRejected { reason });
+                // a rejected change is not acknowledged, a reliable Writer will send it again
+                if matches!(self.is_reliable, ReliabilityKind::Reliable) {
+                    return Ok(());
+                }
+            }
+            Err(ReaderHistoryCacheError::OutdatedSourceTimestamp(source_timestamp)) => {
+                event!(
+                    Level::TRACE,
+                    %source_timestamp,
+                    "DATA older than the last accepted change of the instance"
+                );
+            }
+            _ => (),
+        }
+
         if matches!(self.is_reliable, ReliabilityKind::BestEffort)
             && change_sequence > expected_sequence
         {
-            proxy.lost_changes_update(change_sequence, false);
+            let lost = proxy.lost_changes_update(change_sequence, false);
+            Self::notify_sample_lost(effects, &mut self.sample_lost, lost);
         }
 
         proxy.received_change_set(change_sequence);
 
-        if self.has_unreaded_available_change() {
-            effects.push(Effect::DataAvailable);
-        }
+        self.commit_coherent_sets(effects);
 
         let changes = self
             .cache
@@ -727,8 +1070,39 @@ impl Reader {
         Ok(())
     }
 
+    /// Make the complete coherent sets available, reporting the changes the RESOURCE_LIMITS rejected
+    ///
+    /// The sets which are part of a group coherent set are reported instead, see [`Reader::commit_group_coherent_set`]
+    fn commit_coherent_sets(&mut self, effects: &mut Effects) {
+        for error in self.cache.commit_coherent_sets() {
+            if let ReaderHistoryCacheError::SampleRejected(reason) = error {
+                event!(Level::DEBUG, ?reason, "Change of a coherent set rejected");
+                effects.push(Effect::SampleRejected { reason });
+            }
+        }
+        for group in self.cache.complete_group_coherent_sets() {
+            event!(Level::TRACE, sequence = %group.sequence, "Coherent set of a group complete");
+            effects.push(Effect::GroupCoherentSetComplete { group });
+        }
+        if self.has_unreaded_available_change() {
+            effects.push(Effect::DataAvailable);
+        }
+    }
+
+    /// Report the `lost` changes the best-effort Reader will never receive
+    fn notify_sample_lost(effects: &mut Effects, status: &mut SampleLostStatus, lost: usize) {
+        if lost == 0 {
+            return;
+        }
+        status.total_count += lost as u32;
+        status.total_count_change += lost as u32;
+        effects.push(Effect::SampleLost { status: *status });
+        status.total_count_change = 0;
+    }
+
     fn handle_gap(
         &mut self,
+        effects: &mut Effects,
         writer_guid: Guid,
         gap_start: SequenceNumber,
         gap_list: SequenceNumberSet,
@@ -754,7 +1128,12 @@ impl Reader {
             let set = gap_list.get_set();
             list.extend(set);
 
-            proxy.not_available_change_set(list, ChangeCount::default());
+            proxy.not_available_change_set(list.clone(), ChangeCount::default());
+            if self.qos.presentation.coherent_access {
+                // the changes removed from the Writer history no longer hold their coherent set back
+                self.cache.skip_coherent_changes(writer_guid, list);
+                self.commit_coherent_sets(effects);
+            }
 
             event!(Level::DEBUG, "GAP processed");
         } else {
@@ -765,7 +1144,7 @@ impl Reader {
     }
 
     fn is_gap_valid(gap_start: &SequenceNumber, gap_list: &SequenceNumberSet) -> bool {
-        gap_start.0 <= 0 || gap_start > &gap_list.get_base()
+        gap_start.0 > 0 && gap_start <= &gap_list.get_base()
     }
 
     #[instrument(level = Level::TRACE, skip_all, fields(entity_id = ?self.guid.get_entity_id(), matched_writers = ?self.matched_writers, writer_guid = %writer_guid, first_sn = %first_sn, last_sn = %last_sn))]
@@ -782,6 +1161,8 @@ impl Reader {
             event!(Level::TRACE, remote_writer_guid = %writer_guid, "Matched Writer not found");
             return Ok(());
         };
+        // any HEARTBEAT asserts the liveliness, whether its liveliness flag is set or not
+        self.liveliness.assert_writer(effects, writer_guid, now);
 
         let time_diff = now.saturating_sub(proxy.last_heartbeat_timestamp_ms);
 
@@ -795,14 +1176,20 @@ impl Reader {
         proxy.last_heartbeat_timestamp_ms = now;
 
         proxy.missing_changes_update(last_sn);
+        let lost = proxy
+            .missing_changes()
+            .into_iter()
+            .filter(|sequence| *sequence < first_sn)
+            .collect::<Vec<_>>();
         proxy.lost_changes_update(first_sn, true);
         // proxy.last_missing_frag_remove_until(first_sn);
 
-        let is_final = self.receiver.flags.second() == 0;
+        // without the FinalFlag, the Writer expects an ACKNACK even if nothing is missing,
+        // this is how it learns which changes are acknowledged
+        let response_required = self.receiver.flags.second() == 0;
         let missings = proxy.missing_changes();
-        let are_missings = !missings.is_empty();
 
-        if is_final && are_missings {
+        if response_required {
             let effect = Effect::ScheduleTick {
                 id: self.tick_id,
                 delay: self.config.heartbeat_response_delay_ms,
@@ -810,7 +1197,13 @@ impl Reader {
             effects.push(effect);
         }
 
-        event!(Level::DEBUG, is_final, missings = ?missings, "HEARTBEAT processed");
+        if !lost.is_empty() && self.qos.presentation.coherent_access {
+            // the changes no longer in the Writer history no longer hold their coherent set back
+            self.cache.skip_coherent_changes(writer_guid, lost);
+            self.commit_coherent_sets(effects);
+        }
+
+        event!(Level::DEBUG, response_required, missings = ?missings, "HEARTBEAT processed");
 
         Ok(())
     }
@@ -819,6 +1212,7 @@ impl Reader {
     fn handle_datafrag(
         &mut self,
         effects: &mut Effects,
+        now: i64,
         writer_guid: Guid,
         sequence: SequenceNumber,
         fragment_starting_num: FragmentNumber,
@@ -832,6 +1226,7 @@ impl Reader {
             event!(Level::TRACE, remote_writer_guid = %writer_guid, "Matched Writer not found");
             return Ok(());
         };
+        self.liveliness.assert_writer(effects, writer_guid, now);
 
         let expected_sequence = proxy.expected_sequence();
 
@@ -848,7 +1243,8 @@ impl Reader {
                 Level::TRACE,
                 "This sequence is beyond the one that was expected"
             );
-            proxy.lost_changes_update(sequence, false);
+            let lost = proxy.lost_changes_update(sequence, false);
+            Self::notify_sample_lost(effects, &mut self.sample_lost, lost);
             // for lost_change in proxy.lost_changes() {
             //     let _res = self
             //         .cache
@@ -916,15 +1312,74 @@ impl Reader {
                 );
 
                 if frag_change.is_complete() {
+                    let instance_handle = frag_change.infos.instance_handle;
+                    let coherent_set = frag_change
+                        .infos
+                        .inline_qos
+                        .as_ref()
+                        .and_then(|qos| qos.coherent_set);
+                    let emission_timestamp = frag_change.infos.emission_timestamp;
+                    let liveliness = &self.liveliness;
+                    let is_filtered = if let Some(source_timestamp) = self
+                        .cache
+                        .outdated_source_timestamp(instance_handle, emission_timestamp)
+                    {
+                        event!(
+                            Level::TRACE,
+                            %source_timestamp,
+                            "DATA_FRAG older than the last accepted change of the instance"
+                        );
+                        true
+                    } else if !self.ownership.accept(instance_handle, writer_guid, |guid| {
+                        liveliness.is_alive(guid)
+                    }) {
+                        event!(
+                            Level::TRACE,
+                            "DATA_FRAG from a Writer not owning the instance"
+                        );
+                        true
+                    } else if !self.time_based_filter.accept(instance_handle, now) {
+                        event!(Level::TRACE, "DATA_FRAG filtered by the TIME_BASED_FILTER");
+                        true
+                    } else {
+                        false
+                    };
+                    if is_filtered {
+                        self.cache.remove_fragmented_change(writer_guid, sequence);
+                        if let Some(first) = coherent_set {
+                            self.cache
+                                .skip_coherent_change(writer_guid, first, sequence);
+                        }
+                        return Ok(());
+                    }
                     match self.cache.transfer(writer_guid, sequence) {
                         Ok(()) => {
-                            if self.has_unreaded_available_change() {
-                                effects.push(Effect::DataAvailable);
-                            }
+                            self.deadline.update(effects, instance_handle, now);
+                            let expiration =
+                                self.cache
+                                    .get_change(writer_guid, sequence)
+                                    .and_then(|change| {
+                                        change.expiration_time(change.get_inline_qos()?.lifespan)
+                                    });
+                            self.expiration.schedule(effects, now, expiration);
+                            self.commit_coherent_sets(effects);
+                            Ok(())
+                        }
+                        Err(ReaderHistoryCacheError::SampleRejected(reason)) => {
+                            event!(Level::DEBUG, ?reason, "DATA_FRAG rejected");
+                            effects.push(Effect::SampleRejected { reason });
+                            Ok(())
+                        }
+                        Err(ReaderHistoryCacheError::OutdatedSourceTimestamp(source_timestamp)) => {
+                            event!(
+                                Level::TRACE,
+                                %source_timestamp,
+                                "DATA_FRAG older than the last accepted change of the instance"
+                            );
                             Ok(())
                         }
                         Err(_e) => {
-                            // TODO: for now there are no error than can be raised here
+                            // TODO: for now there are no other error than can be raised here
                             unreachable!()
                         }
                     }
@@ -947,6 +1402,8 @@ impl Reader {
             event!(Level::TRACE, remote_writer_guid = %writer_guid, "Matched Writer not found");
             return Ok(());
         };
+        // any HEARTBEAT asserts the liveliness, whether its liveliness flag is set or not
+        self.liveliness.assert_writer(effects, writer_guid, now);
 
         let time_diff = now.saturating_sub(proxy.last_heartbeat_timestamp_ms);
 
@@ -1007,20 +1464,28 @@ impl Debug for Reader {
 mod tests {
     use crate::{
         messages::MessageFactory,
-        subscription::SampleStateKind,
+        subscription::{
+            InstanceStateKind, InstanceStateMask, SampleStateKind, SampleStateMask, ViewStateKind,
+            ViewStateMask,
+        },
         types::{
-            ContentNature, EntityId, Guid, InlineQos, LocatorList, ReliabilityKind, SequenceNumber,
-            SerializedData,
+            ContentNature, DestinationOrderQosPolicy, EntityId, GroupCoherentSet, Guid,
+            HistoryQosPolicy, InlineQos, InstanceHandle, LifespanQosPolicy, LocatorList,
+            OwnershipQosPolicy, OwnershipStrengthQosPolicy, PresentationAccessScope,
+            PresentationQosPolicy, ReliabilityKind, ResourceLimitsQosPolicy, SequenceNumber,
+            SequenceNumberSet, SerializedData, StatusInfo, TimeBasedFilterQosPolicy, Timestamp,
         },
     };
-    use chrono::Utc;
+    use chrono::{DateTime, Utc};
     use rstest::{fixture, rstest};
 
     use crate::{
         Effects, WriterProxy,
         common::{
-            Effect,
-            tests::{setup_reader_0_guid, setup_uni_locatorlist, setup_writer_0_guid},
+            Effect, Error, SampleRejectedStatusKind, TickId,
+            tests::{
+                setup_guid_prefix, setup_reader_0_guid, setup_uni_locatorlist, setup_writer_0_guid,
+            },
         },
     };
 
@@ -1062,6 +1527,920 @@ mod tests {
         assert_eq!(changes.len(), 1);
     }
 
+    #[rstest]
+    fn read_then_take_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::BestEffort, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 10 }, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+    ) {
+        let mut effects = Effects::new();
+
+        for sequence in 1..=2 {
+            let message = MessageFactory::new(writer_guid_0.get_guid_prefix())
+                .message()
+                .reader(reader_guid_0.get_entity_id())
+                .writer(writer_guid_0.get_entity_id())
+                .data(
+                    ContentNature::Data,
+                    SequenceNumber(sequence),
+                    None,
+                    Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+                )
+                .build();
+            reader
+                .ingest(&mut effects, Utc::now().timestamp_millis(), message)
+                .unwrap();
+        }
+
+        let changes = reader.read_changes(
+            1,
+            SampleStateMask::NOT_READ,
+            ViewStateMask::NEW,
+            InstanceStateMask::ANY,
+            None,
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].get_sequence_number(), SequenceNumber(1));
+        assert_eq!(changes[0].get_sample_state_kind(), SampleStateKind::NotRead);
+        assert_eq!(changes[0].get_view_state_kind(), ViewStateKind::New);
+
+        let changes = reader.read_changes(
+            10,
+            SampleStateMask::NOT_READ,
+            ViewStateMask::NEW,
+            InstanceStateMask::ANY,
+            None,
+        );
+        assert!(changes.is_empty());
+
+        let changes = reader.read_changes(
+            10,
+            SampleStateMask::READ,
+            ViewStateMask::ANY,
+            InstanceStateMask::ANY,
+            None,
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].get_view_state_kind(), ViewStateKind::NotNew);
+
+        let changes = reader.take_changes(
+            10,
+            SampleStateMask::ANY,
+            ViewStateMask::ANY,
+            InstanceStateMask::ALIVE,
+            None,
+        );
+        assert_eq!(changes.len(), 2);
+        assert!(
+            reader
+                .get_all_available_changes(SampleStateKind::Any)
+                .is_empty()
+        );
+    }
+
+    #[rstest]
+    fn dispose_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::BestEffort, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 10 }, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+    ) {
+        let mut effects = Effects::new();
+        let instance = InstanceHandle::new([1; 16]);
+
+        let alive_qos = InlineQos {
+            key_hash: instance,
+            ..Default::default()
+        };
+        let disposed_qos = InlineQos {
+            key_hash: instance,
+            status_info: StatusInfo::new_disposed(),
+            ..Default::default()
+        };
+
+        let message = MessageFactory::new(writer_guid_0.get_guid_prefix())
+            .message()
+            .reader(reader_guid_0.get_entity_id())
+            .writer(writer_guid_0.get_entity_id())
+            .data(
+                ContentNature::Data,
+                SequenceNumber(1),
+                Some(alive_qos),
+                Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+            )
+            .data(
+                ContentNature::None,
+                SequenceNumber(2),
+                Some(disposed_qos),
+                None,
+            )
+            .build();
+        reader
+            .ingest(&mut effects, Utc::now().timestamp_millis(), message)
+            .unwrap();
+
+        let changes = reader.take_changes(
+            10,
+            SampleStateMask::ANY,
+            ViewStateMask::ANY,
+            InstanceStateMask::NOT_ALIVE_DISPOSED,
+            Some(instance),
+        );
+        assert_eq!(changes.len(), 2);
+        assert!(
+            changes
+                .iter()
+                .all(|c| c.get_instance_state_kind() == InstanceStateKind::NotAliveDisposed)
+        );
+        assert!(changes[1].get_data().is_none());
+    }
+
+    #[rstest]
+    fn resource_limits_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::BestEffort, InlineQos { history: HistoryQosPolicy::KeepAll, resource_limits: ResourceLimitsQosPolicy { max_samples: 2, max_instances: 1, max_samples_per_instance: 2 }, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+    ) {
+        let mut effects = Effects::new();
+        let first_instance = InstanceHandle::new([1; 16]);
+        let second_instance = InstanceHandle::new([2; 16]);
+
+        // the 4th change is never received
+        for (sequence, instance) in [
+            (1, first_instance),
+            (2, first_instance),
+            (3, first_instance),
+            (5, second_instance),
+        ] {
+            let message = MessageFactory::new(writer_guid_0.get_guid_prefix())
+                .message()
+                .reader(reader_guid_0.get_entity_id())
+                .writer(writer_guid_0.get_entity_id())
+                .data(
+                    ContentNature::Data,
+                    SequenceNumber(sequence),
+                    Some(InlineQos {
+                        key_hash: instance,
+                        ..Default::default()
+                    }),
+                    Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+                )
+                .build();
+            reader
+                .ingest(&mut effects, Utc::now().timestamp_millis(), message)
+                .unwrap();
+        }
+
+        let mut rejections = Vec::new();
+        let mut lost = Vec::new();
+        while let Some(effect) = effects.pop() {
+            match effect {
+                Effect::SampleRejected { reason } => rejections.insert(0, reason),
+                Effect::SampleLost { status } => lost.push(status),
+                _ => (),
+            }
+        }
+        assert_eq!(
+            rejections,
+            vec![
+                SampleRejectedStatusKind::RejectedBySamplesPerInstanceLimit,
+                SampleRejectedStatusKind::RejectedByInstancesLimit
+            ]
+        );
+        assert_eq!(lost.len(), 1);
+        assert_eq!(lost[0].total_count, 1);
+
+        let changes = reader.get_all_available_changes(SampleStateKind::Any);
+        assert_eq!(changes.len(), 2);
+    }
+
+    #[rstest]
+    fn exclusive_ownership_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::BestEffort, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 10 }, ownership: OwnershipQosPolicy::Exclusive, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] weak_writer_guid: Guid,
+        #[from(setup_uni_locatorlist)] uni_locators: LocatorList,
+    ) {
+        let mut effects = Effects::new();
+        let instance = InstanceHandle::new([1; 16]);
+        let strong_writer_guid =
+            Guid::new(setup_guid_prefix::get(2), weak_writer_guid.get_entity_id());
+        reader.add_proxy(WriterProxy::new(
+            strong_writer_guid,
+            EntityId::default(),
+            60 * 1024,
+            uni_locators,
+            LocatorList::default(),
+        ));
+        reader.track_ownership(weak_writer_guid, OwnershipStrengthQosPolicy { value: 1 });
+        reader.track_ownership(strong_writer_guid, OwnershipStrengthQosPolicy { value: 10 });
+
+        let mut send = |reader: &mut Reader, writer_guid: Guid, sequence: i64| {
+            let message = MessageFactory::new(writer_guid.get_guid_prefix())
+                .message()
+                .reader(reader_guid_0.get_entity_id())
+                .writer(writer_guid.get_entity_id())
+                .data(
+                    ContentNature::Data,
+                    SequenceNumber(sequence),
+                    Some(InlineQos {
+                        key_hash: instance,
+                        ..Default::default()
+                    }),
+                    Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+                )
+                .build();
+            reader
+                .ingest(&mut effects, Utc::now().timestamp_millis(), message)
+                .unwrap();
+        };
+
+        send(&mut reader, weak_writer_guid, 1);
+        send(&mut reader, strong_writer_guid, 1);
+        // the weaker Writer lost the instance
+        send(&mut reader, weak_writer_guid, 2);
+
+        let changes = reader.get_all_available_changes(SampleStateKind::Any);
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| change.get_guid())
+                .collect::<Vec<_>>(),
+            vec![weak_writer_guid, strong_writer_guid]
+        );
+
+        // fail over to the remaining Writer
+        reader.remove_proxy(strong_writer_guid);
+        send(&mut reader, weak_writer_guid, 3);
+
+        let changes = reader.get_all_available_changes(SampleStateKind::Any);
+        assert!(
+            changes
+                .iter()
+                .any(|change| change.get_guid() == weak_writer_guid
+                    && change.get_sequence_number() == SequenceNumber(3))
+        );
+    }
+
+    #[rstest]
+    fn time_based_filter_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::BestEffort, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 10 }, time_based_filter: TimeBasedFilterQosPolicy { minimum_separation: Timestamp::from_datetime(DateTime::from_timestamp_millis(100).unwrap()) }, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+    ) {
+        let mut effects = Effects::new();
+        let instance = InstanceHandle::new([1; 16]);
+
+        let mut send = |reader: &mut Reader, sequence: i64, now: i64| {
+            let message = MessageFactory::new(writer_guid_0.get_guid_prefix())
+                .message()
+                .reader(reader_guid_0.get_entity_id())
+                .writer(writer_guid_0.get_entity_id())
+                .data(
+                    ContentNature::Data,
+                    SequenceNumber(sequence),
+                    Some(InlineQos {
+                        key_hash: instance,
+                        ..Default::default()
+                    }),
+                    Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+                )
+                .build();
+            reader.ingest(&mut effects, now, message).unwrap();
+        };
+
+        send(&mut reader, 1, 1_000);
+        // within the minimum separation of the first sample
+        send(&mut reader, 2, 1_050);
+        send(&mut reader, 3, 1_100);
+
+        let changes = reader.get_all_available_changes(SampleStateKind::Any);
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| change.get_sequence_number())
+                .collect::<Vec<_>>(),
+            vec![SequenceNumber(1), SequenceNumber(3)]
+        );
+        assert_eq!(reader.get_filtered_sample_count(), 1);
+
+        // the filter is lifted at runtime, the history is immutable
+        reader
+            .set_qos(
+                &mut Effects::new(),
+                InlineQos {
+                    history: HistoryQosPolicy::KeepLast { depth: 10 },
+                    ..Default::default()
+                },
+                1_110,
+            )
+            .unwrap();
+        send(&mut reader, 4, 1_120);
+        assert_eq!(
+            reader.get_all_available_changes(SampleStateKind::Any).len(),
+            3
+        );
+        assert!(matches!(
+            reader.set_qos(&mut Effects::new(), InlineQos::default(), 1_120),
+            Err(Error::ImmutablePolicy("HISTORY"))
+        ));
+    }
+
+    #[rstest]
+    fn generation_ranks_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::BestEffort, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 10 }, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+    ) {
+        let mut effects = Effects::new();
+        let instance = InstanceHandle::new([1; 16]);
+
+        let alive_qos = InlineQos {
+            key_hash: instance,
+            ..Default::default()
+        };
+        let disposed_qos = InlineQos {
+            key_hash: instance,
+            status_info: StatusInfo::new_disposed(),
+            ..Default::default()
+        };
+
+        let message = MessageFactory::new(writer_guid_0.get_guid_prefix())
+            .message()
+            .reader(reader_guid_0.get_entity_id())
+            .writer(writer_guid_0.get_entity_id())
+            .data(
+                ContentNature::Data,
+                SequenceNumber(1),
+                Some(alive_qos.clone()),
+                Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+            )
+            .data(
+                ContentNature::None,
+                SequenceNumber(2),
+                Some(disposed_qos),
+                None,
+            )
+            .data(
+                ContentNature::Data,
+                SequenceNumber(3),
+                Some(alive_qos),
+                Some(SerializedData::from_vec(vec![0, 0, 0, 1])),
+            )
+            .build();
+        reader
+            .ingest(&mut effects, Utc::now().timestamp_millis(), message)
+            .unwrap();
+
+        let changes = reader.read_changes(
+            10,
+            SampleStateMask::ANY,
+            ViewStateMask::ANY,
+            InstanceStateMask::ANY,
+            Some(instance),
+        );
+        assert_eq!(changes.len(), 3);
+
+        let first = changes[0].get_generation_infos();
+        assert_eq!(first.disposed_generation_count, 0);
+        assert_eq!(first.sample_rank, 2);
+        assert_eq!(first.generation_rank, 1);
+        assert_eq!(first.absolute_generation_rank, 1);
+
+        let last = changes[2].get_generation_infos();
+        assert_eq!(last.disposed_generation_count, 1);
+        assert_eq!(last.no_writers_generation_count, 0);
+        assert_eq!(last.sample_rank, 0);
+        assert_eq!(last.generation_rank, 0);
+        assert_eq!(last.absolute_generation_rank, 0);
+    }
+
+    #[rstest]
+    fn destination_order_by_source_timestamp_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::BestEffort, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 10 }, destination_order: DestinationOrderQosPolicy::BySourceTimestamp, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+        #[from(setup_uni_locatorlist)] uni_locators: LocatorList,
+    ) {
+        let mut effects = Effects::new();
+        let instance = InstanceHandle::new([1; 16]);
+        let other_writer_guid = Guid::new(setup_guid_prefix::get(2), writer_guid_0.get_entity_id());
+        reader.add_proxy(WriterProxy::new(
+            other_writer_guid,
+            EntityId::default(),
+            60 * 1024,
+            uni_locators,
+            LocatorList::default(),
+        ));
+
+        let mut send = |reader: &mut Reader, writer_guid: Guid, sequence: i64, emission: i64| {
+            let message = MessageFactory::new(writer_guid.get_guid_prefix())
+                .message()
+                .reader(reader_guid_0.get_entity_id())
+                .writer(writer_guid.get_entity_id())
+                .info_timestamp(Some(Timestamp::from_datetime(
+                    DateTime::from_timestamp_millis(emission).unwrap(),
+                )))
+                .data(
+                    ContentNature::Data,
+                    SequenceNumber(sequence),
+                    Some(InlineQos {
+                        key_hash: instance,
+                        ..Default::default()
+                    }),
+                    Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+                )
+                .build();
+            reader
+                .ingest(&mut effects, Utc::now().timestamp_millis(), message)
+                .unwrap();
+        };
+
+        send(&mut reader, writer_guid_0, 1, 2_000);
+        // written before the change already accepted for the instance
+        send(&mut reader, other_writer_guid, 1, 1_000);
+        send(&mut reader, other_writer_guid, 2, 3_000);
+
+        let changes = reader.get_all_available_changes(SampleStateKind::Any);
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| (change.get_guid(), change.get_sequence_number()))
+                .collect::<Vec<_>>(),
+            vec![
+                (writer_guid_0, SequenceNumber(1)),
+                (other_writer_guid, SequenceNumber(2))
+            ]
+        );
+    }
+
+    #[rstest]
+    fn outdated_source_timestamp_schedules_nothing_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::BestEffort, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 10 }, destination_order: DestinationOrderQosPolicy::BySourceTimestamp, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+    ) {
+        let mut effects = Effects::new();
+        let instance = InstanceHandle::new([1; 16]);
+        let now = 1_000_000;
+
+        let build = |sequence: i64, emission: i64, lifespan: LifespanQosPolicy| {
+            MessageFactory::new(writer_guid_0.get_guid_prefix())
+                .message()
+                .reader(reader_guid_0.get_entity_id())
+                .writer(writer_guid_0.get_entity_id())
+                .info_timestamp(Some(Timestamp::from_datetime(
+                    DateTime::from_timestamp_millis(emission).unwrap(),
+                )))
+                .data(
+                    ContentNature::Data,
+                    SequenceNumber(sequence),
+                    Some(InlineQos {
+                        key_hash: instance,
+                        lifespan,
+                        ..Default::default()
+                    }),
+                    Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+                )
+                .build()
+        };
+
+        reader
+            .ingest(
+                &mut effects,
+                now,
+                build(1, 2_000, LifespanQosPolicy::default()),
+            )
+            .unwrap();
+        effects.clean();
+
+        // written before the change already accepted, its lifespan must not be scheduled
+        let lifespan = LifespanQosPolicy {
+            duration: Timestamp::from_datetime(DateTime::from_timestamp_millis(100).unwrap()),
+        };
+        reader
+            .ingest(&mut effects, now, build(2, 1_000, lifespan))
+            .unwrap();
+        assert!(
+            effects
+                .find(|e| matches!(e, Effect::ScheduleTick { .. }))
+                .is_none()
+        );
+        assert_eq!(
+            reader.get_all_available_changes(SampleStateKind::Any).len(),
+            1
+        );
+    }
+
+    #[rstest]
+    fn coherent_set_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::BestEffort, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 10 }, presentation: PresentationQosPolicy { coherent_access: true, ..Default::default() }, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+    ) {
+        let mut effects = Effects::new();
+
+        let mut send = |reader: &mut Reader, sequence: i64, first: i64, is_end: bool| {
+            let qos = InlineQos {
+                coherent_set: Some(SequenceNumber(first)),
+                ..Default::default()
+            };
+            let (nature, data) = if is_end {
+                (ContentNature::None, None)
+            } else {
+                (
+                    ContentNature::Data,
+                    Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+                )
+            };
+            let message = MessageFactory::new(writer_guid_0.get_guid_prefix())
+                .message()
+                .reader(reader_guid_0.get_entity_id())
+                .writer(writer_guid_0.get_entity_id())
+                .data(nature, SequenceNumber(sequence), Some(qos), data)
+                .build();
+            reader
+                .ingest(&mut effects, Utc::now().timestamp_millis(), message)
+                .unwrap();
+        };
+
+        send(&mut reader, 1, 1, false);
+        send(&mut reader, 2, 1, false);
+        assert!(
+            reader
+                .get_all_available_changes(SampleStateKind::Any)
+                .is_empty()
+        );
+
+        send(&mut reader, 3, 1, true);
+        assert_eq!(
+            reader.get_all_available_changes(SampleStateKind::Any).len(),
+            2
+        );
+
+        // a set completed while the changes are accessed is held back
+        reader.begin_access();
+        send(&mut reader, 4, 4, false);
+        send(&mut reader, 5, 4, true);
+        assert_eq!(
+            reader.get_all_available_changes(SampleStateKind::Any).len(),
+            2
+        );
+        reader.end_access(&mut Effects::new());
+        assert_eq!(
+            reader.get_all_available_changes(SampleStateKind::Any).len(),
+            3
+        );
+
+        // a best-effort Reader discards a set it did not entirely receive
+        send(&mut reader, 7, 6, false);
+        send(&mut reader, 8, 6, true);
+        assert_eq!(
+            reader.get_all_available_changes(SampleStateKind::Any).len(),
+            3
+        );
+    }
+
+    #[rstest]
+    fn group_coherent_set_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::BestEffort, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 10 }, presentation: PresentationQosPolicy { access_scope: PresentationAccessScope::Group, coherent_access: true, ..Default::default() }, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+        #[from(setup_uni_locatorlist)] uni_locators: LocatorList,
+    ) {
+        let mut effects = Effects::new();
+        let other_writer_guid = Guid::new(
+            writer_guid_0.get_guid_prefix(),
+            EntityId::writer_no_key([0, 0, 2]),
+        );
+        reader.add_proxy(WriterProxy::new(
+            other_writer_guid,
+            EntityId::default(),
+            60 * 1024,
+            uni_locators,
+            LocatorList::default(),
+        ));
+        // the third Writer of the group is not matched, this Reader expects nothing from it
+        let group = GroupCoherentSet {
+            sequence: SequenceNumber(1),
+            writers: vec![
+                writer_guid_0,
+                other_writer_guid,
+                Guid::new(
+                    writer_guid_0.get_guid_prefix(),
+                    EntityId::writer_no_key([0, 0, 3]),
+                ),
+            ],
+        };
+
+        let mut send = |reader: &mut Reader, writer_guid: Guid| {
+            let factory = || {
+                MessageFactory::new(writer_guid.get_guid_prefix())
+                    .message()
+                    .reader(reader_guid_0.get_entity_id())
+                    .writer(writer_guid.get_entity_id())
+            };
+            let message = factory()
+                .data(
+                    ContentNature::Data,
+                    SequenceNumber(1),
+                    Some(InlineQos {
+                        coherent_set: Some(SequenceNumber(1)),
+                        ..Default::default()
+                    }),
+                    Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+                )
+                .build();
+            reader.ingest(&mut effects, 0, message).unwrap();
+            let message = factory()
+                .data(
+                    ContentNature::None,
+                    SequenceNumber(2),
+                    Some(InlineQos {
+                        coherent_set: Some(SequenceNumber(1)),
+                        group_coherent_set: Some(group.clone()),
+                        ..Default::default()
+                    }),
+                    None,
+                )
+                .build();
+            reader.ingest(&mut effects, 0, message).unwrap();
+        };
+
+        send(&mut reader, writer_guid_0);
+        assert!(
+            reader
+                .get_all_available_changes(SampleStateKind::Any)
+                .is_empty()
+        );
+        assert!(!reader.is_group_coherent_set_complete(&group));
+
+        send(&mut reader, other_writer_guid);
+        assert!(
+            reader
+                .get_all_available_changes(SampleStateKind::Any)
+                .is_empty()
+        );
+        assert!(reader.is_group_coherent_set_complete(&group));
+        assert!(
+            effects
+                .find(|e| matches!(e, Effect::GroupCoherentSetComplete { .. }))
+                .is_some()
+        );
+
+        reader.commit_group_coherent_set(&mut Effects::new(), &group);
+        assert_eq!(
+            reader.get_all_available_changes(SampleStateKind::Any).len(),
+            2
+        );
+    }
+
+    #[rstest]
+    fn coherent_set_gap_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::Reliable, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 10 }, presentation: PresentationQosPolicy { coherent_access: true, ..Default::default() }, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+    ) {
+        let mut effects = Effects::new();
+        let factory = || {
+            MessageFactory::new(writer_guid_0.get_guid_prefix())
+                .message()
+                .reader(reader_guid_0.get_entity_id())
+                .writer(writer_guid_0.get_entity_id())
+        };
+        let qos = InlineQos {
+            coherent_set: Some(SequenceNumber(1)),
+            ..Default::default()
+        };
+
+        let message = factory()
+            .data(
+                ContentNature::Data,
+                SequenceNumber(1),
+                Some(qos.clone()),
+                Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+            )
+            .build();
+        reader.ingest(&mut effects, 0, message).unwrap();
+        let message = factory()
+            .data(ContentNature::None, SequenceNumber(3), Some(qos), None)
+            .build();
+        reader.ingest(&mut effects, 0, message).unwrap();
+        assert!(
+            reader
+                .get_all_available_changes(SampleStateKind::Any)
+                .is_empty()
+        );
+
+        // the second change of the set was removed from the Writer history
+        let message = factory()
+            .gap(
+                SequenceNumber(2),
+                SequenceNumberSet::new(SequenceNumber(3), &[]),
+                None,
+                None,
+            )
+            .build();
+        reader.ingest(&mut effects, 0, message).unwrap();
+        assert_eq!(
+            reader.get_all_available_changes(SampleStateKind::Any).len(),
+            1
+        );
+    }
+
+    #[rstest]
+    fn lifespan_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::Reliable, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 10 }, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+    ) {
+        let mut effects = Effects::new();
+        let emission = 1_000_000;
+
+        let qos = InlineQos {
+            lifespan: LifespanQosPolicy {
+                duration: Timestamp::from_datetime(DateTime::from_timestamp_millis(100).unwrap()),
+            },
+            ..Default::default()
+        };
+
+        let message = MessageFactory::new(writer_guid_0.get_guid_prefix())
+            .message()
+            .reader(reader_guid_0.get_entity_id())
+            .writer(writer_guid_0.get_entity_id())
+            .info_timestamp(Some(Timestamp::from_datetime(
+                DateTime::from_timestamp_millis(emission).unwrap(),
+            )))
+            .data(
+                ContentNature::Data,
+                SequenceNumber(1),
+                Some(qos),
+                Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+            )
+            .build();
+        reader.ingest(&mut effects, emission, message).unwrap();
+        assert!(
+            effects
+                .find(|e| matches!(
+                    e,
+                    Effect::ScheduleTick {
+                        id: TickId::Lifespan,
+                        delay: 100
+                    }
+                ))
+                .is_some()
+        );
+
+        reader.lifespan_tick(&mut effects, emission + 50);
+        assert_eq!(
+            reader.get_all_available_changes(SampleStateKind::Any).len(),
+            1
+        );
+
+        reader.lifespan_tick(&mut effects, emission + 100);
+        assert!(
+            reader
+                .get_all_available_changes(SampleStateKind::Any)
+                .is_empty()
+        );
+    }
+
+    #[rstest]
+    fn lifespan_after_unmatch_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::Reliable, InlineQos { history: HistoryQosPolicy::KeepLast { depth: 10 }, ..Default::default() })]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+    ) {
+        let mut effects = Effects::new();
+        let emission = 1_000_000;
+
+        let qos = InlineQos {
+            lifespan: LifespanQosPolicy {
+                duration: Timestamp::from_datetime(DateTime::from_timestamp_millis(100).unwrap()),
+            },
+            ..Default::default()
+        };
+        let message = MessageFactory::new(writer_guid_0.get_guid_prefix())
+            .message()
+            .reader(reader_guid_0.get_entity_id())
+            .writer(writer_guid_0.get_entity_id())
+            .info_timestamp(Some(Timestamp::from_datetime(
+                DateTime::from_timestamp_millis(emission).unwrap(),
+            )))
+            .data(
+                ContentNature::Data,
+                SequenceNumber(1),
+                Some(qos),
+                Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+            )
+            .build();
+        reader.ingest(&mut effects, emission, message).unwrap();
+
+        // the change of the unmatched Writer expires without its proxy
+        reader.remove_proxy(writer_guid_0);
+        reader.lifespan_tick(&mut effects, emission + 100);
+        assert!(
+            reader
+                .get_all_available_changes(SampleStateKind::Any)
+                .is_empty()
+        );
+    }
+
+    #[rstest]
+    fn gap_test(
+        #[from(setup_reader)]
+        #[with(ReliabilityKind::Reliable)]
+        mut reader: Reader,
+        #[from(setup_reader_0_guid)] reader_guid_0: Guid,
+        #[from(setup_writer_0_guid)] writer_guid_0: Guid,
+    ) {
+        let mut effects = Effects::new();
+
+        let message = MessageFactory::new(writer_guid_0.get_guid_prefix())
+            .message()
+            .reader(reader_guid_0.get_entity_id())
+            .writer(writer_guid_0.get_entity_id())
+            .gap(
+                SequenceNumber(1),
+                SequenceNumberSet::new(SequenceNumber(3), &[SequenceNumber(4)]),
+                None,
+                None,
+            )
+            .build();
+
+        reader
+            .ingest(&mut effects, Utc::now().timestamp_millis(), message)
+            .unwrap();
+        // 1 and 2 are irrelevant, 3 is still expected before the irrelevant 4
+        let proxy = reader.matched_writers.get(&writer_guid_0).unwrap();
+        assert_eq!(proxy.available_changes_max(), SequenceNumber(2));
+        assert!(!proxy.is_change_received(SequenceNumber(3)));
+
+        let message = MessageFactory::new(writer_guid_0.get_guid_prefix())
+            .message()
+            .reader(reader_guid_0.get_entity_id())
+            .writer(writer_guid_0.get_entity_id())
+            .data(
+                ContentNature::Data,
+                SequenceNumber(3),
+                None,
+                Some(SerializedData::from_vec(vec![0, 0, 0, 0])),
+            )
+            .build();
+        reader
+            .ingest(&mut effects, Utc::now().timestamp_millis(), message)
+            .unwrap();
+        let proxy = reader.matched_writers.get(&writer_guid_0).unwrap();
+        assert_eq!(proxy.available_changes_max(), SequenceNumber(4));
+        assert_eq!(
+            reader.get_all_available_changes(SampleStateKind::Any).len(),
+            1
+        );
+
+        // a GAP starting after the base of its list is invalid
+        let message = MessageFactory::new(writer_guid_0.get_guid_prefix())
+            .message()
+            .reader(reader_guid_0.get_entity_id())
+            .writer(writer_guid_0.get_entity_id())
+            .gap(
+                SequenceNumber(6),
+                SequenceNumberSet::new(SequenceNumber(5), &[]),
+                None,
+                None,
+            )
+            .build();
+        assert!(
+            reader
+                .ingest(&mut effects, Utc::now().timestamp_millis(), message)
+                .is_err()
+        );
+    }
+
     #[fixture]
     fn setup_reader(
         #[default(ReliabilityKind::BestEffort)] reliable: ReliabilityKind,
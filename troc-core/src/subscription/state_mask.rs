@@ -0,0 +1,100 @@
+use std::ops::BitOr;
+
+use crate::subscription::{InstanceStateKind, SampleStateKind, ViewStateKind};
+
+/// Set of [`SampleStateKind`] a read or take operation is interested in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleStateMask(u8);
+
+impl SampleStateMask {
+    pub const READ: Self = Self(0b01);
+    pub const NOT_READ: Self = Self(0b10);
+    pub const ANY: Self = Self(0b11);
+
+    pub fn contains(&self, kind: SampleStateKind) -> bool {
+        match kind {
+            SampleStateKind::NotRead => self.0 & Self::NOT_READ.0 != 0,
+            SampleStateKind::Read => self.0 & Self::READ.0 != 0,
+            SampleStateKind::Any => *self == Self::ANY,
+        }
+    }
+}
+
+impl Default for SampleStateMask {
+    fn default() -> Self {
+        Self::ANY
+    }
+}
+
+impl BitOr for SampleStateMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Set of [`ViewStateKind`] a read or take operation is interested in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewStateMask(u8);
+
+impl ViewStateMask {
+    pub const NEW: Self = Self(0b01);
+    pub const NOT_NEW: Self = Self(0b10);
+    pub const ANY: Self = Self(0b11);
+
+    pub fn contains(&self, kind: ViewStateKind) -> bool {
+        match kind {
+            ViewStateKind::New => self.0 & Self::NEW.0 != 0,
+            ViewStateKind::NotNew => self.0 & Self::NOT_NEW.0 != 0,
+        }
+    }
+}
+
+impl Default for ViewStateMask {
+    fn default() -> Self {
+        Self::ANY
+    }
+}
+
+impl BitOr for ViewStateMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Set of [`InstanceStateKind`] a read or take operation is interested in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstanceStateMask(u8);
+
+impl InstanceStateMask {
+    pub const ALIVE: Self = Self(0b001);
+    pub const NOT_ALIVE_DISPOSED: Self = Self(0b010);
+    pub const NOT_ALIVE_NO_WRITERS: Self = Self(0b100);
+    pub const NOT_ALIVE: Self = Self(0b110);
+    pub const ANY: Self = Self(0b111);
+
+    pub fn contains(&self, kind: InstanceStateKind) -> bool {
+        match kind {
+            InstanceStateKind::Alive => self.0 & Self::ALIVE.0 != 0,
+            InstanceStateKind::NotAliveDisposed => self.0 & Self::NOT_ALIVE_DISPOSED.0 != 0,
+            InstanceStateKind::NotAliveNoWriters => self.0 & Self::NOT_ALIVE_NO_WRITERS.0 != 0,
+        }
+    }
+}
+
+impl Default for InstanceStateMask {
+    fn default() -> Self {
+        Self::ANY
+    }
+}
+
+impl BitOr for InstanceStateMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
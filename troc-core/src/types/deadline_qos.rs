@@ -12,6 +12,13 @@ pub struct DeadlineQosPolicy {
     pub period: Timestamp,
 }
 
+impl DeadlineQosPolicy {
+    /// A zero period, the default, stands for an infinite deadline
+    pub fn is_infinite(&self) -> bool {
+        self.period == Timestamp::default()
+    }
+}
+
 impl Display for DeadlineQosPolicy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&format!("Deadline {{ period: {} }}", self.period))?;
@@ -1,8 +1,30 @@
+use std::fmt::Display;
+
+use binrw::binrw;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[binrw]
+#[brw(repr = u32)]
+#[br(import(_len: usize))]
 pub enum DestinationOrderQosPolicy {
+    /// Changes are applied in the order they are received
     #[default]
-    BySourceTimestamp,
     ByReceptionTimestamp,
+    /// Changes are applied in the order of their source timestamp, older changes of an instance are discarded
+    BySourceTimestamp,
+}
+
+impl Display for DestinationOrderQosPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DestinationOrderQosPolicy::ByReceptionTimestamp => {
+                f.write_str("DestinationOrderQosPolicy::ByReceptionTimestamp")?
+            }
+            DestinationOrderQosPolicy::BySourceTimestamp => {
+                f.write_str("DestinationOrderQosPolicy::BySourceTimestamp")?
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+use binrw::binrw;
+use serde::{Deserialize, Serialize};
+
+/// Opaque octets attached by the application to a Publisher or a Subscriber
+///
+/// They are propagated with the DataWriters and DataReaders of the group through the discovery
+#[binrw]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[br(import(_len: usize))]
+pub struct GroupDataQosPolicy {
+    #[br(temp)]
+    #[bw(calc = value.len() as u32)]
+    len: u32,
+    #[br(count = len)]
+    #[brw(align_after = 4)]
+    pub value: Vec<u8>,
+}
+
+impl GroupDataQosPolicy {
+    pub fn new(value: impl Into<Vec<u8>>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+}
+
+impl Display for GroupDataQosPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("GroupDataQosPolicy {{ value: {:?} }}", self.value))?;
+        Ok(())
+    }
+}
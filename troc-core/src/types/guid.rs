@@ -56,6 +56,19 @@ impl Guid {
         bytes
     }
 
+    /// Inverse of [`Guid::as_bytes`], the key of the builtin topics is the GUID of the described entity
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        let mut guid_prefix = [0u8; 12];
+        guid_prefix.copy_from_slice(&bytes[..12]);
+        Self {
+            guid_prefix: GuidPrefix(guid_prefix),
+            entity_id: EntityId {
+                entity_key: [bytes[12], bytes[13], bytes[14]],
+                entity_kind: bytes[15],
+            },
+        }
+    }
+
     pub fn role(&self) -> &'static str {
         match self.get_entity_id() {
             ENTITYID_SPDP_BUILTIN_PARTICIPANT_DETECTOR => "participant_reader",
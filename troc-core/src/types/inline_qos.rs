@@ -4,8 +4,11 @@ use binrw::{BinRead, BinWrite, Endian, binrw};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    DeadlineQosPolicy, DurabilityQosPolicy, HistoryQosPolicy, InstanceHandle, LifespanQosPolicy,
-    LivelinessQosPolicy, ParameterId, ParameterList, ReliabilityQosPolicy, RtpsString,
+    DeadlineQosPolicy, DestinationOrderQosPolicy, DurabilityQosPolicy, GroupCoherentSet,
+    GroupDataQosPolicy, HistoryQosPolicy, InstanceHandle, LifespanQosPolicy, LivelinessQosPolicy,
+    OwnershipQosPolicy, OwnershipStrengthQosPolicy, ParameterId, ParameterList, PartitionQosPolicy,
+    PresentationQosPolicy, ReliabilityQosPolicy, ResourceLimitsQosPolicy, RtpsString,
+    SequenceNumber, StatusInfo, TimeBasedFilterQosPolicy, TopicDataQosPolicy, UserDataQosPolicy,
 };
 
 #[derive(
@@ -23,6 +26,21 @@ pub struct InlineQos {
     pub lifespan: LifespanQosPolicy,
     pub history: HistoryQosPolicy,
     pub liveness: LivelinessQosPolicy,
+    pub resource_limits: ResourceLimitsQosPolicy,
+    pub ownership: OwnershipQosPolicy,
+    pub ownership_strength: OwnershipStrengthQosPolicy,
+    pub partition: PartitionQosPolicy,
+    pub time_based_filter: TimeBasedFilterQosPolicy,
+    pub destination_order: DestinationOrderQosPolicy,
+    pub presentation: PresentationQosPolicy,
+    /// Sequence number of the first change of the coherent set a change belongs to
+    pub coherent_set: Option<SequenceNumber>,
+    /// Group coherent set ended by a change ending a coherent set of a Publisher with GROUP access scope
+    pub group_coherent_set: Option<GroupCoherentSet>,
+    pub user_data: UserDataQosPolicy,
+    pub topic_data: TopicDataQosPolicy,
+    pub group_data: GroupDataQosPolicy,
+    pub status_info: StatusInfo,
 }
 
 impl InlineQos {
@@ -56,6 +74,45 @@ impl InlineQos {
         let liveness = parameter_list
             .get_param::<LivelinessQosPolicy>(ParameterId::PID_LIVELINESS, endian)
             .unwrap_or_default();
+        let resource_limits = parameter_list
+            .get_param::<ResourceLimitsQosPolicy>(ParameterId::PID_RESOURCE_LIMITS, endian)
+            .unwrap_or_default();
+        let ownership = parameter_list
+            .get_param::<OwnershipQosPolicy>(ParameterId::PID_OWNERSHIP, endian)
+            .unwrap_or_default();
+        let ownership_strength = parameter_list
+            .get_param::<OwnershipStrengthQosPolicy>(ParameterId::PID_OWNERSHIP_STRENGTH, endian)
+            .unwrap_or_default();
+        let partition = parameter_list
+            .get_param::<PartitionQosPolicy>(ParameterId::PID_PARTITION, endian)
+            .unwrap_or_default();
+        let time_based_filter = parameter_list
+            .get_param::<TimeBasedFilterQosPolicy>(ParameterId::PID_TIME_BASED_FILTER, endian)
+            .unwrap_or_default();
+        let destination_order = parameter_list
+            .get_param::<DestinationOrderQosPolicy>(ParameterId::PID_DESTINATION_ORDER, endian)
+            .unwrap_or_default();
+        let presentation = parameter_list
+            .get_param::<PresentationQosPolicy>(ParameterId::PID_PRESENTATION, endian)
+            .unwrap_or_default();
+        let coherent_set = parameter_list
+            .get_params::<SequenceNumber>(ParameterId::PID_COHERENT_SET, endian)
+            .into_iter()
+            .next();
+        let group_coherent_set = parameter_list
+            .get_param::<GroupCoherentSet>(ParameterId::PID_GROUP_COHERENT_SET_WRITERS, endian);
+        let user_data = parameter_list
+            .get_param::<UserDataQosPolicy>(ParameterId::PID_USER_DATA, endian)
+            .unwrap_or_default();
+        let topic_data = parameter_list
+            .get_param::<TopicDataQosPolicy>(ParameterId::PID_TOPIC_DATA, endian)
+            .unwrap_or_default();
+        let group_data = parameter_list
+            .get_param::<GroupDataQosPolicy>(ParameterId::PID_GROUP_DATA, endian)
+            .unwrap_or_default();
+        let status_info = parameter_list
+            .get_param::<StatusInfo>(ParameterId::PID_STATUS_INFO, endian)
+            .unwrap_or_default();
 
         Self {
             topic_name,
@@ -67,6 +124,42 @@ impl InlineQos {
             lifespan,
             history,
             liveness,
+            resource_limits,
+            ownership,
+            ownership_strength,
+            partition,
+            time_based_filter,
+            destination_order,
+            presentation,
+            coherent_set,
+            group_coherent_set,
+            user_data,
+            topic_data,
+            group_data,
+            status_info,
+        }
+    }
+
+    /// Name of the first policy that cannot be changed once the entity is enabled and that differs in `new`
+    pub fn changed_immutable_policy(&self, new: &InlineQos) -> Option<&'static str> {
+        if self.durability != new.durability {
+            Some("DURABILITY")
+        } else if self.reliability != new.reliability {
+            Some("RELIABILITY")
+        } else if self.history != new.history {
+            Some("HISTORY")
+        } else if self.resource_limits != new.resource_limits {
+            Some("RESOURCE_LIMITS")
+        } else if self.liveness != new.liveness {
+            Some("LIVELINESS")
+        } else if self.ownership != new.ownership {
+            Some("OWNERSHIP")
+        } else if self.destination_order != new.destination_order {
+            Some("DESTINATION_ORDER")
+        } else if self.presentation != new.presentation {
+            Some("PRESENTATION")
+        } else {
+            None
         }
     }
 
@@ -95,6 +188,54 @@ impl From<InlineQos> for ParameterList {
         param_list.set_param(ParameterId::PID_LIFESPAN, value.lifespan, Endian::Big);
         param_list.set_param(ParameterId::PID_HISTORY, value.history, Endian::Big);
         param_list.set_param(ParameterId::PID_LIVELINESS, value.liveness, Endian::Big);
+        param_list.set_param(
+            ParameterId::PID_RESOURCE_LIMITS,
+            value.resource_limits,
+            Endian::Big,
+        );
+        param_list.set_param(ParameterId::PID_OWNERSHIP, value.ownership, Endian::Big);
+        param_list.set_param(
+            ParameterId::PID_OWNERSHIP_STRENGTH,
+            value.ownership_strength,
+            Endian::Big,
+        );
+        param_list.set_param(ParameterId::PID_PARTITION, value.partition, Endian::Big);
+        param_list.set_param(
+            ParameterId::PID_TIME_BASED_FILTER,
+            value.time_based_filter,
+            Endian::Big,
+        );
+        param_list.set_param(
+            ParameterId::PID_DESTINATION_ORDER,
+            value.destination_order,
+            Endian::Big,
+        );
+        param_list.set_param(
+            ParameterId::PID_PRESENTATION,
+            value.presentation,
+            Endian::Big,
+        );
+        if let Some(coherent_set) = value.coherent_set {
+            param_list.set_param(ParameterId::PID_COHERENT_SET, coherent_set, Endian::Big);
+        }
+        if let Some(group_coherent_set) = value.group_coherent_set {
+            param_list.set_param(
+                ParameterId::PID_GROUP_COHERENT_SET_WRITERS,
+                group_coherent_set,
+                Endian::Big,
+            );
+        }
+        // an empty octet sequence is the default, no need to send it
+        if !value.user_data.value.is_empty() {
+            param_list.set_param(ParameterId::PID_USER_DATA, value.user_data, Endian::Big);
+        }
+        if !value.topic_data.value.is_empty() {
+            param_list.set_param(ParameterId::PID_TOPIC_DATA, value.topic_data, Endian::Big);
+        }
+        if !value.group_data.value.is_empty() {
+            param_list.set_param(ParameterId::PID_GROUP_DATA, value.group_data, Endian::Big);
+        }
+        param_list.set_param(ParameterId::PID_STATUS_INFO, value.status_info, Endian::Big);
         param_list
     }
 }
@@ -108,8 +249,8 @@ impl From<ParameterList> for InlineQos {
 impl Display for InlineQos {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&format!(
-            "Topic name: {}, Type name: {}, Key hash: {}, Durability: {}, Deadline: {}, Reliability: {}, Lifespan: {}, History: {}, Liveness: {}",
-            self.topic_name, self.type_name, self.key_hash, self.durability, self.deadline, self.reliability, self.lifespan, self.history, self.liveness
+            "Topic name: {}, Type name: {}, Key hash: {}, Durability: {}, Deadline: {}, Reliability: {}, Lifespan: {}, History: {}, Liveness: {}, Resource limits: {}, Ownership: {}, Ownership strength: {}, Partition: {}, Time based filter: {}, Destination order: {}, Presentation: {}, Coherent set: {:?}, Group coherent set: {:?}, User data: {}, Topic data: {}, Group data: {}, Status info: {:?}",
+            self.topic_name, self.type_name, self.key_hash, self.durability, self.deadline, self.reliability, self.lifespan, self.history, self.liveness, self.resource_limits, self.ownership, self.ownership_strength, self.partition, self.time_based_filter, self.destination_order, self.presentation, self.coherent_set, self.group_coherent_set, self.user_data, self.topic_data, self.group_data, self.status_info
         ))?;
         Ok(())
     }
@@ -12,6 +12,13 @@ pub struct LifespanQosPolicy {
     pub duration: Timestamp,
 }
 
+impl LifespanQosPolicy {
+    /// A zero duration, the default, stands for an infinite lifespan
+    pub fn is_infinite(&self) -> bool {
+        self.duration == Timestamp::default()
+    }
+}
+
 impl Display for LifespanQosPolicy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&format!(
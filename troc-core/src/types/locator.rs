@@ -1,6 +1,6 @@
 use std::{
     fmt::{Debug, Display},
-    net::{AddrParseError, IpAddr, Ipv4Addr, SocketAddr},
+    net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     str::FromStr,
 };
 
@@ -30,8 +30,10 @@ pub struct Locator {
 }
 
 impl Locator {
-    const REGEX_STRING: &'static str =
-        r"(?P<addr>(?:[0-9]{1,3}\.){3}[0-9]{1,3}):(?P<port>\d{1,5}):(?P<kind>(UDPV4|IPC))";
+    /// `ADDRESS:PORT:KIND`, an IPv6 address is enclosed in brackets (e.g. `[ff02::1]:7400:UDPV6`)
+    ///
+    /// The double colon written by [`Display`] before the kind is accepted too
+    const REGEX_STRING: &'static str = r"(?P<addr>(?:[0-9]{1,3}\.){3}[0-9]{1,3}|\[[0-9a-fA-F:.]+\]):(?P<port>\d{1,5}):{1,2}(?P<kind>(UDPV4|UDPV6|TCPV4|IPC))";
 
     pub fn new(kind: LocatorKind, address: [u8; 16], port: u32) -> Self {
         Self {
@@ -57,9 +59,18 @@ impl FromStr for Locator {
         if let Some(caps) = re.captures(s) {
             let addr = &caps["addr"];
             let port = &caps["port"];
-            let _kind = &caps["kind"];
+            let kind = &caps["kind"];
             let complete = &format!("{addr}:{port}");
-            let locator = Locator::from(complete).unwrap();
+            let mut locator =
+                Locator::from(complete).map_err(|e| regex::Error::Syntax(e.to_string()))?;
+            if kind == "TCPV4" {
+                if locator.kind != LocatorKind::UdpV4 {
+                    return Err(regex::Error::Syntax(String::from(
+                        "a TCPV4 locator has an IPv4 address",
+                    )));
+                }
+                locator.kind = LocatorKind::TcpV4;
+            }
             Ok(locator)
         } else {
             Err(regex::Error::Syntax("No regex match".to_string()))
@@ -80,12 +91,15 @@ impl Debug for Locator {
 impl Display for Locator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let address = match self.kind {
-            LocatorKind::UdpV4 => self
+            LocatorKind::UdpV4 | LocatorKind::TcpV4 => self
                 .address
                 .iter()
                 .skip(12)
                 .map(|s| s.to_string())
                 .join("."),
+            LocatorKind::UdpV6 => format!("[{}]", Ipv6Addr::from(self.address)),
+            // the address of a shared-memory locator identifies its host
+            LocatorKind::Shm => self.address.iter().map(|b| format!("{b:02x}")).join(""),
             _ => self
                 .address
                 .iter()
@@ -107,7 +121,11 @@ impl Locator {
                 address: Locator::from_ipv4addr_to_generic_addr(addr),
                 port: sockaddr.port().into(),
             },
-            _ => unreachable!(),
+            IpAddr::V6(addr) => Locator {
+                kind: LocatorKind::UdpV6,
+                address: addr.octets(),
+                port: sockaddr.port().into(),
+            },
         };
         Ok(locator)
     }
@@ -126,21 +144,34 @@ impl TryFrom<SocketAddr> for Locator {
                 };
                 Ok(loc)
             }
-            _ => unreachable!(),
+            SocketAddr::V6(sock_addr_v6) => {
+                let loc = Self {
+                    kind: LocatorKind::UdpV6,
+                    address: sock_addr_v6.ip().octets(),
+                    port: sock_addr_v6.port().into(),
+                };
+                Ok(loc)
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod locator_tests {
+    use std::str::FromStr;
+
     use regex::Regex;
     use rstest::rstest;
 
     use super::Locator;
+    use crate::types::LocatorKind;
 
     #[rstest]
     #[case("127.0.0.1", "9000", "UDPV4")]
     #[case("0.0.0.0", "65000", "IPC")]
+    #[case("[ff02::1]", "7400", "UDPV6")]
+    #[case("[fd00::2]", "7410", "UDPV6")]
+    #[case("10.0.0.2", "7410", "TCPV4")]
     #[should_panic]
     #[case("0.0.0.0", "65000", "SOMETHING_WRONG")]
     fn from_str_components(#[case] addr: &str, #[case] port: &str, #[case] kind: &str) {
@@ -155,4 +186,23 @@ mod locator_tests {
             panic!()
         }
     }
+
+    #[rstest]
+    #[case("127.0.0.1:9000:UDPV4", LocatorKind::UdpV4, "127.0.0.1:9000::UDPV4")]
+    #[case(
+        "[ff05::1:2]:7400:UDPV6",
+        LocatorKind::UdpV6,
+        "[ff05::1:2]:7400::UDPV6"
+    )]
+    #[case("10.0.0.2:7410:TCPV4", LocatorKind::TcpV4, "10.0.0.2:7410::TCPV4")]
+    fn from_str_then_display(
+        #[case] origin: &str,
+        #[case] kind: LocatorKind,
+        #[case] displayed: &str,
+    ) {
+        let locator = Locator::from_str(origin).unwrap();
+        assert_eq!(locator.kind, kind);
+        assert_eq!(locator.to_string(), displayed);
+        assert_eq!(Locator::from_str(displayed).unwrap(), locator);
+    }
 }
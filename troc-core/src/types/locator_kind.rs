@@ -7,6 +7,7 @@ pub static LOCATOR_KIND_INVALID: LocatorKind = LocatorKind::Invalid;
 pub static LOCATOR_KIND_RESERVED: LocatorKind = LocatorKind::Reserved;
 pub static LOCATOR_KIND_UDPV4: LocatorKind = LocatorKind::UdpV4;
 pub static LOCATOR_KIND_UDPV6: LocatorKind = LocatorKind::UdpV6;
+pub static LOCATOR_KIND_TCPV4: LocatorKind = LocatorKind::TcpV4;
 
 #[binrw]
 #[derive(
@@ -18,6 +19,8 @@ pub enum LocatorKind {
     UdpV4 = 1,
     UdpV6 = 2,
     Shm = 16,
+    /// RTPS over TCP, in the vendor-specific range (0x8000_0004) where the most significant bit is set
+    TcpV4 = -0x7fff_fffc,
     Reserved = 0,
     Invalid = -1,
 }
@@ -27,6 +30,8 @@ impl Display for LocatorKind {
         let kind_str = match self {
             LocatorKind::UdpV4 => "UDPV4",
             LocatorKind::UdpV6 => "UDPV6",
+            LocatorKind::Shm => "SHM",
+            LocatorKind::TcpV4 => "TCPV4",
             _ => "OTHER",
         };
         f.write_str(kind_str)?;
@@ -24,6 +24,7 @@ pub mod filter_signature;
 pub mod filter_signature_sequence;
 pub mod fragment_number;
 pub mod fragment_number_set;
+mod group_data_qos;
 pub mod groupd_digest;
 pub mod guid;
 mod history_qos;
@@ -39,12 +40,16 @@ pub mod locator_kind;
 pub mod locator_list;
 pub mod message_length;
 pub mod original_writer_info;
+mod ownership_qos;
+mod ownership_strength_qos;
 pub mod parameter;
 pub mod parameter_id;
 pub mod parameter_list;
 pub mod participant_builtin_topic_data;
 pub mod participant_message_data;
 mod participant_proxy;
+mod partition_qos;
+mod presentation_qos;
 pub mod property;
 pub mod protocol_id;
 pub mod protocol_version;
@@ -64,6 +69,7 @@ pub mod submessage_kind;
 pub mod time;
 mod time_based_filter_qos;
 mod timestamp;
+mod topic_data_qos;
 pub mod topic_kind;
 pub mod u_extension4;
 pub mod user_data_qos_policy;
@@ -81,6 +87,7 @@ pub use durability_qos::DurabilityQosPolicy;
 pub use duration_kind::DurationKind;
 pub use fragment_number::FragmentNumber;
 pub use fragment_number_set::FragmentNumberSet;
+pub use group_data_qos::GroupDataQosPolicy;
 pub use guid::*;
 pub use history_qos::HistoryQosPolicy;
 pub use inline_qos::InlineQos;
@@ -90,22 +97,29 @@ pub use liveliness_qos::{LivelinessKind, LivelinessQosPolicy};
 pub use locator::Locator;
 pub use locator_kind::*;
 pub use locator_list::LocatorList;
+pub use ownership_qos::OwnershipQosPolicy;
+pub use ownership_strength_qos::OwnershipStrengthQosPolicy;
 pub use parameter::Parameter;
 pub use parameter_id::ParameterId;
 pub use parameter_list::ParameterList;
 pub use participant_proxy::ParticipantProxy;
+pub use partition_qos::{PartitionQosPolicy, wildcard_match};
+pub use presentation_qos::{GroupCoherentSet, PresentationAccessScope, PresentationQosPolicy};
 pub use reliability_kind::ReliabilityKind;
 pub use reliability_qos::ReliabilityQosPolicy;
-pub use resource_limits_qos::ResourceLimitsQosPolicy;
+pub use resource_limits_qos::{LENGTH_UNLIMITED, ResourceLimitsQosPolicy};
 pub use sequence_number::{SEQUENCENUMBER_INVALID, SequenceNumber};
 pub use sequence_number_set::SequenceNumberSet;
 pub use serialized_data::SerializedData;
 pub use spdp_discovered_participant_data::PdpDiscoveredParticipantData;
+pub use status_info::StatusInfo;
 pub use string::RtpsString;
 pub use submessage_flags::SubmessageFlags;
 pub use time_based_filter_qos::TimeBasedFilterQosPolicy;
 pub use timestamp::{TIME_INFINITE, TIME_INVALID, TIME_ZERO, Timestamp};
+pub use topic_data_qos::TopicDataQosPolicy;
 pub use topic_kind::TopicKind;
+pub use user_data_qos_policy::UserDataQosPolicy;
 pub use vendor_id::*;
 
 #[derive(Debug, Clone, Copy)]
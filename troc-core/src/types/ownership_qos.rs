@@ -0,0 +1,26 @@
+use std::fmt::Display;
+
+use binrw::binrw;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[binrw]
+#[brw(repr = u32)]
+#[br(import(_len: usize))]
+pub enum OwnershipQosPolicy {
+    /// Every matched Writer can update an instance
+    #[default]
+    Shared,
+    /// Only the Writer with the highest OWNERSHIP_STRENGTH updates an instance
+    Exclusive,
+}
+
+impl Display for OwnershipQosPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OwnershipQosPolicy::Shared => f.write_str("OwnershipQosPolicy::Shared")?,
+            OwnershipQosPolicy::Exclusive => f.write_str("OwnershipQosPolicy::Exclusive")?,
+        }
+        Ok(())
+    }
+}
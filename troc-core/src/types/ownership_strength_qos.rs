@@ -0,0 +1,21 @@
+use std::fmt::Display;
+
+use binrw::binrw;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[binrw]
+#[br(import(_len: usize))]
+pub struct OwnershipStrengthQosPolicy {
+    pub value: i32,
+}
+
+impl Display for OwnershipStrengthQosPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "OwnershipStrengthQosPolicy {{ value: {} }}",
+            self.value
+        ))?;
+        Ok(())
+    }
+}
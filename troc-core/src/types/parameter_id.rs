@@ -68,6 +68,9 @@ impl ParameterId {
 
     // TODO: is this official ?
     pub const PID_TIMESTAMP: ParameterId = ParameterId(0x6FFF);
+
+    /// Vendor-specific (0x8001) where the most significant bit is set, the group coherent set a coherent set belongs to
+    pub const PID_GROUP_COHERENT_SET_WRITERS: ParameterId = ParameterId(0x8001_u16 as i16);
 }
 
 impl Display for ParameterId {
@@ -1,4 +1,117 @@
-#[derive(Debug, Clone, Default)]
+use std::io::Cursor;
+
+use binrw::{BinRead, BinWrite, Endian, binrw};
+
+use crate::common::Error;
+
+use super::{GuidPrefix, InstanceHandle, LivelinessKind, SerializedData};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[binrw]
+pub struct ParticipantMessageKind(pub [u8; 4]);
+
+impl ParticipantMessageKind {
+    pub const UNKNOWN: ParticipantMessageKind = ParticipantMessageKind([0x00, 0x00, 0x00, 0x00]);
+    pub const AUTOMATIC_LIVELINESS_UPDATE: ParticipantMessageKind =
+        ParticipantMessageKind([0x00, 0x00, 0x00, 0x01]);
+    pub const MANUAL_LIVELINESS_UPDATE: ParticipantMessageKind =
+        ParticipantMessageKind([0x00, 0x00, 0x00, 0x02]);
+
+    /// The LIVELINESS kind of the Writers asserted by this message, if it is a liveliness update
+    pub fn liveliness_kind(&self) -> Option<LivelinessKind> {
+        match *self {
+            Self::AUTOMATIC_LIVELINESS_UPDATE => Some(LivelinessKind::Automatic),
+            Self::MANUAL_LIVELINESS_UPDATE => Some(LivelinessKind::ManualByParticipant),
+            _ => None,
+        }
+    }
+}
+
+/// Payload exchanged by the BuiltinParticipantMessageWriter/Reader, used to assert the liveliness of a Participant
+#[binrw]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ParticipantMessageData {
-    //
+    pub participant_guid_prefix: GuidPrefix,
+    pub kind: ParticipantMessageKind,
+    #[br(temp)]
+    #[bw(calc = data.len() as u32)]
+    data_len: u32,
+    #[br(count = data_len)]
+    pub data: Vec<u8>,
+}
+
+impl ParticipantMessageData {
+    pub fn new(participant_guid_prefix: GuidPrefix, kind: ParticipantMessageKind) -> Self {
+        Self {
+            participant_guid_prefix,
+            kind,
+            data: Vec::new(),
+        }
+    }
+
+    /// The instance is the pair (participant_guid_prefix, kind), only the last message of each kind matters
+    pub fn get_instance(&self) -> InstanceHandle {
+        let mut key = [0; 16];
+        key[..12].copy_from_slice(&self.participant_guid_prefix.0);
+        key[12..].copy_from_slice(&self.kind.0);
+        InstanceHandle(key)
+    }
+
+    pub fn from_serialized_data(data: SerializedData) -> Result<Self, Error> {
+        let data = data.get_data();
+        let endian = match data.get(1) {
+            Some(0x03) => Endian::Little,
+            Some(0x02) => Endian::Big,
+            _ => return Err(Error::DeserializationError),
+        };
+        let mut reader = Cursor::new(&data[4..]);
+        Self::read_options(&mut reader, endian, ()).map_err(|_| Error::DeserializationError)
+    }
+
+    pub fn into_serialized_data(&self, endian: Endian) -> Result<SerializedData, Error> {
+        let mut writer = Cursor::new(Vec::<u8>::new());
+        self.write_options(&mut writer, endian, ())
+            .map_err(|_| Error::SerializationError)?;
+        let buf = writer.into_inner();
+
+        let cdr_header: &[u8] = match endian {
+            Endian::Big => &[0x00, 0x02, 0x00, 0x00],
+            Endian::Little => &[0x00, 0x03, 0x00, 0x00],
+        };
+
+        let buf = [cdr_header, &buf].concat();
+
+        Ok(SerializedData::from_slice(&buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use binrw::Endian;
+    use rstest::rstest;
+
+    use crate::types::{GuidPrefix, LivelinessKind};
+
+    use super::{ParticipantMessageData, ParticipantMessageKind};
+
+    #[rstest]
+    #[case(Endian::Big)]
+    #[case(Endian::Little)]
+    fn serialization_roundtrip(#[case] endian: Endian) {
+        let mut message = ParticipantMessageData::new(
+            GuidPrefix([7; 12]),
+            ParticipantMessageKind::MANUAL_LIVELINESS_UPDATE,
+        );
+        message.data = vec![1, 2, 3];
+
+        let serialized = message.into_serialized_data(endian).unwrap();
+        assert_eq!(serialized.size(), 4 + 12 + 4 + 4 + 3);
+
+        let deserialized = ParticipantMessageData::from_serialized_data(serialized).unwrap();
+        assert_eq!(deserialized, message);
+        assert_eq!(
+            deserialized.kind.liveliness_kind(),
+            Some(LivelinessKind::ManualByParticipant)
+        );
+    }
 }
@@ -3,9 +3,10 @@ use std::fmt::Display;
 use serde::{Deserialize, Serialize};
 
 use super::{
-    Count, DomainTag, ENTITYID_PARTICIPANT, Guid, GuidPrefix, Locator, LocatorList, VendorId,
-    builtin_endpoint_qos::BuiltinEndpointQos, builtin_endpoint_set::BuiltinEndpointSet,
-    domain_id::DomainId, protocol_version::ProtocolVersion,
+    Count, DomainTag, ENTITYID_PARTICIPANT, Guid, GuidPrefix, Locator, LocatorList,
+    UserDataQosPolicy, VendorId, builtin_endpoint_qos::BuiltinEndpointQos,
+    builtin_endpoint_set::BuiltinEndpointSet, domain_id::DomainId,
+    protocol_version::ProtocolVersion,
 };
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
@@ -23,6 +24,7 @@ pub struct ParticipantProxy {
     pub default_unicast_locator_list: LocatorList,
     pub default_multicast_locator_list: LocatorList,
     pub manual_liveliness_count: Count,
+    pub user_data: UserDataQosPolicy,
 }
 
 impl ParticipantProxy {
@@ -53,6 +55,7 @@ impl ParticipantProxy {
             available_builtin_endpoints,
             manual_liveliness_count: Count::default(),
             builtin_endpoint_qos,
+            user_data: UserDataQosPolicy::default(),
         }
     }
 
@@ -91,9 +94,9 @@ impl ParticipantProxy {
 
 impl Display for ParticipantProxy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&format!("GuidPrefix: {}, BuiltinEndpoints: {}, MetaUniLocators: {}, MetaMultiLocators: {}, UniLocators: {}, MultiLocators: {}, DomainId: {}, DomainTag: {:?}, ProtocolVersion: {}, VendorId: {}, ExpectInlineQos: {}, ManualLiveliness: {}, BuiltinEndpointQos: {}", 
+        f.write_str(&format!("GuidPrefix: {}, BuiltinEndpoints: {}, MetaUniLocators: {}, MetaMultiLocators: {}, UniLocators: {}, MultiLocators: {}, DomainId: {}, DomainTag: {:?}, ProtocolVersion: {}, VendorId: {}, ExpectInlineQos: {}, ManualLiveliness: {}, BuiltinEndpointQos: {}, UserData: {}", 
         self.guid_prefix, self.available_builtin_endpoints, self.metatraffic_unicast_locator_list, self.metatraffic_multicast_locator_list, self.default_unicast_locator_list, self.default_multicast_locator_list, self.domain_id, self.domain_tag, self.protocol_version, self.vendor_id, self.expects_inline_qos,
-        self.manual_liveliness_count, self.builtin_endpoint_qos
+        self.manual_liveliness_count, self.builtin_endpoint_qos, self.user_data
     ))?;
         Ok(())
     }
@@ -0,0 +1,200 @@
+use std::fmt::Display;
+
+use binrw::{BinResult, Endian, binrw};
+use serde::{Deserialize, Serialize};
+
+/// Logical partitions a Publisher or a Subscriber belongs to
+///
+/// Names can contain fnmatch-style wildcards (`*`, `?` and `[...]`), an empty list stands for the default partition `""`
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[binrw]
+#[br(import(len: usize))]
+pub struct PartitionQosPolicy {
+    #[br(parse_with = PartitionQosPolicy::custom_parser, args((len,)))]
+    #[bw(write_with = PartitionQosPolicy::custom_writer)]
+    pub names: Vec<String>,
+}
+
+impl PartitionQosPolicy {
+    pub fn new<S: Into<String>>(names: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            names: names.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Whether a name of this partition set matches a name of `other`
+    ///
+    /// A wildcard name matches the plain names of the other set, two wildcard names only match if they are identical
+    pub fn intersects(&self, other: &PartitionQosPolicy) -> bool {
+        self.effective_names().any(|name| {
+            other
+                .effective_names()
+                .any(|other_name| names_match(name, other_name))
+        })
+    }
+
+    fn effective_names(&self) -> impl Iterator<Item = &str> + Clone {
+        let default: &[&str] = if self.names.is_empty() { &[""] } else { &[] };
+        self.names
+            .iter()
+            .map(String::as_str)
+            .chain(default.iter().copied())
+    }
+
+    #[binrw::parser(reader, endian)]
+    fn custom_parser(args: (usize,)) -> BinResult<Vec<String>> {
+        let mut buf = vec![0u8; args.0];
+        reader.read_exact(&mut buf)?;
+
+        let read_u32 = |offset: usize| -> Option<u32> {
+            let bytes: [u8; 4] = buf.get(offset..offset + 4)?.try_into().ok()?;
+            Some(match endian {
+                Endian::Little => u32::from_le_bytes(bytes),
+                Endian::Big => u32::from_be_bytes(bytes),
+            })
+        };
+
+        let mut names = Vec::new();
+        let count = read_u32(0).unwrap_or_default();
+        let mut offset = 4;
+        for _ in 0..count {
+            let Some(len) = read_u32(offset) else {
+                break;
+            };
+            offset += 4;
+            let Some(bytes) = buf.get(offset..offset + len as usize) else {
+                break;
+            };
+            // the serialized length accounts for the terminating NUL character
+            let name = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+            names.push(String::from_utf8_lossy(name).to_string());
+            offset += (len as usize).next_multiple_of(4);
+        }
+
+        Ok(names)
+    }
+
+    #[binrw::writer(writer, endian)]
+    fn custom_writer(names: &Vec<String>) -> BinResult<()> {
+        let to_bytes = |value: u32| match endian {
+            Endian::Little => value.to_le_bytes(),
+            Endian::Big => value.to_be_bytes(),
+        };
+
+        let mut value = to_bytes(names.len() as u32).to_vec();
+        for name in names {
+            let len = name.len() + 1;
+            value.extend(to_bytes(len as u32));
+            value.extend(name.as_bytes());
+            value.resize(value.len() + len.next_multiple_of(4) - name.len(), 0);
+        }
+
+        writer.write_all(&value)?;
+        Ok(())
+    }
+}
+
+impl Display for PartitionQosPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("PartitionQosPolicy {{ names: {:?} }}", self.names))?;
+        Ok(())
+    }
+}
+
+fn is_pattern(name: &str) -> bool {
+    name.contains(['*', '?', '['])
+}
+
+fn names_match(name: &str, other: &str) -> bool {
+    match (is_pattern(name), is_pattern(other)) {
+        (true, true) => name == other,
+        (true, false) => fnmatch(name.as_bytes(), other.as_bytes()),
+        (false, true) => fnmatch(other.as_bytes(), name.as_bytes()),
+        (false, false) => name == other,
+    }
+}
+
+/// Whether `name` matches the fnmatch-style `pattern`, the way the names of a PARTITION are matched
+pub fn wildcard_match(pattern: &str, name: &str) -> bool {
+    fnmatch(pattern.as_bytes(), name.as_bytes())
+}
+
+/// Match `name` against the shell-style `pattern`
+fn fnmatch(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| fnmatch(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && fnmatch(rest, &name[1..]),
+        Some((b'[', rest)) => {
+            let Some(end) = rest.iter().skip(1).position(|c| *c == b']').map(|p| p + 1) else {
+                // an unterminated class is a plain character
+                return name.first() == Some(&b'[') && fnmatch(rest, &name[1..]);
+            };
+            let Some((c, name_rest)) = name.split_first() else {
+                return false;
+            };
+            let (negated, class) = match &rest[..end] {
+                [b'!' | b'^', class @ ..] => (true, class),
+                class => (false, class),
+            };
+            let mut matched = false;
+            let mut i = 0;
+            while i < class.len() {
+                if i + 2 < class.len() && class[i + 1] == b'-' {
+                    matched |= (class[i]..=class[i + 2]).contains(c);
+                    i += 3;
+                } else {
+                    matched |= class[i] == *c;
+                    i += 1;
+                }
+            }
+            matched != negated && fnmatch(&rest[end + 1..], name_rest)
+        }
+        Some((p, rest)) => name.first() == Some(p) && fnmatch(rest, &name[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use binrw::{BinRead, BinWrite, Endian};
+
+    use super::PartitionQosPolicy;
+
+    #[test]
+    fn wildcard_intersection() {
+        let tenant_a = PartitionQosPolicy::new(["tenant-a"]);
+        let tenant_b = PartitionQosPolicy::new(["tenant-b"]);
+        let tenants = PartitionQosPolicy::new(["tenant-?"]);
+        let others = PartitionQosPolicy::new(["tenant-[!a]", "*-c"]);
+
+        assert!(tenant_a.intersects(&tenants));
+        assert!(tenants.intersects(&tenant_b));
+        assert!(!tenant_a.intersects(&tenant_b));
+        assert!(!tenant_a.intersects(&others));
+        assert!(tenant_b.intersects(&others));
+        assert!(!tenants.intersects(&others));
+
+        let default = PartitionQosPolicy::default();
+        assert!(default.intersects(&PartitionQosPolicy::new([""])));
+        assert!(!default.intersects(&tenant_a));
+    }
+
+    #[test]
+    fn serialization() {
+        let partition = PartitionQosPolicy::new(["abc", "tenant-*"]);
+
+        let mut buffer = Vec::new();
+        partition
+            .write_options(&mut Cursor::new(&mut buffer), Endian::Big, ())
+            .unwrap();
+        assert_eq!(buffer.len(), 4 + (4 + 4) + (4 + 12));
+
+        let len = buffer.len();
+        let deserialized =
+            PartitionQosPolicy::read_options(&mut Cursor::new(&buffer), Endian::Big, (len,))
+                .unwrap();
+        assert_eq!(deserialized, partition);
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt::Display;
+
+use binrw::binrw;
+use serde::{Deserialize, Serialize};
+
+use super::{Guid, SequenceNumber};
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[binrw]
+#[brw(repr = u32)]
+#[br(import(_len: usize))]
+pub enum PresentationAccessScope {
+    /// Changes to each instance are independent of each other
+    #[default]
+    Instance,
+    /// Changes of all the instances of a DataWriter are presented together
+    Topic,
+    /// Changes of all the DataWriters of a Publisher are presented together
+    Group,
+}
+
+impl Display for PresentationAccessScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PresentationAccessScope::Instance => {
+                f.write_str("PresentationAccessScope::Instance")?
+            }
+            PresentationAccessScope::Topic => f.write_str("PresentationAccessScope::Topic")?,
+            PresentationAccessScope::Group => f.write_str("PresentationAccessScope::Group")?,
+        }
+        Ok(())
+    }
+}
+
+/// How the changes made by a Publisher are presented to a Subscriber
+///
+/// With `coherent_access`, the changes made between `begin_coherent_changes` and `end_coherent_changes` are
+/// only made visible once all of them have been received
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[binrw]
+#[br(import(_len: usize))]
+pub struct PresentationQosPolicy {
+    pub access_scope: PresentationAccessScope,
+    #[br(map = |value: u8| value != 0)]
+    #[bw(map = |value: &bool| u8::from(*value))]
+    pub coherent_access: bool,
+    #[br(map = |value: u8| value != 0)]
+    #[bw(map = |value: &bool| u8::from(*value))]
+    #[brw(pad_after = 2)]
+    pub ordered_access: bool,
+}
+
+impl PresentationQosPolicy {
+    /// Whether the offered presentation is at least as demanding as the `requested` one
+    pub fn satisfies(&self, requested: &PresentationQosPolicy) -> bool {
+        self.access_scope >= requested.access_scope
+            && self.coherent_access >= requested.coherent_access
+            && self.ordered_access >= requested.ordered_access
+    }
+}
+
+impl Display for PresentationQosPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "PresentationQosPolicy {{ access_scope: {}, coherent_access: {}, ordered_access: {} }}",
+            self.access_scope, self.coherent_access, self.ordered_access
+        ))?;
+        Ok(())
+    }
+}
+
+/// Coherent set of a Publisher with GROUP access scope, made of the coherent sets of its DataWriters
+///
+/// The end of the coherent set of each DataWriter tells the group coherent set it belongs to
+#[binrw]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[br(import(_len: usize))]
+pub struct GroupCoherentSet {
+    /// Number of the set among the ones of the Publisher
+    pub sequence: SequenceNumber,
+    #[br(temp)]
+    #[bw(calc = writers.len() as u32)]
+    writers_count: u32,
+    /// DataWriters of the Publisher taking part in the set
+    #[br(count = writers_count, args { inner: (0,) })]
+    pub writers: Vec<Guid>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use binrw::{BinRead, BinWrite, Endian};
+
+    use super::{GroupCoherentSet, PresentationAccessScope, PresentationQosPolicy};
+    use crate::types::{Guid, SequenceNumber};
+
+    #[test]
+    fn serialization() {
+        let presentation = PresentationQosPolicy {
+            access_scope: PresentationAccessScope::Group,
+            coherent_access: true,
+            ordered_access: false,
+        };
+
+        let mut buffer = Vec::new();
+        presentation
+            .write_options(&mut Cursor::new(&mut buffer), Endian::Big, ())
+            .unwrap();
+        assert_eq!(buffer, vec![0, 0, 0, 2, 1, 0, 0, 0]);
+
+        let deserialized =
+            PresentationQosPolicy::read_options(&mut Cursor::new(&buffer), Endian::Big, (8,))
+                .unwrap();
+        assert_eq!(deserialized, presentation);
+    }
+
+    #[test]
+    fn group_coherent_set_serialization() {
+        let group = GroupCoherentSet {
+            sequence: SequenceNumber(3),
+            writers: vec![Guid::default(); 2],
+        };
+
+        let mut buffer = Vec::new();
+        group
+            .write_options(&mut Cursor::new(&mut buffer), Endian::Big, ())
+            .unwrap();
+        assert_eq!(buffer.len(), 8 + 4 + 2 * 16);
+
+        let deserialized =
+            GroupCoherentSet::read_options(&mut Cursor::new(&buffer), Endian::Big, (buffer.len(),))
+                .unwrap();
+        assert_eq!(deserialized, group);
+    }
+
+    #[test]
+    fn offered_satisfies_requested() {
+        let offered = PresentationQosPolicy {
+            access_scope: PresentationAccessScope::Topic,
+            coherent_access: true,
+            ordered_access: false,
+        };
+        let requested = PresentationQosPolicy {
+            coherent_access: true,
+            ..Default::default()
+        };
+
+        assert!(offered.satisfies(&requested));
+        assert!(!requested.satisfies(&offered));
+        assert!(!offered.satisfies(&PresentationQosPolicy {
+            ordered_access: true,
+            ..Default::default()
+        }));
+    }
+}
@@ -1,8 +1,54 @@
+use std::fmt::Display;
+
+use binrw::binrw;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+use super::HistoryQosPolicy;
+
+/// Value of a limit that is not bounded, the wire representation of the DDS `LENGTH_UNLIMITED` (-1)
+pub const LENGTH_UNLIMITED: u32 = u32::MAX;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[binrw]
+#[br(import(_len: usize))]
 pub struct ResourceLimitsQosPolicy {
-    max_samples: u32,
-    max_instances: u32,
-    max_samples_per_instance: u32,
+    pub max_samples: u32,
+    pub max_instances: u32,
+    pub max_samples_per_instance: u32,
+}
+
+impl ResourceLimitsQosPolicy {
+    /// The limits must be able to hold the samples kept by `history`
+    ///
+    /// `max_samples` can't be lower than `max_samples_per_instance`, which can't be lower than a KEEP_LAST depth, a
+    /// KEEP_LAST history keeps at least one sample
+    pub fn is_consistent_with(&self, history: HistoryQosPolicy) -> bool {
+        let depth_fits = match history {
+            HistoryQosPolicy::KeepAll => true,
+            HistoryQosPolicy::KeepLast { depth } => {
+                depth > 0 && depth <= self.max_samples_per_instance
+            }
+        };
+        depth_fits && self.max_samples >= self.max_samples_per_instance && self.max_instances > 0
+    }
+}
+
+impl Default for ResourceLimitsQosPolicy {
+    fn default() -> Self {
+        Self {
+            max_samples: LENGTH_UNLIMITED,
+            max_instances: LENGTH_UNLIMITED,
+            max_samples_per_instance: LENGTH_UNLIMITED,
+        }
+    }
+}
+
+impl Display for ResourceLimitsQosPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "ResourceLimitsQosPolicy {{ max_samples: {}, max_instances: {}, max_samples_per_instance: {} }}",
+            self.max_samples, self.max_instances, self.max_samples_per_instance
+        ))?;
+        Ok(())
+    }
 }
@@ -110,7 +110,7 @@ impl PdpDiscoveredParticipantData {
             .unwrap_or_default();
 
         Self {
-            dds_participant_data: ParticipantBuiltinTopicData { key, user_data },
+            dds_participant_data: ParticipantBuiltinTopicData { key },
             participant_proxy: ParticipantProxy {
                 domain_id,
                 domain_tag,
@@ -125,6 +125,7 @@ impl PdpDiscoveredParticipantData {
                 default_unicast_locator_list,
                 default_multicast_locator_list,
                 manual_liveliness_count,
+                user_data,
             },
             lease_duration,
         }
@@ -138,11 +139,13 @@ impl PdpDiscoveredParticipantData {
             self.dds_participant_data.key.clone(),
             endian,
         );
-        parameter_list.set_param(
-            ParameterId::PID_USER_DATA,
-            self.dds_participant_data.user_data.clone(),
-            endian,
-        );
+        if !self.participant_proxy.user_data.value.is_empty() {
+            parameter_list.set_param(
+                ParameterId::PID_USER_DATA,
+                self.participant_proxy.user_data.clone(),
+                endian,
+            );
+        }
         parameter_list.set_param(
             ParameterId::PID_DOMAIN_ID,
             self.participant_proxy.domain_id,
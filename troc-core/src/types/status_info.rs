@@ -1,5 +1,8 @@
 #![allow(clippy::identity_op)]
 
+use std::io::{Read, Seek, Write};
+
+use binrw::{BinRead, BinResult, BinWrite, Endian};
 use modular_bitfield::{
     bitfield,
     specifiers::{B1, B29},
@@ -7,7 +10,7 @@ use modular_bitfield::{
 use serde::{Deserialize, Serialize};
 
 #[bitfield]
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct StatusInfo {
     #[skip]
     __: B29,
@@ -17,6 +20,10 @@ pub struct StatusInfo {
 }
 
 impl StatusInfo {
+    const DISPOSED_FLAG: u8 = 0x01;
+    const UNREGISTER_FLAG: u8 = 0x02;
+    const FILTER_FLAG: u8 = 0x04;
+
     pub fn new_alive() -> Self {
         Self::default()
     }
@@ -32,4 +39,50 @@ impl StatusInfo {
         status.set_unregister(1);
         status
     }
+
+    pub fn is_alive(&self) -> bool {
+        self.disposed() == 0 && self.unregister() == 0
+    }
+}
+
+/// On the wire the flags are held by the last octet of the StatusInfo_t (RTPS 9.6.4.9)
+impl BinRead for StatusInfo {
+    type Args<'a> = (usize,);
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let octets = <[u8; 4]>::read_options(reader, endian, ())?;
+        let flags = octets[3];
+        let mut status = Self::default();
+        status.set_disposed((flags & Self::DISPOSED_FLAG != 0) as u8);
+        status.set_unregister((flags & Self::UNREGISTER_FLAG != 0) as u8);
+        status.set_filter((flags & Self::FILTER_FLAG != 0) as u8);
+        Ok(status)
+    }
+}
+
+impl BinWrite for StatusInfo {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let mut flags = 0u8;
+        if self.disposed() == 1 {
+            flags |= Self::DISPOSED_FLAG;
+        }
+        if self.unregister() == 1 {
+            flags |= Self::UNREGISTER_FLAG;
+        }
+        if self.filter() == 1 {
+            flags |= Self::FILTER_FLAG;
+        }
+        [0u8, 0, 0, flags].write_options(writer, endian, ())
+    }
 }
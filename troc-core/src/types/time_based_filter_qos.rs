@@ -1,8 +1,30 @@
+use std::fmt::Display;
+
+use binrw::binrw;
 use serde::{Deserialize, Serialize};
 
-use super::Timestamp;
+use super::{DeadlineQosPolicy, Timestamp};
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[binrw]
+#[br(import(_len: usize))]
 pub struct TimeBasedFilterQosPolicy {
     pub minimum_separation: Timestamp,
 }
+
+impl TimeBasedFilterQosPolicy {
+    /// The DEADLINE period can't be shorter than the minimum separation, an infinite deadline is always consistent
+    pub fn is_consistent_with(&self, deadline: DeadlineQosPolicy) -> bool {
+        deadline.is_infinite() || deadline.period >= self.minimum_separation
+    }
+}
+
+impl Display for TimeBasedFilterQosPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "TimeBasedFilter {{ minimum_separation: {} }}",
+            self.minimum_separation
+        ))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+use binrw::binrw;
+use serde::{Deserialize, Serialize};
+
+/// Opaque octets attached by the application to a Topic
+///
+/// They are propagated with the DataWriters and DataReaders of the Topic through the discovery
+#[binrw]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[br(import(_len: usize))]
+pub struct TopicDataQosPolicy {
+    #[br(temp)]
+    #[bw(calc = value.len() as u32)]
+    len: u32,
+    #[br(count = len)]
+    #[brw(align_after = 4)]
+    pub value: Vec<u8>,
+}
+
+impl TopicDataQosPolicy {
+    pub fn new(value: impl Into<Vec<u8>>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+}
+
+impl Display for TopicDataQosPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("TopicDataQosPolicy {{ value: {:?} }}", self.value))?;
+        Ok(())
+    }
+}
@@ -1,10 +1,60 @@
-use binrw::{BinResult, binrw};
+use std::fmt::Display;
+
+use binrw::binrw;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+/// Opaque octets attached by the application to a DomainParticipant or to a DataWriter/DataReader
+///
+/// They are propagated through the discovery, remote applications can use them to admit or not the entity
 #[binrw]
-#[br(import(len: usize))]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[br(import(_len: usize))]
 pub struct UserDataQosPolicy {
+    #[br(temp)]
+    #[bw(calc = value.len() as u32)]
+    len: u32,
     #[br(count = len)]
+    #[brw(align_after = 4)]
     pub value: Vec<u8>,
 }
+
+impl UserDataQosPolicy {
+    pub fn new(value: impl Into<Vec<u8>>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+}
+
+impl Display for UserDataQosPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("UserDataQosPolicy {{ value: {:?} }}", self.value))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use binrw::{BinRead, BinWrite, Endian};
+
+    use super::UserDataQosPolicy;
+
+    #[test]
+    fn serialization() {
+        let user_data = UserDataQosPolicy::new(b"v1.2.3".as_slice());
+
+        let mut buffer = Vec::new();
+        user_data
+            .write_options(&mut Cursor::new(&mut buffer), Endian::Little, ())
+            .unwrap();
+        assert_eq!(buffer, b"\x06\0\0\0v1.2.3\0\0");
+
+        let len = buffer.len();
+        let deserialized =
+            UserDataQosPolicy::read_options(&mut Cursor::new(&buffer), Endian::Little, (len,))
+                .unwrap();
+        assert_eq!(deserialized, user_data);
+    }
+}
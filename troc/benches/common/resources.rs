@@ -3,9 +3,9 @@ use serde::{Deserialize, Serialize};
 use tokio::net::UdpSocket;
 use troc::cdr::{CdrLe, Infinite};
 use troc::{
-    DDSType, DataReader, DataWriter, DomainParticipant, DurationKind, HistoryQosPolicy,
-    KeyCalculationError, Keyed, Publisher, ReliabilityKind, ReliabilityQosPolicy, Subscriber,
-    TopicKind, cdr,
+    Configuration, DDSType, DataReader, DataWriter, DomainParticipant, DurationKind,
+    HistoryQosPolicy, KeyCalculationError, Keyed, Publisher, ReliabilityKind, ReliabilityQosPolicy,
+    Subscriber, TopicKind, cdr,
 };
 use troc::{DomainParticipantBuilder, SerializedData};
 
@@ -58,6 +58,7 @@ impl SimpleDDSBundle {
         reliability_level: ReliabilityKind,
         topic_name_suffix: &str,
         history_qos: HistoryQosPolicy,
+        configuration: Configuration,
     ) -> Self {
         let reliability = if matches!(reliability_level, ReliabilityKind::BestEffort) {
             ReliabilityQosPolicy::BestEffort
@@ -67,7 +68,12 @@ impl SimpleDDSBundle {
             }
         };
 
-        let mut alpha_p = DomainParticipantBuilder::new().with_domain(0).build().await;
+        let mut alpha_p = DomainParticipantBuilder::new()
+            .with_domain(0)
+            .with_config(configuration.clone())
+            .build()
+            .await
+            .unwrap();
         let alpha_qos = alpha_p
             .create_qos_builder()
             .reliability(reliability)
@@ -87,7 +93,12 @@ impl SimpleDDSBundle {
             .unwrap();
         let mut alpha_reader_listener = alpha_reader.get_listener().await.unwrap();
 
-        let mut beta_p = DomainParticipantBuilder::new().with_domain(0).build().await;
+        let mut beta_p = DomainParticipantBuilder::new()
+            .with_domain(0)
+            .with_config(configuration.clone())
+            .build()
+            .await
+            .unwrap();
         let beta_qos = beta_p
             .create_qos_builder()
             .reliability(reliability)
@@ -154,6 +165,7 @@ impl OneWriterManyReaderDDSBundle {
         reliability_level: ReliabilityKind,
         topic_name_suffix: &str,
         history_qos: HistoryQosPolicy,
+        configuration: Configuration,
     ) -> Self {
         let reliability = if matches!(reliability_level, ReliabilityKind::BestEffort) {
             ReliabilityQosPolicy::BestEffort
@@ -163,7 +175,12 @@ impl OneWriterManyReaderDDSBundle {
             }
         };
 
-        let mut alpha_p = DomainParticipantBuilder::new().with_domain(0).build().await;
+        let mut alpha_p = DomainParticipantBuilder::new()
+            .with_domain(0)
+            .with_config(configuration.clone())
+            .build()
+            .await
+            .unwrap();
         let alpha_qos = alpha_p
             .create_qos_builder()
             .reliability(reliability)
@@ -183,7 +200,12 @@ impl OneWriterManyReaderDDSBundle {
             .unwrap();
         let mut alpha_reader_listener = alpha_reader.get_listener().await.unwrap();
 
-        let mut beta_p = DomainParticipantBuilder::new().with_domain(0).build().await;
+        let mut beta_p = DomainParticipantBuilder::new()
+            .with_domain(0)
+            .with_config(configuration.clone())
+            .build()
+            .await
+            .unwrap();
         let beta_qos = beta_p
             .create_qos_builder()
             .reliability(reliability)
@@ -203,7 +225,12 @@ impl OneWriterManyReaderDDSBundle {
             .unwrap();
         let mut beta_writer_listener = beta_writer.get_listener().await.unwrap();
 
-        let mut gamma_p = DomainParticipantBuilder::new().with_domain(0).build().await;
+        let mut gamma_p = DomainParticipantBuilder::new()
+            .with_domain(0)
+            .with_config(configuration.clone())
+            .build()
+            .await
+            .unwrap();
         let gamma_qos = gamma_p
             .create_qos_builder()
             .reliability(reliability)
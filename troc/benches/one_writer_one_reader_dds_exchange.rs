@@ -2,6 +2,7 @@ mod common;
 
 use crate::common::resources::{BenchMessage, SimpleDDSBundle, one_writer_one_reader_dds_exchange};
 use tokio::time::{Duration, sleep};
+use troc::Configuration;
 use troc_core::{HistoryQosPolicy, ReliabilityKind, TopicKind};
 
 #[tokio::main]
@@ -11,6 +12,7 @@ async fn main() {
         ReliabilityKind::BestEffort,
         "/benchmark/test/dds_exchange/throughput/besteffort_nokey",
         HistoryQosPolicy::KeepLast { depth: 1 },
+        Configuration::default(),
     )
     .await;
 
@@ -4,6 +4,7 @@ use crate::common::resources::{
     BenchMessage, OneWriterManyReaderDDSBundle, one_writer_two_readers_dds_exchange,
 };
 use tokio::time::{Duration, sleep};
+use troc::Configuration;
 use troc_core::{HistoryQosPolicy, ReliabilityKind, TopicKind};
 
 #[tokio::main]
@@ -13,6 +14,7 @@ async fn main() {
         ReliabilityKind::BestEffort,
         "/benchmark/test/dds_exchange/throughput/besteffort_nokey",
         HistoryQosPolicy::KeepLast { depth: 1 },
+        Configuration::default(),
     )
     .await;
 
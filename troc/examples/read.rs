@@ -43,7 +43,11 @@ async fn main() {
         .unwrap();
     }
 
-    let mut domain_participant = DomainParticipantBuilder::new().with_domain(0).build().await;
+    let mut domain_participant = DomainParticipantBuilder::new()
+        .with_domain(0)
+        .build()
+        .await
+        .unwrap();
 
     let qos = domain_participant
         .create_qos_builder()
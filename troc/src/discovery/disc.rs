@@ -8,11 +8,13 @@ use tokio::sync::broadcast::Sender;
 use tracing::{Level, event, instrument, span};
 use troc_core::{
     DdsError, DiscoveredReaderData, DiscoveredWriterData, Discovery as ProtocolDiscovery,
-    DiscoveryBuilder, DiscoveryConfiguration, Effect, Effects, GuidPrefix, Locator, TickId,
+    DiscoveryBuilder, DiscoveryConfiguration, Effect, Effects, GuidPrefix, Locator, LocatorKind,
+    LocatorList, TickId,
 };
 
-use troc_core::{EntityId, ParticipantProxy};
+use troc_core::{EntityId, Guid, ParticipantProxy};
 
+use super::intra_process;
 use crate::ParticipantEvent;
 use crate::publication::{DataWriterActor, DataWriterActorMessage};
 use crate::subscription::{DataReaderActor, DataReaderActorMessage};
@@ -29,12 +31,18 @@ pub enum DiscoveryActorMessage {
         writer_idscovery_data: DiscoveredWriterData,
         actor: ActorRef<DataWriterActor>,
     },
+    /// A local Writer changed its QoS, it is announced again
+    WriterUpdated(DiscoveredWriterData),
     WriterRemoved(EntityId),
     ReaderCreated {
         reader_discovery_data: DiscoveredReaderData,
         actor: ActorRef<DataReaderActor>,
     },
+    /// A local Reader changed its QoS, it is announced again
+    ReaderUpdated(DiscoveredReaderData),
     ReaderRemoved(EntityId),
+    /// The local Participant is shutting down, its disposal is announced to the remote Participants
+    ParticipantDisposed,
     Tick(TickId),
     IncomingMessage {
         message: BytesMut,
@@ -61,40 +69,75 @@ impl Message<DiscoveryActorMessage> for DiscoveryActor {
                 writer_idscovery_data,
                 actor,
             } => {
-                self.local_writers.insert(
-                    writer_idscovery_data
-                        .proxy
-                        .get_remote_writer_guid()
-                        .get_entity_id(),
-                    actor,
-                );
+                let writer_guid = writer_idscovery_data.proxy.get_remote_writer_guid();
+                if let Some(scope) = &self.intra_process {
+                    intra_process::register_writer(scope, writer_guid, &actor);
+                }
+                self.local_writers
+                    .insert(writer_guid.get_entity_id(), actor);
                 self.discovery
                     .add_publications_infos(&mut self.effects, writer_idscovery_data)
                     .unwrap();
             }
+            DiscoveryActorMessage::WriterUpdated(writer_discovery_data) => {
+                self.discovery
+                    .add_publications_infos(&mut self.effects, writer_discovery_data)
+                    .unwrap();
+            }
             DiscoveryActorMessage::WriterRemoved(entity_id) => {
-                self.discovery.remove_publications_infos(entity_id).unwrap();
+                self.local_writers.remove(&entity_id);
+                if let Some(scope) = &self.intra_process {
+                    intra_process::unregister_writer(
+                        scope,
+                        Guid::new(self.participant_guid_prefix, entity_id),
+                    );
+                }
+                if let Err(e) = self
+                    .discovery
+                    .remove_publications_infos(&mut self.effects, entity_id)
+                {
+                    event!(Level::ERROR, error = %e, "Writer discovery data not disposed");
+                }
             }
             DiscoveryActorMessage::ReaderCreated {
                 reader_discovery_data,
                 actor,
             } => {
-                self.local_readers.insert(
-                    reader_discovery_data
-                        .proxy
-                        .get_remote_reader_guid()
-                        .get_entity_id(),
-                    actor,
-                );
+                let reader_guid = reader_discovery_data.proxy.get_remote_reader_guid();
+                if let Some(scope) = &self.intra_process {
+                    intra_process::register_reader(scope, reader_guid, &actor);
+                }
+                self.local_readers
+                    .insert(reader_guid.get_entity_id(), actor);
                 self.discovery
                     .add_subscriptions_infos(&mut self.effects, reader_discovery_data)
                     .unwrap();
             }
-            DiscoveryActorMessage::ReaderRemoved(entity_id) => {
+            DiscoveryActorMessage::ReaderUpdated(reader_discovery_data) => {
                 self.discovery
-                    .remove_subscriptions_infos(entity_id)
+                    .add_subscriptions_infos(&mut self.effects, reader_discovery_data)
                     .unwrap();
             }
+            DiscoveryActorMessage::ReaderRemoved(entity_id) => {
+                self.local_readers.remove(&entity_id);
+                if let Some(scope) = &self.intra_process {
+                    intra_process::unregister_reader(
+                        scope,
+                        Guid::new(self.participant_guid_prefix, entity_id),
+                    );
+                }
+                if let Err(e) = self
+                    .discovery
+                    .remove_subscriptions_infos(&mut self.effects, entity_id)
+                {
+                    event!(Level::ERROR, error = %e, "Reader discovery data not disposed");
+                }
+            }
+            DiscoveryActorMessage::ParticipantDisposed => {
+                if let Err(e) = self.discovery.dispose_participant(&mut self.effects) {
+                    event!(Level::ERROR, error = %e, "Participant disposal not produced");
+                }
+            }
             DiscoveryActorMessage::Tick(id) => {
                 self.discovery.tick(&mut self.effects, now, id).unwrap();
             }
@@ -117,6 +160,36 @@ impl Message<DiscoveryActorMessage> for DiscoveryActor {
     }
 }
 
+/// Assert the liveliness of the local Participant, see [`crate::DomainParticipant::assert_liveliness`]
+#[derive(Debug)]
+pub struct DiscoveryActorAssertLivelinessMessage;
+
+impl Message<DiscoveryActorAssertLivelinessMessage> for DiscoveryActor {
+    type Reply = Result<(), DdsError>;
+
+    #[instrument(name = "discovery", skip_all, fields(participant_guid_prefix = %self.participant_guid_prefix))]
+    async fn handle(
+        &mut self,
+        _msg: DiscoveryActorAssertLivelinessMessage,
+        ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        let res = self.discovery.assert_liveliness(&mut self.effects);
+        self.process_effects(ctx.actor_ref().clone()).await;
+        res?;
+
+        for writer in self.local_writers.values() {
+            writer
+                .tell(DataWriterActorMessage::ParticipantLivelinessAsserted)
+                .await
+                .map_err(|e| DdsError::Actor {
+                    actor: "DataWriter",
+                    reason: e.to_string(),
+                })?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub struct DiscoveryActorCreateObject {
     pub participant_proxy: ParticipantProxy,
@@ -126,6 +199,9 @@ pub struct DiscoveryActorCreateObject {
     pub event_sender: Sender<ParticipantEvent>,
     pub timer: ActorRef<TimerActor>,
     pub wire_factory: ActorRef<WireFactoryActor>,
+    /// Domain in which the matched endpoints of the process are reached without wires,
+    /// `None` if the intra-process delivery is disabled
+    pub intra_process: Option<String>,
 }
 
 #[derive()]
@@ -136,10 +212,13 @@ pub struct DiscoveryActor {
     timer: ActorRef<TimerActor>,
     wire_factory: ActorRef<WireFactoryActor>,
     event_sender: Sender<ParticipantEvent>,
-    _input_wires: HashMap<Locator, ActorRef<ReceiverWireActor>>,
+    input_wires: HashMap<Locator, ActorRef<ReceiverWireActor>>,
     output_wires: HashMap<Locator, ActorRef<SenderWireActor>>,
+    /// Output wires opened for the TCP locators of a remote participant, closed once it's removed
+    participant_wires: HashMap<GuidPrefix, Vec<Locator>>,
     local_readers: HashMap<EntityId, ActorRef<DataReaderActor>>,
     local_writers: HashMap<EntityId, ActorRef<DataWriterActor>>,
+    intra_process: Option<String>,
 }
 
 impl Actor for DiscoveryActor {
@@ -159,6 +238,7 @@ impl Actor for DiscoveryActor {
             event_sender,
             timer,
             wire_factory,
+            intra_process,
         } = args;
 
         let participant_guid_prefix = participant_proxy.get_guid_prefix();
@@ -185,16 +265,34 @@ impl Actor for DiscoveryActor {
             timer,
             wire_factory,
             event_sender,
-            _input_wires: input_wires,
+            input_wires,
             output_wires,
+            participant_wires: Default::default(),
             local_readers: Default::default(),
             local_writers: Default::default(),
+            intra_process,
         };
 
         actor.process_effects(actor_ref.clone()).await;
 
         Ok(actor)
     }
+
+    async fn on_stop(
+        &mut self,
+        _actor_ref: kameo::prelude::WeakActorRef<Self>,
+        _reason: kameo::prelude::ActorStopReason,
+    ) -> Result<(), Self::Error> {
+        for wire in self.input_wires.values() {
+            wire.stop_gracefully().await.unwrap();
+            wire.wait_for_shutdown().await;
+        }
+        for wire in self.output_wires.values() {
+            wire.stop_gracefully().await.unwrap();
+            wire.wait_for_shutdown().await;
+        }
+        Ok(())
+    }
 }
 
 impl DiscoveryActor {
@@ -233,6 +331,27 @@ impl DiscoveryActor {
                     event!(Level::DEBUG, "Effect::Message processed");
                 }
                 Effect::ParticipantMatch { participant_proxy } => {
+                    // a participant out of the multicast reach is reached through its TCP
+                    // listeners, or the connection it dialed if it is behind a NAT
+                    let tcp_locators = participant_proxy
+                        .metatraffic_unicast_locator_list
+                        .iter()
+                        .filter(|locator| {
+                            locator.kind == LocatorKind::TcpV4
+                                && !self.output_wires.contains_key(locator)
+                        })
+                        .copied()
+                        .collect::<Vec<_>>();
+                    if !tcp_locators.is_empty() {
+                        let wires = self
+                            .build_output_wires(LocatorList::new(tcp_locators))
+                            .await;
+                        self.participant_wires.insert(
+                            participant_proxy.guid_prefix,
+                            wires.keys().copied().collect(),
+                        );
+                        self.output_wires.extend(wires);
+                    }
                     let participant_proxy_str = participant_proxy.to_string();
                     let _res = self
                         .event_sender
@@ -243,7 +362,27 @@ impl DiscoveryActor {
                         "Effect::ParticipantMatch processed"
                     );
                 }
+                Effect::ParticipantUpdated { participant_proxy } => {
+                    let participant_proxy_str = participant_proxy.to_string();
+                    let _res = self
+                        .event_sender
+                        .send(ParticipantEvent::ParticipantUpdated { participant_proxy });
+                    event!(
+                        Level::DEBUG,
+                        participant_proxy = participant_proxy_str,
+                        "Effect::ParticipantUpdated processed"
+                    );
+                }
                 Effect::ParticipantRemoved { participant_proxy } => {
+                    for locator in self
+                        .participant_wires
+                        .remove(&participant_proxy.guid_prefix)
+                        .unwrap_or_default()
+                    {
+                        if let Some(wire) = self.output_wires.remove(&locator) {
+                            let _res = wire.stop_gracefully().await;
+                        }
+                    }
                     let participant_proxy_str = participant_proxy.to_string();
                     let _res = self
                         .event_sender
@@ -261,8 +400,9 @@ impl DiscoveryActor {
                 } => {
                     let local_reader_infos_str = local_reader_infos.to_string();
                     let remote_writer_infos_str = remote_writer_infos.to_string();
-                    let _res = self.event_sender.send(ParticipantEvent::ReaderDiscovered {
-                        reader_data: local_reader_infos.clone(),
+                    // the discovered endpoint is the remote one, with the QoS it advertised
+                    let _res = self.event_sender.send(ParticipantEvent::WriterDiscovered {
+                        writer_data: remote_writer_infos.clone(),
                     });
                     event!(
                         Level::DEBUG,
@@ -282,33 +422,57 @@ impl DiscoveryActor {
                             )
                             .unwrap();
 
-                        let mut output_wires = Vec::default();
-                        let (sender_many_to_many, sender_locators_many_to_many) = self
-                            .wire_factory
-                            .ask(SenderWireFactoryActorMessage::FromLocators {
-                                locators: remote_writer_infos.proxy.get_locators(),
-                            })
-                            .await
-                            .unwrap();
-                        output_wires.extend(sender_many_to_many);
-
-                        let output_wires: HashMap<Locator, ActorRef<SenderWireActor>> =
-                            HashMap::from_iter(
-                                sender_locators_many_to_many
-                                    .iter()
-                                    .zip(output_wires)
-                                    .map(|(a, b)| (*a, b)),
-                            );
+                        let intra_process = self.intra_process.as_deref().and_then(|scope| {
+                            intra_process::lookup_writer(
+                                scope,
+                                remote_writer_infos.proxy.get_remote_writer_guid(),
+                            )
+                        });
+                        let output_wires = match intra_process {
+                            Some(_) => HashMap::new(),
+                            None => {
+                                self.build_output_wires(remote_writer_infos.proxy.get_locators())
+                                    .await
+                            }
+                        };
 
                         local_reader
                             .tell(DataReaderActorMessage::AddProxy {
                                 proxy: remote_writer_infos.proxy,
+                                liveliness: remote_writer_infos.params.liveness,
+                                ownership_strength: remote_writer_infos.params.ownership_strength,
                                 wires: output_wires,
+                                intra_process,
                             })
                             .await
                             .unwrap();
                     }
                 }
+                Effect::ReaderMatchUpdated {
+                    local_reader_infos,
+                    remote_writer_infos,
+                } => {
+                    let remote_writer_guid = remote_writer_infos.proxy.get_remote_writer_guid();
+                    if let Some(local_reader) = self.local_readers.get(
+                        &local_reader_infos
+                            .proxy
+                            .get_remote_reader_guid()
+                            .get_entity_id(),
+                    ) {
+                        local_reader
+                            .tell(DataReaderActorMessage::UpdateProxy {
+                                guid: remote_writer_guid,
+                                ownership_strength: remote_writer_infos.params.ownership_strength,
+                            })
+                            .await
+                            .unwrap();
+                    }
+                    event!(
+                        Level::DEBUG,
+                        remote_writer_guid = %remote_writer_guid,
+                        "Effect::ReaderMatchUpdated processed"
+                    );
+                }
                 Effect::WriterMatch {
                     success,
                     local_writer_infos,
@@ -316,8 +480,8 @@ impl DiscoveryActor {
                 } => {
                     let local_writer_infos_str = local_writer_infos.to_string();
                     let remote_reader_infos_str = remote_reader_infos.to_string();
-                    let _res = self.event_sender.send(ParticipantEvent::WriterDiscovered {
-                        writer_data: local_writer_infos.clone(),
+                    let _res = self.event_sender.send(ParticipantEvent::ReaderDiscovered {
+                        reader_data: remote_reader_infos.clone(),
                     });
                     event!(
                         Level::DEBUG,
@@ -337,33 +501,82 @@ impl DiscoveryActor {
                             )
                             .unwrap();
 
-                        let mut output_wires = Vec::default();
-                        let (sender_many_to_many, sender_locators_many_to_many) = self
-                            .wire_factory
-                            .ask(SenderWireFactoryActorMessage::FromLocators {
-                                locators: remote_reader_infos.proxy.get_locators(),
-                            })
-                            .await
-                            .unwrap();
-                        output_wires.extend(sender_many_to_many);
-
-                        let output_wires: HashMap<Locator, ActorRef<SenderWireActor>> =
-                            HashMap::from_iter(
-                                sender_locators_many_to_many
-                                    .iter()
-                                    .zip(output_wires)
-                                    .map(|(a, b)| (*a, b)),
-                            );
+                        let intra_process = self.intra_process.as_deref().and_then(|scope| {
+                            intra_process::lookup_reader(
+                                scope,
+                                remote_reader_infos.proxy.get_remote_reader_guid(),
+                            )
+                        });
+                        let output_wires = match intra_process {
+                            Some(_) => HashMap::new(),
+                            None => {
+                                self.build_output_wires(remote_reader_infos.proxy.get_locators())
+                                    .await
+                            }
+                        };
 
                         local_writer
                             .tell(DataWriterActorMessage::AddProxy {
                                 proxy: remote_reader_infos.proxy,
                                 wires: output_wires,
+                                intra_process,
                             })
                             .await
                             .unwrap();
                     }
                 }
+                Effect::ReaderUnmatch {
+                    local_reader_infos,
+                    remote_writer_infos,
+                } => {
+                    let remote_writer_guid = remote_writer_infos.proxy.get_remote_writer_guid();
+                    if let Some(local_reader) = self.local_readers.get(
+                        &local_reader_infos
+                            .proxy
+                            .get_remote_reader_guid()
+                            .get_entity_id(),
+                    ) {
+                        // the sender wires are kept, other proxies of the remote Participant can share them
+                        local_reader
+                            .tell(DataReaderActorMessage::RemoveProxy {
+                                guid: remote_writer_guid,
+                                locators: Vec::new(),
+                            })
+                            .await
+                            .unwrap();
+                    }
+                    event!(
+                        Level::DEBUG,
+                        remote_writer_guid = %remote_writer_guid,
+                        "Effect::ReaderUnmatch processed"
+                    );
+                }
+                Effect::WriterUnmatch {
+                    local_writer_infos,
+                    remote_reader_infos,
+                } => {
+                    let remote_reader_guid = remote_reader_infos.proxy.get_remote_reader_guid();
+                    if let Some(local_writer) = self.local_writers.get(
+                        &local_writer_infos
+                            .proxy
+                            .get_remote_writer_guid()
+                            .get_entity_id(),
+                    ) {
+                        // the sender wires are kept, other proxies of the remote Participant can share them
+                        local_writer
+                            .tell(DataWriterActorMessage::RemoveProxy {
+                                guid: remote_reader_guid,
+                                locators: Vec::new(),
+                            })
+                            .await
+                            .unwrap();
+                    }
+                    event!(
+                        Level::DEBUG,
+                        remote_reader_guid = %remote_reader_guid,
+                        "Effect::WriterUnmatch processed"
+                    );
+                }
                 Effect::ScheduleTick { id, delay } => {
                     self.timer
                         .tell(TimerActorScheduleTickMessage::Discovery {
@@ -375,12 +588,46 @@ impl DiscoveryActor {
                         .unwrap();
                     event!(Level::DEBUG, delay = %delay, "Effect::ScheduleTick processed");
                 }
+                Effect::RemoteLivelinessAsserted {
+                    participant_guid_prefix,
+                    kind,
+                } => {
+                    for reader in self.local_readers.values() {
+                        reader
+                            .tell(DataReaderActorMessage::RemoteLivelinessAsserted {
+                                participant_guid_prefix,
+                                kind,
+                            })
+                            .await
+                            .unwrap();
+                    }
+                    event!(
+                        Level::DEBUG,
+                        participant_guid_prefix = %participant_guid_prefix,
+                        "Effect::RemoteLivelinessAsserted processed"
+                    );
+                }
                 _ => continue,
             }
         }
     }
 }
 
+impl DiscoveryActor {
+    /// A sender wire per reachable locator of a remote endpoint
+    async fn build_output_wires(
+        &self,
+        locators: LocatorList,
+    ) -> HashMap<Locator, ActorRef<SenderWireActor>> {
+        let (wires, locators) = self
+            .wire_factory
+            .ask(SenderWireFactoryActorMessage::FromLocators { locators })
+            .await
+            .unwrap();
+        HashMap::from_iter(locators.iter().copied().zip(wires))
+    }
+}
+
 impl Sendable for DiscoveryActor {
     type Msg = DiscoveryActorMessage;
 
@@ -0,0 +1,58 @@
+//! The local endpoints are registered by name in the actor registry of the process,
+//! a matched endpoint found there is reached directly instead of through wires
+//!
+//! The GUIDs are only unique in a domain, the names are scoped by the domain of the Participant
+
+use kameo::{actor::ActorRef, registry::ACTOR_REGISTRY};
+use tracing::{Level, event};
+use troc_core::Guid;
+
+use crate::publication::DataWriterActor;
+use crate::subscription::DataReaderActor;
+
+fn reader_name(scope: &str, guid: Guid) -> String {
+    format!("troc/intra-process/{scope}/reader/{guid}")
+}
+
+fn writer_name(scope: &str, guid: Guid) -> String {
+    format!("troc/intra-process/{scope}/writer/{guid}")
+}
+
+pub(crate) fn register_reader(scope: &str, guid: Guid, actor: &ActorRef<DataReaderActor>) {
+    if let Err(e) = actor.register(reader_name(scope, guid)) {
+        event!(Level::WARN, guid = %guid, error = %e, "Reader not reachable intra-process");
+    }
+}
+
+pub(crate) fn register_writer(scope: &str, guid: Guid, actor: &ActorRef<DataWriterActor>) {
+    if let Err(e) = actor.register(writer_name(scope, guid)) {
+        event!(Level::WARN, guid = %guid, error = %e, "Writer not reachable intra-process");
+    }
+}
+
+/// The registry forgets a stopped actor by itself, a deleted entity is removed before
+pub(crate) fn unregister_reader(scope: &str, guid: Guid) {
+    ACTOR_REGISTRY
+        .lock()
+        .unwrap()
+        .remove(reader_name(scope, guid).as_str());
+}
+
+pub(crate) fn unregister_writer(scope: &str, guid: Guid) {
+    ACTOR_REGISTRY
+        .lock()
+        .unwrap()
+        .remove(writer_name(scope, guid).as_str());
+}
+
+pub(crate) fn lookup_reader(scope: &str, guid: Guid) -> Option<ActorRef<DataReaderActor>> {
+    ActorRef::lookup(reader_name(scope, guid).as_str())
+        .ok()
+        .flatten()
+}
+
+pub(crate) fn lookup_writer(scope: &str, guid: Guid) -> Option<ActorRef<DataWriterActor>> {
+    ActorRef::lookup(writer_name(scope, guid).as_str())
+        .ok()
+        .flatten()
+}
@@ -1,8 +1,12 @@
 use troc_core::{EntityId, GuidPrefix, ParticipantProxy, ReaderProxy, WriterProxy};
 
 mod disc;
+mod intra_process;
 
-pub use disc::{DiscoveryActor, DiscoveryActorCreateObject, DiscoveryActorMessage};
+pub use disc::{
+    DiscoveryActor, DiscoveryActorAssertLivelinessMessage, DiscoveryActorCreateObject,
+    DiscoveryActorMessage,
+};
 
 #[derive(Debug)]
 pub enum DiscoveryEvent {
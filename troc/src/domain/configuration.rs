@@ -1,8 +1,88 @@
-use std::{net::Ipv4Addr, ops::Range, str::FromStr, time::Duration};
+use std::{
+    net::{Ipv4Addr, Ipv6Addr},
+    ops::Range,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-use troc_core::{DomainTag, Locator, LocatorList, domain_id::DomainId};
+use troc_core::{DdsError, DomainTag, Locator, LocatorKind, LocatorList, domain_id::DomainId};
+
+use super::network_interface::InterfaceSelector;
+
+/// Prefix of the environment variables overriding the configuration,
+/// nested fields are separated by `__` (e.g. `TROC__RTPS__GLOBAL__DOMAIN_ID=3`)
+pub const CONFIGURATION_ENV_PREFIX: &str = "TROC__RTPS__";
+
+#[derive(Debug, Error)]
+pub enum ConfigurationError {
+    #[error("cannot read configuration file {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid configuration file {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+    #[error(
+        "invalid configuration from the {CONFIGURATION_ENV_PREFIX}* environment variables: {message}"
+    )]
+    Environment { message: String },
+    #[error("{field}: {reason}")]
+    InvalidPort { field: &'static str, reason: String },
+    #[error("{field}: `{value}` is not a valid IPv4 address")]
+    InvalidAddress { field: &'static str, value: String },
+    #[error("{field}: `{value}` is not an interface name, address or CIDR network")]
+    InvalidInterface { field: &'static str, value: String },
+    #[error("{field}: `{value}` is not a valid IPv6 address")]
+    InvalidIpv6Address { field: &'static str, value: String },
+    #[error("{field}: `{value}` is not a multicast address")]
+    NotMulticast { field: &'static str, value: String },
+    #[error(
+        "{field}: `{value}` is not a link-local (ff02::) or site-local (ff05::) multicast address"
+    )]
+    MulticastScope { field: &'static str, value: String },
+    #[error("{field}: invalid locator {locator}, {reason}")]
+    InvalidLocator {
+        field: &'static str,
+        locator: String,
+        reason: String,
+    },
+    #[error("{field}: {value} bytes is lower than the minimum of {minimum} bytes")]
+    SegmentSize {
+        field: &'static str,
+        value: u32,
+        minimum: u32,
+    },
+    #[error("{field}: {reason}")]
+    InvalidDelay { field: &'static str, reason: String },
+    #[error("{field} is required when {reason}")]
+    MissingField {
+        field: &'static str,
+        reason: &'static str,
+    },
+    #[error(
+        "a configuration and a configuration file {path} are both given, only one of them can be used"
+    )]
+    ConflictingSources { path: PathBuf },
+    #[error("tcp.tls: troc is built without the `tls` feature")]
+    TlsUnavailable,
+    #[error(
+        "fragment_size ({fragment_size}) must be non zero and lower or equal to data_max_size_serialized ({data_max_size_serialized})"
+    )]
+    FragmentSize {
+        fragment_size: u16,
+        data_max_size_serialized: u32,
+    },
+}
+
+impl From<ConfigurationError> for DdsError {
+    fn from(value: ConfigurationError) -> Self {
+        DdsError::InvalidConfiguration(value.to_string())
+    }
+}
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Configuration {
@@ -14,15 +94,429 @@ pub struct Configuration {
     pub writer: WriterConfiguration,
     #[serde(default)]
     pub discovery: DiscoveryConfiguration,
+    #[serde(default)]
+    pub udpv6: UdpV6Configuration,
+    #[serde(default)]
+    pub shm: ShmConfiguration,
+    #[serde(default)]
+    pub intra_process: IntraProcessConfiguration,
+    #[serde(default)]
+    pub tcp: TcpConfiguration,
 }
 
 impl Configuration {
-    pub fn get_global_default_unicast_address(&self) -> Vec<Ipv4Addr> {
+    /// Load a TOML file on top of the defaults, absent fields keep their default value
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigurationError> {
+        let table = Self::read_file(path.as_ref())?;
+        let configuration =
+            Self::from_table(table).map_err(|message| ConfigurationError::Parse {
+                path: path.as_ref().to_path_buf(),
+                message,
+            })?;
+        configuration.validate()?;
+        Ok(configuration)
+    }
+
+    /// Layer the defaults, then the TOML file `path` if any, then the
+    /// [`CONFIGURATION_ENV_PREFIX`] environment variables
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigurationError> {
+        let mut table = match path {
+            Some(path) => {
+                let table = Self::read_file(path)?;
+                // report the errors of the file before mixing it with the environment
+                Self::from_table(table.clone()).map_err(|message| ConfigurationError::Parse {
+                    path: path.to_path_buf(),
+                    message,
+                })?;
+                table
+            }
+            None => toml::Table::new(),
+        };
+        apply_env(&mut table, std::env::vars());
+        let configuration = Self::from_table(table)
+            .map_err(|message| ConfigurationError::Environment { message })?;
+        configuration.validate()?;
+        Ok(configuration)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigurationError> {
+        let global = &self.global;
+
+        if global.unicast_port_range.is_empty() || global.unicast_port_range.start == 0 {
+            return Err(ConfigurationError::InvalidPort {
+                field: "global.unicast_port_range",
+                reason: format!(
+                    "{}..{} must be a non empty range of non zero ports",
+                    global.unicast_port_range.start, global.unicast_port_range.end
+                ),
+            });
+        }
+
+        // the well-known ports of the RTPS specification (9.6.1.1)
+        let domain_base = u64::from(global.port_base)
+            + u64::from(global.domain_gain) * u64::from(global.domain_id.0);
+        let highest_offset = [
+            u64::from(global.d0),
+            u64::from(global.d1) + u64::from(global.participant_gain),
+            u64::from(global.d2),
+            u64::from(global.d3) + u64::from(global.participant_gain),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or_default();
+        if domain_base + highest_offset > u64::from(u16::MAX) {
+            return Err(ConfigurationError::InvalidPort {
+                field: "global.port_base",
+                reason: format!(
+                    "the port {} of domain {} is above {}, lower port_base, domain_gain or the d0..d3 offsets",
+                    domain_base + highest_offset,
+                    global.domain_id.0,
+                    u16::MAX
+                ),
+            });
+        }
+
+        if global.fragment_size == 0
+            || u32::from(global.fragment_size) > global.data_max_size_serialized
+        {
+            return Err(ConfigurationError::FragmentSize {
+                fragment_size: global.fragment_size,
+                data_max_size_serialized: global.data_max_size_serialized,
+            });
+        }
+
+        for address in &global.default_unicast_adress {
+            parse_address("global.default_unicast_adress", address)?;
+        }
+        let multicast_address = parse_address(
+            "global.default_multicast_address",
+            &global.default_multicast_address,
+        )?;
+        if !multicast_address.is_multicast() {
+            return Err(ConfigurationError::NotMulticast {
+                field: "global.default_multicast_address",
+                value: global.default_multicast_address.clone(),
+            });
+        }
+
+        parse_interfaces("global.allowed_interfaces", &global.allowed_interfaces)?;
+        parse_interfaces("global.denied_interfaces", &global.denied_interfaces)?;
+
+        validate_locators(
+            "global.default_multicast_locator_list",
+            &global.default_multicast_locator_list,
+            true,
+        )?;
+        let udpv6 = &self.udpv6;
+        if let Some(address) = &udpv6.unicast_address {
+            parse_ipv6_address("udpv6.unicast_address", address)?;
+        }
+        let multicast_address =
+            parse_ipv6_address("udpv6.multicast_address", &udpv6.multicast_address)?;
+        if !multicast_address.is_multicast() {
+            return Err(ConfigurationError::NotMulticast {
+                field: "udpv6.multicast_address",
+                value: udpv6.multicast_address.clone(),
+            });
+        }
+        if !is_supported_multicast_scope(multicast_address) {
+            return Err(ConfigurationError::MulticastScope {
+                field: "udpv6.multicast_address",
+                value: udpv6.multicast_address.clone(),
+            });
+        }
+
+        if self.shm.segment_size < ShmConfiguration::MIN_SEGMENT_SIZE {
+            return Err(ConfigurationError::SegmentSize {
+                field: "shm.segment_size",
+                value: self.shm.segment_size,
+                minimum: ShmConfiguration::MIN_SEGMENT_SIZE,
+            });
+        }
+
+        let tcp = &self.tcp;
+        if let Some(address) = &tcp.address {
+            parse_address("tcp.address", address)?;
+        }
+        if tcp.reconnect_initial_delay.is_zero()
+            || tcp.reconnect_initial_delay > tcp.reconnect_max_delay
+        {
+            return Err(ConfigurationError::InvalidDelay {
+                field: "tcp.reconnect_initial_delay",
+                reason: String::from(
+                    "must be non zero and lower or equal to tcp.reconnect_max_delay",
+                ),
+            });
+        }
+        if tcp.tls.enabled {
+            if !cfg!(feature = "tls") {
+                return Err(ConfigurationError::TlsUnavailable);
+            }
+            let reason = "tcp.tls.enabled is set";
+            if tcp.tls.certificate_chain.is_none() {
+                return Err(ConfigurationError::MissingField {
+                    field: "tcp.tls.certificate_chain",
+                    reason,
+                });
+            }
+            if tcp.tls.private_key.is_none() {
+                return Err(ConfigurationError::MissingField {
+                    field: "tcp.tls.private_key",
+                    reason,
+                });
+            }
+            if tcp.tls.root_certificates.is_none() {
+                return Err(ConfigurationError::MissingField {
+                    field: "tcp.tls.root_certificates",
+                    reason,
+                });
+            }
+        }
+
+        let discovery = &self.discovery;
+        validate_locators("discovery.initial_peers", &discovery.initial_peers, false)?;
+        validate_locators(
+            "discovery.liveliness_locator_list",
+            &discovery.liveliness_locator_list,
+            false,
+        )?;
+        validate_locators(
+            "discovery.participant_discovery_locator_list",
+            &discovery.participant_discovery_locator_list,
+            false,
+        )?;
+        validate_locators(
+            "discovery.endpoint_publication_discovery_locator_list",
+            &discovery.endpoint_publication_discovery_locator_list,
+            false,
+        )?;
+        validate_locators(
+            "discovery.endpoint_subscription_discovery_locator_list",
+            &discovery.endpoint_subscription_discovery_locator_list,
+            false,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_global_default_unicast_address(&self) -> Result<Vec<Ipv4Addr>, ConfigurationError> {
         self.global
             .default_unicast_adress
             .iter()
-            .map(|a| <Ipv4Addr as FromStr>::from_str(a).unwrap())
-            .collect::<Vec<_>>()
+            .map(|a| parse_address("global.default_unicast_adress", a))
+            .collect()
+    }
+
+    /// The allowed and denied interfaces of the UDP and TCP wires
+    pub(crate) fn get_interface_selectors(
+        &self,
+    ) -> Result<(Vec<InterfaceSelector>, Vec<InterfaceSelector>), ConfigurationError> {
+        Ok((
+            parse_interfaces("global.allowed_interfaces", &self.global.allowed_interfaces)?,
+            parse_interfaces("global.denied_interfaces", &self.global.denied_interfaces)?,
+        ))
+    }
+
+    /// Address of the TCP listeners, if configured
+    pub fn get_tcp_address(&self) -> Result<Option<Ipv4Addr>, ConfigurationError> {
+        self.tcp
+            .address
+            .as_ref()
+            .map(|a| parse_address("tcp.address", a))
+            .transpose()
+    }
+
+    /// Unicast address of the UDPv6 wires, if configured
+    pub fn get_udpv6_unicast_address(&self) -> Result<Option<Ipv6Addr>, ConfigurationError> {
+        self.udpv6
+            .unicast_address
+            .as_deref()
+            .map(|a| parse_ipv6_address("udpv6.unicast_address", a))
+            .transpose()
+    }
+
+    pub fn get_udpv6_multicast_address(&self) -> Result<Ipv6Addr, ConfigurationError> {
+        parse_ipv6_address("udpv6.multicast_address", &self.udpv6.multicast_address)
+    }
+
+    fn read_file(path: &Path) -> Result<toml::Table, ConfigurationError> {
+        let content = std::fs::read_to_string(path).map_err(|source| ConfigurationError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        toml::from_str(&content).map_err(|e| ConfigurationError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })
+    }
+
+    fn from_table(table: toml::Table) -> Result<Self, String> {
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(|e: toml::de::Error| e.to_string())
+    }
+}
+
+/// Override the fields of `table` with the variables starting with [`CONFIGURATION_ENV_PREFIX`],
+/// the values are read as TOML values and fall back to strings
+fn apply_env(table: &mut toml::Table, vars: impl Iterator<Item = (String, String)>) {
+    for (name, raw) in vars {
+        let Some(path) = name
+            .get(..CONFIGURATION_ENV_PREFIX.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(CONFIGURATION_ENV_PREFIX))
+            .map(|_| name[CONFIGURATION_ENV_PREFIX.len()..].to_lowercase())
+        else {
+            continue;
+        };
+        let mut keys = path.split("__").collect::<Vec<_>>();
+        let Some(leaf) = keys.pop() else {
+            continue;
+        };
+
+        let mut current = &mut *table;
+        for key in keys {
+            let entry = current
+                .entry(key)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if !entry.is_table() {
+                *entry = toml::Value::Table(toml::Table::new());
+            }
+            let toml::Value::Table(next) = entry else {
+                unreachable!()
+            };
+            current = next;
+        }
+
+        let value = toml::from_str::<toml::Table>(&format!("value = {raw}"))
+            .ok()
+            .and_then(|mut parsed| parsed.remove("value"))
+            .unwrap_or(toml::Value::String(raw));
+        current.insert(leaf.to_string(), value);
+    }
+}
+
+fn parse_address(field: &'static str, value: &str) -> Result<Ipv4Addr, ConfigurationError> {
+    Ipv4Addr::from_str(value).map_err(|_| ConfigurationError::InvalidAddress {
+        field,
+        value: value.to_string(),
+    })
+}
+
+fn parse_interfaces(
+    field: &'static str,
+    values: &[String],
+) -> Result<Vec<InterfaceSelector>, ConfigurationError> {
+    values
+        .iter()
+        .map(|value| {
+            InterfaceSelector::from_str(value).map_err(|_| ConfigurationError::InvalidInterface {
+                field,
+                value: value.clone(),
+            })
+        })
+        .collect()
+}
+
+fn parse_ipv6_address(field: &'static str, value: &str) -> Result<Ipv6Addr, ConfigurationError> {
+    Ipv6Addr::from_str(value).map_err(|_| ConfigurationError::InvalidIpv6Address {
+        field,
+        value: value.to_string(),
+    })
+}
+
+/// Only the link-local (ff02::) and site-local (ff05::) scopes reach the other hosts without routing setup
+fn is_supported_multicast_scope(address: Ipv6Addr) -> bool {
+    matches!(address.segments()[0] & 0x000f, 0x2 | 0x5)
+}
+
+fn validate_locators(
+    field: &'static str,
+    locators: &LocatorList,
+    multicast: bool,
+) -> Result<(), ConfigurationError> {
+    for locator in locators.iter() {
+        let invalid = |reason: &str| ConfigurationError::InvalidLocator {
+            field,
+            locator: locator.to_string(),
+            reason: reason.to_string(),
+        };
+        if locator.port == 0 || locator.port > u32::from(u16::MAX) {
+            return Err(invalid("the port must be in 1..=65535"));
+        }
+        match locator.kind {
+            LocatorKind::UdpV4 => {
+                let address = Ipv4Addr::new(
+                    locator.address[12],
+                    locator.address[13],
+                    locator.address[14],
+                    locator.address[15],
+                );
+                if multicast && !address.is_multicast() {
+                    return Err(invalid("the address is not a multicast address"));
+                }
+            }
+            LocatorKind::UdpV6 => {
+                let address = Ipv6Addr::from(locator.address);
+                if multicast && !address.is_multicast() {
+                    return Err(invalid("the address is not a multicast address"));
+                }
+            }
+            LocatorKind::TcpV4 => {
+                if multicast {
+                    return Err(invalid("a TCPV4 locator is not a multicast locator"));
+                }
+            }
+            _ => {
+                return Err(invalid(
+                    "only UDPV4, UDPV6 and TCPV4 locators are supported",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Accept the locators either as `"ADDRESS:PORT:KIND"` strings or in their serialized form
+mod locator_list {
+    use std::str::FromStr;
+
+    use serde::{Deserialize, Deserializer, de::Error};
+    use troc_core::{Locator, LocatorList};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum LocatorRepr {
+        Text(String),
+        Locator(Locator),
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum LocatorListRepr {
+        List(Vec<LocatorRepr>),
+        LocatorList(LocatorList),
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<LocatorList, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let locators = match LocatorListRepr::deserialize(deserializer)? {
+            LocatorListRepr::List(locators) => locators,
+            LocatorListRepr::LocatorList(locators) => return Ok(locators),
+        };
+        locators
+            .into_iter()
+            .map(|locator| match locator {
+                LocatorRepr::Text(text) => Locator::from_str(&text).map_err(|_| {
+                    D::Error::custom(format!(
+                        "invalid locator `{text}`, expected ADDRESS:PORT:KIND (e.g. 224.0.0.1:9000:UDPV4)"
+                    ))
+                }),
+                LocatorRepr::Locator(locator) => Ok(locator),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(LocatorList::new)
     }
 }
 
@@ -33,12 +527,18 @@ pub struct GlobalConfiguration {
     pub domain_tag: DomainTag,
     pub expects_inline_qos: bool,
     pub unicast_port_range: Range<u16>,
+    #[serde(deserialize_with = "locator_list::deserialize")]
     pub default_multicast_locator_list: LocatorList,
     pub data_max_size_serialized: u32,
     pub fragment_size: u16,
     //
     pub default_unicast_adress: Vec<String>,
     pub default_multicast_address: String,
+    /// Interfaces of the UDP and TCP wires, by name (`eth0`), address or CIDR network (`192.168.1.0/24`),
+    /// every interface but the loopback one if empty
+    pub allowed_interfaces: Vec<String>,
+    /// Interfaces excluded from the allowed ones, in the forms of `allowed_interfaces`
+    pub denied_interfaces: Vec<String>,
     pub port_base: u32,
     pub domain_gain: u32,
     pub participant_gain: u32,
@@ -63,6 +563,8 @@ impl Default for GlobalConfiguration {
             //
             default_unicast_adress: vec![String::from("127.0.0.1")],
             default_multicast_address: String::from("239.255.0.1"),
+            allowed_interfaces: Vec::new(),
+            denied_interfaces: Vec::new(),
             port_base: 7400,
             domain_gain: 250,
             participant_gain: 2,
@@ -74,6 +576,128 @@ impl Default for GlobalConfiguration {
     }
 }
 
+/// The UDPv6 transport, opened next to the UDPv4 one or instead of it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default = "self::UdpV6Configuration::default")]
+pub struct UdpV6Configuration {
+    pub enabled: bool,
+    /// Open no UDPv4 wire, for the IPv6-only hosts
+    pub exclusive: bool,
+    /// Address of the unicast wires, the addresses of the selected interfaces if absent
+    pub unicast_address: Option<String>,
+    /// Group of the participant discovery, in the link-local (ff02::) or site-local (ff05::) scope
+    pub multicast_address: String,
+    pub multicast_hop_limit: u8,
+    /// Index of the network interface sending and joining the multicast groups, 0 for the selected ones
+    pub multicast_interface: u32,
+}
+
+impl UdpV6Configuration {
+    pub fn udpv4_enabled(&self) -> bool {
+        !(self.enabled && self.exclusive)
+    }
+}
+
+impl Default for UdpV6Configuration {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            exclusive: false,
+            unicast_address: None,
+            multicast_address: String::from("ff02::ffff:239.255.0.1"),
+            multicast_hop_limit: 1,
+            multicast_interface: 0,
+        }
+    }
+}
+
+/// The shared-memory transport, preferred over UDP between the participants of the same host
+///
+/// Disabled by default: its segments live in `/dev/shm`, which the hosts or containers meant to communicate
+/// through it must share
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default = "self::ShmConfiguration::default")]
+pub struct ShmConfiguration {
+    pub enabled: bool,
+    /// Size in bytes of the ring buffer receiving the messages of an endpoint
+    pub segment_size: u32,
+}
+
+impl ShmConfiguration {
+    /// A segment holds at least one UDP sized message
+    pub const MIN_SEGMENT_SIZE: u32 = 64 * 1024;
+}
+
+impl Default for ShmConfiguration {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            segment_size: 2 * 1024 * 1024,
+        }
+    }
+}
+
+/// The delivery between the endpoints of the same process, which bypasses the serialization and the wires
+///
+/// Disabled by default, the endpoints of the process are then reached like the remote ones
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default = "self::IntraProcessConfiguration::default")]
+pub struct IntraProcessConfiguration {
+    pub enabled: bool,
+}
+
+/// The RTPS over TCP transport, for the networks multicast and UDP don't cross
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default = "self::TcpConfiguration::default")]
+pub struct TcpConfiguration {
+    pub enabled: bool,
+    /// Address of the listeners, the addresses of the selected interfaces if absent
+    pub address: Option<String>,
+    /// Delay before the first reconnection to a remote listener, doubled at each failure
+    pub reconnect_initial_delay: Duration,
+    pub reconnect_max_delay: Duration,
+    pub tls: TlsConfiguration,
+}
+
+impl Default for TcpConfiguration {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: None,
+            reconnect_initial_delay: Duration::from_millis(100),
+            reconnect_max_delay: Duration::from_secs(5),
+            tls: TlsConfiguration::default(),
+        }
+    }
+}
+
+/// TLS of the TCP connections, available with the `tls` feature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default = "self::TlsConfiguration::default")]
+pub struct TlsConfiguration {
+    pub enabled: bool,
+    /// PEM file of the certificate chain presented by the listeners
+    pub certificate_chain: Option<PathBuf>,
+    /// PEM file of the private key of the certificate
+    pub private_key: Option<PathBuf>,
+    /// PEM file of the authorities the certificates of the remote listeners are verified against
+    pub root_certificates: Option<PathBuf>,
+    /// Name expected in the certificates of the remote listeners
+    pub server_name: String,
+}
+
+impl Default for TlsConfiguration {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            certificate_chain: None,
+            private_key: None,
+            root_certificates: None,
+            server_name: String::from("troc"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default = "self::ReaderConfiguration::default")]
 pub struct ReaderConfiguration {
@@ -115,10 +739,18 @@ impl Default for WriterConfiguration {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default = "self::DiscoveryConfiguration::default")]
 pub struct DiscoveryConfiguration {
+    #[serde(deserialize_with = "locator_list::deserialize")]
     pub liveliness_locator_list: LocatorList,
+    #[serde(deserialize_with = "locator_list::deserialize")]
     pub participant_discovery_locator_list: LocatorList,
+    #[serde(deserialize_with = "locator_list::deserialize")]
     pub endpoint_publication_discovery_locator_list: LocatorList,
+    #[serde(deserialize_with = "locator_list::deserialize")]
     pub endpoint_subscription_discovery_locator_list: LocatorList,
+    /// Locators the participant announcements are sent to besides the multicast group,
+    /// the TCPV4 ones reach the participants behind a NAT or a WAN link
+    #[serde(deserialize_with = "locator_list::deserialize")]
+    pub initial_peers: LocatorList,
     pub announcement_period: Duration,
     pub lease_duration: Duration,
 }
@@ -138,8 +770,196 @@ impl Default for DiscoveryConfiguration {
             liveliness_locator_list: LocatorList::new(vec![
                 <Locator as FromStr>::from_str("224.0.0.4:9003:UDPV4").unwrap(),
             ]),
+            initial_peers: LocatorList::default(),
             announcement_period: Duration::from_secs(5),
             lease_duration: Duration::from_secs(30),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{net::Ipv4Addr, str::FromStr, time::Duration};
+
+    use troc_core::{Locator, LocatorList};
+
+    use super::{Configuration, ConfigurationError, apply_env};
+
+    #[test]
+    fn file_then_environment() {
+        let path = std::env::temp_dir().join(format!("troc-{}-layers.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"
+[global]
+domain_id = 2
+default_unicast_adress = ["192.168.1.10"]
+default_multicast_locator_list = ["239.255.0.2:9005:UDPV4"]
+
+[discovery]
+announcement_period = { secs = 2, nanos = 0 }
+"#,
+        )
+        .unwrap();
+        let configuration = Configuration::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        let configuration = configuration.unwrap();
+
+        assert_eq!(configuration.global.domain_id.0, 2);
+        assert_eq!(
+            configuration.get_global_default_unicast_address().unwrap(),
+            vec![Ipv4Addr::new(192, 168, 1, 10)]
+        );
+        assert_eq!(
+            configuration.global.default_multicast_locator_list[0].port,
+            9005
+        );
+        assert_eq!(
+            configuration.discovery.announcement_period,
+            Duration::from_secs(2)
+        );
+        // untouched fields keep their default value
+        assert_eq!(configuration.global.port_base, 7400);
+
+        let mut table = toml::Value::try_from(&configuration)
+            .unwrap()
+            .as_table()
+            .unwrap()
+            .clone();
+        apply_env(
+            &mut table,
+            [
+                ("TROC__RTPS__GLOBAL__DOMAIN_ID", "5"),
+                ("troc__rtps__global__default_multicast_address", "239.0.0.9"),
+                ("TROC__RTPS__DISCOVERY__ANNOUNCEMENT_PERIOD__SECS", "7"),
+                ("OTHER__GLOBAL__DOMAIN_ID", "9"),
+            ]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+        let configuration = Configuration::from_table(table).unwrap();
+        assert_eq!(configuration.global.domain_id.0, 5);
+        assert_eq!(configuration.global.default_multicast_address, "239.0.0.9");
+        assert_eq!(
+            configuration.discovery.announcement_period,
+            Duration::from_secs(7)
+        );
+        assert_eq!(
+            configuration.get_global_default_unicast_address().unwrap(),
+            vec![Ipv4Addr::new(192, 168, 1, 10)]
+        );
+    }
+
+    #[test]
+    fn invalid_files() {
+        let path = std::env::temp_dir().join(format!("troc-{}-invalid.toml", std::process::id()));
+
+        std::fs::write(
+            &path,
+            "[global]\ndefault_multicast_locator_list = [\"224.0.0.1:UDPV4\"]\n",
+        )
+        .unwrap();
+        let res = Configuration::from_file(&path);
+        assert!(
+            matches!(&res, Err(ConfigurationError::Parse { message, .. }) if message.contains("224.0.0.1:UDPV4")),
+            "{res:?}"
+        );
+
+        std::fs::write(&path, "[global]\nfragment_size = 65000\n").unwrap();
+        let res = Configuration::from_file(&path);
+        assert!(matches!(res, Err(ConfigurationError::FragmentSize { .. })));
+
+        std::fs::remove_file(&path).unwrap();
+        let res = Configuration::from_file(&path);
+        assert!(matches!(res, Err(ConfigurationError::Io { .. })));
+    }
+
+    #[test]
+    fn validation() {
+        assert!(Configuration::default().validate().is_ok());
+
+        let mut configuration = Configuration::default();
+        configuration.global.unicast_port_range = 8000..8000;
+        assert!(matches!(
+            configuration.validate(),
+            Err(ConfigurationError::InvalidPort { .. })
+        ));
+
+        let mut configuration = Configuration::default();
+        configuration.global.allowed_interfaces =
+            vec![String::from("eth0"), String::from("192.168.1.0/24")];
+        configuration.global.denied_interfaces = vec![String::from("10.0.0.0/33")];
+        assert!(matches!(
+            configuration.validate(),
+            Err(ConfigurationError::InvalidInterface { .. })
+        ));
+
+        let mut configuration = Configuration::default();
+        configuration.global.domain_id.0 = 300;
+        assert!(matches!(
+            configuration.validate(),
+            Err(ConfigurationError::InvalidPort { .. })
+        ));
+
+        let mut configuration = Configuration::default();
+        configuration.global.default_unicast_adress = vec![String::from("localhost")];
+        assert!(matches!(
+            configuration.validate(),
+            Err(ConfigurationError::InvalidAddress { .. })
+        ));
+
+        let mut configuration = Configuration::default();
+        configuration.global.default_multicast_address = String::from("10.0.0.1");
+        assert!(matches!(
+            configuration.validate(),
+            Err(ConfigurationError::NotMulticast { .. })
+        ));
+
+        let mut configuration = Configuration::default();
+        configuration.global.default_multicast_locator_list[0].address[12] = 10;
+        assert!(matches!(
+            configuration.validate(),
+            Err(ConfigurationError::InvalidLocator { .. })
+        ));
+
+        let mut configuration = Configuration::default();
+        configuration.udpv6.unicast_address = Some(String::from("fd00::zz"));
+        assert!(matches!(
+            configuration.validate(),
+            Err(ConfigurationError::InvalidIpv6Address { .. })
+        ));
+
+        let mut configuration = Configuration::default();
+        configuration.udpv6.multicast_address = String::from("ff0e::1");
+        assert!(matches!(
+            configuration.validate(),
+            Err(ConfigurationError::MulticastScope { .. })
+        ));
+        configuration.udpv6.multicast_address = String::from("ff05::1:3");
+        assert!(configuration.validate().is_ok());
+
+        let mut configuration = Configuration::default();
+        configuration.shm.segment_size = 1024;
+        assert!(matches!(
+            configuration.validate(),
+            Err(ConfigurationError::SegmentSize { .. })
+        ));
+
+        let mut configuration = Configuration::default();
+        configuration.tcp.reconnect_initial_delay = Duration::from_secs(10);
+        assert!(matches!(
+            configuration.validate(),
+            Err(ConfigurationError::InvalidDelay { .. })
+        ));
+
+        let mut configuration = Configuration::default();
+        configuration.discovery.initial_peers =
+            LocatorList::new(vec![Locator::from_str("10.0.0.2:7410:TCPV4").unwrap()]);
+        assert!(configuration.validate().is_ok());
+        configuration.tcp.tls.enabled = true;
+        assert!(matches!(
+            configuration.validate(),
+            Err(ConfigurationError::TlsUnavailable | ConfigurationError::MissingField { .. })
+        ));
+    }
+}
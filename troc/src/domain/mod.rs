@@ -1,17 +1,20 @@
 mod configuration;
 mod entity_identifier;
+mod network_interface;
 mod participant;
 mod participant_listener;
 mod udp_helper;
 
 pub use configuration::{
-    Configuration, DiscoveryConfiguration, GlobalConfiguration, ReaderConfiguration,
-    WriterConfiguration,
+    CONFIGURATION_ENV_PREFIX, Configuration, ConfigurationError, DiscoveryConfiguration,
+    GlobalConfiguration, IntraProcessConfiguration, ReaderConfiguration, ShmConfiguration,
+    TcpConfiguration, TlsConfiguration, UdpV6Configuration, WriterConfiguration,
 };
 pub use entity_identifier::{
     AskedId, EntityIdentifierActor, EntityIdentifierActorAskMessage,
     EntityIdentifierActorFreeMessage,
 };
+pub(crate) use network_interface::select_interfaces;
 pub use participant::{DomainParticipant, DomainParticipantBuilder};
 pub use participant_listener::{
     DomainParticipantListener, DomainParticipantListenerHandle, ParticipantEvent,
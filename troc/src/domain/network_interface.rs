@@ -0,0 +1,191 @@
+use std::{net::IpAddr, str::FromStr};
+
+/// An entry of the `global.allowed_interfaces` and `global.denied_interfaces` lists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum InterfaceSelector {
+    /// An address of the interface
+    Address(IpAddr),
+    /// A network an address of the interface belongs to, e.g. `192.168.1.0/24`
+    Network { address: IpAddr, prefix_length: u8 },
+    /// The name of the interface, e.g. `eth0`
+    Name(String),
+}
+
+impl FromStr for InterfaceSelector {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((address, prefix_length)) = s.split_once('/') {
+            let address = IpAddr::from_str(address).map_err(|_| ())?;
+            let prefix_length = u8::from_str(prefix_length).map_err(|_| ())?;
+            let max_prefix_length = match address {
+                IpAddr::V4(_) => 32,
+                IpAddr::V6(_) => 128,
+            };
+            if prefix_length > max_prefix_length {
+                return Err(());
+            }
+            return Ok(Self::Network {
+                address,
+                prefix_length,
+            });
+        }
+        if let Ok(address) = IpAddr::from_str(s) {
+            return Ok(Self::Address(address));
+        }
+        if s.is_empty() || s.contains(char::is_whitespace) {
+            return Err(());
+        }
+        Ok(Self::Name(s.to_string()))
+    }
+}
+
+impl InterfaceSelector {
+    pub fn matches(&self, name: &str, address: IpAddr) -> bool {
+        match self {
+            Self::Address(selected) => *selected == address,
+            Self::Network {
+                address: network,
+                prefix_length,
+            } => match (network, address) {
+                (IpAddr::V4(network), IpAddr::V4(address)) => {
+                    let mask = u32::MAX.checked_shl(32 - u32::from(*prefix_length));
+                    let mask = mask.unwrap_or_default();
+                    u32::from(*network) & mask == u32::from(address) & mask
+                }
+                (IpAddr::V6(network), IpAddr::V6(address)) => {
+                    let mask = u128::MAX.checked_shl(128 - u32::from(*prefix_length));
+                    let mask = mask.unwrap_or_default();
+                    u128::from(*network) & mask == u128::from(address) & mask
+                }
+                _ => false,
+            },
+            Self::Name(selected) => selected == name,
+        }
+    }
+}
+
+/// The `(name, address)` of the `interfaces` allowed and not denied
+///
+/// Every interface but the loopback one is allowed by an empty `allowed` list, the loopback
+/// interface is the fallback of a host without any other.
+pub(crate) fn select_interfaces(
+    allowed: &[InterfaceSelector],
+    denied: &[InterfaceSelector],
+    interfaces: Vec<(String, IpAddr)>,
+) -> Vec<(String, IpAddr)> {
+    let is_denied =
+        |(name, address): &(String, IpAddr)| denied.iter().any(|s| s.matches(name, *address));
+    if allowed.is_empty() {
+        let (loopback, others): (Vec<_>, Vec<_>) = interfaces
+            .into_iter()
+            .filter(|interface| !is_denied(interface))
+            .partition(|(_, address)| address.is_loopback());
+        if others.is_empty() { loopback } else { others }
+    } else {
+        interfaces
+            .into_iter()
+            .filter(|(name, address)| allowed.iter().any(|s| s.matches(name, *address)))
+            .filter(|interface| !is_denied(interface))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        net::{IpAddr, Ipv4Addr, Ipv6Addr},
+        str::FromStr,
+    };
+
+    use super::{InterfaceSelector, select_interfaces};
+
+    fn interfaces() -> Vec<(String, IpAddr)> {
+        vec![
+            (String::from("lo"), IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            (
+                String::from("eth0"),
+                IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10)),
+            ),
+            (String::from("eth1"), IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))),
+            (
+                String::from("eth1"),
+                IpAddr::V6(Ipv6Addr::from_str("fd00::2").unwrap()),
+            ),
+        ]
+    }
+
+    fn selectors(values: &[&str]) -> Vec<InterfaceSelector> {
+        values
+            .iter()
+            .map(|v| InterfaceSelector::from_str(v).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            InterfaceSelector::from_str("eth0"),
+            Ok(InterfaceSelector::Name(String::from("eth0")))
+        );
+        assert_eq!(
+            InterfaceSelector::from_str("10.0.0.2"),
+            Ok(InterfaceSelector::Address(IpAddr::V4(Ipv4Addr::new(
+                10, 0, 0, 2
+            ))))
+        );
+        assert_eq!(
+            InterfaceSelector::from_str("192.168.0.0/16"),
+            Ok(InterfaceSelector::Network {
+                address: IpAddr::V4(Ipv4Addr::new(192, 168, 0, 0)),
+                prefix_length: 16
+            })
+        );
+        assert!(InterfaceSelector::from_str("192.168.0.0/33").is_err());
+        assert!(InterfaceSelector::from_str("eth0/24").is_err());
+        assert!(InterfaceSelector::from_str("").is_err());
+    }
+
+    #[test]
+    fn selection() {
+        let names = |selected: Vec<(String, IpAddr)>| {
+            selected
+                .into_iter()
+                .map(|(name, address)| format!("{name}/{address}"))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(select_interfaces(&[], &[], interfaces())),
+            ["eth0/192.168.1.10", "eth1/10.0.0.2", "eth1/fd00::2"]
+        );
+        assert_eq!(
+            names(select_interfaces(
+                &selectors(&["lo", "10.0.0.0/8"]),
+                &[],
+                interfaces()
+            )),
+            ["lo/127.0.0.1", "eth1/10.0.0.2"]
+        );
+        assert_eq!(
+            names(select_interfaces(&[], &selectors(&["eth1"]), interfaces())),
+            ["eth0/192.168.1.10"]
+        );
+        assert_eq!(
+            names(select_interfaces(
+                &[],
+                &selectors(&["eth0", "eth1"]),
+                interfaces()
+            )),
+            ["lo/127.0.0.1"]
+        );
+        assert_eq!(
+            names(select_interfaces(
+                &selectors(&["0.0.0.0/0"]),
+                &selectors(&["192.168.1.10"]),
+                interfaces()
+            )),
+            ["lo/127.0.0.1", "eth1/10.0.0.2"]
+        );
+    }
+}
@@ -1,12 +1,16 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 use kameo::Actor;
 use kameo::actor::{ActorRef, Spawn};
+use kameo::error::{HookError, SendError};
 use kameo::prelude::Message;
 use tokio::sync::broadcast::{Receiver, channel};
+use tracing::{Level, event};
 use troc_core::builtin_endpoint_qos::BuiltinEndpointQos;
 use troc_core::domain_id::DomainId;
-use troc_core::{DdsError, DiscoveryConfiguration, Locator};
+use troc_core::{DdsError, DiscoveryConfiguration, Locator, LocatorList};
 use troc_core::{DomainTag, EntityId, EntityKey};
 use troc_core::{
     ENTITYID_PARTICIPANT, Guid, ParticipantProxy, TopicKind, VENDORID_UNKNOWN,
@@ -14,21 +18,30 @@ use troc_core::{
 };
 
 use crate::ParticipantEvent;
-use crate::discovery::{DiscoveryActor, DiscoveryActorCreateObject};
-use crate::publication::{Publisher, PublisherActor, PublisherActorCreateObject};
-use crate::subscription::{Subscriber, SubscriberActor, SubscriberActorCreateObject};
+use crate::discovery::{
+    DiscoveryActor, DiscoveryActorAssertLivelinessMessage, DiscoveryActorCreateObject,
+    DiscoveryActorMessage,
+};
+use crate::publication::{
+    Publisher, PublisherActor, PublisherActorContainsEntitiesMessage, PublisherActorCreateObject,
+    PublisherActorDeleteMessage,
+};
+use crate::subscription::{
+    Subscriber, SubscriberActor, SubscriberActorContainsEntitiesMessage,
+    SubscriberActorCreateObject, SubscriberActorDeleteMessage,
+};
 use crate::time::TimerActor;
 use crate::wires::{
     ReceiverWireActor, ReceiverWireFactoryActorMessage, SenderWireActor,
-    SenderWireFactoryActorMessage, WireFactoryActor,
+    SenderWireFactoryActorMessage, WireFactoryActor, wire_factory_error,
 };
 use crate::{
     domain::{
-        configuration::Configuration,
+        configuration::{Configuration, ConfigurationError},
         entity_identifier::{EntityIdentifierActor, EntityIdentifierActorAskMessage},
         participant_listener::DomainParticipantListener,
     },
-    infrastructure::{QosPolicy, QosPolicyBuilder},
+    infrastructure::{QosPolicy, QosPolicyBuilder, QosProfiles},
     topic::Topic,
 };
 
@@ -36,7 +49,10 @@ use crate::{
 pub struct DomainParticipantBuilder {
     guid: Option<Guid>,
     domain_id: Option<u32>,
+    qos: Option<QosPolicy>,
+    qos_profiles: Option<QosProfiles>,
     configuration: Option<Configuration>,
+    configuration_file: Option<PathBuf>,
 }
 
 impl DomainParticipantBuilder {
@@ -54,55 +70,99 @@ impl DomainParticipantBuilder {
         self
     }
 
+    /// Only the USER_DATA of `qos` applies to a DomainParticipant, it is advertised to the remote participants
+    pub fn with_qos(mut self, qos: QosPolicy) -> Self {
+        self.qos = Some(qos);
+        self
+    }
+
+    /// Profiles resolving the QoS of the entities created with `create_*_with_profile`
+    pub fn with_qos_profiles(mut self, qos_profiles: QosProfiles) -> Self {
+        self.qos_profiles = Some(qos_profiles);
+        self
+    }
+
+    /// Use `config` as is, the environment is ignored
+    ///
+    /// It can't be combined with [`Self::with_config_file`], building the participant then fails
     pub fn with_config(mut self, config: Configuration) -> Self {
         self.configuration = Some(config);
         self
     }
 
-    pub async fn build(self) -> DomainParticipant {
+    /// TOML file loaded on top of the default configuration, see [`Configuration::load`]
+    ///
+    /// It can't be combined with [`Self::with_config`], building the participant then fails
+    pub fn with_config_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.configuration_file = Some(path.into());
+        self
+    }
+
+    /// The configuration is either the one given to [`Self::with_config`] or the defaults, then the configuration file,
+    /// then the environment. The builder overrides apply last, and the result is validated before starting the
+    /// participant
+    pub async fn build(self) -> Result<DomainParticipant, DdsError> {
         let DomainParticipantBuilder {
             guid,
             domain_id,
+            qos,
+            qos_profiles,
             configuration,
+            configuration_file,
         } = self;
         let configuration =
-            configuration.unwrap_or_else(|| Self::retrieve_configuration(None).unwrap());
+            Self::retrieve_configuration(configuration, configuration_file, domain_id)?;
         let guid = guid.unwrap_or(Guid::generate(VENDORID_UNKNOWN, ENTITYID_PARTICIPANT));
-        let domain_id = domain_id.unwrap_or_default();
+        let domain_id = configuration.global.domain_id.0;
+        let qos_profiles = Arc::new(qos_profiles.unwrap_or_default());
 
         let actor = DomainParticipantActor::spawn(DomainParticipantActorCreationObject {
             guid,
             domain_id,
+            qos: qos.unwrap_or_default(),
+            qos_profiles: qos_profiles.clone(),
             configuration: configuration.clone(),
         });
-        actor.wait_for_startup().await;
-
-        DomainParticipant { guid, actor }
+        actor.wait_for_startup_result().await.map_err(|e| match e {
+            HookError::Error(e) => e,
+            HookError::Panicked(e) => DdsError::Actor {
+                actor: "DomainParticipant",
+                reason: e.to_string(),
+            },
+        })?;
+
+        Ok(DomainParticipant {
+            guid,
+            actor,
+            qos_profiles,
+        })
     }
 
     fn retrieve_configuration(
         configuration: Option<Configuration>,
-    ) -> Result<Configuration, DdsError> {
-        let configuration = configuration.unwrap_or_else(|| {
-            envious::Config::default()
-                .with_prefix("TROC__RTPS__")
-                .case_sensitive(false)
-                .build_from_env::<Configuration>()
-                .unwrap()
-            // if let Some(conf_file_path) = configuration.node.conf_file {
-            //     ConfiguratonFetcher::fetch(&conf_file_path).unwrap()
-            // } else {
-            //     configuration
-            // }
-        });
+        configuration_file: Option<PathBuf>,
+        domain_id: Option<u32>,
+    ) -> Result<Configuration, ConfigurationError> {
+        let mut configuration = match (configuration, configuration_file) {
+            (Some(_), Some(path)) => return Err(ConfigurationError::ConflictingSources { path }),
+            (Some(configuration), None) => configuration,
+            (None, configuration_file) => Configuration::load(configuration_file.as_deref())?,
+        };
+        if let Some(domain_id) = domain_id {
+            configuration.global.domain_id = DomainId(domain_id);
+        }
+        configuration.validate()?;
         Ok(configuration)
     }
 }
 
+/// Dropping a DomainParticipant without [`DomainParticipant::shutdown`] stops it without announcing its disposal,
+/// the remote participants only forget it once its lease duration expires
 #[derive(Debug)]
 pub struct DomainParticipant {
     guid: Guid,
     actor: ActorRef<DomainParticipantActor>,
+    qos_profiles: Arc<QosProfiles>,
 }
 
 impl DomainParticipant {
@@ -139,22 +199,137 @@ impl DomainParticipant {
         Topic::new(topic_name, type_name, qos, topic_kind)
     }
 
+    /// Create a Topic with the QoS of the profile `"library::profile"` applied to `topic_name`
+    pub fn create_topic_with_profile<T>(
+        &self,
+        topic_name: impl AsRef<str>,
+        type_name: impl AsRef<str>,
+        profile: &str,
+        topic_kind: TopicKind,
+    ) -> Result<Topic<T>, DdsError> {
+        let qos = self
+            .qos_profiles
+            .get_topic_qos(profile, topic_name.as_ref())?;
+        Ok(Topic::new(topic_name, type_name, &qos, topic_kind))
+    }
+
     pub async fn create_publisher(&mut self, qos: &QosPolicy) -> Result<Publisher, DdsError> {
-        let publisher = self
-            .actor
-            .ask(DomainParticipantActorCreatePublisherMessage { qos: *qos })
+        self.actor
+            .ask(DomainParticipantActorCreatePublisherMessage { qos: qos.clone() })
             .await
-            .unwrap();
-        Ok(publisher)
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Actor {
+                    actor: "DomainParticipant",
+                    reason: e.to_string(),
+                },
+            })
     }
 
     pub async fn create_subscriber(&mut self, qos: &QosPolicy) -> Result<Subscriber, DdsError> {
-        let subscriber = self
-            .actor
-            .ask(DomainParticipantActorCreateSubscriberMessage { qos: *qos })
+        self.actor
+            .ask(DomainParticipantActorCreateSubscriberMessage { qos: qos.clone() })
             .await
-            .unwrap();
-        Ok(subscriber)
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Actor {
+                    actor: "DomainParticipant",
+                    reason: e.to_string(),
+                },
+            })
+    }
+
+    /// Create a Publisher with the QoS of the profile `"library::profile"`
+    pub async fn create_publisher_with_profile(
+        &mut self,
+        profile: &str,
+    ) -> Result<Publisher, DdsError> {
+        let qos = self.qos_profiles.get_qos(profile)?;
+        self.create_publisher(&qos).await
+    }
+
+    /// Create a Subscriber with the QoS of the profile `"library::profile"`
+    pub async fn create_subscriber_with_profile(
+        &mut self,
+        profile: &str,
+    ) -> Result<Subscriber, DdsError> {
+        let qos = self.qos_profiles.get_qos(profile)?;
+        self.create_subscriber(&qos).await
+    }
+
+    /// Delete `publisher`
+    ///
+    /// Fail with [`DdsError::PreconditionNotMet`] if `publisher` still contains DataWriters or does not belong to this DomainParticipant
+    pub async fn delete_publisher(&mut self, publisher: &Publisher) -> Result<(), DdsError> {
+        self.ask_delete(DomainParticipantActorDeleteMessage::Publisher(
+            publisher.actor().clone(),
+        ))
+        .await
+    }
+
+    /// Delete `subscriber`
+    ///
+    /// Fail with [`DdsError::PreconditionNotMet`] if `subscriber` still contains DataReaders or does not belong to this DomainParticipant
+    pub async fn delete_subscriber(&mut self, subscriber: &Subscriber) -> Result<(), DdsError> {
+        self.ask_delete(DomainParticipantActorDeleteMessage::Subscriber(
+            subscriber.actor().clone(),
+        ))
+        .await
+    }
+
+    /// Delete every Publisher and Subscriber of this DomainParticipant along with their DataWriters and DataReaders
+    pub async fn delete_contained_entities(&mut self) -> Result<(), DdsError> {
+        self.ask_delete(DomainParticipantActorDeleteMessage::All)
+            .await
+    }
+
+    /// Delete the contained entities, announce the disposal of this DomainParticipant to the remote
+    /// participants and stop it, its sockets are released
+    pub async fn shutdown(mut self) -> Result<(), DdsError> {
+        self.delete_contained_entities().await?;
+        self.ask_delete(DomainParticipantActorDeleteMessage::Participant)
+            .await?;
+        self.actor
+            .stop_gracefully()
+            .await
+            .map_err(|e| DdsError::Actor {
+                actor: "DomainParticipant",
+                reason: e.to_string(),
+            })?;
+        self.actor.wait_for_shutdown().await;
+        Ok(())
+    }
+
+    async fn ask_delete(&self, msg: DomainParticipantActorDeleteMessage) -> Result<(), DdsError> {
+        self.actor.ask(msg).await.map_err(|e| match e {
+            SendError::HandlerError(e) => e,
+            e => DdsError::Actor {
+                actor: "DomainParticipant",
+                reason: e.to_string(),
+            },
+        })
+    }
+
+    /// Change the QoS of this DomainParticipant, only its USER_DATA applies and it is announced to the remote participants
+    pub async fn set_qos(&mut self, qos: &QosPolicy) -> Result<(), DdsError> {
+        self.actor
+            .ask(DomainParticipantActorQosMessage { qos: qos.clone() })
+            .await
+            .map_err(|e| DdsError::Error(e.to_string()))
+    }
+
+    /// Assert the liveliness of every MANUAL_BY_PARTICIPANT DataWriter of this DomainParticipant
+    pub async fn assert_liveliness(&self) -> Result<(), DdsError> {
+        self.actor
+            .ask(DomainParticipantActorAssertLivelinessMessage)
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Actor {
+                    actor: "DomainParticipant",
+                    reason: e.to_string(),
+                },
+            })
     }
 }
 
@@ -207,18 +382,133 @@ impl Message<DomainParticipantActorCreateSubscriberMessage> for DomainParticipan
     }
 }
 
+#[derive(Debug)]
+enum DomainParticipantActorDeleteMessage {
+    Publisher(ActorRef<PublisherActor>),
+    Subscriber(ActorRef<SubscriberActor>),
+    All,
+    /// The disposal of the participant is announced, it is about to stop
+    Participant,
+}
+
+impl Message<DomainParticipantActorDeleteMessage> for DomainParticipantActor {
+    type Reply = Result<(), DdsError>;
+
+    async fn handle(
+        &mut self,
+        msg: DomainParticipantActorDeleteMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        match msg {
+            DomainParticipantActorDeleteMessage::Publisher(publisher) => {
+                self.delete_publisher(publisher).await
+            }
+            DomainParticipantActorDeleteMessage::Subscriber(subscriber) => {
+                self.delete_subscriber(subscriber).await
+            }
+            DomainParticipantActorDeleteMessage::All => {
+                for publisher in self.publishers.clone() {
+                    publisher
+                        .ask(PublisherActorDeleteMessage::All)
+                        .await
+                        .map_err(|e| match e {
+                            SendError::HandlerError(e) => e,
+                            e => DdsError::Actor {
+                                actor: "Publisher",
+                                reason: e.to_string(),
+                            },
+                        })?;
+                    self.delete_publisher(publisher).await?;
+                }
+                for subscriber in self.subscribers.clone() {
+                    subscriber
+                        .ask(SubscriberActorDeleteMessage::All)
+                        .await
+                        .map_err(|e| match e {
+                            SendError::HandlerError(e) => e,
+                            e => DdsError::Actor {
+                                actor: "Subscriber",
+                                reason: e.to_string(),
+                            },
+                        })?;
+                    self.delete_subscriber(subscriber).await?;
+                }
+                Ok(())
+            }
+            DomainParticipantActorDeleteMessage::Participant => self
+                .discovery
+                .ask(DiscoveryActorMessage::ParticipantDisposed)
+                .await
+                .map_err(|e| DdsError::Actor {
+                    actor: "Discovery",
+                    reason: e.to_string(),
+                }),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DomainParticipantActorAssertLivelinessMessage;
+
+impl Message<DomainParticipantActorAssertLivelinessMessage> for DomainParticipantActor {
+    type Reply = Result<(), DdsError>;
+
+    async fn handle(
+        &mut self,
+        _msg: DomainParticipantActorAssertLivelinessMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        self.discovery
+            .ask(DiscoveryActorAssertLivelinessMessage)
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Actor {
+                    actor: "Discovery",
+                    reason: e.to_string(),
+                },
+            })
+    }
+}
+
+#[derive(Debug)]
+struct DomainParticipantActorQosMessage {
+    qos: QosPolicy,
+}
+
+impl Message<DomainParticipantActorQosMessage> for DomainParticipantActor {
+    type Reply = ();
+
+    async fn handle(
+        &mut self,
+        msg: DomainParticipantActorQosMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        self.infos.user_data = msg.qos.user_data().clone();
+        self.discovery
+            .tell(DiscoveryActorMessage::ParticipantProxyChanged(
+                self.infos.clone(),
+            ))
+            .await
+            .unwrap();
+    }
+}
+
 #[derive(Debug)]
 struct DomainParticipantActorCreationObject {
     domain_id: u32,
     guid: Guid,
+    qos: QosPolicy,
+    qos_profiles: Arc<QosProfiles>,
     configuration: Configuration,
 }
 
 #[derive(Debug)]
 struct DomainParticipantActor {
     guid: Guid,
-    _infos: ParticipantProxy,
+    infos: ParticipantProxy,
     config: Configuration,
+    qos_profiles: Arc<QosProfiles>,
     timer: ActorRef<TimerActor>,
     wire_factory: ActorRef<WireFactoryActor>,
     discovery: ActorRef<DiscoveryActor>,
@@ -234,7 +524,10 @@ impl DomainParticipantActor {
             .entity_identifier
             .ask(EntityIdentifierActorAskMessage::AskPublisherId)
             .await
-            .unwrap()
+            .map_err(|e| DdsError::Actor {
+                actor: "EntityIdentifier",
+                reason: e.to_string(),
+            })?
             .into();
         let pub_id = EntityId::writer_group_builtin(pub_key.0);
         let pub_guid = Guid::new(self.guid.get_guid_prefix(), pub_id);
@@ -243,19 +536,24 @@ impl DomainParticipantActor {
         let default_multicast_locators = self.config.global.default_multicast_locator_list.clone();
 
         let publisher_actor = PublisherActor::spawn(PublisherActorCreateObject {
+            guid: pub_guid,
+            presentation: qos.presentation(),
             discovery: self.discovery.clone(),
         });
+        self.publishers.push(publisher_actor.clone());
 
         let publisher = Publisher::new(
             pub_guid,
             default_unicast_locators,
             default_multicast_locators,
-            *qos,
+            qos.clone(),
             self.config.clone(),
             publisher_actor,
             self.wire_factory.clone(),
             self.entity_identifier.clone(),
             self.timer.clone(),
+            self.discovery.clone(),
+            self.qos_profiles.clone(),
         );
 
         Ok(publisher)
@@ -266,7 +564,10 @@ impl DomainParticipantActor {
             .entity_identifier
             .ask(EntityIdentifierActorAskMessage::AskSubscriberId)
             .await
-            .unwrap()
+            .map_err(|e| DdsError::Actor {
+                actor: "EntityIdentifier",
+                reason: e.to_string(),
+            })?
             .into();
         let sub_id = EntityId::writer_group_builtin(sub_key.0);
         let sub_guid = Guid::new(self.guid.get_guid_prefix(), sub_id);
@@ -277,21 +578,100 @@ impl DomainParticipantActor {
         let subscriber_actor = SubscriberActor::spawn(SubscriberActorCreateObject {
             discovery: self.discovery.clone(),
         });
+        self.subscribers.push(subscriber_actor.clone());
 
         let subscriber = Subscriber::new(
             sub_guid,
             default_unicast_locators,
             default_multicast_locators,
-            *qos,
+            qos.clone(),
             self.config.clone(),
             subscriber_actor,
             self.wire_factory.clone(),
             self.entity_identifier.clone(),
             self.timer.clone(),
+            self.discovery.clone(),
+            self.qos_profiles.clone(),
         );
 
         Ok(subscriber)
     }
+
+    async fn delete_publisher(
+        &mut self,
+        publisher: ActorRef<PublisherActor>,
+    ) -> Result<(), DdsError> {
+        let Some(position) = self
+            .publishers
+            .iter()
+            .position(|p| p.id() == publisher.id())
+        else {
+            return Err(DdsError::PreconditionNotMet);
+        };
+        let contains_entities = publisher
+            .ask(PublisherActorContainsEntitiesMessage)
+            .await
+            .map_err(|e| DdsError::Actor {
+                actor: "Publisher",
+                reason: e.to_string(),
+            })?;
+        if contains_entities {
+            return Err(DdsError::PreconditionNotMet);
+        }
+
+        self.publishers.remove(position);
+        publisher
+            .stop_gracefully()
+            .await
+            .map_err(|e| DdsError::Actor {
+                actor: "Publisher",
+                reason: e.to_string(),
+            })?;
+        publisher.wait_for_shutdown().await;
+        Ok(())
+    }
+
+    async fn delete_subscriber(
+        &mut self,
+        subscriber: ActorRef<SubscriberActor>,
+    ) -> Result<(), DdsError> {
+        let Some(position) = self
+            .subscribers
+            .iter()
+            .position(|s| s.id() == subscriber.id())
+        else {
+            return Err(DdsError::PreconditionNotMet);
+        };
+        let contains_entities = subscriber
+            .ask(SubscriberActorContainsEntitiesMessage)
+            .await
+            .map_err(|e| DdsError::Actor {
+                actor: "Subscriber",
+                reason: e.to_string(),
+            })?;
+        if contains_entities {
+            return Err(DdsError::PreconditionNotMet);
+        }
+
+        self.subscribers.remove(position);
+        subscriber
+            .stop_gracefully()
+            .await
+            .map_err(|e| DdsError::Actor {
+                actor: "Subscriber",
+                reason: e.to_string(),
+            })?;
+        subscriber.wait_for_shutdown().await;
+        Ok(())
+    }
+}
+
+/// Stop `actor` and wait for it, a failure is logged so that the other actors still stop
+async fn stop_actor<A: Actor>(name: &str, actor: &ActorRef<A>) {
+    if let Err(e) = actor.stop_gracefully().await {
+        event!(Level::WARN, actor = name, error = %e, "actor not stopped");
+    }
+    actor.wait_for_shutdown().await;
 }
 
 impl Actor for DomainParticipantActor {
@@ -306,10 +686,14 @@ impl Actor for DomainParticipantActor {
         let timer = TimerActor::spawn(TimerActor::new());
         timer.wait_for_startup().await;
         actor_ref.link(&timer).await;
-        let wire_factory = WireFactoryActor::spawn(WireFactoryActor::new(
-            args.domain_id,
-            args.configuration.clone(),
-        ));
+        let wire_factory = WireFactoryActor::spawn(
+            WireFactoryActor::new(
+                args.domain_id,
+                args.guid.get_guid_prefix(),
+                args.configuration.clone(),
+            )
+            .map_err(DdsError::from)?,
+        );
         wire_factory.wait_for_startup().await;
         actor_ref.link(&wire_factory).await;
         let (event_sender, event_receiver) = channel(64);
@@ -318,12 +702,12 @@ impl Actor for DomainParticipantActor {
         let (receiver_many_to_many, receiver_locators_many_to_many) = wire_factory
             .ask(ReceiverWireFactoryActorMessage::SPDP)
             .await
-            .unwrap();
+            .map_err(wire_factory_error)?;
         input_wires.extend(receiver_many_to_many);
         let (receiver_on_to_one, receiver_locators_on_to_one) = wire_factory
             .ask(ReceiverWireFactoryActorMessage::SEDP)
             .await
-            .unwrap();
+            .map_err(wire_factory_error)?;
         input_wires.extend(receiver_on_to_one);
         let locators = receiver_locators_many_to_many
             .clone()
@@ -336,12 +720,26 @@ impl Actor for DomainParticipantActor {
         let (sender_many_to_many, sender_locators_many_to_many) = wire_factory
             .ask(SenderWireFactoryActorMessage::SPDP)
             .await
-            .unwrap();
+            .map_err(wire_factory_error)?;
         output_wires.extend(sender_many_to_many);
+        // the participants unreachable through multicast are announced to directly, each peer is
+        // asked for on its own since the factory keeps the preferred locators of a single entity
+        let mut initial_peers = LocatorList::default();
+        for peer in args.configuration.discovery.initial_peers.iter() {
+            let (sender_initial_peer, reachable) = wire_factory
+                .ask(SenderWireFactoryActorMessage::FromLocators {
+                    locators: LocatorList::new(vec![*peer]),
+                })
+                .await
+                .map_err(wire_factory_error)?;
+            output_wires.extend(sender_initial_peer);
+            initial_peers = initial_peers.merge(reachable);
+        }
 
         let output_wires: HashMap<Locator, ActorRef<SenderWireActor>> = HashMap::from_iter(
             sender_locators_many_to_many
                 .iter()
+                .chain(initial_peers.iter())
                 .zip(output_wires)
                 .map(|(a, b)| (*a, b)),
         );
@@ -367,7 +765,7 @@ impl Actor for DomainParticipantActor {
         endpoint_set.set_builtin_endpoint_participant_message_data_reader(1);
         endpoint_set.set_builtin_endpoint_participant_message_data_writer(1);
 
-        let infos = ParticipantProxy::new(
+        let mut infos = ParticipantProxy::new(
             args.guid.get_guid_prefix(),
             DomainId(args.domain_id),
             DomainTag::default(),
@@ -379,6 +777,7 @@ impl Actor for DomainParticipantActor {
             endpoint_set,
             BuiltinEndpointQos::default(),
         );
+        infos.user_data = args.qos.user_data().clone();
 
         let discovery_configuration = DiscoveryConfiguration {
             announcement_period: args.configuration.discovery.announcement_period.as_millis()
@@ -386,6 +785,7 @@ impl Actor for DomainParticipantActor {
             lease_duration: args.configuration.discovery.lease_duration,
             metatraffic_unicast_locator_list,
             metatraffic_multicast_locator_list,
+            initial_peers,
         };
 
         let discovery = DiscoveryActor::spawn(DiscoveryActorCreateObject {
@@ -396,6 +796,12 @@ impl Actor for DomainParticipantActor {
             event_sender,
             timer: timer.clone(),
             wire_factory: wire_factory.clone(),
+            intra_process: args.configuration.intra_process.enabled.then(|| {
+                format!(
+                    "{}/{}",
+                    args.domain_id, args.configuration.global.domain_tag
+                )
+            }),
         });
 
         discovery.wait_for_startup().await;
@@ -403,8 +809,9 @@ impl Actor for DomainParticipantActor {
 
         let domain_participant_actor = Self {
             guid: args.guid,
-            _infos: infos,
+            infos,
             config: args.configuration,
+            qos_profiles: args.qos_profiles,
             timer,
             wire_factory,
             discovery,
@@ -421,20 +828,17 @@ impl Actor for DomainParticipantActor {
         _actor_ref: kameo::prelude::WeakActorRef<Self>,
         _reason: kameo::prelude::ActorStopReason,
     ) -> Result<(), Self::Error> {
+        // the discovery stops first, it can no longer reach the stopped endpoints
+        stop_actor("Discovery", &self.discovery).await;
         for publisher in self.publishers.iter() {
-            publisher.stop_gracefully().await.unwrap();
-            publisher.wait_for_shutdown().await;
+            stop_actor("Publisher", publisher).await;
         }
         for subscriber in self.subscribers.iter() {
-            subscriber.stop_gracefully().await.unwrap();
-            subscriber.wait_for_shutdown().await;
+            stop_actor("Subscriber", subscriber).await;
         }
-        self.discovery.stop_gracefully().await.unwrap();
-        self.discovery.wait_for_shutdown().await;
-        self.timer.stop_gracefully().await.unwrap();
-        self.timer.wait_for_shutdown().await;
-        self.wire_factory.stop_gracefully().await.unwrap();
-        self.wire_factory.wait_for_shutdown().await;
+        stop_actor("Timer", &self.timer).await;
+        stop_actor("WireFactory", &self.wire_factory).await;
+        stop_actor("EntityIdentifier", &self.entity_identifier).await;
         Ok(())
     }
 
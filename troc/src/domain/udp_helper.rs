@@ -1,4 +1,4 @@
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
 use troc_core::{locator::Locator, locator_kind::LocatorKind};
 
@@ -7,7 +7,7 @@ pub struct UdpHelper {}
 impl UdpHelper {
     pub fn get_socket_addr(locator: &Locator) -> SocketAddr {
         match locator.kind {
-            LocatorKind::UdpV4 => {
+            LocatorKind::UdpV4 | LocatorKind::TcpV4 => {
                 let addr: [u8; 4] = locator.address[12..].try_into().unwrap();
                 let addr = Ipv4Addr::from(addr);
                 let port: u16 = locator.port.try_into().unwrap();
@@ -15,6 +15,12 @@ impl UdpHelper {
                 let sock_addr: SocketAddr = SocketAddr::new(IpAddr::V4(addr), port);
                 sock_addr
             }
+            LocatorKind::UdpV6 => {
+                let addr = Ipv6Addr::from(locator.address);
+                let port: u16 = locator.port.try_into().unwrap();
+
+                SocketAddr::new(IpAddr::V6(addr), port)
+            }
             _ => unreachable!(),
         }
     }
@@ -1,4 +1,8 @@
-mod consistency_checker;
 mod qos_policy;
+mod qos_profile;
 
 pub use qos_policy::{QosPolicy, QosPolicyBuilder};
+pub use qos_profile::{
+    LivelinessProfile, QosProfile, QosProfileFormat, QosProfilePolicies, QosProfiles,
+    ReliabilityProfile,
+};
@@ -1,9 +1,12 @@
 use troc_core::{
-    DeadlineQosPolicy, DurabilityQosPolicy, HistoryQosPolicy, InlineQos, LifespanQosPolicy,
-    LivelinessQosPolicy, ParameterId, ReliabilityQosPolicy,
+    DeadlineQosPolicy, DestinationOrderQosPolicy, DurabilityQosPolicy, GroupDataQosPolicy,
+    HistoryQosPolicy, InlineQos, LifespanQosPolicy, LivelinessQosPolicy, OwnershipQosPolicy,
+    OwnershipStrengthQosPolicy, ParameterId, PartitionQosPolicy, PresentationQosPolicy,
+    ReliabilityQosPolicy, ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, TopicDataQosPolicy,
+    UserDataQosPolicy,
 };
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 pub struct QosPolicy {
     durability: DurabilityQosPolicy,
     deadline: DeadlineQosPolicy,
@@ -11,6 +14,16 @@ pub struct QosPolicy {
     lifespan: LifespanQosPolicy,
     history: HistoryQosPolicy,
     liveness: LivelinessQosPolicy,
+    resource_limits: ResourceLimitsQosPolicy,
+    ownership: OwnershipQosPolicy,
+    ownership_strength: OwnershipStrengthQosPolicy,
+    partition: PartitionQosPolicy,
+    time_based_filter: TimeBasedFilterQosPolicy,
+    destination_order: DestinationOrderQosPolicy,
+    presentation: PresentationQosPolicy,
+    user_data: UserDataQosPolicy,
+    topic_data: TopicDataQosPolicy,
+    group_data: GroupDataQosPolicy,
 }
 
 impl QosPolicy {
@@ -41,6 +54,52 @@ impl QosPolicy {
     pub fn liveness(&self) -> LivelinessQosPolicy {
         self.liveness
     }
+
+    pub fn resource_limits(&self) -> ResourceLimitsQosPolicy {
+        self.resource_limits
+    }
+
+    pub fn ownership(&self) -> OwnershipQosPolicy {
+        self.ownership
+    }
+
+    pub fn ownership_strength(&self) -> OwnershipStrengthQosPolicy {
+        self.ownership_strength
+    }
+
+    pub fn partition(&self) -> &PartitionQosPolicy {
+        &self.partition
+    }
+
+    pub fn time_based_filter(&self) -> TimeBasedFilterQosPolicy {
+        self.time_based_filter
+    }
+
+    pub fn destination_order(&self) -> DestinationOrderQosPolicy {
+        self.destination_order
+    }
+
+    pub fn presentation(&self) -> PresentationQosPolicy {
+        self.presentation
+    }
+
+    pub fn user_data(&self) -> &UserDataQosPolicy {
+        &self.user_data
+    }
+
+    pub fn topic_data(&self) -> &TopicDataQosPolicy {
+        &self.topic_data
+    }
+
+    pub fn group_data(&self) -> &GroupDataQosPolicy {
+        &self.group_data
+    }
+
+    /// Check the policies of this QosPolicy are consistent with each other
+    pub fn is_consistent(&self) -> bool {
+        self.resource_limits.is_consistent_with(self.history)
+            && self.time_based_filter.is_consistent_with(self.deadline)
+    }
 }
 
 impl From<InlineQos> for QosPolicy {
@@ -52,6 +111,16 @@ impl From<InlineQos> for QosPolicy {
             lifespan,
             history,
             liveness,
+            resource_limits,
+            ownership,
+            ownership_strength,
+            partition,
+            time_based_filter,
+            destination_order,
+            presentation,
+            user_data,
+            topic_data,
+            group_data,
             ..
         } = value;
         QosPolicy {
@@ -61,6 +130,16 @@ impl From<InlineQos> for QosPolicy {
             lifespan,
             history,
             liveness,
+            resource_limits,
+            ownership,
+            ownership_strength,
+            partition,
+            time_based_filter,
+            destination_order,
+            presentation,
+            user_data,
+            topic_data,
+            group_data,
         }
     }
 }
@@ -74,6 +153,16 @@ impl From<QosPolicy> for InlineQos {
             lifespan,
             history,
             liveness,
+            resource_limits,
+            ownership,
+            ownership_strength,
+            partition,
+            time_based_filter,
+            destination_order,
+            presentation,
+            user_data,
+            topic_data,
+            group_data,
         } = value;
         InlineQos {
             durability,
@@ -82,6 +171,16 @@ impl From<QosPolicy> for InlineQos {
             lifespan,
             history,
             liveness,
+            resource_limits,
+            ownership,
+            ownership_strength,
+            partition,
+            time_based_filter,
+            destination_order,
+            presentation,
+            user_data,
+            topic_data,
+            group_data,
             ..Default::default()
         }
     }
@@ -95,6 +194,16 @@ pub struct QosPolicyBuilder {
     lifespan: Option<LifespanQosPolicy>,
     history: Option<HistoryQosPolicy>,
     liveness: Option<LivelinessQosPolicy>,
+    resource_limits: Option<ResourceLimitsQosPolicy>,
+    ownership: Option<OwnershipQosPolicy>,
+    ownership_strength: Option<OwnershipStrengthQosPolicy>,
+    partition: Option<PartitionQosPolicy>,
+    time_based_filter: Option<TimeBasedFilterQosPolicy>,
+    destination_order: Option<DestinationOrderQosPolicy>,
+    presentation: Option<PresentationQosPolicy>,
+    user_data: Option<UserDataQosPolicy>,
+    topic_data: Option<TopicDataQosPolicy>,
+    group_data: Option<GroupDataQosPolicy>,
 }
 
 impl QosPolicyBuilder {
@@ -132,6 +241,56 @@ impl QosPolicyBuilder {
         self
     }
 
+    pub fn resource_limits(mut self, resource_limits: ResourceLimitsQosPolicy) -> Self {
+        self.resource_limits.replace(resource_limits);
+        self
+    }
+
+    pub fn ownership(mut self, ownership: OwnershipQosPolicy) -> Self {
+        self.ownership.replace(ownership);
+        self
+    }
+
+    pub fn ownership_strength(mut self, ownership_strength: OwnershipStrengthQosPolicy) -> Self {
+        self.ownership_strength.replace(ownership_strength);
+        self
+    }
+
+    pub fn partition(mut self, partition: PartitionQosPolicy) -> Self {
+        self.partition.replace(partition);
+        self
+    }
+
+    pub fn time_based_filter(mut self, time_based_filter: TimeBasedFilterQosPolicy) -> Self {
+        self.time_based_filter.replace(time_based_filter);
+        self
+    }
+
+    pub fn destination_order(mut self, destination_order: DestinationOrderQosPolicy) -> Self {
+        self.destination_order.replace(destination_order);
+        self
+    }
+
+    pub fn presentation(mut self, presentation: PresentationQosPolicy) -> Self {
+        self.presentation.replace(presentation);
+        self
+    }
+
+    pub fn user_data(mut self, user_data: UserDataQosPolicy) -> Self {
+        self.user_data.replace(user_data);
+        self
+    }
+
+    pub fn topic_data(mut self, topic_data: TopicDataQosPolicy) -> Self {
+        self.topic_data.replace(topic_data);
+        self
+    }
+
+    pub fn group_data(mut self, group_data: GroupDataQosPolicy) -> Self {
+        self.group_data.replace(group_data);
+        self
+    }
+
     pub fn build(self) -> QosPolicy {
         QosPolicy {
             durability: self.durability.unwrap_or_default(),
@@ -140,6 +299,16 @@ impl QosPolicyBuilder {
             lifespan: self.lifespan.unwrap_or_default(),
             history: self.history.unwrap_or_default(),
             liveness: self.liveness.unwrap_or_default(),
+            resource_limits: self.resource_limits.unwrap_or_default(),
+            ownership: self.ownership.unwrap_or_default(),
+            ownership_strength: self.ownership_strength.unwrap_or_default(),
+            partition: self.partition.unwrap_or_default(),
+            time_based_filter: self.time_based_filter.unwrap_or_default(),
+            destination_order: self.destination_order.unwrap_or_default(),
+            presentation: self.presentation.unwrap_or_default(),
+            user_data: self.user_data.unwrap_or_default(),
+            topic_data: self.topic_data.unwrap_or_default(),
+            group_data: self.group_data.unwrap_or_default(),
         }
     }
 }
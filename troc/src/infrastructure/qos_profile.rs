@@ -0,0 +1,402 @@
+use std::{collections::HashMap, path::Path, time::Duration};
+
+use chrono::DateTime;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use troc_core::{
+    DdsError, DeadlineQosPolicy, DestinationOrderQosPolicy, DurabilityQosPolicy,
+    GroupDataQosPolicy, HistoryQosPolicy, LifespanQosPolicy, LivelinessKind, LivelinessQosPolicy,
+    OwnershipQosPolicy, OwnershipStrengthQosPolicy, PartitionQosPolicy, PresentationQosPolicy,
+    ReliabilityQosPolicy, ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, Timestamp,
+    TopicDataQosPolicy, UserDataQosPolicy, wildcard_match,
+};
+
+use super::{QosPolicy, QosPolicyBuilder};
+
+/// Separator of the library and the profile in a qualified profile name, as in `"library::profile"`
+const PROFILE_SEPARATOR: &str = "::";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QosProfileFormat {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl QosProfileFormat {
+    /// Format of a file according to its extension
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        match path.as_ref().extension()?.to_str()? {
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Libraries of named QoS profiles, tunable without recompiling the application
+///
+/// A profile is designated by `"library::profile"`. It can extend another profile with `base_name`,
+/// and override its policies for the topics whose name matches a fnmatch-style pattern:
+///
+/// ```toml
+/// [sensors.reliable]
+/// reliability = { Reliable = { max_blocking_time = { secs = 0, nanos = 100000000 } } }
+/// history = { KeepLast = { depth = 10 } }
+///
+/// [sensors.fast]
+/// base_name = "reliable"
+/// deadline = { secs = 1, nanos = 0 }
+///
+/// [sensors.fast.topics."sensor/imu/*"]
+/// deadline = { secs = 0, nanos = 10000000 }
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct QosProfiles {
+    libraries: HashMap<String, HashMap<String, QosProfile>>,
+}
+
+impl QosProfiles {
+    /// Load the profiles of a `.toml`, `.yaml`, `.yml` or `.json` file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, DdsError> {
+        let path = path.as_ref();
+        let format = QosProfileFormat::from_path(path).ok_or(DdsError::BadParameter)?;
+        let content = std::fs::read_to_string(path).map_err(|e| DdsError::Error(e.to_string()))?;
+        Self::parse(&content, format)
+    }
+
+    pub fn parse(content: &str, format: QosProfileFormat) -> Result<Self, DdsError> {
+        match format {
+            QosProfileFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+            QosProfileFormat::Yaml => serde_yaml_ng::from_str(content).map_err(|e| e.to_string()),
+            QosProfileFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+        }
+        .map_err(DdsError::Error)
+    }
+
+    /// QoS of the profile `name`, with the policies inherited from its base profiles
+    ///
+    /// Fail with [`DdsError::BadParameter`] if a profile of the inheritance chain is unknown or if the chain loops
+    pub fn get_qos(&self, name: &str) -> Result<QosPolicy, DdsError> {
+        let policies = self
+            .resolve(name)?
+            .iter()
+            .fold(QosProfilePolicies::default(), |policies, profile| {
+                policies.overlay(&profile.policies)
+            });
+        Ok(policies.to_qos())
+    }
+
+    /// QoS of the profile `name` applied to the topic `topic_name`
+    ///
+    /// The overrides of the topic patterns matching `topic_name` apply over the policies of each profile of the inheritance chain,
+    /// in the order of the patterns in the file: when several patterns match, the last one wins
+    pub fn get_topic_qos(&self, name: &str, topic_name: &str) -> Result<QosPolicy, DdsError> {
+        let policies =
+            self.resolve(name)?
+                .iter()
+                .fold(QosProfilePolicies::default(), |policies, profile| {
+                    profile
+                        .topics
+                        .iter()
+                        .filter(|(pattern, _)| wildcard_match(pattern, topic_name))
+                        .fold(
+                            policies.overlay(&profile.policies),
+                            |policies, (_, topic)| policies.overlay(topic),
+                        )
+                });
+        Ok(policies.to_qos())
+    }
+
+    /// Inheritance chain of the profile `name`, from the root base profile to the profile itself
+    fn resolve(&self, name: &str) -> Result<Vec<&QosProfile>, DdsError> {
+        let mut chain: Vec<&QosProfile> = Vec::new();
+        let mut visited: Vec<(&str, &str)> = Vec::new();
+        let (mut library, mut profile) = name
+            .split_once(PROFILE_SEPARATOR)
+            .ok_or(DdsError::BadParameter)?;
+        loop {
+            if visited.contains(&(library, profile)) {
+                return Err(DdsError::BadParameter);
+            }
+            visited.push((library, profile));
+
+            let qos_profile = self
+                .libraries
+                .get(library)
+                .and_then(|profiles| profiles.get(profile))
+                .ok_or(DdsError::BadParameter)?;
+            chain.push(qos_profile);
+
+            match qos_profile.base_name.as_deref() {
+                // a base profile without a library belongs to the same library
+                Some(base_name) => {
+                    (library, profile) = base_name
+                        .split_once(PROFILE_SEPARATOR)
+                        .unwrap_or((library, base_name))
+                }
+                None => break,
+            }
+        }
+        chain.reverse();
+        Ok(chain)
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct QosProfile {
+    /// Profile extended by this one, either `"library::profile"` or `"profile"` of the same library
+    #[serde(default)]
+    pub base_name: Option<String>,
+    #[serde(flatten)]
+    pub policies: QosProfilePolicies,
+    /// Policies overridden for the topics whose name matches the pattern, in the order of the file
+    #[serde(default)]
+    pub topics: IndexMap<String, QosProfilePolicies>,
+}
+
+/// Policies set by a profile, the unset ones are inherited
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QosProfilePolicies {
+    pub durability: Option<DurabilityQosPolicy>,
+    pub deadline: Option<Duration>,
+    pub reliability: Option<ReliabilityProfile>,
+    pub lifespan: Option<Duration>,
+    pub history: Option<HistoryQosPolicy>,
+    pub liveliness: Option<LivelinessProfile>,
+    pub resource_limits: Option<ResourceLimitsQosPolicy>,
+    pub ownership: Option<OwnershipQosPolicy>,
+    pub ownership_strength: Option<i32>,
+    pub partition: Option<Vec<String>>,
+    pub time_based_filter: Option<Duration>,
+    pub destination_order: Option<DestinationOrderQosPolicy>,
+    pub presentation: Option<PresentationQosPolicy>,
+    pub user_data: Option<String>,
+    pub topic_data: Option<String>,
+    pub group_data: Option<String>,
+}
+
+impl QosProfilePolicies {
+    /// These policies, replaced by the ones set in `other`
+    fn overlay(self, other: &QosProfilePolicies) -> Self {
+        let other = other.clone();
+        Self {
+            durability: other.durability.or(self.durability),
+            deadline: other.deadline.or(self.deadline),
+            reliability: other.reliability.or(self.reliability),
+            lifespan: other.lifespan.or(self.lifespan),
+            history: other.history.or(self.history),
+            liveliness: other.liveliness.or(self.liveliness),
+            resource_limits: other.resource_limits.or(self.resource_limits),
+            ownership: other.ownership.or(self.ownership),
+            ownership_strength: other.ownership_strength.or(self.ownership_strength),
+            partition: other.partition.or(self.partition),
+            time_based_filter: other.time_based_filter.or(self.time_based_filter),
+            destination_order: other.destination_order.or(self.destination_order),
+            presentation: other.presentation.or(self.presentation),
+            user_data: other.user_data.or(self.user_data),
+            topic_data: other.topic_data.or(self.topic_data),
+            group_data: other.group_data.or(self.group_data),
+        }
+    }
+
+    fn to_qos(&self) -> QosPolicy {
+        let mut builder = QosPolicyBuilder::new();
+        if let Some(durability) = self.durability {
+            builder = builder.durability(durability);
+        }
+        if let Some(period) = self.deadline {
+            builder = builder.deadline(DeadlineQosPolicy {
+                period: to_timestamp(period),
+            });
+        }
+        if let Some(reliability) = self.reliability {
+            builder = builder.reliability(reliability.into());
+        }
+        if let Some(duration) = self.lifespan {
+            builder = builder.lifespan(LifespanQosPolicy {
+                duration: to_timestamp(duration),
+            });
+        }
+        if let Some(history) = self.history {
+            builder = builder.history(history);
+        }
+        if let Some(liveliness) = self.liveliness {
+            builder = builder.liveness(liveliness.into());
+        }
+        if let Some(resource_limits) = self.resource_limits {
+            builder = builder.resource_limits(resource_limits);
+        }
+        if let Some(ownership) = self.ownership {
+            builder = builder.ownership(ownership);
+        }
+        if let Some(value) = self.ownership_strength {
+            builder = builder.ownership_strength(OwnershipStrengthQosPolicy { value });
+        }
+        if let Some(partition) = &self.partition {
+            builder = builder.partition(PartitionQosPolicy::new(partition.iter().cloned()));
+        }
+        if let Some(minimum_separation) = self.time_based_filter {
+            builder = builder.time_based_filter(TimeBasedFilterQosPolicy {
+                minimum_separation: to_timestamp(minimum_separation),
+            });
+        }
+        if let Some(destination_order) = self.destination_order {
+            builder = builder.destination_order(destination_order);
+        }
+        if let Some(presentation) = self.presentation {
+            builder = builder.presentation(presentation);
+        }
+        if let Some(user_data) = &self.user_data {
+            builder = builder.user_data(UserDataQosPolicy::new(user_data.as_bytes()));
+        }
+        if let Some(topic_data) = &self.topic_data {
+            builder = builder.topic_data(TopicDataQosPolicy::new(topic_data.as_bytes()));
+        }
+        if let Some(group_data) = &self.group_data {
+            builder = builder.group_data(GroupDataQosPolicy::new(group_data.as_bytes()));
+        }
+        builder.build()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ReliabilityProfile {
+    BestEffort,
+    Reliable {
+        #[serde(default)]
+        max_blocking_time: Duration,
+    },
+}
+
+impl From<ReliabilityProfile> for ReliabilityQosPolicy {
+    fn from(value: ReliabilityProfile) -> Self {
+        match value {
+            ReliabilityProfile::BestEffort => ReliabilityQosPolicy::BestEffort,
+            ReliabilityProfile::Reliable { max_blocking_time } => ReliabilityQosPolicy::Reliable {
+                max_blocking_time: to_timestamp(max_blocking_time),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LivelinessProfile {
+    #[serde(default)]
+    pub kind: LivelinessKind,
+    /// An unset lease duration is infinite
+    #[serde(default)]
+    pub lease_duration: Duration,
+}
+
+impl From<LivelinessProfile> for LivelinessQosPolicy {
+    fn from(value: LivelinessProfile) -> Self {
+        LivelinessQosPolicy {
+            kind: value.kind,
+            lease_duration: to_timestamp(value.lease_duration),
+        }
+    }
+}
+
+fn to_timestamp(duration: Duration) -> Timestamp {
+    Timestamp::from_datetime(
+        DateTime::from_timestamp_millis(duration.as_millis() as i64).unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use troc_core::{DdsError, HistoryQosPolicy, ReliabilityQosPolicy};
+
+    use super::{QosProfileFormat, QosProfiles};
+
+    const PROFILES: &str = r#"
+        [sensors.reliable]
+        reliability = { Reliable = { max_blocking_time = { secs = 0, nanos = 100000000 } } }
+        history = { KeepLast = { depth = 10 } }
+        partition = ["sensors"]
+
+        [sensors.fast]
+        base_name = "reliable"
+        history = { KeepLast = { depth = 1 } }
+
+        [sensors.fast.topics."imu/*"]
+        partition = ["imu"]
+
+        [sensors.fast.topics."imu/left"]
+        partition = ["imu_left"]
+
+        [sensors.fast.topics."*/left"]
+        partition = ["left"]
+
+        [sensors.fast.topics."*"]
+        history = { KeepLast = { depth = 2 } }
+
+        [app.loop]
+        base_name = "app::loop"
+    "#;
+
+    #[rstest]
+    fn inheritance() {
+        let profiles = QosProfiles::parse(PROFILES, QosProfileFormat::Toml).unwrap();
+
+        let qos = profiles.get_qos("sensors::fast").unwrap();
+        assert!(matches!(
+            qos.reliability(),
+            ReliabilityQosPolicy::Reliable { .. }
+        ));
+        assert_eq!(qos.history(), HistoryQosPolicy::KeepLast { depth: 1 });
+        assert_eq!(qos.partition().names, vec!["sensors".to_string()]);
+
+        assert!(matches!(
+            profiles.get_qos("sensors::unknown"),
+            Err(DdsError::BadParameter)
+        ));
+        assert!(matches!(
+            profiles.get_qos("app::loop"),
+            Err(DdsError::BadParameter)
+        ));
+    }
+
+    #[rstest]
+    fn topic_patterns() {
+        let profiles = QosProfiles::parse(PROFILES, QosProfileFormat::Toml).unwrap();
+
+        let qos = profiles
+            .get_topic_qos("sensors::fast", "imu/right")
+            .unwrap();
+        assert_eq!(qos.partition().names, vec!["imu".to_string()]);
+        assert_eq!(qos.history(), HistoryQosPolicy::KeepLast { depth: 2 });
+
+        // the last matching pattern of the file wins
+        let qos = profiles.get_topic_qos("sensors::fast", "imu/left").unwrap();
+        assert_eq!(qos.partition().names, vec!["left".to_string()]);
+
+        let qos = profiles.get_topic_qos("sensors::fast", "gps").unwrap();
+        assert_eq!(qos.partition().names, vec!["sensors".to_string()]);
+        assert_eq!(qos.history(), HistoryQosPolicy::KeepLast { depth: 2 });
+    }
+
+    #[rstest]
+    fn formats() {
+        let json = r#"{ "lib": { "profile": { "history": "KeepAll" } } }"#;
+        let profiles = QosProfiles::parse(json, QosProfileFormat::Json).unwrap();
+        assert_eq!(
+            profiles.get_qos("lib::profile").unwrap().history(),
+            HistoryQosPolicy::KeepAll
+        );
+
+        let yaml = "lib:\n  profile:\n    history: KeepAll\n    topics:\n      'b*': { partition: [b] }\n      '*': { partition: [all] }\n";
+        let profiles = QosProfiles::parse(yaml, QosProfileFormat::Yaml).unwrap();
+        assert_eq!(
+            profiles.get_qos("lib::profile").unwrap().history(),
+            HistoryQosPolicy::KeepAll
+        );
+        let qos = profiles.get_topic_qos("lib::profile", "b").unwrap();
+        assert_eq!(qos.partition().names, vec!["all".to_string()]);
+    }
+}
@@ -14,15 +14,21 @@ pub use troc_core::*;
 pub use troc_derive::DDSType;
 
 pub use domain::{
-    Configuration, DiscoveryConfiguration, DomainParticipant, DomainParticipantBuilder,
-    DomainParticipantListener, DomainParticipantListenerHandle, GlobalConfiguration,
-    ParticipantEvent, ReaderConfiguration, WriterConfiguration,
+    CONFIGURATION_ENV_PREFIX, Configuration, ConfigurationError, DiscoveryConfiguration,
+    DomainParticipant, DomainParticipantBuilder, DomainParticipantListener,
+    DomainParticipantListenerHandle, GlobalConfiguration, IntraProcessConfiguration,
+    ParticipantEvent, ReaderConfiguration, ShmConfiguration, TcpConfiguration, TlsConfiguration,
+    UdpV6Configuration, WriterConfiguration,
+};
+pub use infrastructure::{
+    LivelinessProfile, QosPolicy, QosPolicyBuilder, QosProfile, QosProfileFormat,
+    QosProfilePolicies, QosProfiles, ReliabilityProfile,
 };
-pub use infrastructure::{QosPolicy, QosPolicyBuilder};
 pub use publication::{
     DataWriter, DataWriterEvent, DataWriterListener, DataWriterListenerHandle, Publisher,
 };
 pub use subscription::{
     DataReader, DataReaderEvent, DataReaderListener, DataReaderListenerHandle, DataSample,
-    Subscriber,
+    InstanceStateKind, InstanceStateMask, ReadCondition, SampleInfo, SampleStateKind,
+    SampleStateMask, Subscriber, ViewStateKind, ViewStateMask,
 };
@@ -1,9 +1,17 @@
-use std::{collections::HashMap, marker::PhantomData};
+use std::{
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+    pin::pin,
+    sync::Arc,
+    time::Duration,
+};
 
 use crate::{
     DataWriterEvent,
+    discovery::{DiscoveryActor, DiscoveryActorMessage},
     infrastructure::QosPolicy,
     publication::DataWriterListener,
+    subscription::{DataReaderActor, DataReaderActorMessage},
     time::{TimerActor, TimerActorScheduleTickMessage},
     wires::{
         ReceiverWireActor, ReceiverWireActorMessage, Sendable, SenderWireActor,
@@ -12,26 +20,31 @@ use crate::{
 };
 use bytes::BytesMut;
 use chrono::Utc;
-use kameo::{Actor, actor::ActorRef, prelude::Message};
+use kameo::{Actor, actor::ActorRef, error::SendError, prelude::Message};
 use serde::Serialize;
 
-use tokio::sync::broadcast::{Receiver, Sender, channel};
+use tokio::sync::{
+    Notify,
+    broadcast::{Receiver, Sender, channel},
+};
 use tracing::{Level, event, instrument};
 use troc_core::{
-    ChangeKind, Guid, InlineQos, InstanceHandle, Locator, LocatorList, SequenceNumber,
-    SerializedData, cdr,
+    ChangeKind, DurationKind, GroupCoherentSet, Guid, InlineQos, InstanceHandle, Locator,
+    LocatorList, ReliabilityQosPolicy, SequenceNumber, SerializedData, Timestamp, cdr,
 };
 use troc_core::{
-    DdsError, Effect, ReaderProxy, Writer,
+    DdsError, DiscoveredWriterData, Effect, Error, ReaderProxy, Writer,
     cdr::{CdrLe, Infinite},
 };
-use troc_core::{Effects, Keyed};
+use troc_core::{Effects, Keyed, TickId};
 
 #[derive(Debug)]
 pub struct DataWriter<T> {
     guid: Guid,
     qos: InlineQos,
     data_writer_actor: ActorRef<DataWriterActor>,
+    /// Notified by the actor when the matched Readers acknowledge changes or stop matching
+    acknowledgments: Arc<Notify>,
     phantom: PhantomData<T>,
 }
 
@@ -40,11 +53,13 @@ impl<T> DataWriter<T> {
         guid: Guid,
         qos: QosPolicy,
         data_writer_actor: ActorRef<DataWriterActor>,
+        acknowledgments: Arc<Notify>,
     ) -> Self {
         Self {
             guid,
             qos: qos.into(),
             data_writer_actor,
+            acknowledgments,
             phantom: PhantomData,
         }
     }
@@ -69,25 +84,227 @@ impl<T> DataWriter<T> {
         let key = data.key().unwrap();
         let data = cdr::serialize::<_, _, CdrLe>(&data, Infinite).unwrap();
         let data = SerializedData::from_vec(data);
-        self.write_raw(data, InstanceHandle(key)).await.unwrap();
-        Ok(())
+        self.write_raw(data, InstanceHandle(key)).await
     }
 
+    /// Write a serialized sample of the instance `key`
+    ///
+    /// When the history is full, a reliable DataWriter blocks up to `max_blocking_time` for the matched
+    /// DataReaders to acknowledge enough samples, then fails with [`DdsError::Timeout`]
     pub async fn write_raw(
         &mut self,
         data: SerializedData,
         key: InstanceHandle,
+    ) -> Result<(), DdsError> {
+        // waiting before writing, an acknowledgment received in between is not missed
+        let mut acknowledged = pin!(self.acknowledgments.notified());
+        acknowledged.as_mut().enable();
+        let res = self.ask_write(data.clone(), key).await;
+        let ReliabilityQosPolicy::Reliable { max_blocking_time } = self.qos.reliability else {
+            return res;
+        };
+        if !matches!(res, Err(DdsError::OutOfResources)) {
+            return res;
+        }
+
+        let max_blocking_time = max_blocking_time.to_datetime().timestamp_millis().max(0) as u64;
+        let retry = async {
+            loop {
+                acknowledged.as_mut().await;
+                acknowledged.set(self.acknowledgments.notified());
+                acknowledged.as_mut().enable();
+                match self.ask_write(data.clone(), key).await {
+                    Err(DdsError::OutOfResources) => continue,
+                    res => break res,
+                }
+            }
+        };
+        tokio::time::timeout(Duration::from_millis(max_blocking_time), retry)
+            .await
+            .map_err(|e| DdsError::Timeout {
+                cause: e.to_string(),
+            })?
+    }
+
+    async fn ask_write(
+        &self,
+        data: SerializedData,
+        instance: InstanceHandle,
     ) -> Result<(), DdsError> {
         self.data_writer_actor
-            .tell(DataWriterActorMessage::Write {
-                data,
-                instance: key,
+            .ask(DataWriterActorWriteMessage { data, instance })
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Error(e.to_string()),
             })
+    }
+
+    /// Declare that this DataWriter is about to write samples of `instance`
+    pub async fn register_instance(&mut self, instance: &T) -> Result<InstanceHandle, DdsError>
+    where
+        T: Keyed,
+    {
+        let instance = Self::instance_handle(instance)?;
+        self.ask_instance(DataWriterActorInstanceMessage::Register {
+            instance,
+            timestamp: None,
+        })
+        .await?;
+        Ok(instance)
+    }
+
+    pub async fn register_instance_w_timestamp(
+        &mut self,
+        instance: &T,
+        timestamp: Timestamp,
+    ) -> Result<InstanceHandle, DdsError>
+    where
+        T: Keyed,
+    {
+        let instance = Self::instance_handle(instance)?;
+        self.ask_instance(DataWriterActorInstanceMessage::Register {
+            instance,
+            timestamp: Some(timestamp),
+        })
+        .await?;
+        Ok(instance)
+    }
+
+    /// Declare that this DataWriter will no longer write samples of `instance`
+    pub async fn unregister_instance(&mut self, instance: &T) -> Result<(), DdsError>
+    where
+        T: Keyed,
+    {
+        let instance = Self::instance_handle(instance)?;
+        self.ask_instance(DataWriterActorInstanceMessage::Unregister {
+            instance,
+            timestamp: None,
+        })
+        .await
+    }
+
+    pub async fn unregister_instance_w_timestamp(
+        &mut self,
+        instance: &T,
+        timestamp: Timestamp,
+    ) -> Result<(), DdsError>
+    where
+        T: Keyed,
+    {
+        let instance = Self::instance_handle(instance)?;
+        self.ask_instance(DataWriterActorInstanceMessage::Unregister {
+            instance,
+            timestamp: Some(timestamp),
+        })
+        .await
+    }
+
+    /// Request the deletion of `instance`, matched DataReaders will see it as NOT_ALIVE_DISPOSED
+    pub async fn dispose(&mut self, instance: &T) -> Result<(), DdsError>
+    where
+        T: Keyed,
+    {
+        let instance = Self::instance_handle(instance)?;
+        self.ask_instance(DataWriterActorInstanceMessage::Dispose {
+            instance,
+            timestamp: None,
+        })
+        .await
+    }
+
+    pub async fn dispose_w_timestamp(
+        &mut self,
+        instance: &T,
+        timestamp: Timestamp,
+    ) -> Result<(), DdsError>
+    where
+        T: Keyed,
+    {
+        let instance = Self::instance_handle(instance)?;
+        self.ask_instance(DataWriterActorInstanceMessage::Dispose {
+            instance,
+            timestamp: Some(timestamp),
+        })
+        .await
+    }
+
+    fn instance_handle(instance: &T) -> Result<InstanceHandle, DdsError>
+    where
+        T: Keyed,
+    {
+        let key = instance.key().map_err(|_| DdsError::BadParameter)?;
+        Ok(InstanceHandle(key))
+    }
+
+    async fn ask_instance(&self, msg: DataWriterActorInstanceMessage) -> Result<(), DdsError> {
+        self.data_writer_actor.ask(msg).await.map_err(|e| match e {
+            SendError::HandlerError(e) => e,
+            e => DdsError::Error(e.to_string()),
+        })
+    }
+
+    /// Change the QoS of this DataWriter, the new QoS is announced to the remote Participants
+    ///
+    /// Fail with [`DdsError::ImmutablePolicy`] if a policy which cannot change once the DataWriter is enabled differs
+    pub async fn set_qos(&mut self, qos: &QosPolicy) -> Result<(), DdsError> {
+        if !qos.is_consistent() {
+            return Err(DdsError::InconsistentPolicy);
+        }
+
+        self.data_writer_actor
+            .ask(DataWriterActorQosMessage::Writer(qos.clone()))
             .await
-            .unwrap();
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Error(e.to_string()),
+            })?;
+        self.qos = qos.clone().into();
         Ok(())
     }
 
+    /// Wait until every matched reliable DataReader acknowledged all the samples written so far
+    ///
+    /// Fail with [`DdsError::Timeout`] if they are not acknowledged within `timeout`
+    pub async fn wait_for_acknowledgments(&self, timeout: DurationKind) -> Result<(), DdsError> {
+        let fut = async {
+            loop {
+                let mut notified = pin!(self.acknowledgments.notified());
+                notified.as_mut().enable();
+                let acknowledged = self
+                    .data_writer_actor
+                    .ask(DataWriterActorAcknowledgedMessage)
+                    .await
+                    .map_err(|e| DdsError::Actor {
+                        actor: "DataWriter",
+                        reason: e.to_string(),
+                    })?;
+                if acknowledged {
+                    break Ok(());
+                }
+                notified.await;
+            }
+        };
+
+        if let DurationKind::Finite(duration) = timeout {
+            tokio::time::timeout(duration, fut)
+                .await
+                .map_err(|e| DdsError::Timeout {
+                    cause: e.to_string(),
+                })?
+        } else {
+            fut.await
+        }
+    }
+
+    /// Assert the liveliness of this DataWriter, only needed for MANUAL_BY_PARTICIPANT and MANUAL_BY_TOPIC LIVELINESS
+    pub async fn assert_liveliness(&self) -> Result<(), DdsError> {
+        self.data_writer_actor
+            .tell(DataWriterActorMessage::AssertLiveliness)
+            .await
+            .map_err(|e| DdsError::Error(e.to_string()))
+    }
+
     // TODO: should not be used
     // instead leverages the QoS
     pub async fn remove_sample(&self, sequence: SequenceNumber) {
@@ -95,6 +312,193 @@ impl<T> DataWriter<T> {
     }
 }
 
+#[derive(Debug)]
+pub enum DataWriterActorInstanceMessage {
+    Register {
+        instance: InstanceHandle,
+        timestamp: Option<Timestamp>,
+    },
+    Unregister {
+        instance: InstanceHandle,
+        timestamp: Option<Timestamp>,
+    },
+    Dispose {
+        instance: InstanceHandle,
+        timestamp: Option<Timestamp>,
+    },
+}
+
+impl Message<DataWriterActorInstanceMessage> for DataWriterActor {
+    type Reply = Result<(), DdsError>;
+
+    #[instrument(name = "datawriter", skip_all, fields(guid = %self.writer.get_guid()))]
+    async fn handle(
+        &mut self,
+        msg: DataWriterActorInstanceMessage,
+        ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        let res = match msg {
+            DataWriterActorInstanceMessage::Register {
+                instance,
+                timestamp,
+            } => {
+                self.writer.register_instance(instance, timestamp);
+                Ok(())
+            }
+            DataWriterActorInstanceMessage::Unregister {
+                instance,
+                timestamp,
+            } => self
+                .writer
+                .unregister_instance(&mut self.effects, instance, timestamp),
+            DataWriterActorInstanceMessage::Dispose {
+                instance,
+                timestamp,
+            } => self
+                .writer
+                .dispose_instance(&mut self.effects, instance, timestamp),
+        };
+
+        self.process_effects(ctx.actor_ref()).await;
+
+        res.map_err(|e| match e {
+            Error::UnknownInstance(_) => DdsError::BadParameter,
+            e => DdsError::from(e),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum DataWriterActorCoherentMessage {
+    Begin,
+    /// End the coherent set, part of the group coherent set of a Publisher with GROUP access scope if any
+    End(Option<GroupCoherentSet>),
+}
+
+impl Message<DataWriterActorCoherentMessage> for DataWriterActor {
+    type Reply = Result<(), DdsError>;
+
+    #[instrument(name = "datawriter", skip_all, fields(guid = %self.writer.get_guid()))]
+    async fn handle(
+        &mut self,
+        msg: DataWriterActorCoherentMessage,
+        ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        let res = match msg {
+            DataWriterActorCoherentMessage::Begin => {
+                self.writer.begin_coherent_set();
+                Ok(())
+            }
+            DataWriterActorCoherentMessage::End(group) => {
+                self.writer.end_coherent_set(&mut self.effects, group)
+            }
+        };
+
+        self.process_effects(ctx.actor_ref()).await;
+
+        res.map_err(DdsError::from)
+    }
+}
+
+/// New QoS of a DataWriter, either its own or the one of its enclosing Publisher
+#[derive(Debug)]
+pub enum DataWriterActorQosMessage {
+    Writer(QosPolicy),
+    Group(QosPolicy),
+}
+
+impl Message<DataWriterActorQosMessage> for DataWriterActor {
+    type Reply = Result<(), DdsError>;
+
+    #[instrument(name = "datawriter", skip_all, fields(guid = %self.writer.get_guid()))]
+    async fn handle(
+        &mut self,
+        msg: DataWriterActorQosMessage,
+        ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        let qos = match msg {
+            // the Topic and the Publisher keep their own policies
+            DataWriterActorQosMessage::Writer(qos) => InlineQos {
+                topic_name: self.qos.topic_name.clone(),
+                type_name: self.qos.type_name.clone(),
+                topic_data: self.qos.topic_data.clone(),
+                partition: self.qos.partition.clone(),
+                presentation: self.qos.presentation,
+                group_data: self.qos.group_data.clone(),
+                ..qos.into()
+            },
+            DataWriterActorQosMessage::Group(qos) => InlineQos {
+                partition: qos.partition().clone(),
+                presentation: qos.presentation(),
+                group_data: qos.group_data().clone(),
+                ..self.qos.clone()
+            },
+        };
+        self.writer.set_qos(
+            &mut self.effects,
+            qos.clone(),
+            Utc::now().timestamp_millis(),
+        )?;
+        self.qos = qos.clone();
+        self.process_effects(ctx.actor_ref()).await;
+
+        event!(Level::DEBUG, "QoS changed, announcing it");
+        self.discovery
+            .tell(DiscoveryActorMessage::WriterUpdated(DiscoveredWriterData {
+                proxy: self.writer.extract_proxy(),
+                params: qos,
+            }))
+            .await
+            .map_err(|e| DdsError::Error(e.to_string()))
+    }
+}
+
+#[derive(Debug)]
+pub struct DataWriterActorWriteMessage {
+    data: SerializedData,
+    instance: InstanceHandle,
+}
+
+impl Message<DataWriterActorWriteMessage> for DataWriterActor {
+    type Reply = Result<(), DdsError>;
+
+    #[instrument(name = "datawriter", skip_all, fields(guid = %self.writer.get_guid()))]
+    async fn handle(
+        &mut self,
+        msg: DataWriterActorWriteMessage,
+        ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        let DataWriterActorWriteMessage { data, instance } = msg;
+        let change = self.writer.new_change(
+            ChangeKind::Alive,
+            Some(data),
+            Some(self.qos.clone()),
+            instance,
+        );
+        let res = self.writer.add_change(&mut self.effects, change);
+
+        self.process_effects(ctx.actor_ref()).await;
+
+        res.map_err(DdsError::from)
+    }
+}
+
+/// Whether every matched Reader acknowledged the changes written so far
+#[derive(Debug)]
+pub struct DataWriterActorAcknowledgedMessage;
+
+impl Message<DataWriterActorAcknowledgedMessage> for DataWriterActor {
+    type Reply = bool;
+
+    async fn handle(
+        &mut self,
+        _msg: DataWriterActorAcknowledgedMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        self.writer.is_acknowledged_by_all()
+    }
+}
+
 #[derive(Debug)]
 pub struct DataWriterListenerCreate;
 
@@ -112,26 +516,30 @@ impl Message<DataWriterListenerCreate> for DataWriterActor {
 
 #[derive(Debug)]
 pub enum DataWriterActorMessage {
-    Write {
-        data: SerializedData,
-        instance: InstanceHandle,
-    },
     IncomingMessage {
         message: BytesMut,
     },
+    /// A message of a matched Reader of the process, neither serialized nor sent through a wire
+    IntraProcessMessage {
+        message: troc_core::Message,
+    },
     AddProxy {
         proxy: ReaderProxy,
         wires: HashMap<Locator, ActorRef<SenderWireActor>>,
+        /// The Reader lives in the process, it is reached without wires
+        intra_process: Option<ActorRef<DataReaderActor>>,
     },
     RemoveProxy {
         guid: Guid,
         locators: Vec<Locator>,
     },
-    Tick,
+    Tick(TickId),
     AddInputWire {
         wires: Vec<ActorRef<ReceiverWireActor>>,
         locators: LocatorList,
     },
+    AssertLiveliness,
+    ParticipantLivelinessAsserted,
 }
 
 impl Message<DataWriterActorMessage> for DataWriterActor {
@@ -145,27 +553,35 @@ impl Message<DataWriterActorMessage> for DataWriterActor {
     ) -> Self::Reply {
         let now = Utc::now().timestamp_millis();
         match msg {
-            DataWriterActorMessage::Write { data, instance } => {
-                let change = self.writer.new_change(
-                    ChangeKind::Alive,
-                    Some(data),
-                    Some(self.qos.clone()),
-                    instance,
-                );
-                self.writer.add_change(&mut self.effects, change).unwrap();
-            }
             DataWriterActorMessage::IncomingMessage { message } => {
                 let message = troc_core::Message::deserialize_from(&message).unwrap();
 
-                self.writer.ingest(&mut self.effects, now, message).unwrap()
+                self.writer.ingest(&mut self.effects, now, message).unwrap();
+                self.acknowledgments.notify_waiters();
+            }
+            DataWriterActorMessage::IntraProcessMessage { message } => {
+                self.writer.ingest(&mut self.effects, now, message).unwrap();
+                self.acknowledgments.notify_waiters();
             }
-            DataWriterActorMessage::AddProxy { proxy, wires } => {
+            DataWriterActorMessage::AddProxy {
+                proxy,
+                wires,
+                intra_process,
+            } => {
                 self.output_wires.extend(wires);
-                self.writer.add_proxy(proxy.clone());
+                if let Some(reader) = intra_process {
+                    let guid = proxy.get_remote_reader_guid();
+                    for locator in proxy.get_locators().iter() {
+                        self.intra_process_readers
+                            .insert(*locator, (guid, reader.clone()));
+                    }
+                }
+                self.writer.add_proxy(&mut self.effects, proxy.clone());
                 self.timer
                     .tell(TimerActorScheduleTickMessage::Writer {
                         delay: 100,
                         target: ctx.actor_ref().clone(),
+                        id: TickId::Writer,
                     })
                     .await
                     .unwrap();
@@ -177,9 +593,26 @@ impl Message<DataWriterActorMessage> for DataWriterActor {
                 for locator in locators {
                     self.output_wires.remove(&locator);
                 }
-                self.writer.remove_proxy(guid)
+                self.intra_process_readers
+                    .retain(|_, (reader_guid, _)| *reader_guid != guid);
+                self.writer.remove_proxy(guid);
+                self.acknowledgments.notify_waiters();
+                let _res = self
+                    .event_sender
+                    .send(DataWriterEvent::SubscriptionStopped(guid));
             }
-            DataWriterActorMessage::Tick => self.writer.tick(&mut self.effects, now),
+            DataWriterActorMessage::Tick(TickId::Deadline) => {
+                self.writer.deadline_tick(&mut self.effects, now)
+            }
+            DataWriterActorMessage::Tick(TickId::Lifespan) => {
+                // the expired changes free room in the history
+                self.writer.lifespan_tick(&mut self.effects, now);
+                self.acknowledgments.notify_waiters();
+            }
+            DataWriterActorMessage::Tick(TickId::Liveliness) => {
+                self.writer.liveliness_tick(&mut self.effects, now)
+            }
+            DataWriterActorMessage::Tick(_) => self.writer.tick(&mut self.effects, now),
             DataWriterActorMessage::AddInputWire { wires, locators } => {
                 for wire in &wires {
                     wire.tell(ReceiverWireActorMessage::Start {
@@ -191,8 +624,20 @@ impl Message<DataWriterActorMessage> for DataWriterActor {
                 self.input_wires.extend(wires);
                 self.writer.add_unicast_locators(locators);
             }
+            DataWriterActorMessage::AssertLiveliness => {
+                self.writer.assert_liveliness(&mut self.effects, now)
+            }
+            DataWriterActorMessage::ParticipantLivelinessAsserted => self
+                .writer
+                .participant_liveliness_asserted(&mut self.effects, now),
         }
 
+        self.process_effects(ctx.actor_ref()).await;
+    }
+}
+
+impl DataWriterActor {
+    async fn process_effects(&mut self, actor_ref: &ActorRef<Self>) {
         while let Some(effect) = self.effects.pop() {
             match effect {
                 Effect::Message {
@@ -200,6 +645,26 @@ impl Message<DataWriterActorMessage> for DataWriterActor {
                     message,
                     locators,
                 } => {
+                    // a Reader is reached once, whatever the number of its locators
+                    let mut reached = HashSet::new();
+                    for locator in locators.iter() {
+                        if let Some((_, reader)) = self.intra_process_readers.get(locator)
+                            && reached.insert(reader.id())
+                        {
+                            let _res = reader
+                                .tell(DataReaderActorMessage::IntraProcessMessage {
+                                    message: message.clone(),
+                                })
+                                .await;
+                        }
+                    }
+                    if !locators
+                        .iter()
+                        .any(|locator| self.output_wires.contains_key(locator))
+                    {
+                        continue;
+                    }
+
                     let mut buffer = BytesMut::zeroed(65 * 1024);
                     let nb_bytes = message.serialize_to(&mut buffer).unwrap();
                     let message = buffer.split_to(nb_bytes);
@@ -215,15 +680,26 @@ impl Message<DataWriterActorMessage> for DataWriterActor {
                         }
                     }
                 }
-                Effect::ScheduleTick { id: _, delay } => {
+                Effect::ScheduleTick { id, delay } => {
                     self.timer
                         .tell(TimerActorScheduleTickMessage::Writer {
-                            delay: 200,
-                            target: ctx.actor_ref().clone(),
+                            delay,
+                            target: actor_ref.clone(),
+                            id,
                         })
                         .await
                         .unwrap();
                 }
+                Effect::DeadlineMissed { status } => {
+                    let _res = self
+                        .event_sender
+                        .send(DataWriterEvent::OfferedDeadlineMissed(status));
+                }
+                Effect::LivelinessLost { status } => {
+                    let _res = self
+                        .event_sender
+                        .send(DataWriterEvent::LivelinessLost(status));
+                }
                 Effect::Qos => todo!(),
                 _ => unreachable!(),
             }
@@ -236,6 +712,8 @@ pub struct DataWriterActorCreateObject {
     pub writer: Writer,
     pub qos: InlineQos,
     pub timer: ActorRef<TimerActor>,
+    pub discovery: ActorRef<DiscoveryActor>,
+    pub acknowledgments: Arc<Notify>,
 }
 
 #[derive(Debug)]
@@ -244,8 +722,14 @@ pub struct DataWriterActor {
     qos: InlineQos,
     effects: Effects,
     timer: ActorRef<TimerActor>,
+    discovery: ActorRef<DiscoveryActor>,
     input_wires: Vec<ActorRef<ReceiverWireActor>>,
     output_wires: HashMap<Locator, ActorRef<SenderWireActor>>,
+    /// The matched Readers of the process, by locator
+    intra_process_readers: HashMap<Locator, (Guid, ActorRef<DataReaderActor>)>,
+    /// Wakes the blocked writes and the acknowledgment waits of the DataWriter when the matched Readers acknowledge
+    /// changes or stop matching
+    acknowledgments: Arc<Notify>,
     _event_receiver: Option<Receiver<DataWriterEvent>>,
     event_sender: Sender<DataWriterEvent>,
 }
@@ -259,7 +743,13 @@ impl Actor for DataWriterActor {
         args: Self::Args,
         _actor_ref: kameo::prelude::ActorRef<Self>,
     ) -> Result<Self, Self::Error> {
-        let DataWriterActorCreateObject { writer, qos, timer } = args;
+        let DataWriterActorCreateObject {
+            writer,
+            qos,
+            timer,
+            discovery,
+            acknowledgments,
+        } = args;
 
         let (event_sender, event_receiver) = channel(64);
 
@@ -268,8 +758,11 @@ impl Actor for DataWriterActor {
             qos,
             effects: Effects::default(),
             timer,
+            discovery,
             input_wires: Default::default(),
             output_wires: Default::default(),
+            intra_process_readers: Default::default(),
+            acknowledgments,
             _event_receiver: Some(event_receiver),
             event_sender,
         };
@@ -302,17 +795,221 @@ impl Sendable for DataWriterActor {
     }
 }
 
+/// Locators of the sender wires of the DataWriter
+#[cfg(test)]
+#[derive(Debug)]
+pub(crate) struct DataWriterActorOutputWiresMessage;
+
+#[cfg(test)]
+impl Message<DataWriterActorOutputWiresMessage> for DataWriterActor {
+    type Reply = Vec<Locator>;
+
+    async fn handle(
+        &mut self,
+        _msg: DataWriterActorOutputWiresMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        self.output_wires.keys().copied().collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
+    use std::{str::FromStr, time::Duration};
 
     use rstest::*;
-    use tokio::sync::mpsc::channel;
-    use troc_core::{Guid, Locator};
+    use troc_core::LocatorKind;
 
     use crate::{
-        domain::Configuration, infrastructure::QosPolicy, publication::datawriter::DataWriter,
+        DomainParticipantBuilder, DurationKind, QosPolicyBuilder, ReliabilityQosPolicy, TopicKind,
+        domain::Configuration, publication::datawriter::DataWriterActorOutputWiresMessage,
+        subscription::DataReaderActorOutputWiresMessage,
     };
+    use troc_core::DomainTag;
+
+    #[tokio::test]
+    async fn intra_process_match_creates_no_wire() {
+        let mut configuration = Configuration::default();
+        configuration.intra_process.enabled = true;
+        configuration.global.domain_tag =
+            DomainTag::from_str("datawriter/intra_process_match_creates_no_wire").unwrap();
+        let mut participant = DomainParticipantBuilder::new()
+            .with_config(configuration.clone())
+            .build()
+            .await
+            .unwrap();
+        let mut other_participant = DomainParticipantBuilder::new()
+            .with_config(configuration)
+            .build()
+            .await
+            .unwrap();
+        let qos = QosPolicyBuilder::new()
+            .reliability(ReliabilityQosPolicy::Reliable {
+                max_blocking_time: Default::default(),
+            })
+            .build();
+        let topic = participant.create_topic(
+            String::from("/tests/datawriter/intra_process"),
+            "u32",
+            &qos,
+            TopicKind::NoKey,
+        );
+        let mut publisher = participant.create_publisher(&qos).await.unwrap();
+        let mut subscriber = other_participant.create_subscriber(&qos).await.unwrap();
+        let mut writer = publisher
+            .create_datawriter::<u32>(&topic, &qos)
+            .await
+            .unwrap();
+        let mut reader = subscriber
+            .create_datareader::<u32>(&topic, &qos)
+            .await
+            .unwrap();
+
+        let mut writer_listener = writer.get_listener().await.unwrap();
+        writer_listener
+            .wait_subscription_matched(DurationKind::Finite(Duration::from_secs(5)))
+            .await
+            .unwrap();
+        let mut reader_listener = reader.get_listener().await.unwrap();
+        reader_listener
+            .wait_publication_matched(DurationKind::Finite(Duration::from_secs(5)))
+            .await
+            .unwrap();
+
+        writer.write(7).await.unwrap();
+        let sample = reader.read_next_sample().await.unwrap();
+        assert_eq!(sample.take_data(), Some(7));
+
+        // neither the samples nor the acknowledgments go through a wire
+        let wires = writer
+            .data_writer_actor
+            .ask(DataWriterActorOutputWiresMessage)
+            .await
+            .unwrap();
+        assert!(wires.is_empty());
+        let wires = reader
+            .actor()
+            .ask(DataReaderActorOutputWiresMessage)
+            .await
+            .unwrap();
+        assert_eq!(wires, 0);
+    }
+
+    #[tokio::test]
+    async fn local_match_sends_through_the_segments() {
+        let mut configuration = Configuration::default();
+        configuration.shm.enabled = true;
+        configuration.global.domain_tag =
+            DomainTag::from_str("datawriter/local_match_sends_through_the_segments").unwrap();
+        let mut participant = DomainParticipantBuilder::new()
+            .with_config(configuration.clone())
+            .build()
+            .await
+            .unwrap();
+        let mut other_participant = DomainParticipantBuilder::new()
+            .with_config(configuration)
+            .build()
+            .await
+            .unwrap();
+        let qos = QosPolicyBuilder::new()
+            .reliability(ReliabilityQosPolicy::Reliable {
+                max_blocking_time: Default::default(),
+            })
+            .build();
+        let topic = participant.create_topic(
+            String::from("/tests/datawriter/shm"),
+            "u32",
+            &qos,
+            TopicKind::NoKey,
+        );
+        let mut publisher = participant.create_publisher(&qos).await.unwrap();
+        let mut subscriber = other_participant.create_subscriber(&qos).await.unwrap();
+        let mut writer = publisher
+            .create_datawriter::<u32>(&topic, &qos)
+            .await
+            .unwrap();
+        let mut reader = subscriber
+            .create_datareader::<u32>(&topic, &qos)
+            .await
+            .unwrap();
+
+        let mut writer_listener = writer.get_listener().await.unwrap();
+        writer_listener
+            .wait_subscription_matched(DurationKind::Finite(Duration::from_secs(5)))
+            .await
+            .unwrap();
+
+        writer.write(7).await.unwrap();
+        let sample = reader.read_next_sample().await.unwrap();
+        assert_eq!(sample.take_data(), Some(7));
+
+        // the UDP locators of the DataReader are advertised too, only its segment is written
+        let wires = writer
+            .data_writer_actor
+            .ask(DataWriterActorOutputWiresMessage)
+            .await
+            .unwrap();
+        assert!(!wires.is_empty());
+        assert!(wires.iter().all(|l| l.kind == LocatorKind::Shm));
+    }
+
+    #[tokio::test]
+    async fn remote_match_sends_through_tcp() {
+        let mut configuration = Configuration::default();
+        configuration.tcp.enabled = true;
+        configuration.global.domain_tag =
+            DomainTag::from_str("datawriter/remote_match_sends_through_tcp").unwrap();
+        let mut participant = DomainParticipantBuilder::new()
+            .with_config(configuration.clone())
+            .build()
+            .await
+            .unwrap();
+        let mut other_participant = DomainParticipantBuilder::new()
+            .with_config(configuration)
+            .build()
+            .await
+            .unwrap();
+        let qos = QosPolicyBuilder::new()
+            .reliability(ReliabilityQosPolicy::Reliable {
+                max_blocking_time: Default::default(),
+            })
+            .build();
+        let topic = participant.create_topic(
+            String::from("/tests/datawriter/tcp"),
+            "u32",
+            &qos,
+            TopicKind::NoKey,
+        );
+        let mut publisher = participant.create_publisher(&qos).await.unwrap();
+        let mut subscriber = other_participant.create_subscriber(&qos).await.unwrap();
+        let mut writer = publisher
+            .create_datawriter::<u32>(&topic, &qos)
+            .await
+            .unwrap();
+        let mut reader = subscriber
+            .create_datareader::<u32>(&topic, &qos)
+            .await
+            .unwrap();
+
+        let mut writer_listener = writer.get_listener().await.unwrap();
+        writer_listener
+            .wait_subscription_matched(DurationKind::Finite(Duration::from_secs(5)))
+            .await
+            .unwrap();
+
+        writer.write(7).await.unwrap();
+        let sample = reader.read_next_sample().await.unwrap();
+        assert_eq!(sample.take_data(), Some(7));
+
+        // the UDP locators of the DataReader are advertised too, only its listener is reached
+        let wires = writer
+            .data_writer_actor
+            .ask(DataWriterActorOutputWiresMessage)
+            .await
+            .unwrap();
+        assert!(!wires.is_empty());
+        assert!(wires.iter().all(|l| l.kind == LocatorKind::TcpV4));
+    }
 
     // #[fixture]
     // async fn new() -> DataWriter<()> {
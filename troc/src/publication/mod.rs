@@ -1,16 +1,25 @@
 mod datawriter;
 mod publisher;
 
-pub use datawriter::{DataWriter, DataWriterActor, DataWriterActorMessage};
-pub use publisher::{Publisher, PublisherActor, PublisherActorCreateObject, PublisherActorMessage};
+pub use datawriter::{
+    DataWriter, DataWriterActor, DataWriterActorCoherentMessage, DataWriterActorMessage,
+    DataWriterActorQosMessage,
+};
+pub use publisher::{
+    Publisher, PublisherActor, PublisherActorContainsEntitiesMessage, PublisherActorCreateObject,
+    PublisherActorDeleteMessage,
+};
 
-use troc_core::DurationKind;
-use troc_core::{DdsError, ReaderProxy};
+use troc_core::{DdsError, DeadlineMissedStatus, LivelinessLostStatus, ReaderProxy};
+use troc_core::{DurationKind, Guid};
 
 #[derive(Debug, Clone)]
 pub enum DataWriterEvent {
     SubscriptionMatched(ReaderProxy),
-    SubscriptionStopped,
+    /// The remote Reader `Guid` is no longer matched, its QoS or the local one changed
+    SubscriptionStopped(Guid),
+    OfferedDeadlineMissed(DeadlineMissedStatus),
+    LivelinessLost(LivelinessLostStatus),
 }
 
 #[derive()]
@@ -57,6 +66,75 @@ impl DataWriterListener {
             fut.await
         }
     }
+
+    pub async fn wait_subscription_stopped(
+        &mut self,
+        duration: DurationKind,
+    ) -> Result<Guid, DdsError> {
+        let fut = async move {
+            loop {
+                if let DataWriterEvent::SubscriptionStopped(guid) = self.wait_event().await? {
+                    break Ok(guid);
+                }
+            }
+        };
+
+        if let DurationKind::Finite(duration) = duration {
+            tokio::time::timeout(duration, fut)
+                .await
+                .map_err(|e| DdsError::Timeout {
+                    cause: e.to_string(),
+                })?
+        } else {
+            fut.await
+        }
+    }
+
+    pub async fn wait_offered_deadline_missed(
+        &mut self,
+        duration: DurationKind,
+    ) -> Result<DeadlineMissedStatus, DdsError> {
+        let fut = async move {
+            loop {
+                if let DataWriterEvent::OfferedDeadlineMissed(status) = self.wait_event().await? {
+                    break Ok(status);
+                }
+            }
+        };
+
+        if let DurationKind::Finite(duration) = duration {
+            tokio::time::timeout(duration, fut)
+                .await
+                .map_err(|e| DdsError::Timeout {
+                    cause: e.to_string(),
+                })?
+        } else {
+            fut.await
+        }
+    }
+
+    pub async fn wait_liveliness_lost(
+        &mut self,
+        duration: DurationKind,
+    ) -> Result<LivelinessLostStatus, DdsError> {
+        let fut = async move {
+            loop {
+                if let DataWriterEvent::LivelinessLost(status) = self.wait_event().await? {
+                    break Ok(status);
+                }
+            }
+        };
+
+        if let DurationKind::Finite(duration) = duration {
+            tokio::time::timeout(duration, fut)
+                .await
+                .map_err(|e| DdsError::Timeout {
+                    cause: e.to_string(),
+                })?
+        } else {
+            fut.await
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -1,13 +1,22 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use kameo::Actor;
 use kameo::actor::ActorRef;
 use kameo::actor::Spawn;
+use kameo::error::SendError;
 use kameo::prelude::Message;
 use serde::Serialize;
+use tokio::sync::Notify;
 use troc_core::DdsError;
 use troc_core::DiscoveredWriterData;
 use troc_core::EntityKey;
+use troc_core::GroupCoherentSet;
 use troc_core::InlineQos;
 use troc_core::Keyed;
+use troc_core::PresentationAccessScope;
+use troc_core::PresentationQosPolicy;
+use troc_core::SequenceNumber;
 use troc_core::WriterBuilder;
 use troc_core::WriterProxy;
 use troc_core::{EntityId, Guid, GuidPrefix, LocatorList, TopicKind};
@@ -16,14 +25,16 @@ use crate::discovery::DiscoveryActor;
 use crate::discovery::DiscoveryActorMessage;
 use crate::domain::EntityIdentifierActorAskMessage;
 use crate::publication::DataWriterActor;
+use crate::publication::DataWriterActorCoherentMessage;
 use crate::publication::DataWriterActorMessage;
+use crate::publication::DataWriterActorQosMessage;
 use crate::publication::datawriter::DataWriterActorCreateObject;
 use crate::time::TimerActor;
-use crate::wires::ReceiverWireFactoryActorMessage;
 use crate::wires::WireFactoryActor;
+use crate::wires::{ReceiverWireFactoryActorMessage, wire_factory_error};
 use crate::{
     domain::{Configuration, EntityIdentifierActor},
-    infrastructure::QosPolicy,
+    infrastructure::{QosPolicy, QosProfiles},
     publication::DataWriter,
     topic::Topic,
 };
@@ -36,6 +47,8 @@ pub struct Publisher {
     wire_factory: ActorRef<WireFactoryActor>,
     entity_identifier: ActorRef<EntityIdentifierActor>,
     timer: ActorRef<TimerActor>,
+    discovery: ActorRef<DiscoveryActor>,
+    qos_profiles: Arc<QosProfiles>,
 }
 
 impl Publisher {
@@ -49,6 +62,8 @@ impl Publisher {
         wire_factory: ActorRef<WireFactoryActor>,
         entity_identifier: ActorRef<EntityIdentifierActor>,
         timer: ActorRef<TimerActor>,
+        discovery: ActorRef<DiscoveryActor>,
+        qos_profiles: Arc<QosProfiles>,
     ) -> Self {
         Self {
             guid,
@@ -57,6 +72,8 @@ impl Publisher {
             wire_factory,
             entity_identifier,
             timer,
+            discovery,
+            qos_profiles,
         }
     }
 
@@ -68,6 +85,21 @@ impl Publisher {
         self.guid.get_entity_id()
     }
 
+    /// Create a DataWriter with the QoS of the profile `"library::profile"` applied to the name of `topic`
+    pub async fn create_datawriter_with_profile<T>(
+        &mut self,
+        topic: &Topic<T>,
+        profile: &str,
+    ) -> Result<DataWriter<T>, DdsError>
+    where
+        T: Serialize + Keyed + 'static,
+    {
+        let qos = self
+            .qos_profiles
+            .get_topic_qos(profile, topic.topic_name())?;
+        self.create_datawriter(topic, &qos).await
+    }
+
     pub async fn create_datawriter<T>(
         &mut self,
         topic: &Topic<T>,
@@ -76,11 +108,18 @@ impl Publisher {
     where
         T: Serialize + Keyed + 'static,
     {
+        if !qos.is_consistent() {
+            return Err(DdsError::InconsistentPolicy);
+        }
+
         let writer_key: EntityKey = self
             .entity_identifier
             .ask(EntityIdentifierActorAskMessage::AskWriterId)
             .await
-            .unwrap()
+            .map_err(|e| DdsError::Actor {
+                actor: "EntityIdentifier",
+                reason: e.to_string(),
+            })?
             .into();
 
         let writer_id = if matches!(topic.topic_kind, TopicKind::WithKey) {
@@ -91,28 +130,42 @@ impl Publisher {
         let writer_guid = Guid::new(self.guid.get_guid_prefix(), writer_id);
 
         let reliable = qos.reliability().into();
-        let mut inline_qos: InlineQos = (*qos).into();
+        let mut inline_qos: InlineQos = qos.clone().into();
         inline_qos.topic_name = topic.topic_name.clone();
         inline_qos.type_name = topic.type_name.clone();
+        inline_qos.topic_data = topic.qos.topic_data().clone();
+        // the PARTITION, the PRESENTATION and the GROUP_DATA are policies of the enclosing group
+        inline_qos.partition = self.qos.partition().clone();
+        inline_qos.presentation = self.qos.presentation();
+        inline_qos.group_data = self.qos.group_data().clone();
 
         let (input_wires, locators) = self
             .wire_factory
             .ask(ReceiverWireFactoryActorMessage::Applicative)
             .await
-            .unwrap();
+            .map_err(wire_factory_error)?;
 
         let writer = WriterBuilder::new(writer_guid, inline_qos.clone())
             .reliability(reliable)
             .with_unicast_locators(locators.clone())
             .build();
         let writer_proxy = writer.extract_proxy();
+        let acknowledgments = Arc::new(Notify::new());
         let writer_actor = DataWriterActor::spawn(DataWriterActorCreateObject {
             writer,
             qos: inline_qos.clone(),
             timer: self.timer.clone(),
+            discovery: self.discovery.clone(),
+            acknowledgments: acknowledgments.clone(),
         });
 
-        let datawriter = DataWriter::new(writer_guid, *qos, writer_actor.clone()).await;
+        let datawriter = DataWriter::new(
+            writer_guid,
+            qos.clone(),
+            writer_actor.clone(),
+            acknowledgments,
+        )
+        .await;
 
         writer_actor
             .ask(DataWriterActorMessage::AddInputWire {
@@ -120,7 +173,10 @@ impl Publisher {
                 locators,
             })
             .await
-            .unwrap();
+            .map_err(|e| DdsError::Actor {
+                actor: "DataWriter",
+                reason: e.to_string(),
+            })?;
 
         self.publisher_actor
             .ask(PublisherActorMessage {
@@ -129,10 +185,94 @@ impl Publisher {
                 writer: writer_actor,
             })
             .await
-            .unwrap();
+            .map_err(|e| DdsError::Actor {
+                actor: "Publisher",
+                reason: e.to_string(),
+            })?;
 
         Ok(datawriter)
     }
+
+    /// Delete `datawriter`, its disposal is announced so that the matched remote DataReaders stop matching it
+    ///
+    /// Fail with [`DdsError::PreconditionNotMet`] if `datawriter` does not belong to this Publisher
+    pub async fn delete_datawriter<T>(
+        &mut self,
+        datawriter: &DataWriter<T>,
+    ) -> Result<(), DdsError> {
+        self.publisher_actor
+            .ask(PublisherActorDeleteMessage::Writer(
+                datawriter.get_guid().get_entity_id(),
+            ))
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Actor {
+                    actor: "Publisher",
+                    reason: e.to_string(),
+                },
+            })
+    }
+
+    /// Delete every DataWriter of this Publisher
+    pub async fn delete_contained_entities(&mut self) -> Result<(), DdsError> {
+        self.publisher_actor
+            .ask(PublisherActorDeleteMessage::All)
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Actor {
+                    actor: "Publisher",
+                    reason: e.to_string(),
+                },
+            })
+    }
+
+    pub(crate) fn actor(&self) -> &ActorRef<PublisherActor> {
+        &self.publisher_actor
+    }
+
+    /// Change the QoS of this Publisher, the new PARTITION and GROUP_DATA are applied to its DataWriters and announced to the remote Participants
+    ///
+    /// Fail with [`DdsError::ImmutablePolicy`] if the PRESENTATION differs
+    pub async fn set_qos(&mut self, qos: &QosPolicy) -> Result<(), DdsError> {
+        if qos.presentation() != self.qos.presentation() {
+            return Err(DdsError::ImmutablePolicy);
+        }
+
+        self.publisher_actor
+            .ask(PublisherActorQosMessage(qos.clone()))
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Error(e.to_string()),
+            })?;
+        self.qos = qos.clone();
+        Ok(())
+    }
+
+    /// Start a coherent set, the samples written by the DataWriters of this Publisher until
+    /// [`Publisher::end_coherent_changes`] are presented together to the Subscribers requesting coherent access
+    pub async fn begin_coherent_changes(&self) -> Result<(), DdsError> {
+        self.publisher_actor
+            .ask(PublisherActorCoherentMessage::Begin)
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Error(e.to_string()),
+            })
+    }
+
+    /// End the coherent set started by [`Publisher::begin_coherent_changes`]
+    pub async fn end_coherent_changes(&self) -> Result<(), DdsError> {
+        self.publisher_actor
+            .ask(PublisherActorCoherentMessage::End)
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Error(e.to_string()),
+            })
+    }
 }
 
 #[derive(Debug)]
@@ -150,7 +290,16 @@ impl Message<PublisherActorMessage> for PublisherActor {
         msg: PublisherActorMessage,
         _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
     ) -> Self::Reply {
-        self.writers.push(msg.writer.clone());
+        if self.is_coherent {
+            msg.writer
+                .ask(DataWriterActorCoherentMessage::Begin)
+                .await
+                .unwrap();
+        }
+        self.writers.insert(
+            msg.proxy.get_remote_writer_guid().get_entity_id(),
+            msg.writer.clone(),
+        );
         let disc_writer_data = DiscoveredWriterData {
             proxy: msg.proxy,
             params: msg.qos,
@@ -165,15 +314,167 @@ impl Message<PublisherActorMessage> for PublisherActor {
     }
 }
 
+#[derive(Debug)]
+pub enum PublisherActorCoherentMessage {
+    Begin,
+    End,
+}
+
+impl Message<PublisherActorCoherentMessage> for PublisherActor {
+    type Reply = Result<(), DdsError>;
+
+    async fn handle(
+        &mut self,
+        msg: PublisherActorCoherentMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        let begin = matches!(msg, PublisherActorCoherentMessage::Begin);
+        if self.is_coherent == begin {
+            return Err(DdsError::PreconditionNotMet);
+        }
+        self.is_coherent = begin;
+
+        // with GROUP access scope, the sets of all the DataWriters are presented together
+        let group = (!begin
+            && matches!(
+                self.presentation.access_scope,
+                PresentationAccessScope::Group
+            ))
+        .then(|| {
+            self.group_coherent_sets += 1;
+            GroupCoherentSet {
+                sequence: self.group_coherent_sets,
+                writers: self
+                    .writers
+                    .keys()
+                    .map(|entity_id| Guid::new(self.guid.get_guid_prefix(), *entity_id))
+                    .collect(),
+            }
+        });
+
+        for writer in self.writers.values() {
+            let message = if begin {
+                DataWriterActorCoherentMessage::Begin
+            } else {
+                DataWriterActorCoherentMessage::End(group.clone())
+            };
+            writer.ask(message).await.map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Error(e.to_string()),
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct PublisherActorQosMessage(QosPolicy);
+
+impl Message<PublisherActorQosMessage> for PublisherActor {
+    type Reply = Result<(), DdsError>;
+
+    async fn handle(
+        &mut self,
+        msg: PublisherActorQosMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        for writer in self.writers.values() {
+            writer
+                .ask(DataWriterActorQosMessage::Group(msg.0.clone()))
+                .await
+                .map_err(|e| match e {
+                    SendError::HandlerError(e) => e,
+                    e => DdsError::Error(e.to_string()),
+                })?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum PublisherActorDeleteMessage {
+    Writer(EntityId),
+    All,
+}
+
+impl Message<PublisherActorDeleteMessage> for PublisherActor {
+    type Reply = Result<(), DdsError>;
+
+    async fn handle(
+        &mut self,
+        msg: PublisherActorDeleteMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        match msg {
+            PublisherActorDeleteMessage::Writer(entity_id) => self.delete_writer(entity_id).await,
+            PublisherActorDeleteMessage::All => {
+                let entity_ids = self.writers.keys().copied().collect::<Vec<_>>();
+                for entity_id in entity_ids {
+                    self.delete_writer(entity_id).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Whether the Publisher still contains DataWriters
+#[derive(Debug)]
+pub struct PublisherActorContainsEntitiesMessage;
+
+impl Message<PublisherActorContainsEntitiesMessage> for PublisherActor {
+    type Reply = bool;
+
+    async fn handle(
+        &mut self,
+        _msg: PublisherActorContainsEntitiesMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        !self.writers.is_empty()
+    }
+}
+
 #[derive(Debug)]
 pub struct PublisherActorCreateObject {
+    pub guid: Guid,
+    pub presentation: PresentationQosPolicy,
     pub discovery: ActorRef<DiscoveryActor>,
 }
 
 #[derive(Debug)]
 pub struct PublisherActor {
-    writers: Vec<ActorRef<DataWriterActor>>,
+    guid: Guid,
+    presentation: PresentationQosPolicy,
+    writers: HashMap<EntityId, ActorRef<DataWriterActor>>,
     discovery: ActorRef<DiscoveryActor>,
+    is_coherent: bool,
+    /// Number of the last group coherent set
+    group_coherent_sets: SequenceNumber,
+}
+
+impl PublisherActor {
+    /// The discovery forgets the DataWriter before it stops, no match can reach a stopped actor
+    async fn delete_writer(&mut self, entity_id: EntityId) -> Result<(), DdsError> {
+        let Some(writer) = self.writers.remove(&entity_id) else {
+            return Err(DdsError::PreconditionNotMet);
+        };
+        self.discovery
+            .ask(DiscoveryActorMessage::WriterRemoved(entity_id))
+            .await
+            .map_err(|e| DdsError::Actor {
+                actor: "Discovery",
+                reason: e.to_string(),
+            })?;
+        writer
+            .stop_gracefully()
+            .await
+            .map_err(|e| DdsError::Actor {
+                actor: "DataWriter",
+                reason: e.to_string(),
+            })?;
+        writer.wait_for_shutdown().await;
+        Ok(())
+    }
 }
 
 impl Actor for PublisherActor {
@@ -186,8 +487,12 @@ impl Actor for PublisherActor {
         _actor_ref: kameo::prelude::ActorRef<Self>,
     ) -> Result<Self, Self::Error> {
         let publisher_actor = Self {
+            guid: args.guid,
+            presentation: args.presentation,
             writers: Default::default(),
             discovery: args.discovery,
+            is_coherent: false,
+            group_coherent_sets: SequenceNumber::default(),
         };
 
         Ok(publisher_actor)
@@ -198,7 +503,7 @@ impl Actor for PublisherActor {
         _actor_ref: kameo::prelude::WeakActorRef<Self>,
         _reason: kameo::prelude::ActorStopReason,
     ) -> Result<(), Self::Error> {
-        for datawriter in self.writers.iter() {
+        for datawriter in self.writers.values() {
             datawriter.stop_gracefully().await.unwrap();
             datawriter.wait_for_shutdown().await;
         }
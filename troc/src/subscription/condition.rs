@@ -1,7 +1,68 @@
-#[derive(Debug, Default, Clone, Copy)]
-pub enum ReadCondition {
-    #[default]
-    NotRead,
-    Read,
-    All,
+use troc_core::{InstanceStateMask, SampleStateMask, ViewStateMask};
+
+/// Selects the samples a read or take operation applies to,
+/// based on their sample, view and instance states
+///
+/// The default condition selects every not yet read sample
+#[derive(Debug, Clone, Copy)]
+pub struct ReadCondition {
+    sample_states: SampleStateMask,
+    view_states: ViewStateMask,
+    instance_states: InstanceStateMask,
+}
+
+impl ReadCondition {
+    pub fn new(
+        sample_states: SampleStateMask,
+        view_states: ViewStateMask,
+        instance_states: InstanceStateMask,
+    ) -> Self {
+        Self {
+            sample_states,
+            view_states,
+            instance_states,
+        }
+    }
+
+    pub fn any() -> Self {
+        Self::new(
+            SampleStateMask::ANY,
+            ViewStateMask::ANY,
+            InstanceStateMask::ANY,
+        )
+    }
+
+    pub fn not_read() -> Self {
+        Self::new(
+            SampleStateMask::NOT_READ,
+            ViewStateMask::ANY,
+            InstanceStateMask::ANY,
+        )
+    }
+
+    pub fn read() -> Self {
+        Self::new(
+            SampleStateMask::READ,
+            ViewStateMask::ANY,
+            InstanceStateMask::ANY,
+        )
+    }
+
+    pub fn get_sample_states(&self) -> SampleStateMask {
+        self.sample_states
+    }
+
+    pub fn get_view_states(&self) -> ViewStateMask {
+        self.view_states
+    }
+
+    pub fn get_instance_states(&self) -> InstanceStateMask {
+        self.instance_states
+    }
+}
+
+impl Default for ReadCondition {
+    fn default() -> Self {
+        Self::not_read()
+    }
 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     marker::PhantomData,
     sync::{Arc, Mutex},
     time::Duration,
@@ -7,23 +7,34 @@ use std::{
 
 use bytes::BytesMut;
 use chrono::Utc;
-use kameo::{Actor, actor::ActorRef, prelude::Message};
+use kameo::{
+    Actor,
+    actor::{ActorRef, WeakActorRef},
+    error::SendError,
+    prelude::Message,
+};
 use serde::Deserialize;
 use tokio::sync::{
     Notify,
     broadcast::{Receiver, Sender, channel},
 };
 use tracing::{Level, Span, event};
-use troc_core::{CacheChangeContainer, DdsError, Effect, LocatorList, Reader, WriterProxy};
-use troc_core::{Effects, Keyed};
-use troc_core::{Guid, InlineQos, Locator, SerializedData, cdr};
+use troc_core::{
+    CacheChangeContainer, DdsError, DiscoveredReaderData, Effect, GroupCoherentSet, GuidPrefix,
+    InstanceStateMask, LivelinessKind, LivelinessQosPolicy, LocatorList,
+    OwnershipStrengthQosPolicy, Reader, SampleStateMask, ViewStateMask, WriterProxy,
+};
+use troc_core::{Effects, Keyed, TickId};
+use troc_core::{Guid, InlineQos, InstanceHandle, Locator, SerializedData, cdr};
 
 use crate::{
     DataReaderEvent,
+    discovery::{DiscoveryActor, DiscoveryActorMessage},
     infrastructure::QosPolicy,
+    publication::{DataWriterActor, DataWriterActorMessage},
     subscription::{
-        DataReaderListener, condition::ReadCondition, data_sample::DataSample,
-        sample_info::SampleInfo,
+        DataReaderListener, SubscriberActor, SubscriberActorGroupCoherentSetMessage,
+        condition::ReadCondition, data_sample::DataSample, sample_info::SampleInfo,
     },
     time::{TimerActor, TimerActorScheduleTickMessage},
     wires::{
@@ -61,6 +72,11 @@ impl<T> DataReader<T> {
         self.guid
     }
 
+    #[cfg(test)]
+    pub(crate) fn actor(&self) -> &ActorRef<DataReaderActor> {
+        &self.data_reader_actor
+    }
+
     pub async fn get_listener(&self) -> Result<DataReaderListener, DdsError> {
         let receiver = self
             .data_reader_actor
@@ -70,23 +86,40 @@ impl<T> DataReader<T> {
         Ok(DataReaderListener { receiver })
     }
 
-    pub async fn read_next_sample_raw(&mut self) -> Result<DataSample<SerializedData>, DdsError> {
-        loop {
-            match self
-                .data_reader_actor
-                .ask(DataReaderActorReadOneMessage::Read {})
-                .await
-                .unwrap()
-            {
-                Some(change) => {
-                    let data = change.data.clone();
-                    let infos = SampleInfo::from(&change.infos);
-                    let sample = DataSample::<SerializedData>::new(infos, data);
-                    return Ok(sample);
-                }
-                None => self.data_availability_notifier.notified().await,
-            }
+    /// Number of samples dropped because of the TIME_BASED_FILTER
+    pub async fn get_filtered_sample_count(&self) -> Result<u32, DdsError> {
+        let count = self
+            .data_reader_actor
+            .ask(DataReaderFilteredSampleCount {})
+            .await
+            .map_err(|e| DdsError::Actor {
+                actor: "DataReader",
+                reason: e.to_string(),
+            })?;
+        Ok(count)
+    }
+
+    /// Change the QoS of this DataReader, the new QoS is announced to the remote Participants
+    ///
+    /// Fail with [`DdsError::ImmutablePolicy`] if a policy which cannot change once the DataReader is enabled differs
+    pub async fn set_qos(&mut self, qos: &QosPolicy) -> Result<(), DdsError> {
+        if !qos.is_consistent() {
+            return Err(DdsError::InconsistentPolicy);
         }
+
+        self.data_reader_actor
+            .ask(DataReaderActorQosMessage::Reader(qos.clone()))
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Error(e.to_string()),
+            })?;
+        self.qos = qos.clone().into();
+        Ok(())
+    }
+
+    pub async fn read_next_sample_raw(&mut self) -> Result<DataSample<SerializedData>, DdsError> {
+        self.read_next_raw(None).await
     }
 
     pub async fn read_next_sample_raw_timeout(
@@ -135,68 +168,216 @@ impl<T> DataReader<T> {
         }
     }
 
+    /// Same as [`DataReader::read_next_sample_raw`], restricted to the samples of the instance of `key`
     pub async fn read_next_sample_instance_raw(
         &mut self,
         key: [u8; 16],
-    ) -> Result<DataSample<Vec<u8>>, DdsError> {
-        unimplemented!()
+    ) -> Result<DataSample<SerializedData>, DdsError> {
+        self.read_next_raw(Some(InstanceHandle(key))).await
     }
 
+    /// Same as [`DataReader::read_next_sample`], restricted to the samples of the instance of `key`
     pub async fn read_next_sample_instance(
         &mut self,
         key: &impl Keyed,
-    ) -> Result<DataSample<T>, DdsError> {
-        unimplemented!()
+    ) -> Result<DataSample<T>, DdsError>
+    where
+        for<'a> T: Deserialize<'a> + 'static + Keyed,
+    {
+        let key = key.key().map_err(|_| DdsError::BadParameter)?;
+        let sample = self.read_next_sample_instance_raw(key).await?;
+        Self::deserialize_samples(vec![sample])?
+            .pop()
+            .ok_or(DdsError::NoData)
     }
 
     pub async fn read_next_sample_instance_timeout(
         &mut self,
         key: &impl Keyed,
         timeout: Duration,
-    ) -> Result<DataSample<T>, DdsError> {
-        unimplemented!()
+    ) -> Result<DataSample<T>, DdsError>
+    where
+        for<'a> T: Deserialize<'a> + 'static + Keyed,
+    {
+        match tokio::time::timeout(timeout, self.read_next_sample_instance(key)).await {
+            Ok(res) => res,
+            Err(e) => Err(DdsError::Timeout {
+                cause: e.to_string(),
+            }),
+        }
     }
 
+    pub async fn read_raw(
+        &mut self,
+        max_samples: usize,
+        read_condition: ReadCondition,
+    ) -> Result<Vec<DataSample<SerializedData>>, DdsError> {
+        self.access_raw(DataReaderActorReadManyMessage::Read {
+            max_samples,
+            read_condition,
+            instance: None,
+        })
+        .await
+    }
+
+    pub async fn take_raw(
+        &mut self,
+        max_samples: usize,
+        read_condition: ReadCondition,
+    ) -> Result<Vec<DataSample<SerializedData>>, DdsError> {
+        self.access_raw(DataReaderActorReadManyMessage::Take {
+            max_samples,
+            read_condition,
+            instance: None,
+        })
+        .await
+    }
+
+    /// Read up to `max_samples` samples matching `read_condition`, the samples stay in the DataReader and are marked as READ
     pub async fn read(
         &mut self,
         max_samples: usize,
         read_condition: ReadCondition,
-    ) -> Result<Vec<DataSample<T>>, DdsError> {
-        unimplemented!()
+    ) -> Result<Vec<DataSample<T>>, DdsError>
+    where
+        for<'a> T: Deserialize<'a> + 'static + Keyed,
+    {
+        let samples = self.read_raw(max_samples, read_condition).await?;
+        Self::deserialize_samples(samples)
     }
 
+    /// Take up to `max_samples` samples matching `read_condition`, the samples are removed from the DataReader
     pub async fn take(
         &mut self,
         max_samples: usize,
         read_condition: ReadCondition,
-    ) -> Result<Vec<DataSample<T>>, DdsError> {
-        unimplemented!()
+    ) -> Result<Vec<DataSample<T>>, DdsError>
+    where
+        for<'a> T: Deserialize<'a> + 'static + Keyed,
+    {
+        let samples = self.take_raw(max_samples, read_condition).await?;
+        Self::deserialize_samples(samples)
     }
 
+    /// Same as [`DataReader::read`], restricted to the samples of `instance`
     pub async fn read_instance(
         &mut self,
         max_samples: usize,
         read_condition: ReadCondition,
         instance: impl Keyed,
-    ) -> Result<Vec<DataSample<T>>, DdsError> {
-        unimplemented!()
+    ) -> Result<Vec<DataSample<T>>, DdsError>
+    where
+        for<'a> T: Deserialize<'a> + 'static + Keyed,
+    {
+        let key = instance.key().map_err(|_| DdsError::BadParameter)?;
+        let samples = self
+            .access_raw(DataReaderActorReadManyMessage::Read {
+                max_samples,
+                read_condition,
+                instance: Some(InstanceHandle(key)),
+            })
+            .await?;
+        Self::deserialize_samples(samples)
     }
 
+    /// Same as [`DataReader::take`], restricted to the samples of `instance`
     pub async fn take_instance(
         &mut self,
         max_samples: usize,
         read_condition: ReadCondition,
         instance: impl Keyed,
-    ) -> Result<Vec<DataSample<T>>, DdsError> {
-        unimplemented!()
+    ) -> Result<Vec<DataSample<T>>, DdsError>
+    where
+        for<'a> T: Deserialize<'a> + 'static + Keyed,
+    {
+        let key = instance.key().map_err(|_| DdsError::BadParameter)?;
+        let samples = self
+            .access_raw(DataReaderActorReadManyMessage::Take {
+                max_samples,
+                read_condition,
+                instance: Some(InstanceHandle(key)),
+            })
+            .await?;
+        Self::deserialize_samples(samples)
+    }
+
+    /// Wait for the next not yet read sample, of `instance` only if given, and read it
+    async fn read_next_raw(
+        &mut self,
+        instance: Option<InstanceHandle>,
+    ) -> Result<DataSample<SerializedData>, DdsError> {
+        loop {
+            let change = self
+                .data_reader_actor
+                .ask(DataReaderActorReadOneMessage::Read { instance })
+                .await
+                .map_err(|e| DdsError::Actor {
+                    actor: "DataReader",
+                    reason: e.to_string(),
+                })?;
+            match change {
+                Some(change) => {
+                    let infos = SampleInfo::from(&change);
+                    return Ok(DataSample::<SerializedData>::new(
+                        infos,
+                        change.into_inner().data,
+                    ));
+                }
+                None => self.data_availability_notifier.notified().await,
+            }
+        }
+    }
+
+    async fn access_raw(
+        &mut self,
+        msg: DataReaderActorReadManyMessage,
+    ) -> Result<Vec<DataSample<SerializedData>>, DdsError> {
+        let changes = self
+            .data_reader_actor
+            .ask(msg)
+            .await
+            .map_err(|e| DdsError::Error(e.to_string()))?;
+
+        if changes.is_empty() {
+            return Err(DdsError::NoData);
+        }
+
+        let samples = changes
+            .into_iter()
+            .map(|change| {
+                let infos = SampleInfo::from(&change);
+                DataSample::<SerializedData>::new(infos, change.into_inner().data)
+            })
+            .collect();
+        Ok(samples)
+    }
+
+    fn deserialize_samples(
+        samples: Vec<DataSample<SerializedData>>,
+    ) -> Result<Vec<DataSample<T>>, DdsError>
+    where
+        for<'a> T: Deserialize<'a> + 'static + Keyed,
+    {
+        samples
+            .into_iter()
+            .map(|DataSample { infos, data }| {
+                let data = match data {
+                    Some(data) => Some(
+                        cdr::deserialize(data.get_data())
+                            .map_err(|e| DdsError::Error(e.to_string()))?,
+                    ),
+                    None => None,
+                };
+                Ok(DataSample::<T>::new(infos, data))
+            })
+            .collect()
     }
 }
 
 #[derive(Debug)]
 pub enum DataReaderActorReadOneMessage {
-    Read {
-        // TODO
-    },
+    /// Read the first not yet read sample, of `instance` only if given
+    Read { instance: Option<InstanceHandle> },
 }
 
 impl Message<DataReaderActorReadOneMessage> for DataReaderActor {
@@ -208,10 +389,65 @@ impl Message<DataReaderActorReadOneMessage> for DataReaderActor {
         ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
     ) -> Self::Reply {
         match msg {
-            DataReaderActorReadOneMessage::Read {} => {
-                let a = self.reader.get_first_available_change();
-                a.cloned()
-            }
+            DataReaderActorReadOneMessage::Read { instance } => self
+                .reader
+                .read_changes(
+                    1,
+                    SampleStateMask::NOT_READ,
+                    ViewStateMask::ANY,
+                    InstanceStateMask::ANY,
+                    instance,
+                )
+                .pop(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DataReaderActorReadManyMessage {
+    Read {
+        max_samples: usize,
+        read_condition: ReadCondition,
+        instance: Option<InstanceHandle>,
+    },
+    Take {
+        max_samples: usize,
+        read_condition: ReadCondition,
+        instance: Option<InstanceHandle>,
+    },
+}
+
+impl Message<DataReaderActorReadManyMessage> for DataReaderActor {
+    type Reply = Vec<CacheChangeContainer>;
+
+    async fn handle(
+        &mut self,
+        msg: DataReaderActorReadManyMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        match msg {
+            DataReaderActorReadManyMessage::Read {
+                max_samples,
+                read_condition,
+                instance,
+            } => self.reader.read_changes(
+                max_samples,
+                read_condition.get_sample_states(),
+                read_condition.get_view_states(),
+                read_condition.get_instance_states(),
+                instance,
+            ),
+            DataReaderActorReadManyMessage::Take {
+                max_samples,
+                read_condition,
+                instance,
+            } => self.reader.take_changes(
+                max_samples,
+                read_condition.get_sample_states(),
+                read_condition.get_view_states(),
+                read_condition.get_instance_states(),
+                instance,
+            ),
         }
     }
 }
@@ -231,24 +467,146 @@ impl Message<DataReaderListenerCreate> for DataReaderActor {
     }
 }
 
+#[derive(Debug)]
+pub struct DataReaderFilteredSampleCount;
+
+impl Message<DataReaderFilteredSampleCount> for DataReaderActor {
+    type Reply = u32;
+
+    async fn handle(
+        &mut self,
+        _msg: DataReaderFilteredSampleCount,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        self.reader.get_filtered_sample_count()
+    }
+}
+
+/// Whether the coherent sets of the group coherent set are complete for this DataReader
+#[derive(Debug)]
+pub struct DataReaderGroupCoherentSetComplete(pub GroupCoherentSet);
+
+impl Message<DataReaderGroupCoherentSetComplete> for DataReaderActor {
+    type Reply = bool;
+
+    async fn handle(
+        &mut self,
+        msg: DataReaderGroupCoherentSetComplete,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        self.reader.is_group_coherent_set_complete(&msg.0)
+    }
+}
+
+/// Number of the sender wires of the DataReader
+#[cfg(test)]
+#[derive(Debug)]
+pub(crate) struct DataReaderActorOutputWiresMessage;
+
+#[cfg(test)]
+impl Message<DataReaderActorOutputWiresMessage> for DataReaderActor {
+    type Reply = usize;
+
+    async fn handle(
+        &mut self,
+        _msg: DataReaderActorOutputWiresMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        self.output_wires.len()
+    }
+}
+
+/// New QoS of a DataReader, either its own or the one of its enclosing Subscriber
+#[derive(Debug)]
+pub enum DataReaderActorQosMessage {
+    Reader(QosPolicy),
+    Group(QosPolicy),
+}
+
+impl Message<DataReaderActorQosMessage> for DataReaderActor {
+    type Reply = Result<(), DdsError>;
+
+    async fn handle(
+        &mut self,
+        msg: DataReaderActorQosMessage,
+        ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        let qos = match msg {
+            // the Topic and the Subscriber keep their own policies
+            DataReaderActorQosMessage::Reader(qos) => InlineQos {
+                topic_name: self.qos.topic_name.clone(),
+                type_name: self.qos.type_name.clone(),
+                topic_data: self.qos.topic_data.clone(),
+                partition: self.qos.partition.clone(),
+                presentation: self.qos.presentation,
+                group_data: self.qos.group_data.clone(),
+                ..qos.into()
+            },
+            DataReaderActorQosMessage::Group(qos) => InlineQos {
+                partition: qos.partition().clone(),
+                presentation: qos.presentation(),
+                group_data: qos.group_data().clone(),
+                ..self.qos.clone()
+            },
+        };
+        self.reader.set_qos(
+            &mut self.effects,
+            qos.clone(),
+            Utc::now().timestamp_millis(),
+        )?;
+        self.qos = qos.clone();
+        self.process_effects(ctx.actor_ref()).await;
+
+        event!(Level::DEBUG, "QoS changed, announcing it");
+        self.discovery
+            .tell(DiscoveryActorMessage::ReaderUpdated(DiscoveredReaderData {
+                proxy: self.reader.extract_proxy(),
+                params: qos,
+            }))
+            .await
+            .map_err(|e| DdsError::Error(e.to_string()))
+    }
+}
+
 #[derive(Debug)]
 pub enum DataReaderActorMessage {
     IncomingMessage {
         message: BytesMut,
     },
+    /// A message of a matched Writer of the process, neither serialized nor sent through a wire
+    IntraProcessMessage {
+        message: troc_core::Message,
+    },
     AddProxy {
         proxy: WriterProxy,
+        liveliness: LivelinessQosPolicy,
+        ownership_strength: OwnershipStrengthQosPolicy,
         wires: HashMap<Locator, ActorRef<SenderWireActor>>,
+        /// The Writer lives in the process, it is reached without wires
+        intra_process: Option<ActorRef<DataWriterActor>>,
     },
     RemoveProxy {
         guid: Guid,
         locators: Vec<Locator>,
     },
-    Tick,
+    /// The matched Writer `guid` announced a new QoS
+    UpdateProxy {
+        guid: Guid,
+        ownership_strength: OwnershipStrengthQosPolicy,
+    },
+    Tick(TickId),
     AddInputWire {
         wires: Vec<ActorRef<ReceiverWireActor>>,
         locators: LocatorList,
     },
+    RemoteLivelinessAsserted {
+        participant_guid_prefix: GuidPrefix,
+        kind: LivelinessKind,
+    },
+    BeginAccess,
+    EndAccess,
+    /// The group coherent set is complete for every DataReader of the Subscriber
+    CommitGroupCoherentSet(GroupCoherentSet),
 }
 
 impl Message<DataReaderActorMessage> for DataReaderActor {
@@ -266,9 +624,33 @@ impl Message<DataReaderActorMessage> for DataReaderActor {
 
                 self.reader.ingest(&mut self.effects, now, message).unwrap()
             }
-            DataReaderActorMessage::AddProxy { proxy, wires } => {
+            DataReaderActorMessage::IntraProcessMessage { message } => {
+                self.reader.ingest(&mut self.effects, now, message).unwrap()
+            }
+            DataReaderActorMessage::AddProxy {
+                proxy,
+                liveliness,
+                ownership_strength,
+                wires,
+                intra_process,
+            } => {
                 self.output_wires.extend(wires);
+                if let Some(writer) = intra_process {
+                    let guid = proxy.get_remote_writer_guid();
+                    for locator in proxy.get_locators().iter() {
+                        self.intra_process_writers
+                            .insert(*locator, (guid, writer.clone()));
+                    }
+                }
                 self.reader.add_proxy(proxy.clone());
+                self.reader
+                    .track_ownership(proxy.get_remote_writer_guid(), ownership_strength);
+                self.reader.track_liveliness(
+                    &mut self.effects,
+                    proxy.get_remote_writer_guid(),
+                    liveliness,
+                    now,
+                );
                 let res = self
                     .event_sender
                     .send(DataReaderEvent::PublicationMatched(proxy));
@@ -277,9 +659,27 @@ impl Message<DataReaderActorMessage> for DataReaderActor {
                 for locator in locators {
                     self.output_wires.remove(&locator);
                 }
-                self.reader.remove_proxy(guid)
+                self.intra_process_writers
+                    .retain(|_, (writer_guid, _)| *writer_guid != guid);
+                self.reader.remove_proxy(guid);
+                let _res = self
+                    .event_sender
+                    .send(DataReaderEvent::PublicationStopped(guid));
+            }
+            DataReaderActorMessage::UpdateProxy {
+                guid,
+                ownership_strength,
+            } => self.reader.track_ownership(guid, ownership_strength),
+            DataReaderActorMessage::Tick(TickId::Deadline) => {
+                self.reader.deadline_tick(&mut self.effects, now)
             }
-            DataReaderActorMessage::Tick => self.reader.tick(&mut self.effects, now),
+            DataReaderActorMessage::Tick(TickId::Lifespan) => {
+                self.reader.lifespan_tick(&mut self.effects, now)
+            }
+            DataReaderActorMessage::Tick(TickId::Liveliness) => {
+                self.reader.liveliness_tick(&mut self.effects, now)
+            }
+            DataReaderActorMessage::Tick(_) => self.reader.tick(&mut self.effects, now),
             DataReaderActorMessage::AddInputWire { wires, locators } => {
                 for wire in &wires {
                     wire.tell(ReceiverWireActorMessage::Start {
@@ -291,8 +691,28 @@ impl Message<DataReaderActorMessage> for DataReaderActor {
                 self.input_wires.extend(wires);
                 self.reader.add_unicast_locators(locators);
             }
+            DataReaderActorMessage::RemoteLivelinessAsserted {
+                participant_guid_prefix,
+                kind,
+            } => self.reader.participant_liveliness_asserted(
+                &mut self.effects,
+                participant_guid_prefix,
+                kind,
+                now,
+            ),
+            DataReaderActorMessage::BeginAccess => self.reader.begin_access(),
+            DataReaderActorMessage::EndAccess => self.reader.end_access(&mut self.effects),
+            DataReaderActorMessage::CommitGroupCoherentSet(group) => self
+                .reader
+                .commit_group_coherent_set(&mut self.effects, &group),
         }
 
+        self.process_effects(ctx.actor_ref()).await;
+    }
+}
+
+impl DataReaderActor {
+    async fn process_effects(&mut self, actor_ref: &ActorRef<Self>) {
         while let Some(effect) = self.effects.pop() {
             match effect {
                 Effect::DataAvailable => {
@@ -303,6 +723,31 @@ impl Message<DataReaderActorMessage> for DataReaderActor {
                     message,
                     locators,
                 } => {
+                    // a Writer is reached once, whatever the number of its locators
+                    let mut reached = HashSet::new();
+                    for locator in locators.iter() {
+                        if let Some((_, writer)) = self.intra_process_writers.get(locator)
+                            && reached.insert(writer.id())
+                        {
+                            // the Writer waits for the mailbox of its Readers, a Reader never waits for
+                            // the one of a Writer: a message which doesn't fit is lost like on a wire
+                            let res = writer
+                                .tell(DataWriterActorMessage::IntraProcessMessage {
+                                    message: message.clone(),
+                                })
+                                .try_send();
+                            if let Err(e) = res {
+                                event!(Level::DEBUG, error = %e, "intra-process message dropped");
+                            }
+                        }
+                    }
+                    if !locators
+                        .iter()
+                        .any(|locator| self.output_wires.contains_key(locator))
+                    {
+                        continue;
+                    }
+
                     let mut buffer = BytesMut::zeroed(65 * 1024);
                     let nb_bytes = message.serialize_to(&mut buffer).unwrap();
                     let message = buffer.split_to(nb_bytes);
@@ -318,15 +763,47 @@ impl Message<DataReaderActorMessage> for DataReaderActor {
                         }
                     }
                 }
-                Effect::ScheduleTick { id: _, delay } => {
+                Effect::ScheduleTick { id, delay } => {
                     self.timer
                         .tell(TimerActorScheduleTickMessage::Reader {
                             delay,
-                            target: ctx.actor_ref().clone(),
+                            target: actor_ref.clone(),
+                            id,
                         })
                         .await
                         .unwrap();
                 }
+                Effect::DeadlineMissed { status } => {
+                    let _res = self
+                        .event_sender
+                        .send(DataReaderEvent::RequestedDeadlineMissed(status));
+                }
+                Effect::LivelinessChanged { status } => {
+                    let _res = self
+                        .event_sender
+                        .send(DataReaderEvent::LivelinessChanged(status));
+                }
+                Effect::SampleRejected { reason } => {
+                    let _res = self
+                        .event_sender
+                        .send(DataReaderEvent::SampleRejected { reason });
+                }
+                Effect::SampleLost { status } => {
+                    let _res = self.event_sender.send(DataReaderEvent::SampleLost(status));
+                }
+                Effect::GroupCoherentSetComplete { group } => {
+                    // the Subscriber asks its DataReaders in turn, this one must not wait for its mailbox
+                    if let Some(subscriber) = self.subscriber.upgrade() {
+                        tokio::spawn(async move {
+                            let res = subscriber
+                                .tell(SubscriberActorGroupCoherentSetMessage(group))
+                                .await;
+                            if let Err(e) = res {
+                                event!(Level::DEBUG, error = %e, "group coherent set not reported");
+                            }
+                        });
+                    }
+                }
                 Effect::Qos => todo!(),
                 _ => unreachable!(),
             }
@@ -340,6 +817,8 @@ pub struct DataReaderActorCreateObject {
     pub qos: InlineQos,
     pub data_availability_notifier: Arc<Notify>,
     pub timer: ActorRef<TimerActor>,
+    pub discovery: ActorRef<DiscoveryActor>,
+    pub subscriber: WeakActorRef<SubscriberActor>,
 }
 
 #[derive(Debug)]
@@ -348,8 +827,12 @@ pub struct DataReaderActor {
     qos: InlineQos,
     effects: Effects,
     timer: ActorRef<TimerActor>,
+    discovery: ActorRef<DiscoveryActor>,
+    subscriber: WeakActorRef<SubscriberActor>,
     input_wires: Vec<ActorRef<ReceiverWireActor>>,
     output_wires: HashMap<Locator, ActorRef<SenderWireActor>>,
+    /// The matched Writers of the process, by locator
+    intra_process_writers: HashMap<Locator, (Guid, ActorRef<DataWriterActor>)>,
     data_availability_notifier: Arc<Notify>,
     _event_receiver: Option<Receiver<DataReaderEvent>>,
     event_sender: Sender<DataReaderEvent>,
@@ -369,6 +852,8 @@ impl Actor for DataReaderActor {
             qos,
             data_availability_notifier,
             timer,
+            discovery,
+            subscriber,
         } = args;
 
         let (event_sender, event_receiver) = channel(64);
@@ -378,8 +863,11 @@ impl Actor for DataReaderActor {
             qos,
             effects: Effects::default(),
             timer,
+            discovery,
+            subscriber,
             input_wires: Default::default(),
             output_wires: Default::default(),
+            intra_process_writers: Default::default(),
             data_availability_notifier,
             _event_receiver: Some(event_receiver),
             event_sender,
@@ -1,7 +1,17 @@
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum InstanceStateKind {
     #[default]
     Alive,
     NotAliveDisposed,
     NotAliveNoWriters,
 }
+
+impl From<troc_core::InstanceStateKind> for InstanceStateKind {
+    fn from(value: troc_core::InstanceStateKind) -> Self {
+        match value {
+            troc_core::InstanceStateKind::Alive => InstanceStateKind::Alive,
+            troc_core::InstanceStateKind::NotAliveDisposed => InstanceStateKind::NotAliveDisposed,
+            troc_core::InstanceStateKind::NotAliveNoWriters => InstanceStateKind::NotAliveNoWriters,
+        }
+    }
+}
@@ -7,21 +7,43 @@ mod sample_state_kind;
 mod subscriber;
 mod view_state_kind;
 
+pub use condition::ReadCondition;
 pub use data_sample::DataSample;
+#[cfg(test)]
+pub(crate) use datareader::DataReaderActorOutputWiresMessage;
 pub use datareader::{
     DataReader, DataReaderActor, DataReaderActorCreateObject, DataReaderActorMessage,
+    DataReaderActorQosMessage, DataReaderGroupCoherentSetComplete,
 };
+pub use instance_state_kind::InstanceStateKind;
+pub use sample_info::SampleInfo;
+pub use sample_state_kind::SampleStateKind;
 pub use subscriber::{
-    Subscriber, SubscriberActor, SubscriberActorCreateObject, SubscriberActorMessage,
+    Subscriber, SubscriberActor, SubscriberActorContainsEntitiesMessage,
+    SubscriberActorCreateObject, SubscriberActorDeleteMessage,
+    SubscriberActorGroupCoherentSetMessage,
 };
+pub use troc_core::{InstanceStateMask, SampleStateMask, ViewStateMask};
+pub use view_state_kind::ViewStateKind;
 
-use troc_core::DurationKind;
-use troc_core::{DdsError, WriterProxy};
+use troc_core::{
+    DdsError, DeadlineMissedStatus, LivelinessChangedStatus, SampleLostStatus,
+    SampleRejectedStatusKind, WriterProxy,
+};
+use troc_core::{DurationKind, Guid};
 
 #[derive(Debug, Clone)]
 pub enum DataReaderEvent {
     PublicationMatched(WriterProxy),
-    PublicationStopped,
+    /// The remote Writer `Guid` is no longer matched, its QoS or the local one changed
+    PublicationStopped(Guid),
+    RequestedDeadlineMissed(DeadlineMissedStatus),
+    LivelinessChanged(LivelinessChangedStatus),
+    /// A received sample could not be kept because of the RESOURCE_LIMITS
+    SampleRejected {
+        reason: SampleRejectedStatusKind,
+    },
+    SampleLost(SampleLostStatus),
 }
 
 #[derive()]
@@ -68,6 +90,121 @@ impl DataReaderListener {
             fut.await
         }
     }
+
+    pub async fn wait_publication_stopped(
+        &mut self,
+        duration: DurationKind,
+    ) -> Result<Guid, DdsError> {
+        let fut = async move {
+            loop {
+                if let DataReaderEvent::PublicationStopped(guid) = self.wait_event().await? {
+                    break Ok(guid);
+                }
+            }
+        };
+
+        if let DurationKind::Finite(duration) = duration {
+            tokio::time::timeout(duration, fut)
+                .await
+                .map_err(|e| DdsError::Timeout {
+                    cause: e.to_string(),
+                })?
+        } else {
+            fut.await
+        }
+    }
+
+    pub async fn wait_requested_deadline_missed(
+        &mut self,
+        duration: DurationKind,
+    ) -> Result<DeadlineMissedStatus, DdsError> {
+        let fut = async move {
+            loop {
+                if let DataReaderEvent::RequestedDeadlineMissed(status) = self.wait_event().await? {
+                    break Ok(status);
+                }
+            }
+        };
+
+        if let DurationKind::Finite(duration) = duration {
+            tokio::time::timeout(duration, fut)
+                .await
+                .map_err(|e| DdsError::Timeout {
+                    cause: e.to_string(),
+                })?
+        } else {
+            fut.await
+        }
+    }
+
+    pub async fn wait_liveliness_changed(
+        &mut self,
+        duration: DurationKind,
+    ) -> Result<LivelinessChangedStatus, DdsError> {
+        let fut = async move {
+            loop {
+                if let DataReaderEvent::LivelinessChanged(status) = self.wait_event().await? {
+                    break Ok(status);
+                }
+            }
+        };
+
+        if let DurationKind::Finite(duration) = duration {
+            tokio::time::timeout(duration, fut)
+                .await
+                .map_err(|e| DdsError::Timeout {
+                    cause: e.to_string(),
+                })?
+        } else {
+            fut.await
+        }
+    }
+
+    pub async fn wait_sample_rejected(
+        &mut self,
+        duration: DurationKind,
+    ) -> Result<SampleRejectedStatusKind, DdsError> {
+        let fut = async move {
+            loop {
+                if let DataReaderEvent::SampleRejected { reason } = self.wait_event().await? {
+                    break Ok(reason);
+                }
+            }
+        };
+
+        if let DurationKind::Finite(duration) = duration {
+            tokio::time::timeout(duration, fut)
+                .await
+                .map_err(|e| DdsError::Timeout {
+                    cause: e.to_string(),
+                })?
+        } else {
+            fut.await
+        }
+    }
+
+    pub async fn wait_sample_lost(
+        &mut self,
+        duration: DurationKind,
+    ) -> Result<SampleLostStatus, DdsError> {
+        let fut = async move {
+            loop {
+                if let DataReaderEvent::SampleLost(status) = self.wait_event().await? {
+                    break Ok(status);
+                }
+            }
+        };
+
+        if let DurationKind::Finite(duration) = duration {
+            tokio::time::timeout(duration, fut)
+                .await
+                .map_err(|e| DdsError::Timeout {
+                    cause: e.to_string(),
+                })?
+        } else {
+            fut.await
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
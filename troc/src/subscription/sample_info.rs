@@ -1,4 +1,4 @@
-use troc_core::{CacheChange, CacheChangeInfos};
+use troc_core::{CacheChangeContainer, CacheChangeInfos, ChangeKind};
 use troc_core::{InstanceHandle, Timestamp};
 
 use crate::subscription::sample_state_kind::SampleStateKind;
@@ -23,23 +23,23 @@ pub struct SampleInfo {
 
 impl From<&CacheChangeInfos> for SampleInfo {
     fn from(change: &CacheChangeInfos) -> Self {
-        let valid_data = !change.sample_size.eq(&0);
-        let mut source_timestamp = Default::default();
+        let is_alive = matches!(change.kind, ChangeKind::Alive | ChangeKind::AliveFiltered);
+        let valid_data = is_alive && !change.sample_size.eq(&0);
+        let source_timestamp = change.emission_timestamp.unwrap_or_default();
         let sample_state = Default::default();
         let view_state = Default::default();
         let instance_handle = change.instance_handle;
-        let instance_state = Default::default();
+        let instance_state = match change.kind {
+            ChangeKind::Alive | ChangeKind::AliveFiltered => InstanceStateKind::Alive,
+            ChangeKind::NotAliveDisposed => InstanceStateKind::NotAliveDisposed,
+            ChangeKind::NotAliveUnregistered => InstanceStateKind::NotAliveNoWriters,
+        };
         let disposed_generation_count = Default::default();
         let no_writers_generation_cont = Default::default();
         let absolute_generation_rank = Default::default();
         let sample_rank = Default::default();
         let generation_rank = Default::default();
-        let publication_handle = instance_handle;
-
-        if let Some(qos) = &change.inline_qos {
-            // source_timestamp = qos.get_timestamp();
-            // TODO: get other values from QoS: StatusInfo (must be implemented in RTPS)
-        }
+        let publication_handle = InstanceHandle::new(change.writer_guid.as_bytes());
 
         SampleInfo {
             valid_data,
@@ -57,3 +57,20 @@ impl From<&CacheChangeInfos> for SampleInfo {
         }
     }
 }
+
+impl From<&CacheChangeContainer> for SampleInfo {
+    fn from(container: &CacheChangeContainer) -> Self {
+        let generation = container.get_generation_infos();
+        SampleInfo {
+            sample_state: container.get_sample_state_kind().into(),
+            view_state: container.get_view_state_kind().into(),
+            instance_state: container.get_instance_state_kind().into(),
+            disposed_generation_count: generation.disposed_generation_count,
+            no_writers_generation_cont: generation.no_writers_generation_count,
+            absolute_generation_rank: generation.absolute_generation_rank,
+            sample_rank: generation.sample_rank,
+            generation_rank: generation.generation_rank,
+            ..SampleInfo::from(&container.infos)
+        }
+    }
+}
@@ -4,3 +4,12 @@ pub enum SampleStateKind {
     NotRead,
     Read,
 }
+
+impl From<troc_core::SampleStateKind> for SampleStateKind {
+    fn from(value: troc_core::SampleStateKind) -> Self {
+        match value {
+            troc_core::SampleStateKind::Read => SampleStateKind::Read,
+            _ => SampleStateKind::NotRead,
+        }
+    }
+}
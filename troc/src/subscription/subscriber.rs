@@ -1,27 +1,30 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use kameo::{
     Actor,
     actor::{ActorRef, Spawn},
+    error::SendError,
     prelude::Message,
 };
 use serde::Deserialize;
 use tokio::sync::Notify;
+use tracing::{Level, event};
 use troc_core::{
-    DdsError, DiscoveredReaderData, EntityId, EntityKey, Guid, GuidPrefix, InlineQos, Keyed,
-    LocatorList, ReaderBuilder, ReaderProxy, TopicKind,
+    DdsError, DiscoveredReaderData, EntityId, EntityKey, GroupCoherentSet, Guid, GuidPrefix,
+    InlineQos, Keyed, LocatorList, ReaderBuilder, ReaderProxy, TopicKind,
 };
 
 use crate::{
     discovery::{DiscoveryActor, DiscoveryActorMessage},
     domain::{Configuration, EntityIdentifierActor, EntityIdentifierActorAskMessage},
-    infrastructure::QosPolicy,
+    infrastructure::{QosPolicy, QosProfiles},
     subscription::{
         DataReader, DataReaderActor, DataReaderActorCreateObject, DataReaderActorMessage,
+        DataReaderActorQosMessage, DataReaderGroupCoherentSetComplete,
     },
     time::TimerActor,
     topic::Topic,
-    wires::{ReceiverWireFactoryActorMessage, WireFactoryActor},
+    wires::{ReceiverWireFactoryActorMessage, WireFactoryActor, wire_factory_error},
 };
 
 #[derive(Clone)]
@@ -32,6 +35,8 @@ pub struct Subscriber {
     wire_factory: ActorRef<WireFactoryActor>,
     entity_identifier: ActorRef<EntityIdentifierActor>,
     timer: ActorRef<TimerActor>,
+    discovery: ActorRef<DiscoveryActor>,
+    qos_profiles: Arc<QosProfiles>,
 }
 
 impl Subscriber {
@@ -45,6 +50,8 @@ impl Subscriber {
         wire_factory: ActorRef<WireFactoryActor>,
         entity_identifier: ActorRef<EntityIdentifierActor>,
         timer: ActorRef<TimerActor>,
+        discovery: ActorRef<DiscoveryActor>,
+        qos_profiles: Arc<QosProfiles>,
     ) -> Self {
         Self {
             guid,
@@ -53,6 +60,8 @@ impl Subscriber {
             wire_factory,
             entity_identifier,
             timer,
+            discovery,
+            qos_profiles,
         }
     }
 
@@ -64,6 +73,21 @@ impl Subscriber {
         self.guid.get_entity_id()
     }
 
+    /// Create a DataReader with the QoS of the profile `"library::profile"` applied to the name of `topic`
+    pub async fn create_datareader_with_profile<T>(
+        &mut self,
+        topic: &Topic<T>,
+        profile: &str,
+    ) -> Result<DataReader<T>, DdsError>
+    where
+        for<'a> T: Deserialize<'a> + Keyed + 'static,
+    {
+        let qos = self
+            .qos_profiles
+            .get_topic_qos(profile, topic.topic_name())?;
+        self.create_datareader(topic, &qos).await
+    }
+
     pub async fn create_datareader<T>(
         &mut self,
         topic: &Topic<T>,
@@ -72,11 +96,18 @@ impl Subscriber {
     where
         for<'a> T: Deserialize<'a> + Keyed + 'static,
     {
+        if !qos.is_consistent() {
+            return Err(DdsError::InconsistentPolicy);
+        }
+
         let writer_key: EntityKey = self
             .entity_identifier
             .ask(EntityIdentifierActorAskMessage::AskReaderId)
             .await
-            .unwrap()
+            .map_err(|e| DdsError::Actor {
+                actor: "EntityIdentifier",
+                reason: e.to_string(),
+            })?
             .into();
 
         let writer_id = if matches!(topic.topic_kind, TopicKind::WithKey) {
@@ -87,15 +118,20 @@ impl Subscriber {
         let reader_guid = Guid::new(self.guid.get_guid_prefix(), writer_id);
 
         let reliable = qos.reliability().into();
-        let mut inline_qos: InlineQos = (*qos).into();
+        let mut inline_qos: InlineQos = qos.clone().into();
         inline_qos.topic_name = topic.topic_name.clone();
         inline_qos.type_name = topic.type_name.clone();
+        inline_qos.topic_data = topic.qos.topic_data().clone();
+        // the PARTITION, the PRESENTATION and the GROUP_DATA are policies of the enclosing group
+        inline_qos.partition = self.qos.partition().clone();
+        inline_qos.presentation = self.qos.presentation();
+        inline_qos.group_data = self.qos.group_data().clone();
 
         let (input_wires, locators) = self
             .wire_factory
             .ask(ReceiverWireFactoryActorMessage::Applicative)
             .await
-            .unwrap();
+            .map_err(wire_factory_error)?;
 
         let reader = ReaderBuilder::new(reader_guid, inline_qos.clone())
             .reliability(reliable)
@@ -109,11 +145,13 @@ impl Subscriber {
             qos: inline_qos.clone(),
             data_availability_notifier: data_availability_notifier.clone(),
             timer: self.timer.clone(),
+            discovery: self.discovery.clone(),
+            subscriber: self.subscriber_actor.downgrade(),
         });
 
         let datareader = DataReader::new(
             reader_guid,
-            *qos,
+            qos.clone(),
             reader_actor.clone(),
             data_availability_notifier,
         )
@@ -125,7 +163,10 @@ impl Subscriber {
                 locators,
             })
             .await
-            .unwrap();
+            .map_err(|e| DdsError::Actor {
+                actor: "DataReader",
+                reason: e.to_string(),
+            })?;
 
         self.subscriber_actor
             .ask(SubscriberActorMessage {
@@ -134,10 +175,95 @@ impl Subscriber {
                 readers: reader_actor,
             })
             .await
-            .unwrap();
+            .map_err(|e| DdsError::Actor {
+                actor: "Subscriber",
+                reason: e.to_string(),
+            })?;
 
         Ok(datareader)
     }
+
+    /// Delete `datareader`, its disposal is announced so that the matched remote DataWriters stop matching it
+    ///
+    /// Fail with [`DdsError::PreconditionNotMet`] if `datareader` does not belong to this Subscriber
+    pub async fn delete_datareader<T>(
+        &mut self,
+        datareader: &DataReader<T>,
+    ) -> Result<(), DdsError> {
+        self.subscriber_actor
+            .ask(SubscriberActorDeleteMessage::Reader(
+                datareader.get_guid().get_entity_id(),
+            ))
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Actor {
+                    actor: "Subscriber",
+                    reason: e.to_string(),
+                },
+            })
+    }
+
+    /// Delete every DataReader of this Subscriber
+    pub async fn delete_contained_entities(&mut self) -> Result<(), DdsError> {
+        self.subscriber_actor
+            .ask(SubscriberActorDeleteMessage::All)
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Actor {
+                    actor: "Subscriber",
+                    reason: e.to_string(),
+                },
+            })
+    }
+
+    pub(crate) fn actor(&self) -> &ActorRef<SubscriberActor> {
+        &self.subscriber_actor
+    }
+
+    /// Change the QoS of this Subscriber, the new PARTITION and GROUP_DATA are applied to its DataReaders and announced to the remote Participants
+    ///
+    /// Fail with [`DdsError::ImmutablePolicy`] if the PRESENTATION differs
+    pub async fn set_qos(&mut self, qos: &QosPolicy) -> Result<(), DdsError> {
+        if qos.presentation() != self.qos.presentation() {
+            return Err(DdsError::ImmutablePolicy);
+        }
+
+        self.subscriber_actor
+            .ask(SubscriberActorQosMessage(qos.clone()))
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Error(e.to_string()),
+            })?;
+        self.qos = qos.clone();
+        Ok(())
+    }
+
+    /// Indicate the application is about to access the samples of the DataReaders of this Subscriber
+    ///
+    /// Until [`Subscriber::end_access`], the coherent sets completed meanwhile are held back so that the accessed samples stay consistent
+    pub async fn begin_access(&self) -> Result<(), DdsError> {
+        self.subscriber_actor
+            .ask(SubscriberActorAccessMessage::Begin)
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Error(e.to_string()),
+            })
+    }
+
+    /// Indicate the application is done accessing the samples, the coherent sets held back become available
+    pub async fn end_access(&self) -> Result<(), DdsError> {
+        self.subscriber_actor
+            .ask(SubscriberActorAccessMessage::End)
+            .await
+            .map_err(|e| match e {
+                SendError::HandlerError(e) => e,
+                e => DdsError::Error(e.to_string()),
+            })
+    }
 }
 
 #[derive(Debug)]
@@ -155,7 +281,16 @@ impl Message<SubscriberActorMessage> for SubscriberActor {
         msg: SubscriberActorMessage,
         _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
     ) -> Self::Reply {
-        self.readers.push(msg.readers.clone());
+        if self.is_accessed {
+            msg.readers
+                .ask(DataReaderActorMessage::BeginAccess)
+                .await
+                .unwrap();
+        }
+        self.readers.insert(
+            msg.proxy.get_remote_reader_guid().get_entity_id(),
+            msg.readers.clone(),
+        );
         let disc_reader_data = DiscoveredReaderData {
             proxy: msg.proxy,
             params: msg.qos,
@@ -170,6 +305,144 @@ impl Message<SubscriberActorMessage> for SubscriberActor {
     }
 }
 
+#[derive(Debug)]
+pub enum SubscriberActorAccessMessage {
+    Begin,
+    End,
+}
+
+impl Message<SubscriberActorAccessMessage> for SubscriberActor {
+    type Reply = Result<(), DdsError>;
+
+    async fn handle(
+        &mut self,
+        msg: SubscriberActorAccessMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        let begin = matches!(msg, SubscriberActorAccessMessage::Begin);
+        if self.is_accessed == begin {
+            return Err(DdsError::PreconditionNotMet);
+        }
+        self.is_accessed = begin;
+
+        for reader in self.readers.values() {
+            let message = if begin {
+                DataReaderActorMessage::BeginAccess
+            } else {
+                DataReaderActorMessage::EndAccess
+            };
+            reader.ask(message).await.unwrap();
+        }
+        Ok(())
+    }
+}
+
+/// A DataReader holds the coherent sets of a group coherent set, they are made available once every DataReader does
+#[derive(Debug)]
+pub struct SubscriberActorGroupCoherentSetMessage(pub GroupCoherentSet);
+
+impl Message<SubscriberActorGroupCoherentSetMessage> for SubscriberActor {
+    type Reply = ();
+
+    async fn handle(
+        &mut self,
+        msg: SubscriberActorGroupCoherentSetMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        // the last DataReader to complete the group coherent set commits it, the earlier ones find it incomplete
+        for reader in self.readers.values() {
+            let res = reader
+                .ask(DataReaderGroupCoherentSetComplete(msg.0.clone()))
+                .await;
+            match res {
+                Ok(true) => (),
+                Ok(false) => return,
+                Err(e) => {
+                    event!(Level::DEBUG, error = %e, "group coherent set not checked");
+                    return;
+                }
+            }
+        }
+        for reader in self.readers.values() {
+            let res = reader
+                .tell(DataReaderActorMessage::CommitGroupCoherentSet(
+                    msg.0.clone(),
+                ))
+                .await;
+            if let Err(e) = res {
+                event!(Level::DEBUG, error = %e, "group coherent set not committed");
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SubscriberActorQosMessage(QosPolicy);
+
+impl Message<SubscriberActorQosMessage> for SubscriberActor {
+    type Reply = Result<(), DdsError>;
+
+    async fn handle(
+        &mut self,
+        msg: SubscriberActorQosMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        for reader in self.readers.values() {
+            reader
+                .ask(DataReaderActorQosMessage::Group(msg.0.clone()))
+                .await
+                .map_err(|e| match e {
+                    SendError::HandlerError(e) => e,
+                    e => DdsError::Error(e.to_string()),
+                })?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum SubscriberActorDeleteMessage {
+    Reader(EntityId),
+    All,
+}
+
+impl Message<SubscriberActorDeleteMessage> for SubscriberActor {
+    type Reply = Result<(), DdsError>;
+
+    async fn handle(
+        &mut self,
+        msg: SubscriberActorDeleteMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        match msg {
+            SubscriberActorDeleteMessage::Reader(entity_id) => self.delete_reader(entity_id).await,
+            SubscriberActorDeleteMessage::All => {
+                let entity_ids = self.readers.keys().copied().collect::<Vec<_>>();
+                for entity_id in entity_ids {
+                    self.delete_reader(entity_id).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Whether the Subscriber still contains DataReaders
+#[derive(Debug)]
+pub struct SubscriberActorContainsEntitiesMessage;
+
+impl Message<SubscriberActorContainsEntitiesMessage> for SubscriberActor {
+    type Reply = bool;
+
+    async fn handle(
+        &mut self,
+        _msg: SubscriberActorContainsEntitiesMessage,
+        _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        !self.readers.is_empty()
+    }
+}
+
 #[derive(Debug)]
 pub struct SubscriberActorCreateObject {
     pub discovery: ActorRef<DiscoveryActor>,
@@ -177,8 +450,34 @@ pub struct SubscriberActorCreateObject {
 
 #[derive(Debug)]
 pub struct SubscriberActor {
-    readers: Vec<ActorRef<DataReaderActor>>,
+    readers: HashMap<EntityId, ActorRef<DataReaderActor>>,
     discovery: ActorRef<DiscoveryActor>,
+    is_accessed: bool,
+}
+
+impl SubscriberActor {
+    /// The discovery forgets the DataReader before it stops, no match can reach a stopped actor
+    async fn delete_reader(&mut self, entity_id: EntityId) -> Result<(), DdsError> {
+        let Some(reader) = self.readers.remove(&entity_id) else {
+            return Err(DdsError::PreconditionNotMet);
+        };
+        self.discovery
+            .ask(DiscoveryActorMessage::ReaderRemoved(entity_id))
+            .await
+            .map_err(|e| DdsError::Actor {
+                actor: "Discovery",
+                reason: e.to_string(),
+            })?;
+        reader
+            .stop_gracefully()
+            .await
+            .map_err(|e| DdsError::Actor {
+                actor: "DataReader",
+                reason: e.to_string(),
+            })?;
+        reader.wait_for_shutdown().await;
+        Ok(())
+    }
 }
 
 impl Actor for SubscriberActor {
@@ -193,6 +492,7 @@ impl Actor for SubscriberActor {
         let subscriber_actor = Self {
             readers: Default::default(),
             discovery: args.discovery,
+            is_accessed: false,
         };
 
         Ok(subscriber_actor)
@@ -203,7 +503,7 @@ impl Actor for SubscriberActor {
         _actor_ref: kameo::prelude::WeakActorRef<Self>,
         _reason: kameo::prelude::ActorStopReason,
     ) -> Result<(), Self::Error> {
-        for datawreader in self.readers.iter() {
+        for datawreader in self.readers.values() {
             datawreader.stop_gracefully().await.unwrap();
             datawreader.wait_for_shutdown().await;
         }
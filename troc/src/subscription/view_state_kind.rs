@@ -1,6 +1,15 @@
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum ViewStateKind {
     #[default]
     New,
     NotNew,
 }
+
+impl From<troc_core::ViewStateKind> for ViewStateKind {
+    fn from(value: troc_core::ViewStateKind) -> Self {
+        match value {
+            troc_core::ViewStateKind::New => ViewStateKind::New,
+            troc_core::ViewStateKind::NotNew => ViewStateKind::NotNew,
+        }
+    }
+}
@@ -16,10 +16,12 @@ pub enum TimerActorScheduleTickMessage {
     Writer {
         delay: i64,
         target: ActorRef<DataWriterActor>,
+        id: TickId,
     },
     Reader {
         delay: i64,
         target: ActorRef<DataReaderActor>,
+        id: TickId,
     },
     Discovery {
         delay: i64,
@@ -38,12 +40,13 @@ impl Message<TimerActorScheduleTickMessage> for TimerActor {
         _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
     ) -> Self::Reply {
         match msg {
-            TimerActorScheduleTickMessage::Writer { delay, target } => {
+            TimerActorScheduleTickMessage::Writer { delay, target, id } => {
                 tokio::spawn(async move {
                     event!(
                         Level::TRACE,
                         %delay,
                         ?target,
+                        ?id,
                         "TimerActorScheduleTickMessage::Writer received"
                     );
                     sleep(Duration::from_millis(delay as u64)).await;
@@ -53,15 +56,16 @@ impl Message<TimerActorScheduleTickMessage> for TimerActor {
                         ?target,
                         "TimerActorScheduleTickMessage::Writer delay reached"
                     );
-                    let _err = target.tell(DataWriterActorMessage::Tick).await;
+                    let _err = target.tell(DataWriterActorMessage::Tick(id)).await;
                 });
             }
-            TimerActorScheduleTickMessage::Reader { delay, target } => {
+            TimerActorScheduleTickMessage::Reader { delay, target, id } => {
                 tokio::spawn(async move {
                     event!(
                         Level::TRACE,
                         %delay,
                         ?target,
+                        ?id,
                         "TimerActorScheduleTickMessage::Reader received"
                     );
                     sleep(Duration::from_millis(delay as u64)).await;
@@ -71,7 +75,7 @@ impl Message<TimerActorScheduleTickMessage> for TimerActor {
                         ?target,
                         "TimerActorScheduleTickMessage::Reader delay reached"
                     );
-                    let _err = target.tell(DataReaderActorMessage::Tick).await;
+                    let _err = target.tell(DataReaderActorMessage::Tick(id)).await;
                 });
             }
             TimerActorScheduleTickMessage::Discovery { delay, target, id } => {
@@ -25,7 +25,7 @@ impl<T> Topic<T> {
         Self {
             topic_name,
             type_name,
-            qos: *qos,
+            qos: qos.clone(),
             topic_kind,
             _phantom: PhantomData,
         }
@@ -1,5 +1,6 @@
+use kameo::error::SendError;
 use thiserror::Error;
-use troc_core::{Guid, SequenceNumber};
+use troc_core::{DdsError, Locator};
 
 #[derive(Debug, Error, Default)]
 pub enum WireError {
@@ -9,7 +10,41 @@ pub enum WireError {
     SendError(String),
     #[error("{0}")]
     CreationError(#[from] std::io::Error),
+    #[error("cannot open a socket on {locator}: {source}")]
+    Open {
+        locator: Locator,
+        source: std::io::Error,
+    },
+    #[error("every port from {locator} is already in use")]
+    PortsExhausted { locator: Locator },
     #[default]
     #[error("UnkownError")]
     Unkown,
 }
+
+impl From<WireError> for DdsError {
+    fn from(value: WireError) -> Self {
+        match value {
+            WireError::Open { locator, source } => DdsError::Transport {
+                locator,
+                reason: source.to_string(),
+            },
+            WireError::PortsExhausted { locator } => DdsError::Transport {
+                locator,
+                reason: String::from("every port from this one is already in use"),
+            },
+            e => DdsError::Error(e.to_string()),
+        }
+    }
+}
+
+/// Error of a request to the `WireFactoryActor`
+pub(crate) fn wire_factory_error<M>(e: SendError<M, WireError>) -> DdsError {
+    match e {
+        SendError::HandlerError(e) => e.into(),
+        e => DdsError::Actor {
+            actor: "WireFactory",
+            reason: e.to_string(),
+        },
+    }
+}
@@ -1,11 +1,15 @@
 mod error;
+mod shm_wire;
+mod tcpv4_wire;
 mod udpv4_wire;
+mod udpv6_wire;
 mod wire;
 mod wire_factory;
 
 use async_trait::async_trait;
 use bytes::BytesMut;
 pub use error::WireError;
+pub(crate) use error::wire_factory_error;
 use troc_core::Locator;
 pub use wire::*;
 pub use wire_factory::{
@@ -31,7 +35,5 @@ pub trait Wired: Send + Sync {
     async fn recv(&mut self) -> Result<BytesMut, WireError>;
     async fn send(&mut self, msg: BytesMut) -> Result<(), WireError>;
     fn transmission_kind(&self) -> TransmissionKind;
-    fn transmission_direction(&self) -> TransmissionDirection;
     fn locator(&self) -> Locator;
-    fn duplicate(&self) -> Box<dyn Wired>;
 }
@@ -0,0 +1,580 @@
+use bytes::BytesMut;
+use memmap2::MmapMut;
+use tracing::{Level, event, instrument};
+
+use std::{
+    fs::{File, OpenOptions},
+    io::ErrorKind,
+    os::unix::fs::OpenOptionsExt,
+    path::PathBuf,
+    ptr::NonNull,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
+use troc_core::{GuidPrefix, Locator};
+
+use super::{TransmissionDirection, TransmissionKind, WireError, Wired};
+
+/// Directory of the segments, a tmpfs on Linux
+const SEGMENT_DIRECTORY: &str = "/dev/shm";
+const SEGMENT_MAGIC: u32 = u32::from_be_bytes(*b"TROC");
+/// The header is followed by the ring buffer of the messages
+const HEADER_SIZE: usize = 64;
+const MAGIC_OFFSET: usize = 0;
+const OWNER_OFFSET: usize = 4;
+const CAPACITY_OFFSET: usize = 8;
+/// Process id of the sender holding the segment, 0 if it is free
+const LOCK_OFFSET: usize = 16;
+const NOTIFY_OFFSET: usize = 20;
+const HEAD_OFFSET: usize = 24;
+const TAIL_OFFSET: usize = 32;
+/// GUID prefix of the participant which created the segment
+const OWNER_PREFIX_OFFSET: usize = 40;
+/// Each message is preceded by its length
+const LENGTH_SIZE: u64 = 4;
+/// Period at which an idle listener checks whether its wire was dropped
+const LISTENER_WAKE_PERIOD: Duration = Duration::from_millis(100);
+/// Time a sender waits for another sender to release the segment
+const LOCK_TIMEOUT: Duration = Duration::from_millis(10);
+
+/// The address of a SHM locator is the identifier of the host followed by the GUID prefix of the participant
+const HOST_ID_SIZE: usize = 4;
+
+/// Address of the SHM locators of the participant `guid_prefix`
+///
+/// The host is identified by its machine id, or its host name if there is none. Cloned hosts share it, the GUID
+/// prefix then names a segment they don't share: the segment is not found and another transport is used
+pub(crate) fn shm_address(guid_prefix: GuidPrefix) -> [u8; 16] {
+    let id = ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .into_iter()
+        .find_map(|path| std::fs::read_to_string(path).ok())
+        .or_else(|| std::fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .unwrap_or_default();
+    let mut address = [0; 16];
+    address[..HOST_ID_SIZE].copy_from_slice(&md5::compute(id.trim()).0[..HOST_ID_SIZE]);
+    address[HOST_ID_SIZE..].copy_from_slice(&guid_prefix.0);
+    address
+}
+
+/// Whether the SHM locator `locator` may belong to the host of the SHM address `address`
+pub(crate) fn is_same_host(locator: &Locator, address: &[u8; 16]) -> bool {
+    locator.address[..HOST_ID_SIZE] == address[..HOST_ID_SIZE]
+}
+
+fn owner_prefix(locator: &Locator) -> [u8; 12] {
+    let mut prefix = [0; 12];
+    prefix.copy_from_slice(&locator.address[HOST_ID_SIZE..]);
+    prefix
+}
+
+fn segment_path(locator: &Locator) -> PathBuf {
+    let prefix = owner_prefix(locator)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    PathBuf::from(SEGMENT_DIRECTORY).join(format!("troc-{prefix}-{}", locator.port))
+}
+
+/// A memory mapped ring buffer, written by any process of the host and read by its creator only
+///
+/// The senders serialize their writes with a lock stored in the header, then bump a futex word the reader waits on.
+/// The lock holds the process id of its sender, the lock of a sender which died holding it is taken over
+struct Segment {
+    _mmap: MmapMut,
+    base: NonNull<u8>,
+    capacity: u64,
+}
+
+// SAFETY: the shared state of the mapping is only accessed through atomics or under the segment lock
+unsafe impl Send for Segment {}
+unsafe impl Sync for Segment {}
+
+impl Segment {
+    fn create(path: &PathBuf, capacity: u64, owner_prefix: [u8; 12]) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)?;
+        file.set_len(HEADER_SIZE as u64 + capacity)?;
+        let segment = Self::map(&file, capacity)?;
+        segment
+            .atomic_u32(OWNER_OFFSET)
+            .store(std::process::id(), Ordering::Relaxed);
+        segment
+            .atomic_u64(CAPACITY_OFFSET)
+            .store(capacity, Ordering::Relaxed);
+        // SAFETY: the field is in the header, the segment is not published until its magic is stored
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                owner_prefix.as_ptr(),
+                segment.base.as_ptr().add(OWNER_PREFIX_OFFSET),
+                owner_prefix.len(),
+            );
+        }
+        segment
+            .atomic_u32(MAGIC_OFFSET)
+            .store(SEGMENT_MAGIC, Ordering::Release);
+        Ok(segment)
+    }
+
+    /// Open the segment at `path`, created by the participant `owner_prefix`
+    fn open(path: &PathBuf, owner_prefix: [u8; 12]) -> std::io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        let size = file.metadata()?.len();
+        let capacity = size.checked_sub(HEADER_SIZE as u64).ok_or_else(|| {
+            std::io::Error::new(ErrorKind::InvalidData, "the segment is too small")
+        })?;
+        let segment = Self::map(&file, capacity)?;
+        let valid = segment.atomic_u32(MAGIC_OFFSET).load(Ordering::Acquire) == SEGMENT_MAGIC
+            && segment.atomic_u64(CAPACITY_OFFSET).load(Ordering::Relaxed) == capacity;
+        if !valid {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                "the segment is not initialized",
+            ));
+        }
+        if segment.owner_prefix() != owner_prefix {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                "the segment belongs to another participant",
+            ));
+        }
+        Ok(segment)
+    }
+
+    /// Whether the process which created the segment at `path` is gone
+    fn is_stale(path: &PathBuf, owner_prefix: [u8; 12]) -> bool {
+        let Ok(segment) = Self::open(path, owner_prefix) else {
+            return false;
+        };
+        !is_alive(segment.atomic_u32(OWNER_OFFSET).load(Ordering::Relaxed))
+    }
+
+    fn map(file: &File, capacity: u64) -> std::io::Result<Self> {
+        // SAFETY: the file is a segment of troc, its other users only access it through this type
+        let mut mmap = unsafe { MmapMut::map_mut(file)? };
+        let base = NonNull::new(mmap.as_mut_ptr()).expect("a mapping is never null");
+        Ok(Self {
+            _mmap: mmap,
+            base,
+            capacity,
+        })
+    }
+
+    fn atomic_u32(&self, offset: usize) -> &AtomicU32 {
+        // SAFETY: the offset is a 4-bytes aligned field of the header, in the page aligned mapping
+        unsafe { &*(self.base.as_ptr().add(offset) as *const AtomicU32) }
+    }
+
+    fn atomic_u64(&self, offset: usize) -> &AtomicU64 {
+        // SAFETY: the offset is a 8-bytes aligned field of the header, in the page aligned mapping
+        unsafe { &*(self.base.as_ptr().add(offset) as *const AtomicU64) }
+    }
+
+    fn owner_prefix(&self) -> [u8; 12] {
+        let mut prefix = [0; 12];
+        // SAFETY: the field is in the header, written once before the segment is published
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                self.base.as_ptr().add(OWNER_PREFIX_OFFSET),
+                prefix.as_mut_ptr(),
+                prefix.len(),
+            );
+        }
+        prefix
+    }
+
+    fn notify_word(&self) -> &AtomicU32 {
+        self.atomic_u32(NOTIFY_OFFSET)
+    }
+
+    /// Copy `bytes` in the ring at the absolute position `position`
+    fn write_at(&self, position: u64, bytes: &[u8]) {
+        let start = (position % self.capacity) as usize;
+        let first = bytes.len().min(self.capacity as usize - start);
+        // SAFETY: the ring range from `position` is free and reserved by the segment lock
+        unsafe {
+            let data = self.base.as_ptr().add(HEADER_SIZE);
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), data.add(start), first);
+            std::ptr::copy_nonoverlapping(bytes[first..].as_ptr(), data, bytes.len() - first);
+        }
+    }
+
+    /// Copy the bytes of the ring at the absolute position `position` into `bytes`
+    fn read_at(&self, position: u64, bytes: &mut [u8]) {
+        let start = (position % self.capacity) as usize;
+        let first = bytes.len().min(self.capacity as usize - start);
+        // SAFETY: the ring range from `position` was published by a sender and only the reader frees it
+        unsafe {
+            let data = self.base.as_ptr().add(HEADER_SIZE);
+            std::ptr::copy_nonoverlapping(data.add(start), bytes.as_mut_ptr(), first);
+            std::ptr::copy_nonoverlapping(data, bytes[first..].as_mut_ptr(), bytes.len() - first);
+        }
+    }
+
+    /// Append a message, fail if the reader is too late to make room for it
+    fn push(&self, msg: &[u8]) -> Result<(), WireError> {
+        let size = LENGTH_SIZE + msg.len() as u64;
+        if size > self.capacity {
+            return Err(WireError::SendError(format!(
+                "a message of {} bytes exceeds the segment",
+                msg.len()
+            )));
+        }
+
+        self.lock()?;
+        let head = self.atomic_u64(HEAD_OFFSET).load(Ordering::Acquire);
+        let tail = self.atomic_u64(TAIL_OFFSET).load(Ordering::Relaxed);
+        let free = tail
+            .checked_sub(head)
+            .and_then(|published| self.capacity.checked_sub(published));
+        let res = match free {
+            None => {
+                event!(Level::WARN, head, tail, "corrupted segment");
+                Err(WireError::SendError(String::from(
+                    "the segment is corrupted, its published range exceeds its capacity",
+                )))
+            }
+            Some(free) if free < size => {
+                Err(WireError::SendError(String::from("the segment is full")))
+            }
+            Some(_) => {
+                self.write_at(tail, &(msg.len() as u32).to_le_bytes());
+                self.write_at(tail + LENGTH_SIZE, msg);
+                self.atomic_u64(TAIL_OFFSET)
+                    .store(tail + size, Ordering::Release);
+                Ok(())
+            }
+        };
+        self.unlock();
+
+        if res.is_ok() {
+            self.notify_word().fetch_add(1, Ordering::Release);
+            futex_wake(self.notify_word());
+        }
+        res
+    }
+
+    /// Take the oldest message, if any
+    ///
+    /// Any process of the host can write the segment: a message which doesn't fit in the published range means
+    /// the segment is corrupted, everything published so far is then dropped
+    fn pop(&self) -> Option<BytesMut> {
+        let head = self.atomic_u64(HEAD_OFFSET).load(Ordering::Relaxed);
+        let tail = self.atomic_u64(TAIL_OFFSET).load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        let published = tail
+            .checked_sub(head)
+            .filter(|published| *published >= LENGTH_SIZE && *published <= self.capacity);
+        let length = published.and_then(|published| {
+            let mut length = [0u8; LENGTH_SIZE as usize];
+            self.read_at(head, &mut length);
+            let length = u64::from(u32::from_le_bytes(length));
+            (LENGTH_SIZE + length <= published).then_some(length)
+        });
+        let Some(length) = length else {
+            event!(
+                Level::WARN,
+                head,
+                tail,
+                "corrupted segment, its messages are dropped"
+            );
+            self.atomic_u64(HEAD_OFFSET).store(tail, Ordering::Release);
+            return None;
+        };
+        let mut msg = BytesMut::zeroed(length as usize);
+        self.read_at(head + LENGTH_SIZE, &mut msg);
+        self.atomic_u64(HEAD_OFFSET)
+            .store(head + LENGTH_SIZE + length, Ordering::Release);
+        Some(msg)
+    }
+
+    fn lock(&self) -> Result<(), WireError> {
+        let lock = self.atomic_u32(LOCK_OFFSET);
+        let pid = std::process::id();
+        let start = Instant::now();
+        while let Err(holder) =
+            lock.compare_exchange_weak(0, pid, Ordering::Acquire, Ordering::Relaxed)
+        {
+            // a sender died between its lock and unlock, its message was not published
+            if holder != 0
+                && !is_alive(holder)
+                && lock
+                    .compare_exchange(holder, pid, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                event!(Level::DEBUG, holder, "lock of a dead sender taken over");
+                break;
+            }
+            if start.elapsed() > LOCK_TIMEOUT {
+                return Err(WireError::SendError(String::from(
+                    "the segment is locked by another sender",
+                )));
+            }
+            std::thread::yield_now();
+        }
+        Ok(())
+    }
+
+    fn unlock(&self) {
+        self.atomic_u32(LOCK_OFFSET).store(0, Ordering::Release);
+    }
+}
+
+/// Whether the process `pid` of this host is running
+fn is_alive(pid: u32) -> bool {
+    PathBuf::from(format!("/proc/{pid}")).exists()
+}
+
+#[cfg(target_os = "linux")]
+fn futex_wait(word: &AtomicU32, expected: u32, timeout: Duration) {
+    let timeout = libc::timespec {
+        tv_sec: timeout.as_secs() as libc::time_t,
+        tv_nsec: timeout.subsec_nanos() as libc::c_long,
+    };
+    // SAFETY: the futex word lives in the mapping for the duration of the call,
+    // the operation is not private as the word is shared between processes
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            word.as_ptr(),
+            libc::FUTEX_WAIT,
+            expected,
+            &timeout as *const libc::timespec,
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn futex_wake(word: &AtomicU32) {
+    // SAFETY: see `futex_wait`
+    unsafe {
+        libc::syscall(libc::SYS_futex, word.as_ptr(), libc::FUTEX_WAKE, i32::MAX);
+    }
+}
+
+/// Without futex, the listener polls the segment
+#[cfg(not(target_os = "linux"))]
+fn futex_wait(word: &AtomicU32, expected: u32, _timeout: Duration) {
+    if word.load(Ordering::Acquire) == expected {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn futex_wake(_word: &AtomicU32) {}
+
+/// A wire exchanging messages through a shared-memory segment with the processes of the same host
+pub(crate) struct ShmWire {
+    segment: Arc<Segment>,
+    locator: Locator,
+    direction: TransmissionDirection,
+    /// Messages taken from the segment by the listener thread
+    receiver: Option<UnboundedReceiver<BytesMut>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl ShmWire {
+    /// Create the segment of the SHM locator `locator`, a stale segment left by a dead process is replaced
+    pub fn new_listener(locator: &Locator, capacity: u64) -> Result<Self, WireError> {
+        let open = |source| WireError::Open {
+            locator: *locator,
+            source,
+        };
+        let path = segment_path(locator);
+        let owner = owner_prefix(locator);
+        let segment = match Segment::create(&path, capacity, owner) {
+            Err(e) if e.kind() == ErrorKind::AlreadyExists && Segment::is_stale(&path, owner) => {
+                event!(Level::DEBUG, path = %path.display(), "stale segment replaced");
+                std::fs::remove_file(&path).map_err(open)?;
+                Segment::create(&path, capacity, owner)
+            }
+            res => res,
+        }
+        .map_err(open)?;
+        let segment = Arc::new(segment);
+
+        let (sender, receiver) = unbounded_channel();
+        let stopped = Arc::new(AtomicBool::new(false));
+        {
+            let segment = segment.clone();
+            let stopped = stopped.clone();
+            std::thread::Builder::new()
+                .name(format!("troc-shm-{}", locator.port))
+                .spawn(move || {
+                    while !stopped.load(Ordering::Acquire) {
+                        let notified = segment.notify_word().load(Ordering::Acquire);
+                        while let Some(msg) = segment.pop() {
+                            if sender.send(msg).is_err() {
+                                return;
+                            }
+                        }
+                        futex_wait(segment.notify_word(), notified, LISTENER_WAKE_PERIOD);
+                    }
+                })
+                .map_err(open)?;
+        }
+
+        Ok(Self {
+            segment,
+            locator: *locator,
+            direction: TransmissionDirection::Listener,
+            receiver: Some(receiver),
+            stopped,
+        })
+    }
+
+    /// Open the segment of the SHM locator `locator`, created by a listener of this host
+    pub fn new_sender(locator: &Locator) -> Result<Self, WireError> {
+        let segment =
+            Segment::open(&segment_path(locator), owner_prefix(locator)).map_err(|source| {
+                WireError::Open {
+                    locator: *locator,
+                    source,
+                }
+            })?;
+        Ok(Self {
+            segment: Arc::new(segment),
+            locator: *locator,
+            direction: TransmissionDirection::Sender,
+            receiver: None,
+            stopped: Arc::new(AtomicBool::new(false)),
+        })
+    }
+}
+
+impl Drop for ShmWire {
+    fn drop(&mut self) {
+        if matches!(self.direction, TransmissionDirection::Listener) {
+            self.stopped.store(true, Ordering::Release);
+            self.segment.notify_word().fetch_add(1, Ordering::Release);
+            futex_wake(self.segment.notify_word());
+            let _res = std::fs::remove_file(segment_path(&self.locator));
+        }
+    }
+}
+
+#[async_trait]
+impl Wired for ShmWire {
+    #[instrument(level = "TRACE", skip_all)]
+    async fn recv(&mut self) -> Result<BytesMut, WireError> {
+        let Some(receiver) = self.receiver.as_mut() else {
+            return std::future::pending().await;
+        };
+        receiver
+            .recv()
+            .await
+            .ok_or_else(|| WireError::ReceptionError(String::from("the listener thread stopped")))
+    }
+
+    #[instrument(level = "TRACE", skip_all)]
+    async fn send(&mut self, msg: BytesMut) -> Result<(), WireError> {
+        self.segment.push(&msg)
+    }
+
+    fn transmission_kind(&self) -> TransmissionKind {
+        TransmissionKind::ToOne
+    }
+
+    fn locator(&self) -> Locator {
+        self.locator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use troc_core::{GuidPrefix, Locator, LocatorKind};
+
+    use super::{
+        HEAD_OFFSET, LENGTH_SIZE, LOCK_OFFSET, Segment, TAIL_OFFSET, owner_prefix, segment_path,
+    };
+    use std::sync::atomic::Ordering;
+
+    fn locator(port: u32) -> Locator {
+        let prefix = GuidPrefix::new(Default::default());
+        Locator::new(LocatorKind::Shm, super::shm_address(prefix), port)
+    }
+
+    #[test]
+    fn corrupted_segment() {
+        let locator = locator(1);
+        let path = segment_path(&locator);
+        let segment = Segment::create(&path, 1024, owner_prefix(&locator)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        segment.push(&[1, 2, 3]).unwrap();
+        assert_eq!(segment.pop().unwrap().as_ref(), &[1, 2, 3]);
+
+        // a length beyond the published range
+        segment.push(&[1, 2, 3]).unwrap();
+        let head = segment.atomic_u64(HEAD_OFFSET).load(Ordering::Relaxed);
+        segment.write_at(head, &u32::MAX.to_le_bytes());
+        assert!(segment.pop().is_none());
+        assert_eq!(
+            segment.atomic_u64(HEAD_OFFSET).load(Ordering::Relaxed),
+            segment.atomic_u64(TAIL_OFFSET).load(Ordering::Relaxed)
+        );
+
+        // a published range shorter than a length
+        let tail = segment.atomic_u64(TAIL_OFFSET).load(Ordering::Relaxed);
+        segment
+            .atomic_u64(TAIL_OFFSET)
+            .store(tail + LENGTH_SIZE - 1, Ordering::Relaxed);
+        assert!(segment.pop().is_none());
+
+        segment.push(&[4]).unwrap();
+        assert_eq!(segment.pop().unwrap().as_ref(), &[4]);
+
+        // a head past the tail
+        let tail = segment.atomic_u64(TAIL_OFFSET).load(Ordering::Relaxed);
+        segment
+            .atomic_u64(HEAD_OFFSET)
+            .store(tail + 1, Ordering::Relaxed);
+        assert!(segment.push(&[5]).is_err());
+    }
+
+    #[test]
+    fn lock_of_a_dead_sender() {
+        let locator = locator(3);
+        let path = segment_path(&locator);
+        let segment = Segment::create(&path, 1024, owner_prefix(&locator)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // the lock is held by a running process
+        segment
+            .atomic_u32(LOCK_OFFSET)
+            .store(std::process::id(), Ordering::Relaxed);
+        assert!(segment.push(&[1]).is_err());
+
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let dead = child.id();
+        child.wait().unwrap();
+        segment
+            .atomic_u32(LOCK_OFFSET)
+            .store(dead, Ordering::Relaxed);
+        segment.push(&[1]).unwrap();
+        assert_eq!(segment.pop().unwrap().as_ref(), &[1]);
+        assert_eq!(segment.atomic_u32(LOCK_OFFSET).load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn segment_of_another_participant() {
+        let locator = locator(2);
+        let path = segment_path(&locator);
+        let _segment = Segment::create(&path, 1024, owner_prefix(&locator)).unwrap();
+
+        assert!(Segment::open(&path, owner_prefix(&locator)).is_ok());
+        assert!(Segment::open(&path, [0; 12]).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
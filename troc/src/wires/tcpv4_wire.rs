@@ -0,0 +1,706 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use futures::{SinkExt, StreamExt};
+use tracing::{Level, event, instrument};
+
+use std::{
+    collections::HashMap,
+    net::TcpListener as StdTcpListener,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::{TcpListener, TcpStream},
+    select,
+    sync::mpsc::{
+        Receiver, Sender, UnboundedReceiver, UnboundedSender, WeakSender, channel,
+        unbounded_channel,
+    },
+    task::{JoinHandle, JoinSet},
+};
+use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
+use troc_core::{Locator, LocatorKind};
+
+use crate::domain::{TcpConfiguration, UdpHelper};
+
+use super::{TransmissionKind, WireError, Wired};
+
+/// A frame is a RTPS message, the larger ones are fragmented by the writers
+const MAX_FRAME_LENGTH: usize = 1024 * 1024;
+/// Messages waiting for the connection to a remote listener
+const CONNECTION_QUEUE_SIZE: usize = 1024;
+/// A frame carrying a message, preceded by the port of the listener it is sent to
+const FRAME_MESSAGE: u8 = 0;
+/// A frame carrying the port and the address of each listener of the participant which dialed the connection
+const FRAME_ANNOUNCE: u8 = 1;
+const ANNOUNCED_LOCATOR_SIZE: usize = 20;
+
+fn codec() -> LengthDelimitedCodec {
+    LengthDelimitedCodec::builder()
+        .max_frame_length(MAX_FRAME_LENGTH)
+        .new_codec()
+}
+
+/// A connection, encrypted or not
+trait Stream: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T> Stream for T where T: AsyncRead + AsyncWrite + Send + Unpin {}
+
+/// Options of the TCP connections
+#[derive(Clone)]
+pub(crate) struct TcpOptions {
+    reconnect_initial_delay: Duration,
+    reconnect_max_delay: Duration,
+    #[cfg(feature = "tls")]
+    tls: Option<tls::TlsContext>,
+}
+
+impl std::fmt::Debug for TcpOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcpOptions")
+            .field("reconnect_initial_delay", &self.reconnect_initial_delay)
+            .field("reconnect_max_delay", &self.reconnect_max_delay)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TryFrom<&TcpConfiguration> for TcpOptions {
+    type Error = WireError;
+
+    fn try_from(value: &TcpConfiguration) -> Result<Self, Self::Error> {
+        Ok(Self {
+            reconnect_initial_delay: value.reconnect_initial_delay,
+            reconnect_max_delay: value.reconnect_max_delay,
+            #[cfg(feature = "tls")]
+            tls: value
+                .tls
+                .enabled
+                .then(|| tls::TlsContext::new(&value.tls))
+                .transpose()?,
+        })
+    }
+}
+
+impl TcpOptions {
+    async fn accept(&self, stream: TcpStream) -> std::io::Result<Box<dyn Stream>> {
+        #[cfg(feature = "tls")]
+        if let Some(tls) = &self.tls {
+            return Ok(Box::new(tls.acceptor.accept(stream).await?));
+        }
+        Ok(Box::new(stream))
+    }
+
+    async fn connect(&self, locator: &Locator) -> std::io::Result<Box<dyn Stream>> {
+        let stream = TcpStream::connect(UdpHelper::get_socket_addr(locator)).await?;
+        stream.set_nodelay(true)?;
+        #[cfg(feature = "tls")]
+        if let Some(tls) = &self.tls {
+            let stream = tls
+                .connector
+                .connect(tls.server_name.clone(), stream)
+                .await?;
+            return Ok(Box::new(stream));
+        }
+        Ok(Box::new(stream))
+    }
+}
+
+/// What a connection writes
+#[derive(Debug)]
+enum Frame {
+    /// A message to the listener of `port` at the other end
+    Message { port: u32, msg: BytesMut },
+    /// The listeners of the participant, for the other end to reach them through this connection
+    Announce,
+}
+
+/// What a connection reads
+#[derive(Debug)]
+enum ReceivedFrame {
+    Message { port: u32, msg: BytesMut },
+    Announce(Vec<Locator>),
+}
+
+impl ReceivedFrame {
+    fn decode(mut frame: BytesMut) -> Option<Self> {
+        if frame.is_empty() {
+            return None;
+        }
+        match frame.get_u8() {
+            FRAME_MESSAGE if frame.len() >= 4 => {
+                let port = frame.get_u32();
+                Some(Self::Message { port, msg: frame })
+            }
+            FRAME_ANNOUNCE if frame.len().is_multiple_of(ANNOUNCED_LOCATOR_SIZE) => {
+                let locators = frame
+                    .chunks_exact(ANNOUNCED_LOCATOR_SIZE)
+                    .map(|mut chunk| {
+                        let port = chunk.get_u32();
+                        let address = chunk.try_into().expect("the chunk holds an address");
+                        Locator::new(LocatorKind::TcpV4, address, port)
+                    })
+                    .collect();
+                Some(Self::Announce(locators))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The TCP connections of a participant, shared by its wires
+#[derive(Debug, Default)]
+struct Connections {
+    /// The connections dialed to the remote listeners, closed once their last sender wire is dropped
+    dialed: HashMap<Locator, WeakSender<Frame>>,
+    /// The connections accepted from the remote participants, by the listeners they announced
+    accepted: HashMap<Locator, Sender<Frame>>,
+    /// The listeners of the participant, by port
+    listeners: HashMap<u32, (Locator, UnboundedSender<BytesMut>)>,
+}
+
+impl Connections {
+    fn announce(&self) {
+        for connection in self.dialed.values().filter_map(WeakSender::upgrade) {
+            let _res = connection.try_send(Frame::Announce);
+        }
+    }
+}
+
+/// The connections of a participant, a connection carries the messages of both of its ends
+///
+/// The participant which dials a connection announces its listeners through it: the other end reaches them through
+/// that connection rather than dialing them, which crosses the NATs and the firewalls letting only one end dial.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TcpConnections(Arc<Mutex<Connections>>);
+
+impl TcpConnections {
+    /// The queue of the connection dialed to `locator`, dialed if there is none
+    fn get_or_connect(&self, locator: &Locator, options: &TcpOptions) -> Sender<Frame> {
+        let mut connections = self.0.lock().unwrap();
+        if let Some(sender) = connections.dialed.get(locator).and_then(|s| s.upgrade()) {
+            return sender;
+        }
+        let (sender, receiver) = channel(CONNECTION_QUEUE_SIZE);
+        connections.dialed.retain(|_, s| s.strong_count() > 0);
+        connections.dialed.insert(*locator, sender.downgrade());
+        tokio::spawn(connection_task(
+            *locator,
+            options.clone(),
+            receiver,
+            self.clone(),
+        ));
+        sender
+    }
+
+    /// The queue of the connection accepted from the participant which announced the listener `locator`, if any
+    fn accepted(&self, locator: &Locator) -> Option<Sender<Frame>> {
+        let connections = self.0.lock().unwrap();
+        connections
+            .accepted
+            .get(locator)
+            .filter(|sender| !sender.is_closed())
+            .cloned()
+    }
+
+    /// Reach the listeners `locators` through the accepted connection `sender`, instead of the ones `previous`
+    fn route(&self, previous: &[Locator], locators: &[Locator], sender: &Sender<Frame>) {
+        let mut connections = self.0.lock().unwrap();
+        connections.accepted.retain(|locator, s| {
+            !s.is_closed() && (!s.same_channel(sender) || !previous.contains(locator))
+        });
+        for locator in locators {
+            connections.accepted.insert(*locator, sender.clone());
+        }
+    }
+
+    fn add_listener(&self, locator: &Locator, sender: UnboundedSender<BytesMut>) {
+        let mut connections = self.0.lock().unwrap();
+        connections
+            .listeners
+            .insert(locator.port, (*locator, sender));
+        connections.announce();
+    }
+
+    fn remove_listener(&self, locator: &Locator) {
+        let mut connections = self.0.lock().unwrap();
+        connections.listeners.remove(&locator.port);
+        connections.announce();
+    }
+
+    /// Forward a message read from a dialed connection to the listener of `port`
+    fn deliver(&self, port: u32, msg: BytesMut) {
+        let connections = self.0.lock().unwrap();
+        match connections.listeners.get(&port) {
+            Some((_, listener)) => {
+                let _res = listener.send(msg);
+            }
+            None => event!(Level::DEBUG, port, "message to an unknown listener dropped"),
+        }
+    }
+
+    fn encode(&self, frame: Frame) -> Bytes {
+        match frame {
+            Frame::Message { port, msg } => {
+                let mut bytes = BytesMut::with_capacity(5 + msg.len());
+                bytes.put_u8(FRAME_MESSAGE);
+                bytes.put_u32(port);
+                bytes.extend_from_slice(&msg);
+                bytes.freeze()
+            }
+            Frame::Announce => {
+                let connections = self.0.lock().unwrap();
+                let mut bytes = BytesMut::with_capacity(
+                    1 + connections.listeners.len() * ANNOUNCED_LOCATOR_SIZE,
+                );
+                bytes.put_u8(FRAME_ANNOUNCE);
+                for (locator, _) in connections.listeners.values() {
+                    bytes.put_u32(locator.port);
+                    bytes.put_slice(&locator.address);
+                }
+                bytes.freeze()
+            }
+        }
+    }
+}
+
+/// Exchange the messages with a remote listener, reconnecting with an exponential backoff
+///
+/// The messages queued while disconnected are dropped like on the network, the reliability recovers them.
+async fn connection_task(
+    locator: Locator,
+    options: TcpOptions,
+    mut receiver: Receiver<Frame>,
+    connections: TcpConnections,
+) {
+    let mut delay = options.reconnect_initial_delay;
+    loop {
+        let stream = match options.connect(&locator).await {
+            Ok(stream) => {
+                event!(Level::DEBUG, locator = %locator, "connected");
+                delay = options.reconnect_initial_delay;
+                stream
+            }
+            Err(e) => {
+                event!(Level::DEBUG, locator = %locator, error = %e, delay = ?delay, "cannot connect");
+                let sleep = tokio::time::sleep(delay);
+                tokio::pin!(sleep);
+                loop {
+                    select! {
+                        _ = &mut sleep => break,
+                        msg = receiver.recv() => if msg.is_none() {
+                            return;
+                        }
+                    }
+                }
+                delay = (delay * 2).min(options.reconnect_max_delay);
+                continue;
+            }
+        };
+
+        let (reader, writer) = tokio::io::split(stream);
+        let mut reader = FramedRead::new(reader, codec());
+        let mut writer = FramedWrite::new(writer, codec());
+        // the listeners are announced first, the messages answering the following ones can take this connection
+        let mut res = writer.send(connections.encode(Frame::Announce)).await;
+        while res.is_ok() {
+            select! {
+                frame = receiver.recv() => {
+                    let Some(frame) = frame else {
+                        return;
+                    };
+                    res = writer.send(connections.encode(frame)).await;
+                }
+                frame = reader.next() => match frame {
+                    Some(Ok(frame)) => match ReceivedFrame::decode(frame) {
+                        Some(ReceivedFrame::Message { port, msg }) => connections.deliver(port, msg),
+                        Some(ReceivedFrame::Announce(_)) => (),
+                        None => event!(Level::DEBUG, locator = %locator, "malformed frame dropped"),
+                    },
+                    Some(Err(e)) => res = Err(e),
+                    None => res = Err(std::io::ErrorKind::UnexpectedEof.into()),
+                }
+            }
+        }
+        if let Err(e) = res {
+            event!(Level::WARN, locator = %locator, error = %e, "connection lost");
+        }
+    }
+}
+
+/// Forward the messages of an accepted connection to the listener wire, and write the messages routed through it
+async fn serve_connection(
+    stream: TcpStream,
+    options: TcpOptions,
+    listener: UnboundedSender<BytesMut>,
+    connections: TcpConnections,
+) {
+    let peer = stream.peer_addr().ok();
+    let stream = match options.accept(stream).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            event!(Level::WARN, peer = ?peer, error = %e, "connection refused");
+            return;
+        }
+    };
+    let (reader, writer) = tokio::io::split(stream);
+    let mut reader = FramedRead::new(reader, codec());
+    let mut writer = FramedWrite::new(writer, codec());
+    let (sender, mut receiver) = channel(CONNECTION_QUEUE_SIZE);
+    let mut announced = Vec::new();
+    loop {
+        select! {
+            frame = receiver.recv() => {
+                let Some(frame) = frame else {
+                    break;
+                };
+                if let Err(e) = writer.send(connections.encode(frame)).await {
+                    event!(Level::DEBUG, peer = ?peer, error = %e, "connection closed");
+                    break;
+                }
+            }
+            frame = reader.next() => match frame {
+                Some(Ok(frame)) => match ReceivedFrame::decode(frame) {
+                    Some(ReceivedFrame::Message { msg, .. }) => {
+                        if listener.send(msg).is_err() {
+                            break;
+                        }
+                    }
+                    Some(ReceivedFrame::Announce(locators)) => {
+                        connections.route(&announced, &locators, &sender);
+                        announced = locators;
+                    }
+                    None => event!(Level::DEBUG, peer = ?peer, "malformed frame dropped"),
+                },
+                Some(Err(e)) => {
+                    event!(Level::DEBUG, peer = ?peer, error = %e, "connection closed");
+                    break;
+                }
+                None => break,
+            }
+        }
+    }
+    connections.route(&announced, &[], &sender);
+}
+
+/// A TCP wire, a listener accepts the connections of the remote senders and a sender writes to one listener
+///
+/// A sender reaches its listener through the connection the remote participant dialed to this one if there is
+/// one, otherwise it dials the listener.
+pub(crate) struct TcpV4Wire {
+    locator: Locator,
+    connections: TcpConnections,
+    /// Messages read from the accepted connections
+    receiver: Option<UnboundedReceiver<BytesMut>>,
+    /// Accepts the connections, its connections are closed once it's aborted
+    accept_task: Option<JoinHandle<()>>,
+    /// Options of the connection dialed by a sender
+    options: Option<TcpOptions>,
+    /// Queue of the connection dialed to the remote listener, once it's needed
+    dialed: Option<Sender<Frame>>,
+}
+
+impl TcpV4Wire {
+    pub fn new_listener(
+        locator: &Locator,
+        options: &TcpOptions,
+        connections: &TcpConnections,
+    ) -> Result<Self, WireError> {
+        let open = |source| WireError::Open {
+            locator: *locator,
+            source,
+        };
+        let listener = StdTcpListener::bind(UdpHelper::get_socket_addr(locator)).map_err(open)?;
+        listener.set_nonblocking(true).map_err(open)?;
+        let listener = TcpListener::from_std(listener).map_err(open)?;
+
+        let (sender, receiver) = unbounded_channel();
+        connections.add_listener(locator, sender.clone());
+        let accept_task = {
+            let options = options.clone();
+            let connections = connections.clone();
+            tokio::spawn(async move {
+                let mut accepted = JoinSet::new();
+                loop {
+                    match listener.accept().await {
+                        Ok((stream, _peer)) => {
+                            let _res = stream.set_nodelay(true);
+                            accepted.spawn(serve_connection(
+                                stream,
+                                options.clone(),
+                                sender.clone(),
+                                connections.clone(),
+                            ));
+                        }
+                        Err(e) => event!(Level::WARN, error = %e, "cannot accept a connection"),
+                    }
+                    while accepted.try_join_next().is_some() {}
+                }
+            })
+        };
+
+        Ok(Self {
+            locator: *locator,
+            connections: connections.clone(),
+            receiver: Some(receiver),
+            accept_task: Some(accept_task),
+            options: None,
+            dialed: None,
+        })
+    }
+
+    /// A sender to the listener of `locator`, sharing the connection of the other senders of this locator
+    pub fn new_sender(
+        locator: &Locator,
+        options: &TcpOptions,
+        connections: &TcpConnections,
+    ) -> Self {
+        Self {
+            locator: *locator,
+            connections: connections.clone(),
+            receiver: None,
+            accept_task: None,
+            options: Some(options.clone()),
+            dialed: None,
+        }
+    }
+}
+
+impl Drop for TcpV4Wire {
+    fn drop(&mut self) {
+        if let Some(accept_task) = &self.accept_task {
+            accept_task.abort();
+            self.connections.remove_listener(&self.locator);
+        }
+    }
+}
+
+#[async_trait]
+impl Wired for TcpV4Wire {
+    #[instrument(level = "TRACE", skip_all)]
+    async fn recv(&mut self) -> Result<BytesMut, WireError> {
+        let Some(receiver) = self.receiver.as_mut() else {
+            return std::future::pending().await;
+        };
+        receiver
+            .recv()
+            .await
+            .ok_or_else(|| WireError::ReceptionError(String::from("the listener stopped")))
+    }
+
+    #[instrument(level = "TRACE", skip_all)]
+    async fn send(&mut self, msg: BytesMut) -> Result<(), WireError> {
+        let Some(options) = self.options.as_ref() else {
+            return Err(WireError::SendError(String::from(
+                "a listener wire can't send",
+            )));
+        };
+        let sender = match self.connections.accepted(&self.locator) {
+            Some(sender) => sender,
+            None => self
+                .dialed
+                .get_or_insert_with(|| self.connections.get_or_connect(&self.locator, options))
+                .clone(),
+        };
+        let frame = Frame::Message {
+            port: self.locator.port,
+            msg,
+        };
+        sender
+            .send(frame)
+            .await
+            .map_err(|_| WireError::SendError(String::from("the connection is closed")))
+    }
+
+    fn transmission_kind(&self) -> TransmissionKind {
+        TransmissionKind::ToOne
+    }
+
+    fn locator(&self) -> Locator {
+        self.locator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener as StdTcpListener, time::Duration};
+
+    use bytes::BytesMut;
+    use tokio::sync::mpsc::unbounded_channel;
+    use troc_core::{Locator, LocatorKind};
+
+    use super::{TcpConnections, TcpOptions, TcpV4Wire};
+    use crate::{
+        domain::{TcpConfiguration, UdpHelper},
+        wires::Wired,
+    };
+
+    fn options() -> TcpOptions {
+        TcpOptions::try_from(&TcpConfiguration {
+            enabled: true,
+            reconnect_initial_delay: Duration::from_millis(10),
+            reconnect_max_delay: Duration::from_millis(40),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    /// A locator of the loopback no one listens on
+    fn free_locator() -> Locator {
+        let listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        Locator::new(
+            LocatorKind::TcpV4,
+            UdpHelper::from_ipv4addr_to_generic_addr([127, 0, 0, 1].into()),
+            port.into(),
+        )
+    }
+
+    /// Send `msg` through `sender` until `listener` receives it
+    async fn exchange(sender: &mut TcpV4Wire, listener: &mut TcpV4Wire, msg: &[u8]) {
+        loop {
+            sender.send(BytesMut::from(msg)).await.unwrap();
+            let received = tokio::time::timeout(Duration::from_millis(50), listener.recv()).await;
+            if let Ok(received) = received {
+                assert_eq!(received.unwrap().as_ref(), msg);
+                return;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn reconnect() {
+        let locator = free_locator();
+        let mut sender = TcpV4Wire::new_sender(&locator, &options(), &TcpConnections::default());
+
+        // the messages sent while no one listens are dropped, the connection is retried meanwhile
+        for _ in 0..15 {
+            sender.send(BytesMut::from(&[0][..])).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        let mut listener =
+            TcpV4Wire::new_listener(&locator, &options(), &TcpConnections::default()).unwrap();
+        // the delay between the attempts stopped doubling at its maximum
+        tokio::time::timeout(
+            Duration::from_millis(500),
+            exchange(&mut sender, &mut listener, &[1]),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn answer_through_dialed_connection() {
+        // the listener of the dialing participant can't be dialed, like behind a NAT
+        let dialing = TcpConnections::default();
+        let unreachable = Locator::new(
+            LocatorKind::TcpV4,
+            UdpHelper::from_ipv4addr_to_generic_addr([192, 0, 2, 1].into()),
+            7400,
+        );
+        let (unreachable_sender, mut unreachable_receiver) = unbounded_channel();
+        dialing.add_listener(&unreachable, unreachable_sender);
+
+        let dialed = TcpConnections::default();
+        let locator = free_locator();
+        let mut listener = TcpV4Wire::new_listener(&locator, &options(), &dialed).unwrap();
+        let mut sender = TcpV4Wire::new_sender(&locator, &options(), &dialing);
+        tokio::time::timeout(
+            Duration::from_secs(2),
+            exchange(&mut sender, &mut listener, &[1]),
+        )
+        .await
+        .unwrap();
+
+        let mut answer = TcpV4Wire::new_sender(&unreachable, &options(), &dialed);
+        answer.send(BytesMut::from(&[2][..])).await.unwrap();
+        let received = tokio::time::timeout(Duration::from_secs(2), unreachable_receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(received.as_ref(), &[2]);
+    }
+}
+
+#[cfg(feature = "tls")]
+mod tls {
+    use std::{io::ErrorKind, path::Path, sync::Arc};
+
+    use tokio_rustls::{
+        TlsAcceptor, TlsConnector,
+        rustls::{
+            ClientConfig, RootCertStore, ServerConfig,
+            crypto::ring::default_provider,
+            pki_types::{CertificateDer, PrivateKeyDer, ServerName, pem::PemObject},
+        },
+    };
+
+    use crate::{domain::TlsConfiguration, wires::WireError};
+
+    fn invalid<E: std::fmt::Display>(path: &Path) -> impl Fn(E) -> WireError {
+        move |e| {
+            WireError::CreationError(std::io::Error::new(
+                ErrorKind::InvalidData,
+                format!("{}: {e}", path.display()),
+            ))
+        }
+    }
+
+    fn load_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, WireError> {
+        CertificateDer::pem_file_iter(path)
+            .map_err(invalid(path))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(invalid(path))
+    }
+
+    /// The validated configuration's paths are all set
+    fn path(path: &Option<std::path::PathBuf>) -> &Path {
+        path.as_deref().unwrap_or(Path::new(""))
+    }
+
+    #[derive(Clone)]
+    pub(crate) struct TlsContext {
+        pub acceptor: TlsAcceptor,
+        pub connector: TlsConnector,
+        pub server_name: ServerName<'static>,
+    }
+
+    impl TlsContext {
+        pub fn new(config: &TlsConfiguration) -> Result<Self, WireError> {
+            let provider = Arc::new(default_provider());
+            let certificate_chain = path(&config.certificate_chain);
+            let private_key = path(&config.private_key);
+            let root_certificates = path(&config.root_certificates);
+
+            let chain = load_certificates(certificate_chain)?;
+            let key = PrivateKeyDer::from_pem_file(private_key).map_err(invalid(private_key))?;
+            let server_config = ServerConfig::builder_with_provider(provider.clone())
+                .with_safe_default_protocol_versions()
+                .map_err(invalid(certificate_chain))?
+                .with_no_client_auth()
+                .with_single_cert(chain, key)
+                .map_err(invalid(certificate_chain))?;
+
+            let mut roots = RootCertStore::empty();
+            for certificate in load_certificates(root_certificates)? {
+                roots.add(certificate).map_err(invalid(root_certificates))?;
+            }
+            let client_config = ClientConfig::builder_with_provider(provider)
+                .with_safe_default_protocol_versions()
+                .map_err(invalid(root_certificates))?
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+
+            let server_name = ServerName::try_from(config.server_name.clone()).map_err(|e| {
+                WireError::CreationError(std::io::Error::new(ErrorKind::InvalidInput, e))
+            })?;
+
+            Ok(Self {
+                acceptor: TlsAcceptor::from(Arc::new(server_config)),
+                connector: TlsConnector::from(Arc::new(client_config)),
+                server_name,
+            })
+        }
+    }
+}
@@ -30,100 +30,136 @@ use troc_core::{
 
 use crate::domain::UdpHelper;
 
-use super::{TransmissionDirection, TransmissionKind, WireError, Wired};
+use super::{TransmissionKind, WireError, Wired};
 
 pub(crate) struct UdpV4Wire {
-    socket: UdpSocket,
+    /// A socket per interface of a multicast sender, a single one otherwise
+    sockets: Vec<UdpSocket>,
     locator: Locator,
     buffer: Vec<u8>,
     _kind: TransmissionKind,
-    direction: TransmissionDirection,
     bucket: RateLimiter<NotKeyed, InMemoryState, QuantaClock, NoOpMiddleware<QuantaInstant>>,
 }
 
 impl UdpV4Wire {
-    pub fn new_listener(locator: &Locator, reuse: bool) -> Result<Self, WireError> {
+    pub fn new_listener(
+        locator: &Locator,
+        reuse: bool,
+        interfaces: &[Ipv4Addr],
+    ) -> Result<Self, WireError> {
+        let open = |source| WireError::Open {
+            locator: *locator,
+            source,
+        };
         let sockaddr = UdpHelper::get_socket_addr(locator);
-        let socket = UdpV4Wire::common_socket_setup(sockaddr, reuse)?;
-        let socket = UdpSocket::from_std(socket.into()).unwrap();
+        let socket = UdpV4Wire::common_socket_setup(sockaddr, reuse, interfaces).map_err(open)?;
+        let socket = UdpSocket::from_std(socket.into()).map_err(open)?;
         let wire = Self {
-            socket,
+            sockets: vec![socket],
             locator: *locator,
             buffer: Vec::with_capacity(64 * 1024),
             _kind: TransmissionKind::ToMany,
-            direction: TransmissionDirection::Listener,
             bucket: RateLimiter::direct(Quota::per_second(NonZeroU32::new(1).unwrap())),
         };
         Ok(wire)
     }
 
-    pub fn new_sender(locator: &Locator, reuse: bool) -> Result<Self, WireError> {
+    pub fn new_sender(
+        locator: &Locator,
+        reuse: bool,
+        interfaces: &[Ipv4Addr],
+    ) -> Result<Self, WireError> {
+        let open = |source| WireError::Open {
+            locator: *locator,
+            source,
+        };
         let sockaddr = UdpHelper::get_socket_addr(locator);
-        let bind_addr: SocketAddr = format!("{}:{}", sockaddr.ip(), 0).parse().unwrap();
-
-        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP)).unwrap();
-        socket.set_reuse_address(reuse).unwrap();
-        socket.set_nonblocking(true).unwrap();
-
-        if bind_addr.ip().is_multicast() {
-            socket.bind(&SockAddr::from(bind_addr)).unwrap();
-
-            let IpAddr::V4(ipv4_sockaddr_connect) = bind_addr.ip() else {
-                panic!()
-            };
-
-            socket
-                .join_multicast_v4(&ipv4_sockaddr_connect, &Ipv4Addr::new(0, 0, 0, 0))
-                .unwrap();
-            socket.set_multicast_loop_v4(true).unwrap();
-        } else {
-            let sockaddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 0);
-            let sockaddr = SockAddr::from(sockaddr);
-            socket.bind(&sockaddr).unwrap();
+        let sending_interfaces = match sockaddr.ip().is_multicast() && !interfaces.is_empty() {
+            true => interfaces,
+            false => &[Ipv4Addr::UNSPECIFIED][..],
+        };
+        let mut sockets = Vec::with_capacity(sending_interfaces.len());
+        for interface in sending_interfaces {
+            let socket =
+                UdpV4Wire::sender_socket_setup(sockaddr, reuse, interface).map_err(open)?;
+            sockets.push(UdpSocket::from_std(socket.into()).map_err(open)?);
         }
-
-        socket.connect(&SockAddr::from(sockaddr)).unwrap();
-        let socket = UdpSocket::from_std(socket.into()).unwrap();
         let bucket = RateLimiter::direct(Quota::per_second(
             NonZeroU32::new(80 * 1024 * 1024).unwrap(),
         ));
         let wire = Self {
-            socket,
+            sockets,
             locator: *locator,
             buffer: Vec::with_capacity(64 * 1024),
             _kind: TransmissionKind::ToMany,
-            direction: TransmissionDirection::Sender,
             bucket,
         };
         Ok(wire)
     }
 
-    fn common_socket_setup(sockaddr: SocketAddr, reuse: bool) -> Result<Socket, WireError> {
-        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP)).unwrap();
+    /// The group of a multicast listener is joined on every interface, or the one chosen by the system if there is none
+    fn common_socket_setup(
+        sockaddr: SocketAddr,
+        reuse: bool,
+        interfaces: &[Ipv4Addr],
+    ) -> std::io::Result<Socket> {
+        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
         socket.set_reuse_address(reuse)?;
         socket.set_nonblocking(true)?;
         socket.bind(&SockAddr::from(sockaddr))?;
 
-        if sockaddr.ip().is_multicast() {
-            let IpAddr::V4(ipv4_sockaddr_connect) = sockaddr.ip() else {
-                panic!()
-            };
-            socket.join_multicast_v4(&ipv4_sockaddr_connect, &Ipv4Addr::new(0, 0, 0, 0))?;
+        if let IpAddr::V4(ipv4_sockaddr_connect) = sockaddr.ip()
+            && ipv4_sockaddr_connect.is_multicast()
+        {
+            if interfaces.is_empty() {
+                socket.join_multicast_v4(&ipv4_sockaddr_connect, &Ipv4Addr::UNSPECIFIED)?;
+            }
+            for interface in interfaces {
+                socket.join_multicast_v4(&ipv4_sockaddr_connect, interface)?;
+            }
             socket.set_multicast_loop_v4(true)?;
         }
 
         Ok(socket)
     }
+
+    /// A multicast socket emits on `interface`, the one chosen by the system if unspecified
+    fn sender_socket_setup(
+        sockaddr: SocketAddr,
+        reuse: bool,
+        interface: &Ipv4Addr,
+    ) -> std::io::Result<Socket> {
+        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_reuse_address(reuse)?;
+        socket.set_nonblocking(true)?;
+
+        if let IpAddr::V4(ipv4_sockaddr_connect) = sockaddr.ip()
+            && ipv4_sockaddr_connect.is_multicast()
+        {
+            let bind_addr = SocketAddr::new(sockaddr.ip(), 0);
+            socket.bind(&SockAddr::from(bind_addr))?;
+            socket.join_multicast_v4(&ipv4_sockaddr_connect, interface)?;
+            socket.set_multicast_if_v4(interface)?;
+            socket.set_multicast_loop_v4(true)?;
+        } else {
+            let bind_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 0);
+            socket.bind(&SockAddr::from(bind_addr))?;
+        }
+
+        socket.connect(&SockAddr::from(sockaddr))?;
+        Ok(socket)
+    }
 }
 
 #[async_trait]
 impl Wired for UdpV4Wire {
     #[instrument(level = "TRACE", skip_all)]
     async fn recv(&mut self) -> Result<BytesMut, WireError> {
+        let socket = &self.sockets[0];
         loop {
-            self.socket.readable().await?;
+            socket.readable().await?;
 
-            match self.socket.try_recv_buf(&mut self.buffer) {
+            match socket.try_recv_buf(&mut self.buffer) {
                 Ok(nb_bytes) => {
                     if nb_bytes == 0 {
                         event!(Level::WARN, "Udp packet is empty");
@@ -146,51 +182,35 @@ impl Wired for UdpV4Wire {
 
     #[instrument(level = "TRACE", skip_all)]
     async fn send(&mut self, msg: BytesMut) -> Result<(), WireError> {
-        loop {
-            self.socket.writable().await?;
-
-            // self.bucket
-            //     .until_n_ready(NonZeroU32::new(msg.len() as u32).unwrap())
-            //     .await
-            //     .unwrap();
-            // sleep(Duration::from_micros(50)).await;
-
-            match self.socket.try_send(&msg) {
-                Ok(_nb_bytes) => {
-                    self.buffer.clear();
-                    break Ok(());
-                }
-                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                    continue;
-                }
-                Err(e) => {
-                    self.buffer.clear();
-                    break Err(WireError::SendError(e.to_string()));
+        for socket in &self.sockets {
+            loop {
+                socket.writable().await?;
+
+                // self.bucket
+                //     .until_n_ready(NonZeroU32::new(msg.len() as u32).unwrap())
+                //     .await
+                //     .unwrap();
+                // sleep(Duration::from_micros(50)).await;
+
+                match socket.try_send(&msg) {
+                    Ok(_nb_bytes) => break,
+                    Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        continue;
+                    }
+                    Err(e) => return Err(WireError::SendError(e.to_string())),
                 }
             }
         }
+        Ok(())
     }
 
     fn transmission_kind(&self) -> TransmissionKind {
         self._kind
     }
 
-    fn transmission_direction(&self) -> TransmissionDirection {
-        self.direction
-    }
-
     fn locator(&self) -> Locator {
         self.locator
     }
-
-    fn duplicate(&self) -> Box<dyn Wired> {
-        let wire = match self.transmission_direction() {
-            TransmissionDirection::Listener => UdpV4Wire::new_listener(&self.locator(), true),
-            TransmissionDirection::Sender => UdpV4Wire::new_sender(&self.locator(), true),
-        };
-        let wire = wire.unwrap();
-        Box::new(wire)
-    }
 }
 
 // #[cfg(test)]
@@ -259,3 +279,32 @@ impl Wired for UdpV4Wire {
 //         assert_eq!(actual_msg, expected_msg);
 //     }
 // }
+
+#[cfg(test)]
+mod test {
+    use std::net::Ipv4Addr;
+
+    use bytes::BytesMut;
+    use troc_core::{Locator, LocatorKind};
+
+    use super::UdpV4Wire;
+    use crate::{domain::UdpHelper, wires::Wired};
+
+    #[tokio::test]
+    async fn multicast_sender_per_interface() {
+        let locator = Locator::new(
+            LocatorKind::UdpV4,
+            UdpHelper::from_ipv4addr_to_generic_addr(Ipv4Addr::new(239, 255, 0, 42)),
+            17942,
+        );
+        let interfaces = [Ipv4Addr::LOCALHOST, Ipv4Addr::LOCALHOST];
+        let mut listener = UdpV4Wire::new_listener(&locator, true, &interfaces[..1]).unwrap();
+        let mut sender = UdpV4Wire::new_sender(&locator, true, &interfaces).unwrap();
+        assert_eq!(sender.sockets.len(), 2);
+
+        sender.send(BytesMut::from(&b"troc"[..])).await.unwrap();
+        for _ in interfaces {
+            assert_eq!(&listener.recv().await.unwrap()[..], b"troc");
+        }
+    }
+}
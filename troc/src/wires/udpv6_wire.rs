@@ -0,0 +1,206 @@
+use bytes::BytesMut;
+use tracing::{Level, event, instrument};
+
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+
+use async_trait::async_trait;
+use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+use tokio::net::UdpSocket;
+use troc_core::Locator;
+
+use crate::domain::UdpHelper;
+
+use super::{TransmissionKind, WireError, Wired};
+
+/// Options of the multicast traffic of a UDPv6 wire
+#[derive(Debug, Clone)]
+pub(crate) struct MulticastOptions {
+    pub hop_limit: u32,
+    /// Index of the network interfaces, the system chooses one if there is none
+    pub interfaces: Vec<u32>,
+}
+
+pub(crate) struct UdpV6Wire {
+    /// A socket per interface of a multicast sender, a single one otherwise
+    sockets: Vec<UdpSocket>,
+    locator: Locator,
+    buffer: Vec<u8>,
+    _kind: TransmissionKind,
+    /// A socket can't be connected to a link-local group without an interface, a multicast sender sends to it instead
+    group: Option<SocketAddr>,
+}
+
+impl UdpV6Wire {
+    pub fn new_listener(
+        locator: &Locator,
+        reuse: bool,
+        options: MulticastOptions,
+    ) -> Result<Self, WireError> {
+        let open = |source| WireError::Open {
+            locator: *locator,
+            source,
+        };
+        let sockaddr = UdpHelper::get_socket_addr(locator);
+        let socket = UdpV6Wire::listener_socket_setup(sockaddr, reuse, &options).map_err(open)?;
+        let socket = UdpSocket::from_std(socket.into()).map_err(open)?;
+        let wire = Self {
+            sockets: vec![socket],
+            locator: *locator,
+            buffer: Vec::with_capacity(64 * 1024),
+            _kind: TransmissionKind::ToMany,
+            group: None,
+        };
+        Ok(wire)
+    }
+
+    pub fn new_sender(
+        locator: &Locator,
+        reuse: bool,
+        options: MulticastOptions,
+    ) -> Result<Self, WireError> {
+        let open = |source| WireError::Open {
+            locator: *locator,
+            source,
+        };
+        let sockaddr = UdpHelper::get_socket_addr(locator);
+        let group = sockaddr.ip().is_multicast().then_some(sockaddr);
+        let sending_interfaces = match group.is_some() && !options.interfaces.is_empty() {
+            true => &options.interfaces[..],
+            false => &[0][..],
+        };
+        let mut sockets = Vec::with_capacity(sending_interfaces.len());
+        for interface in sending_interfaces {
+            let socket =
+                UdpV6Wire::sender_socket_setup(sockaddr, reuse, options.hop_limit, *interface)
+                    .map_err(open)?;
+            sockets.push(UdpSocket::from_std(socket.into()).map_err(open)?);
+        }
+        let wire = Self {
+            sockets,
+            locator: *locator,
+            buffer: Vec::with_capacity(64 * 1024),
+            _kind: TransmissionKind::ToMany,
+            group,
+        };
+        Ok(wire)
+    }
+
+    /// The group of a multicast listener is joined on every interface, or the one chosen by the system if there is none
+    fn listener_socket_setup(
+        sockaddr: SocketAddr,
+        reuse: bool,
+        options: &MulticastOptions,
+    ) -> std::io::Result<Socket> {
+        let socket = Socket::new(Domain::IPV6, Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_only_v6(true)?;
+        socket.set_reuse_address(reuse)?;
+        socket.set_nonblocking(true)?;
+
+        if let IpAddr::V6(ipv6_sockaddr_connect) = sockaddr.ip()
+            && ipv6_sockaddr_connect.is_multicast()
+        {
+            // a link-local group can't be bound without its scope, the port is enough to filter
+            let bind_addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), sockaddr.port());
+            socket.bind(&SockAddr::from(bind_addr))?;
+            if options.interfaces.is_empty() {
+                socket.join_multicast_v6(&ipv6_sockaddr_connect, 0)?;
+            }
+            for interface in &options.interfaces {
+                socket.join_multicast_v6(&ipv6_sockaddr_connect, *interface)?;
+            }
+            socket.set_multicast_loop_v6(true)?;
+        } else {
+            socket.bind(&SockAddr::from(sockaddr))?;
+        }
+
+        Ok(socket)
+    }
+
+    /// A multicast socket emits on the interface of index `interface`, the one chosen by the system if 0
+    fn sender_socket_setup(
+        sockaddr: SocketAddr,
+        reuse: bool,
+        hop_limit: u32,
+        interface: u32,
+    ) -> std::io::Result<Socket> {
+        let socket = Socket::new(Domain::IPV6, Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_only_v6(true)?;
+        socket.set_reuse_address(reuse)?;
+        socket.set_nonblocking(true)?;
+
+        let bind_addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0);
+        socket.bind(&SockAddr::from(bind_addr))?;
+
+        if let IpAddr::V6(ipv6_sockaddr_connect) = sockaddr.ip()
+            && ipv6_sockaddr_connect.is_multicast()
+        {
+            socket.set_multicast_if_v6(interface)?;
+            socket.set_multicast_hops_v6(hop_limit)?;
+            socket.set_multicast_loop_v6(true)?;
+        } else {
+            socket.connect(&SockAddr::from(sockaddr))?;
+        }
+
+        Ok(socket)
+    }
+}
+
+#[async_trait]
+impl Wired for UdpV6Wire {
+    #[instrument(level = "TRACE", skip_all)]
+    async fn recv(&mut self) -> Result<BytesMut, WireError> {
+        let socket = &self.sockets[0];
+        loop {
+            socket.readable().await?;
+
+            match socket.try_recv_buf(&mut self.buffer) {
+                Ok(nb_bytes) => {
+                    if nb_bytes == 0 {
+                        event!(Level::WARN, "Udp packet is empty");
+                        continue;
+                    }
+                    let msg = BytesMut::from_iter(&self.buffer);
+                    self.buffer.clear();
+                    break Ok(msg);
+                }
+                Err(e) => match e.kind() {
+                    std::io::ErrorKind::WouldBlock => continue,
+                    _ => {
+                        self.buffer.clear();
+                        break Err(WireError::ReceptionError(e.to_string()));
+                    }
+                },
+            }
+        }
+    }
+
+    #[instrument(level = "TRACE", skip_all)]
+    async fn send(&mut self, msg: BytesMut) -> Result<(), WireError> {
+        for socket in &self.sockets {
+            loop {
+                socket.writable().await?;
+
+                let res = match self.group {
+                    Some(group) => socket.try_send_to(&msg, group),
+                    None => socket.try_send(&msg),
+                };
+                match res {
+                    Ok(_nb_bytes) => break,
+                    Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        continue;
+                    }
+                    Err(e) => return Err(WireError::SendError(e.to_string())),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn transmission_kind(&self) -> TransmissionKind {
+        self._kind
+    }
+
+    fn locator(&self) -> Locator {
+        self.locator
+    }
+}
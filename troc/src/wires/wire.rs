@@ -81,15 +81,6 @@ impl WireList {
         self.0.iter_mut()
     }
 
-    pub fn duplicate(&self) -> Self {
-        let wires = self
-            .0
-            .iter()
-            .map(|w| Wire(w.duplicate()))
-            .collect::<Vec<Wire>>();
-        Self(wires)
-    }
-
     pub fn pop(&mut self) -> Option<Wire> {
         self.0.pop()
     }
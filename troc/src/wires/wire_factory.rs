@@ -1,21 +1,31 @@
-use super::{Wire, WireError, WireList, udpv4_wire::UdpV4Wire};
-use crate::{domain::Configuration, domain::UdpHelper};
+use super::{
+    Wire, WireError, WireList, Wired,
+    shm_wire::{ShmWire, is_same_host, shm_address},
+    tcpv4_wire::{TcpConnections, TcpOptions, TcpV4Wire},
+    udpv4_wire::UdpV4Wire,
+    udpv6_wire::{MulticastOptions, UdpV6Wire},
+};
+use crate::{
+    domain::Configuration,
+    domain::{UdpHelper, select_interfaces},
+};
 use bytes::BytesMut;
 use kameo::{
     Actor,
     actor::{ActorRef, Spawn},
     prelude::Message,
 };
-use local_ip_address::local_ip;
+use local_ip_address::list_afinet_netifas;
 use std::{
+    ffi::CString,
     io::ErrorKind,
-    net::{IpAddr, Ipv4Addr},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
     str::FromStr,
     sync::Arc,
 };
 use tokio::{select, sync::Notify};
 use tracing::{Level, event};
-use troc_core::{Locator, LocatorKind, LocatorList};
+use troc_core::{GuidPrefix, Locator, LocatorKind, LocatorList};
 
 pub trait Sendable: Actor + Sized {
     type Msg: Send + 'static;
@@ -29,7 +39,7 @@ pub enum SenderWireFactoryActorMessage {
 }
 
 impl Message<SenderWireFactoryActorMessage> for WireFactoryActor {
-    type Reply = (Vec<ActorRef<SenderWireActor>>, LocatorList);
+    type Reply = Result<(Vec<ActorRef<SenderWireActor>>, LocatorList), WireError>;
 
     async fn handle(
         &mut self,
@@ -38,34 +48,47 @@ impl Message<SenderWireFactoryActorMessage> for WireFactoryActor {
     ) -> Self::Reply {
         match msg {
             SenderWireFactoryActorMessage::FromLocators { locators } => {
-                let mut wires = Vec::default();
-                for locator in locators.iter() {
-                    match locator.kind {
-                        LocatorKind::UdpV4 => {
-                            let wired = UdpV4Wire::new_sender(locator, true).unwrap();
-                            let wire = Wire::new(Box::new(wired));
-                            let sender_wire_actor =
-                                SenderWireActor::spawn(SenderWireActor { wire });
-                            wires.push(sender_wire_actor);
-                        }
-                        _ => {
-                            event!(Level::ERROR, "unsupported locator kind");
-                        }
+                // a remote entity of the same host is reached through its segments, then TCP is
+                // preferred since it crosses the networks UDP doesn't, UDP is the fallback
+                let (shm_locators, other_locators): (Vec<&Locator>, Vec<&Locator>) = locators
+                    .iter()
+                    .partition(|locator| matches!(locator.kind, LocatorKind::Shm));
+                let local_shm_locators = shm_locators
+                    .into_iter()
+                    .filter(|locator| is_same_host(locator, &self.shm_address))
+                    .collect::<Vec<_>>();
+                let (tcp_locators, other_locators): (Vec<&Locator>, Vec<&Locator>) = other_locators
+                    .into_iter()
+                    .partition(|locator| matches!(locator.kind, LocatorKind::TcpV4));
+                for locators in [local_shm_locators, tcp_locators] {
+                    let (wires, reachable_locators) = self.spawn_sender_wires(&locators);
+                    if !wires.is_empty() {
+                        return Ok((wires, LocatorList::new(reachable_locators)));
                     }
                 }
-                (wires, locators)
+                let (wires, reachable_locators) = self.spawn_sender_wires(&other_locators);
+                Ok((wires, LocatorList::new(reachable_locators)))
             }
             SenderWireFactoryActorMessage::SPDP => {
-                let wire = self.build_discovery_sender_multicast_wire().unwrap();
-                let locator = wire.locator();
-                let sender_wire_actor = SenderWireActor::spawn(SenderWireActor { wire });
-                (vec![sender_wire_actor], LocatorList::new(vec![locator]))
+                let mut wires = Vec::default();
+                if self.is_enabled(LocatorKind::UdpV4) {
+                    wires.push(self.build_discovery_sender_multicast_wire()?);
+                }
+                if self.is_enabled(LocatorKind::UdpV6) {
+                    wires.push(self.build_discovery_sender_multicast_wire_v6()?);
+                }
+                let locators = wires.iter().map(|w| w.locator()).collect::<Vec<_>>();
+                let sender_wire_actors = wires
+                    .into_iter()
+                    .map(|wire| SenderWireActor::spawn(SenderWireActor { wire }))
+                    .collect();
+                Ok((sender_wire_actors, LocatorList::new(locators)))
             }
         }
     }
 }
 
-#[derive()]
+#[derive(Debug, Clone, Copy)]
 pub enum ReceiverWireFactoryActorMessage {
     Applicative,
     SPDP,
@@ -73,35 +96,124 @@ pub enum ReceiverWireFactoryActorMessage {
 }
 
 impl Message<ReceiverWireFactoryActorMessage> for WireFactoryActor {
-    type Reply = (Vec<ActorRef<ReceiverWireActor>>, LocatorList);
+    type Reply = Result<(Vec<ActorRef<ReceiverWireActor>>, LocatorList), WireError>;
 
     async fn handle(
         &mut self,
         msg: ReceiverWireFactoryActorMessage,
         _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
     ) -> Self::Reply {
-        match msg {
-            ReceiverWireFactoryActorMessage::Applicative => {
-                let wire = self
-                    .build_user_wire(Some(Ipv4Addr::new(127, 0, 0, 1)))
-                    .unwrap();
-                let locator = wire.locator();
-                let receiver_wire_actor = ReceiverWireActor::spawn(wire);
-                (vec![receiver_wire_actor], LocatorList::new(vec![locator]))
+        let mut wires = Vec::default();
+        if self.is_enabled(LocatorKind::UdpV4) {
+            wires.extend(match msg {
+                ReceiverWireFactoryActorMessage::Applicative => self.build_user_wires()?,
+                ReceiverWireFactoryActorMessage::SPDP => {
+                    vec![self.build_discovery_listener_multicast_wire()?]
+                }
+                ReceiverWireFactoryActorMessage::SEDP => self.build_discovery_unicast_wires()?,
+            });
+        }
+        if self.is_enabled(LocatorKind::UdpV6) {
+            wires.extend(match msg {
+                ReceiverWireFactoryActorMessage::Applicative => self.build_user_wires_v6()?,
+                ReceiverWireFactoryActorMessage::SPDP => {
+                    vec![self.build_discovery_listener_multicast_wire_v6()?]
+                }
+                ReceiverWireFactoryActorMessage::SEDP => self.build_discovery_unicast_wires_v6()?,
+            });
+        }
+        if self.is_enabled(LocatorKind::Shm) {
+            let base_port = match msg {
+                ReceiverWireFactoryActorMessage::Applicative => {
+                    Some(self.generate_user_unicast_base_port())
+                }
+                ReceiverWireFactoryActorMessage::SEDP => {
+                    Some(self.generate_discovery_unicast_port())
+                }
+                // the participants are discovered through the multicast group only
+                ReceiverWireFactoryActorMessage::SPDP => None,
+            };
+            if let Some(base_port) = base_port {
+                // the UDP wires are enough to communicate, a segment is an optimization
+                match self.build_shm_listener_wire(base_port) {
+                    Ok(wire) => wires.push(wire),
+                    Err(e) => {
+                        event!(Level::WARN, error = %e, "cannot open a shared-memory segment")
+                    }
+                }
+            }
+        }
+        if self.is_enabled(LocatorKind::TcpV4) {
+            match msg {
+                ReceiverWireFactoryActorMessage::Applicative => wires
+                    .extend(self.build_tcp_listener_wires(self.generate_user_unicast_base_port())?),
+                ReceiverWireFactoryActorMessage::SEDP => wires
+                    .extend(self.build_tcp_listener_wires(self.generate_discovery_unicast_port())?),
+                // the initial peers are announced to through the discovery unicast listeners
+                ReceiverWireFactoryActorMessage::SPDP => (),
+            }
+        }
+        let locators = wires.iter().map(|w| w.locator()).collect::<Vec<_>>();
+        let receiver_wire_actors = wires.into_iter().map(ReceiverWireActor::spawn).collect();
+        Ok((receiver_wire_actors, LocatorList::new(locators)))
+    }
+}
+
+/// The addresses of the interfaces allowed and not denied by the configuration
+#[derive(Debug, Default)]
+struct SelectedInterfaces {
+    ipv4: Vec<Ipv4Addr>,
+    /// The unicast addresses but the link-local ones, which can't be bound without their scope
+    ipv6: Vec<Ipv6Addr>,
+    /// Index of the interfaces of the IPv6 addresses, they send and join the multicast groups
+    ipv6_indexes: Vec<u32>,
+}
+
+impl SelectedInterfaces {
+    fn new(config: &Configuration) -> Result<Self, WireError> {
+        let (allowed, denied) = config.get_interface_selectors().map_err(|e| {
+            WireError::CreationError(std::io::Error::new(ErrorKind::InvalidInput, e))
+        })?;
+        let interfaces = list_afinet_netifas().map_err(|e| {
+            WireError::CreationError(std::io::Error::new(
+                ErrorKind::AddrNotAvailable,
+                e.to_string(),
+            ))
+        })?;
+        let (ipv4, ipv6): (Vec<_>, Vec<_>) =
+            interfaces.into_iter().partition(|(_, ip)| ip.is_ipv4());
+
+        // an address of several interfaces is listened on once
+        let mut selected = Self::default();
+        for (_, ip) in select_interfaces(&allowed, &denied, ipv4) {
+            if let IpAddr::V4(ip) = ip
+                && !selected.ipv4.contains(&ip)
+            {
+                selected.ipv4.push(ip);
             }
-            ReceiverWireFactoryActorMessage::SPDP => {
-                let wire = self.build_discovery_listener_multicast_wire().unwrap();
-                let locator = wire.locator();
-                let receiver_wire_actor = ReceiverWireActor::spawn(wire);
-                (vec![receiver_wire_actor], LocatorList::new(vec![locator]))
+        }
+        for (name, ip) in select_interfaces(&allowed, &denied, ipv6) {
+            if let IpAddr::V6(ip) = ip
+                && !ip.is_unicast_link_local()
+                && !selected.ipv6.contains(&ip)
+            {
+                selected.ipv6.push(ip);
             }
-            ReceiverWireFactoryActorMessage::SEDP => {
-                let wire = self.build_discovery_unicast_wire().unwrap();
-                let locator = wire.locator();
-                let receiver_wire_actor = ReceiverWireActor::spawn(wire);
-                (vec![receiver_wire_actor], LocatorList::new(vec![locator]))
+            if let Some(index) = Self::index(&name)
+                && !selected.ipv6_indexes.contains(&index)
+            {
+                selected.ipv6_indexes.push(index);
             }
         }
+        Ok(selected)
+    }
+
+    /// The index of the interface `name`, if it still exists
+    fn index(name: &str) -> Option<u32> {
+        let name = CString::new(name).ok()?;
+        // SAFETY: `name` is a NUL-terminated string outliving the call
+        let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        (index != 0).then_some(index)
     }
 }
 
@@ -109,6 +221,13 @@ impl Message<ReceiverWireFactoryActorMessage> for WireFactoryActor {
 pub struct WireFactoryActor {
     domain_id: Arc<u32>,
     config: Arc<Configuration>,
+    /// The interfaces of the wires, selected once
+    interfaces: Arc<SelectedInterfaces>,
+    /// Address of the SHM locators of the participant
+    shm_address: [u8; 16],
+    /// Options of the TCP wires, their certificates are loaded once, if TCP is enabled
+    tcp_options: Option<TcpOptions>,
+    tcp_connections: TcpConnections,
 }
 
 impl Actor for WireFactoryActor {
@@ -125,11 +244,28 @@ impl Actor for WireFactoryActor {
 }
 
 impl WireFactoryActor {
-    pub fn new(domain_id: u32, config: Configuration) -> Self {
+    pub fn new(
+        domain_id: u32,
+        guid_prefix: GuidPrefix,
+        config: Configuration,
+    ) -> Result<Self, WireError> {
+        let tcp_options = config
+            .tcp
+            .enabled
+            .then(|| TcpOptions::try_from(&config.tcp))
+            .transpose()?;
+        let interfaces = Arc::new(SelectedInterfaces::new(&config)?);
         let domain_id = Arc::new(domain_id);
         let config = Arc::new(config);
 
-        Self { domain_id, config }
+        Ok(Self {
+            domain_id,
+            config,
+            interfaces,
+            shm_address: shm_address(guid_prefix),
+            tcp_options,
+            tcp_connections: TcpConnections::default(),
+        })
     }
 
     pub fn build_sender_wirelist_from_locators(
@@ -161,63 +297,76 @@ impl WireFactoryActor {
     pub fn build_sender_wire_from_locator(&self, locator: &Locator) -> Result<Wire, WireError> {
         match locator.kind {
             LocatorKind::UdpV4 => {
-                let wired = UdpV4Wire::new_sender(locator, true)?;
+                let wired = UdpV4Wire::new_sender(locator, true, self.selected_ipv4()?)?;
+                let wire = Wire::new(Box::new(wired));
+                Ok(wire)
+            }
+            LocatorKind::UdpV6 => {
+                let wired = UdpV6Wire::new_sender(locator, true, self.multicast_options())?;
+                let wire = Wire::new(Box::new(wired));
+                Ok(wire)
+            }
+            LocatorKind::Shm => {
+                let wired = ShmWire::new_sender(locator)?;
+                let wire = Wire::new(Box::new(wired));
+                Ok(wire)
+            }
+            LocatorKind::TcpV4 => {
+                let wired =
+                    TcpV4Wire::new_sender(locator, self.tcp_options()?, &self.tcp_connections);
                 let wire = Wire::new(Box::new(wired));
                 Ok(wire)
             }
-            _ => Err(WireError::CreationError(std::io::Error::new(
-                ErrorKind::InvalidInput,
-                "UdpV6 not supported",
-            ))),
+            kind => Err(Self::unsupported(kind)),
         }
     }
 
     pub fn build_listener_wire_from_locator(&self, locator: &Locator) -> Result<Wire, WireError> {
         match locator.kind {
             LocatorKind::UdpV4 => {
-                let wired = UdpV4Wire::new_listener(locator, true)?;
+                let wired = UdpV4Wire::new_listener(locator, true, self.selected_ipv4()?)?;
                 let wire = Wire::new(Box::new(wired));
                 Ok(wire)
             }
-            _ => Err(WireError::CreationError(std::io::Error::new(
-                ErrorKind::InvalidInput,
-                "UdpV6 not supported",
-            ))),
-        }
-    }
-
-    pub fn build_user_wire(&self, ip: Option<Ipv4Addr>) -> Result<Wire, WireError> {
-        let ip = if let Some(ip) = ip {
-            ip
-        } else {
-            let IpAddr::V4(ip) = local_ip().unwrap() else {
-                panic!()
-            };
-            ip
-        };
-
-        let address = UdpHelper::from_ipv4addr_to_generic_addr(ip);
-        let mut port = self.generate_user_unicast_base_port() as u16;
-
-        let wired = loop {
-            let locator = Locator::new(LocatorKind::UdpV4, address, port as u32);
-            if let Ok(wire) = UdpV4Wire::new_listener(&locator, false) {
-                break wire;
+            LocatorKind::UdpV6 => {
+                let wired = UdpV6Wire::new_listener(locator, true, self.multicast_options())?;
+                let wire = Wire::new(Box::new(wired));
+                Ok(wire)
             }
-            if port == u16::MAX {
-                panic!("all port has been exhausted");
+            LocatorKind::Shm => {
+                let wired = ShmWire::new_listener(locator, self.config.shm.segment_size.into())?;
+                let wire = Wire::new(Box::new(wired));
+                Ok(wire)
             }
-            port += 1;
-        };
+            LocatorKind::TcpV4 => {
+                let wired =
+                    TcpV4Wire::new_listener(locator, self.tcp_options()?, &self.tcp_connections)?;
+                let wire = Wire::new(Box::new(wired));
+                Ok(wire)
+            }
+            kind => Err(Self::unsupported(kind)),
+        }
+    }
 
-        let wire = Wire::new(Box::new(wired));
+    /// A wire per address of the selected interfaces
+    pub fn build_user_wires(&self) -> Result<Vec<Wire>, WireError> {
+        let ips = self.selected_ipv4()?.iter().copied().map(IpAddr::V4);
+        let base_port = self.generate_user_unicast_base_port();
+        self.build_unicast_listener_wires(&ips.collect::<Vec<_>>(), base_port)
+    }
 
-        Ok(wire)
+    /// A wire per IPv6 unicast address
+    pub fn build_user_wires_v6(&self) -> Result<Vec<Wire>, WireError> {
+        let ips = self.unicast_ipv6()?.into_iter().map(IpAddr::V6);
+        let base_port = self.generate_user_unicast_base_port();
+        self.build_unicast_listener_wires(&ips.collect::<Vec<_>>(), base_port)
     }
 
     pub fn build_discovery_sender_multicast_wire(&self) -> Result<Wire, WireError> {
         let discovery_multicast_address =
-            Ipv4Addr::from_str(&self.config.global.default_multicast_address).unwrap();
+            Ipv4Addr::from_str(&self.config.global.default_multicast_address).map_err(|e| {
+                WireError::CreationError(std::io::Error::new(ErrorKind::InvalidInput, e))
+            })?;
         let discovery_multicast_generic_address =
             UdpHelper::from_ipv4addr_to_generic_addr(discovery_multicast_address);
         let discovery_multicast_port = self.generate_discovery_multicast_port();
@@ -229,7 +378,7 @@ impl WireFactoryActor {
 
         match locator.kind {
             LocatorKind::UdpV4 => {
-                let wired = UdpV4Wire::new_sender(&locator, true)?;
+                let wired = UdpV4Wire::new_sender(&locator, true, self.selected_ipv4()?)?;
                 let wire = Wire::new(Box::new(wired));
                 Ok(wire)
             }
@@ -239,7 +388,9 @@ impl WireFactoryActor {
 
     pub fn build_discovery_listener_multicast_wire(&self) -> Result<Wire, WireError> {
         let discovery_multicast_address =
-            Ipv4Addr::from_str(&self.config.global.default_multicast_address).unwrap();
+            Ipv4Addr::from_str(&self.config.global.default_multicast_address).map_err(|e| {
+                WireError::CreationError(std::io::Error::new(ErrorKind::InvalidInput, e))
+            })?;
         let discovery_multicast_generic_address =
             UdpHelper::from_ipv4addr_to_generic_addr(discovery_multicast_address);
         let discovery_multicast_port = self.generate_discovery_multicast_port();
@@ -251,7 +402,7 @@ impl WireFactoryActor {
 
         match locator.kind {
             LocatorKind::UdpV4 => {
-                let wired = UdpV4Wire::new_listener(&locator, true)?;
+                let wired = UdpV4Wire::new_listener(&locator, true, self.selected_ipv4()?)?;
                 let wire = Wire::new(Box::new(wired));
                 Ok(wire)
             }
@@ -259,27 +410,235 @@ impl WireFactoryActor {
         }
     }
 
-    pub fn build_discovery_unicast_wire(&self) -> Result<Wire, WireError> {
-        let IpAddr::V4(ip) = local_ip().unwrap() else {
-            panic!()
+    pub fn build_discovery_sender_multicast_wire_v6(&self) -> Result<Wire, WireError> {
+        let locator = self.discovery_multicast_locator_v6()?;
+        let wired = UdpV6Wire::new_sender(&locator, true, self.multicast_options())?;
+        Ok(Wire::new(Box::new(wired)))
+    }
+
+    pub fn build_discovery_listener_multicast_wire_v6(&self) -> Result<Wire, WireError> {
+        let locator = self.discovery_multicast_locator_v6()?;
+        let wired = UdpV6Wire::new_listener(&locator, true, self.multicast_options())?;
+        Ok(Wire::new(Box::new(wired)))
+    }
+
+    /// A wire per address of the selected interfaces
+    pub fn build_discovery_unicast_wires(&self) -> Result<Vec<Wire>, WireError> {
+        let ips = self.selected_ipv4()?.iter().copied().map(IpAddr::V4);
+        let base_port = self.generate_discovery_unicast_port();
+        self.build_unicast_listener_wires(&ips.collect::<Vec<_>>(), base_port)
+    }
+
+    /// A wire per IPv6 unicast address
+    pub fn build_discovery_unicast_wires_v6(&self) -> Result<Vec<Wire>, WireError> {
+        let ips = self.unicast_ipv6()?.into_iter().map(IpAddr::V6);
+        let base_port = self.generate_discovery_unicast_port();
+        self.build_unicast_listener_wires(&ips.collect::<Vec<_>>(), base_port)
+    }
+
+    /// Listen on every address `ips`, on the first port from `base_port` free on all of them
+    fn build_unicast_listener_wires(
+        &self,
+        ips: &[IpAddr],
+        base_port: u32,
+    ) -> Result<Vec<Wire>, WireError> {
+        let locator = |ip: &IpAddr, port| match ip {
+            IpAddr::V4(ip) => Locator::new(
+                LocatorKind::UdpV4,
+                UdpHelper::from_ipv4addr_to_generic_addr(*ip),
+                port,
+            ),
+            IpAddr::V6(ip) => Locator::new(LocatorKind::UdpV6, ip.octets(), port),
+        };
+        let interfaces = match ips.iter().any(IpAddr::is_ipv4) {
+            true => self.selected_ipv4()?,
+            false => &[],
+        };
+        'ports: for port in base_port..=u32::from(u16::MAX) {
+            let mut wires = Vec::with_capacity(ips.len());
+            for ip in ips {
+                let locator = locator(ip, port);
+                let wired: Result<Box<dyn Wired>, _> = match ip {
+                    IpAddr::V6(_) => {
+                        UdpV6Wire::new_listener(&locator, false, self.multicast_options())
+                            .map(|w| Box::new(w) as _)
+                    }
+                    IpAddr::V4(_) => UdpV4Wire::new_listener(&locator, false, interfaces)
+                        .map(|w| Box::new(w) as _),
+                };
+                match wired {
+                    Ok(wired) => wires.push(Wire::new(wired)),
+                    Err(WireError::Open { source, .. })
+                        if source.kind() == ErrorKind::AddrInUse =>
+                    {
+                        continue 'ports;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            return Ok(wires);
+        }
+        let first = ips
+            .first()
+            .copied()
+            .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        Err(WireError::PortsExhausted {
+            locator: locator(&first, base_port),
+        })
+    }
+
+    /// Create the first free segment from `base_port`, the segments of a host share the ports numbering
+    fn build_shm_listener_wire(&self, base_port: u32) -> Result<Wire, WireError> {
+        let segment_size = self.config.shm.segment_size.into();
+        for port in base_port..=u32::from(u16::MAX) {
+            let locator = Locator::new(LocatorKind::Shm, self.shm_address, port);
+            match ShmWire::new_listener(&locator, segment_size) {
+                Ok(wired) => return Ok(Wire::new(Box::new(wired))),
+                Err(WireError::Open { source, .. })
+                    if source.kind() == ErrorKind::AlreadyExists =>
+                {
+                    continue;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(WireError::PortsExhausted {
+            locator: Locator::new(LocatorKind::Shm, self.shm_address, base_port),
+        })
+    }
+
+    /// Listen on the configured address or every selected one, on the first port from `base_port` free on all of them
+    fn build_tcp_listener_wires(&self, base_port: u32) -> Result<Vec<Wire>, WireError> {
+        let configured = self.config.get_tcp_address().map_err(|e| {
+            WireError::CreationError(std::io::Error::new(ErrorKind::InvalidInput, e))
+        })?;
+        let ips = match configured {
+            Some(ip) => vec![ip],
+            None => self.selected_ipv4()?.to_vec(),
+        };
+        let locator = |ip, port| {
+            Locator::new(
+                LocatorKind::TcpV4,
+                UdpHelper::from_ipv4addr_to_generic_addr(ip),
+                port,
+            )
         };
-        let address = UdpHelper::from_ipv4addr_to_generic_addr(ip);
-        let mut port = self.generate_discovery_unicast_port() as u16;
+        let options = self.tcp_options()?;
+        'ports: for port in base_port..=u32::from(u16::MAX) {
+            let mut wires = Vec::with_capacity(ips.len());
+            for ip in &ips {
+                match TcpV4Wire::new_listener(&locator(*ip, port), options, &self.tcp_connections) {
+                    Ok(wired) => wires.push(Wire::new(Box::new(wired))),
+                    Err(WireError::Open { source, .. })
+                        if source.kind() == ErrorKind::AddrInUse =>
+                    {
+                        continue 'ports;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            return Ok(wires);
+        }
+        Err(WireError::PortsExhausted {
+            locator: locator(ips[0], base_port),
+        })
+    }
 
-        let wired = loop {
-            let locator = Locator::new(LocatorKind::UdpV4, address, port as u32);
-            if let Ok(wire) = UdpV4Wire::new_listener(&locator, false) {
-                break wire;
+    /// Spawn a sender wire per reachable locator, the others are skipped
+    fn spawn_sender_wires(
+        &self,
+        locators: &[&Locator],
+    ) -> (Vec<ActorRef<SenderWireActor>>, Vec<Locator>) {
+        let mut wires = Vec::default();
+        let mut reachable_locators = Vec::default();
+        for locator in locators {
+            if !self.is_enabled(locator.kind) {
+                event!(Level::DEBUG, locator = %locator, "the transport of the locator is disabled");
+                continue;
             }
-            if port == u16::MAX {
-                panic!("all port has been exhausted");
+            match self.build_sender_wire_from_locator(locator) {
+                Ok(wire) => {
+                    wires.push(SenderWireActor::spawn(SenderWireActor { wire }));
+                    reachable_locators.push(**locator);
+                }
+                Err(e) => {
+                    event!(Level::ERROR, locator = %locator, error = %e, "cannot open a sender wire");
+                }
             }
-            port += 1;
+        }
+        (wires, reachable_locators)
+    }
+
+    /// Whether the configuration enables the wires of the locator `kind`
+    fn is_enabled(&self, kind: LocatorKind) -> bool {
+        match kind {
+            LocatorKind::UdpV4 => self.config.udpv6.udpv4_enabled(),
+            LocatorKind::UdpV6 => self.config.udpv6.enabled,
+            LocatorKind::Shm => self.config.shm.enabled,
+            LocatorKind::TcpV4 => self.config.tcp.enabled,
+            _ => false,
+        }
+    }
+
+    fn tcp_options(&self) -> Result<&TcpOptions, WireError> {
+        self.tcp_options
+            .as_ref()
+            .ok_or_else(|| Self::unsupported(LocatorKind::TcpV4))
+    }
+
+    /// The configured interface sends and joins the IPv6 multicast groups, otherwise the selected ones
+    fn multicast_options(&self) -> MulticastOptions {
+        let interfaces = match self.config.udpv6.multicast_interface {
+            0 => self.interfaces.ipv6_indexes.clone(),
+            interface => vec![interface],
         };
+        MulticastOptions {
+            hop_limit: self.config.udpv6.multicast_hop_limit.into(),
+            interfaces,
+        }
+    }
+
+    fn discovery_multicast_locator_v6(&self) -> Result<Locator, WireError> {
+        let address = self.config.get_udpv6_multicast_address().map_err(|e| {
+            WireError::CreationError(std::io::Error::new(ErrorKind::InvalidInput, e))
+        })?;
+        let port = self.generate_discovery_multicast_port();
+        Ok(Locator::new(LocatorKind::UdpV6, address.octets(), port))
+    }
+
+    /// The configured IPv6 unicast address, otherwise the selected ones, never empty
+    fn unicast_ipv6(&self) -> Result<Vec<Ipv6Addr>, WireError> {
+        let configured = self.config.get_udpv6_unicast_address().map_err(|e| {
+            WireError::CreationError(std::io::Error::new(ErrorKind::InvalidInput, e))
+        })?;
+        if let Some(ip) = configured {
+            return Ok(vec![ip]);
+        }
+        if self.interfaces.ipv6.is_empty() {
+            return Err(WireError::CreationError(std::io::Error::new(
+                ErrorKind::AddrNotAvailable,
+                "global.allowed_interfaces and global.denied_interfaces select no IPv6 address",
+            )));
+        }
+        Ok(self.interfaces.ipv6.clone())
+    }
 
-        let wire = Wire::new(Box::new(wired));
+    fn unsupported(kind: LocatorKind) -> WireError {
+        WireError::CreationError(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("unsupported locator kind {kind}"),
+        ))
+    }
 
-        Ok(wire)
+    /// The IPv4 addresses of the interfaces allowed and not denied by the configuration, never empty
+    fn selected_ipv4(&self) -> Result<&[Ipv4Addr], WireError> {
+        if self.interfaces.ipv4.is_empty() {
+            return Err(WireError::CreationError(std::io::Error::new(
+                ErrorKind::AddrNotAvailable,
+                "global.allowed_interfaces and global.denied_interfaces select no IPv4 address",
+            )));
+        }
+        Ok(&self.interfaces.ipv4)
     }
 
     fn generate_discovery_multicast_port(&self) -> u32 {
@@ -322,7 +681,10 @@ impl Message<SenderWireActorMessage> for SenderWireActor {
         msg: SenderWireActorMessage,
         _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
     ) -> Self::Reply {
-        self.wire.send(msg.buffer).await.unwrap()
+        // a message which can't be sent is lost like on the network, the reliability recovers it
+        if let Err(e) = self.wire.send(msg.buffer).await {
+            event!(Level::WARN, locator = %self.wire.locator(), error = %e, "message not sent");
+        }
     }
 }
 
@@ -363,10 +725,13 @@ where
         msg: ReceiverWireActorMessage<T>,
         _ctx: &mut kameo::prelude::Context<Self, Self::Reply>,
     ) -> Self::Reply {
-        let mut wire = self.wire.take().unwrap();
         let notifier = self.notifier.clone();
         match msg {
             ReceiverWireActorMessage::Start { actor_dest } => {
+                let Some(mut wire) = self.wire.take() else {
+                    event!(Level::ERROR, "the wire is already started");
+                    return;
+                };
                 tokio::spawn(async move {
                     loop {
                         select! {
@@ -407,11 +772,24 @@ impl Actor for ReceiverWireActor {
             wire: Some(args),
         })
     }
+
+    async fn on_stop(
+        &mut self,
+        _actor_ref: kameo::prelude::WeakActorRef<Self>,
+        _reason: kameo::prelude::ActorStopReason,
+    ) -> Result<(), Self::Error> {
+        // the reception task stops with its wire, the socket is then released
+        self.notifier.notify_one();
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use std::marker::PhantomData;
+    use std::{
+        marker::PhantomData,
+        net::{Ipv4Addr, Ipv6Addr},
+    };
 
     use kameo::{
         Actor,
@@ -420,6 +798,10 @@ mod test {
         prelude::Message,
     };
     use tokio::net::UdpSocket;
+    use troc_core::GuidPrefix;
+
+    use super::WireFactoryActor;
+    use crate::domain::{Configuration, UdpHelper};
 
     trait Sendable: Actor + Sized {
         type Msg: Send + 'static;
@@ -585,4 +967,32 @@ mod test {
             .await
             .unwrap();
     }
+
+    fn factory(allowed_interfaces: &[&str]) -> WireFactoryActor {
+        let mut config = Configuration::default();
+        config.global.allowed_interfaces =
+            allowed_interfaces.iter().map(|s| s.to_string()).collect();
+        config.tcp.enabled = true;
+        WireFactoryActor::new(0, GuidPrefix::new(Default::default()), config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn tcp_listeners_on_selected_interfaces() {
+        let factory = factory(&["127.0.0.0/8"]);
+        let wires = factory.build_tcp_listener_wires(17400).unwrap();
+        assert!(!wires.is_empty());
+        let loopback = UdpHelper::from_ipv4addr_to_generic_addr(Ipv4Addr::LOCALHOST);
+        assert!(wires.iter().all(|w| w.locator().address == loopback));
+    }
+
+    #[tokio::test]
+    async fn ipv6_unicast_on_selected_interfaces() {
+        assert_eq!(
+            factory(&["::1"]).unicast_ipv6().unwrap(),
+            vec![Ipv6Addr::LOCALHOST]
+        );
+        assert!(factory(&["127.0.0.0/8"]).unicast_ipv6().is_err());
+        let defaults = factory(&[]).unicast_ipv6().unwrap_or_default();
+        assert!(defaults.iter().all(|ip| !ip.is_unicast_link_local()));
+    }
 }
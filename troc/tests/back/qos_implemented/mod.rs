@@ -1,3 +1 @@
-mod deadline;
-mod lifespan;
-mod liveliness;
\ No newline at end of file
+mod liveliness;
@@ -0,0 +1,48 @@
+use std::{net::Ipv4Addr, time::Duration};
+
+use rstest::*;
+use troc::{Configuration, LocatorKind, TopicKind};
+
+use crate::fixture::{
+    TwoParticipantsBundle, build_payload, exchange, reliable_qos, setup_log, two_participants,
+};
+
+fn loopback_only() -> Configuration {
+    let mut configuration = Configuration::default();
+    configuration.global.allowed_interfaces = vec![String::from("127.0.0.0/8")];
+    configuration
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn exchange_over_allowed_interface(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "comm/interfaces/loopback",
+        TopicKind::NoKey,
+        reliable_qos(),
+        reliable_qos(),
+        0,
+        0,
+        loopback_only()
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let locators = exchange(&mut bundle, &payload, 1).await;
+    let udpv4_locators = locators
+        .iter()
+        .filter(|l| l.kind == LocatorKind::UdpV4)
+        .copied()
+        .collect::<Vec<_>>();
+    assert!(!udpv4_locators.is_empty());
+    assert!(
+        udpv4_locators
+            .iter()
+            .all(|l| l.address[12..] == Ipv4Addr::LOCALHOST.octets())
+    );
+}
@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use rstest::*;
+use troc::{
+    Configuration, DurationKind, HistoryQosPolicy, QosPolicy, QosPolicyBuilder,
+    ReliabilityQosPolicy, TopicKind,
+};
+
+use crate::fixture::{
+    DummyStruct, TwoParticipantsBundle, build_payload, setup_log, two_participants,
+};
+
+fn reliable_keep_all_qos() -> QosPolicy {
+    QosPolicyBuilder::new()
+        .reliability(ReliabilityQosPolicy::Reliable {
+            max_blocking_time: Default::default(),
+        })
+        .history(HistoryQosPolicy::KeepAll)
+        .build()
+}
+
+fn intra_process_enabled() -> Configuration {
+    let mut configuration = Configuration::default();
+    configuration.intra_process.enabled = true;
+    configuration
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn exchange_intra_process(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "comm/intra_process/exchange",
+        TopicKind::NoKey,
+        reliable_keep_all_qos(),
+        reliable_keep_all_qos(),
+        0,
+        0,
+        intra_process_enabled()
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    for i in 0..50 {
+        let expected_msg = DummyStruct::new(i, &payload);
+        bundle
+            .beta_writer
+            .write(expected_msg.clone())
+            .await
+            .unwrap();
+        let sample = bundle.alpha_reader.read_next_sample().await.unwrap();
+        assert_eq!(sample.take_data().unwrap(), expected_msg);
+    }
+    // the acknowledgments go back to the DataWriter the same way
+    bundle
+        .beta_writer
+        .wait_for_acknowledgments(DurationKind::Finite(Duration::from_secs(5)))
+        .await
+        .unwrap();
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn exchange_without_intra_process(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "comm/intra_process/disabled",
+        TopicKind::NoKey,
+        reliable_keep_all_qos(),
+        reliable_keep_all_qos(),
+        0,
+        0,
+        Configuration::default()
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    let expected_msg = DummyStruct::new(0, &payload);
+    bundle
+        .beta_writer
+        .write(expected_msg.clone())
+        .await
+        .unwrap();
+    let sample = bundle.alpha_reader.read_next_sample().await.unwrap();
+    assert_eq!(sample.take_data().unwrap(), expected_msg);
+}
@@ -1,11 +1,15 @@
 use std::time::Duration;
 
-use troc::{DdsError, TopicKind};
+use chrono::Utc;
+use troc::{
+    DdsError, DurationKind, InstanceHandle, InstanceStateKind, InstanceStateMask, QosPolicy,
+    ReadCondition, SampleStateKind, SampleStateMask, TopicKind, ViewStateKind, ViewStateMask,
+};
 
 use rstest::*;
 
 use crate::fixture::{
-    DummyStruct, TwoParticipantsBundle, build_payload, setup_log, two_participants,
+    DummyStruct, TwoParticipantsBundle, build_payload, build_qos, setup_log, two_participants,
 };
 
 #[rstest]
@@ -42,3 +46,104 @@ async fn endpoints_have_matched(
         .await
         .unwrap();
 }
+
+#[rstest]
+#[timeout(Duration::from_secs(10))]
+#[tokio::test]
+async fn read_with_state_masks(
+    #[from(setup_log)] _setup_log: (),
+    #[from(build_qos)] _qos: QosPolicy,
+    #[with(format!("comm/keyed/masks"), TopicKind::WithKey, _qos.clone(), _qos.clone())]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    reader_listener
+        .wait_publication_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    let first = DummyStruct::new(1, &payload);
+    let second = DummyStruct::new(2, &payload);
+    bundle.beta_writer.write(first.clone()).await.unwrap();
+    bundle.beta_writer.write(second.clone()).await.unwrap();
+
+    // reading the next sample of an instance gives its states before the access
+    let sample = bundle
+        .alpha_reader
+        .read_next_sample_instance(&second)
+        .await
+        .unwrap();
+    assert_eq!(sample.data(), Some(&second));
+    assert_eq!(sample.infos.sample_state, SampleStateKind::NotRead);
+    assert_eq!(sample.infos.view_state, ViewStateKind::New);
+    assert_eq!(sample.infos.instance_state, InstanceStateKind::Alive);
+
+    let not_viewed = ReadCondition::new(
+        SampleStateMask::ANY,
+        ViewStateMask::NEW,
+        InstanceStateMask::ALIVE,
+    );
+    let sample = bundle.alpha_reader.read_next_sample().await.unwrap();
+    assert_eq!(sample.data(), Some(&first));
+    match bundle.alpha_reader.read(10, not_viewed).await {
+        Err(DdsError::NoData) => (),
+        other => panic!("every instance has been viewed: {other:?}"),
+    }
+
+    let samples = bundle
+        .alpha_reader
+        .take(10, ReadCondition::read())
+        .await
+        .unwrap();
+    assert_eq!(samples.len(), 2);
+    assert!(samples.iter().all(|sample| {
+        sample.infos.sample_state == SampleStateKind::Read
+            && sample.infos.view_state == ViewStateKind::NotNew
+    }));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(10))]
+#[tokio::test]
+async fn sample_info_identifies_the_writer(
+    #[from(setup_log)] _setup_log: (),
+    #[from(build_qos)] _qos: QosPolicy,
+    #[with(format!("comm/keyed/sample_info"), TopicKind::WithKey, _qos.clone(), _qos.clone())]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    let msg = DummyStruct::new(1, &payload);
+    let before = Utc::now();
+    bundle.beta_writer.write(msg.clone()).await.unwrap();
+    let after = Utc::now();
+
+    let sample = bundle.alpha_reader.read_next_sample().await.unwrap();
+    assert_eq!(sample.data(), Some(&msg));
+    // the source timestamp is the one of the INFO_TS sent by the writer, rounded to its fractions of second
+    let source_timestamp = sample.infos.source_timestamp.to_datetime();
+    let precision = chrono::Duration::milliseconds(1);
+    assert!(before - precision <= source_timestamp && source_timestamp <= after + precision);
+    assert_eq!(
+        sample.infos.publication_handle,
+        InstanceHandle::new(bundle.beta_writer.get_guid().as_bytes())
+    );
+}
@@ -1,2 +1,7 @@
+mod interfaces;
+mod intra_process;
 mod keyed;
 mod reliability;
+mod shm;
+mod tcp;
+mod udpv6;
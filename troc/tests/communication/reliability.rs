@@ -28,8 +28,8 @@ async fn endpoints_have_matched(
     #[with(
         "comm/reliability/exchange",
         TopicKind::NoKey,
-        _reader_qos,
-        _writer_qos
+        _reader_qos.clone(),
+        _writer_qos.clone()
     )]
     #[future]
     two_participants: TwoParticipantsBundle,
@@ -65,6 +65,44 @@ async fn endpoints_have_matched(
     }
 }
 
+#[rstest]
+#[timeout(Duration::from_secs(10))]
+#[tokio::test]
+async fn writer_waits_for_acknowledgments(
+    #[from(setup_log)] _setup_log: (),
+    #[from(build_qos)] _qos: QosPolicy,
+    #[with(
+        "comm/reliability/acknowledgments",
+        TopicKind::NoKey,
+        _qos.clone(),
+        _qos.clone()
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    for i in 0..10 {
+        bundle
+            .beta_writer
+            .write(DummyStruct::new(i, &payload))
+            .await
+            .unwrap();
+    }
+    bundle
+        .beta_writer
+        .wait_for_acknowledgments(DurationKind::Finite(Duration::from_secs(5)))
+        .await
+        .unwrap();
+}
+
 // FIXME: this test case should be ok, but now it doesn't pass because Durability QoS is not implemented
 // #[rstest]
 // #[tokio::test]
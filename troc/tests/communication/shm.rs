@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use rstest::*;
+use troc::{Configuration, LocatorKind, TopicKind};
+
+use crate::fixture::{
+    TwoParticipantsBundle, build_payload, exchange, reliable_qos, setup_log, two_participants,
+};
+
+fn shm_enabled() -> Configuration {
+    let mut configuration = Configuration::default();
+    configuration.shm.enabled = true;
+    configuration
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn exchange_over_shm(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "comm/shm/exchange",
+        TopicKind::NoKey,
+        reliable_qos(),
+        reliable_qos(),
+        0,
+        0,
+        shm_enabled()
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let locators = exchange(&mut bundle, &payload, 10).await;
+    // the UDP locators are advertised too, for the participants of the other hosts
+    assert!(locators.iter().any(|l| l.kind == LocatorKind::Shm));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn exchange_over_udp_without_shm(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "comm/shm/disabled",
+        TopicKind::NoKey,
+        reliable_qos(),
+        reliable_qos(),
+        0,
+        0,
+        Configuration::default()
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let locators = exchange(&mut bundle, &payload, 1).await;
+    assert!(locators.iter().all(|l| l.kind != LocatorKind::Shm));
+}
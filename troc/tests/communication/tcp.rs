@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use rstest::*;
+use troc::{Configuration, LocatorKind, TopicKind};
+
+use crate::fixture::{
+    TwoParticipantsBundle, build_payload, exchange, reliable_qos, setup_log, two_participants,
+};
+
+fn tcp_enabled() -> Configuration {
+    let mut configuration = Configuration::default();
+    configuration.tcp.enabled = true;
+    configuration
+}
+
+/// The listeners present the certificate of `troc`, issued by the authority the participants trust
+#[cfg(feature = "tls")]
+fn tls_enabled() -> Configuration {
+    let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixture/tls");
+    let mut configuration = tcp_enabled();
+    configuration.tcp.tls.enabled = true;
+    configuration.tcp.tls.certificate_chain = Some(directory.join("chain.pem"));
+    configuration.tcp.tls.private_key = Some(directory.join("key.pem"));
+    configuration.tcp.tls.root_certificates = Some(directory.join("ca.pem"));
+    configuration
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn exchange_over_tcp(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "comm/tcp/exchange",
+        TopicKind::NoKey,
+        reliable_qos(),
+        reliable_qos(),
+        0,
+        0,
+        tcp_enabled()
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let locators = exchange(&mut bundle, &payload, 10).await;
+    assert!(locators.iter().any(|l| l.kind == LocatorKind::TcpV4));
+}
+
+#[cfg(feature = "tls")]
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn exchange_over_tls(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "comm/tcp/tls",
+        TopicKind::NoKey,
+        reliable_qos(),
+        reliable_qos(),
+        0,
+        0,
+        tls_enabled()
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let locators = exchange(&mut bundle, &payload, 1).await;
+    assert!(locators.iter().any(|l| l.kind == LocatorKind::TcpV4));
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use rstest::*;
+use troc::{Configuration, LocatorKind, TopicKind};
+
+use crate::fixture::{
+    TwoParticipantsBundle, build_payload, exchange, reliable_qos, setup_log, two_participants,
+};
+
+fn udpv6_only() -> Configuration {
+    let mut configuration = Configuration::default();
+    configuration.udpv6.enabled = true;
+    configuration.udpv6.exclusive = true;
+    configuration
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn exchange_over_udpv6_only(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "comm/udpv6/exchange",
+        TopicKind::NoKey,
+        reliable_qos(),
+        reliable_qos(),
+        0,
+        0,
+        udpv6_only()
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let locators = exchange(&mut bundle, &payload, 1).await;
+    assert!(locators.iter().all(|l| l.kind == LocatorKind::UdpV6));
+}
@@ -74,8 +74,8 @@ async fn domain_isolation(
     #[with(
         "discovery/basic/domain_isolation",
         TopicKind::NoKey,
-        _qos,
-        _qos,
+        _qos.clone(),
+        _qos.clone(),
         DOMAIN_ID_98,
         DOMAIN_ID_99
     )]
@@ -0,0 +1,140 @@
+//! This module regroup tests that assert deleted entities are promptly forgotten by the remote Participants
+
+use std::time::Duration;
+
+use troc::{DdsError, DurationKind, TopicKind};
+
+use rstest::*;
+
+use crate::fixture::{TwoParticipantsBundle, setup_log, two_participants};
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn deleted_datawriter_is_unmatched(
+    #[from(setup_log)] _setup_log: (),
+    #[with(format!("discovery/deletion/datawriter"), TopicKind::NoKey)]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+) {
+    let mut bundle = two_participants.await;
+    let writer_guid = bundle.beta_writer.get_guid();
+
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    reader_listener
+        .wait_publication_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    // the Publisher still contains its DataWriter
+    let res = bundle
+        .beta_domain_participant
+        .delete_publisher(&bundle.beta_publisher)
+        .await;
+    assert!(matches!(res, Err(DdsError::PreconditionNotMet)));
+
+    // the DataWriter belongs to another Publisher, it is kept
+    let mut other_publisher = bundle
+        .beta_domain_participant
+        .create_publisher(&Default::default())
+        .await
+        .unwrap();
+    let res = other_publisher.delete_datawriter(&bundle.beta_writer).await;
+    assert!(matches!(res, Err(DdsError::PreconditionNotMet)));
+
+    bundle
+        .beta_publisher
+        .delete_datawriter(&bundle.beta_writer)
+        .await
+        .unwrap();
+    let stopped_guid = reader_listener
+        .wait_publication_stopped(DurationKind::Finite(Duration::from_secs(3)))
+        .await
+        .unwrap();
+    assert_eq!(stopped_guid, writer_guid);
+
+    bundle
+        .beta_domain_participant
+        .delete_publisher(&bundle.beta_publisher)
+        .await
+        .unwrap();
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn deleted_datareader_is_unmatched(
+    #[from(setup_log)] _setup_log: (),
+    #[with(format!("discovery/deletion/datareader"), TopicKind::NoKey)]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+) {
+    let mut bundle = two_participants.await;
+    let reader_guid = bundle.alpha_reader.get_guid();
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    bundle
+        .alpha_subscriber
+        .delete_datareader(&bundle.alpha_reader)
+        .await
+        .unwrap();
+    let stopped_guid = writer_listener
+        .wait_subscription_stopped(DurationKind::Finite(Duration::from_secs(3)))
+        .await
+        .unwrap();
+    assert_eq!(stopped_guid, reader_guid);
+
+    bundle
+        .alpha_domain_participant
+        .delete_contained_entities()
+        .await
+        .unwrap();
+    // the Subscriber has been deleted with the other contained entities
+    let res = bundle
+        .alpha_domain_participant
+        .delete_subscriber(&bundle.alpha_subscriber)
+        .await;
+    assert!(matches!(res, Err(DdsError::PreconditionNotMet)));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn participant_shutdown_is_announced(
+    #[from(setup_log)] _setup_log: (),
+    #[with(format!("discovery/deletion/participant"), TopicKind::NoKey)]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+) {
+    let bundle = two_participants.await;
+    let beta_guid_prefix = bundle.beta_domain_participant.get_guid().get_guid_prefix();
+
+    let mut participant_listener = bundle
+        .alpha_domain_participant
+        .get_listener()
+        .await
+        .unwrap();
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    reader_listener
+        .wait_publication_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    bundle.beta_domain_participant.shutdown().await.unwrap();
+
+    // the lease of the Participant has not expired yet, the removal comes from its disposal
+    let removed = participant_listener
+        .wait_participant_removed(DurationKind::Finite(Duration::from_secs(2)))
+        .await
+        .unwrap();
+    assert_eq!(removed.get_guid_prefix(), beta_guid_prefix);
+    reader_listener
+        .wait_publication_stopped(DurationKind::Finite(Duration::from_secs(2)))
+        .await
+        .unwrap();
+}
@@ -24,7 +24,7 @@ async fn reliability(
     #[with(_writer_reliability_qos)]
     #[from(build_qos)]
     _writer_qos: QosPolicy,
-    #[with(format!("discovery/matching/reliability"), TopicKind::NoKey, _reader_qos, _writer_qos)]
+    #[with(format!("discovery/matching/reliability"), TopicKind::NoKey, _reader_qos.clone(), _writer_qos.clone())]
     #[future]
     two_participants: TwoParticipantsBundle,
 ) {
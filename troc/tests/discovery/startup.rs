@@ -0,0 +1,86 @@
+//! This module regroup tests that assert a DomainParticipant reports its startup errors instead of panicking
+
+use std::{
+    net::{IpAddr, SocketAddr, UdpSocket},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::fixture::{get_unique_id, setup_log};
+use troc::{Configuration, DdsError, DomainParticipantBuilder, DomainTag};
+
+use rstest::*;
+
+#[rstest]
+#[timeout(Duration::from_secs(5))]
+#[tokio::test]
+async fn invalid_configuration_is_reported(
+    #[from(setup_log)] _setup_log: (),
+    #[from(get_unique_id)] unique_id: String,
+) {
+    let mut configuration = Configuration::default();
+    configuration.global.domain_tag = DomainTag::from_str(&unique_id).unwrap();
+    configuration.global.fragment_size = 1024;
+    configuration.global.data_max_size_serialized = 512;
+
+    let res = DomainParticipantBuilder::new()
+        .with_config(configuration)
+        .build()
+        .await;
+    assert!(matches!(res, Err(DdsError::InvalidConfiguration(_))));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(5))]
+#[tokio::test]
+async fn configuration_and_file_are_exclusive(
+    #[from(setup_log)] _setup_log: (),
+    #[from(get_unique_id)] unique_id: String,
+) {
+    let mut configuration = Configuration::default();
+    configuration.global.domain_tag = DomainTag::from_str(&unique_id).unwrap();
+
+    // the file is rejected before being read
+    let res = DomainParticipantBuilder::new()
+        .with_config(configuration)
+        .with_config_file("troc.toml")
+        .build()
+        .await;
+    assert!(matches!(res, Err(DdsError::InvalidConfiguration(_))));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(5))]
+#[tokio::test]
+async fn port_clash_is_reported(
+    #[from(setup_log)] _setup_log: (),
+    #[from(get_unique_id)] unique_id: String,
+) {
+    // the discovery unicast port of the participant is the last one, it cannot move further
+    let mut configuration = Configuration::default();
+    configuration.global.domain_tag = DomainTag::from_str(&unique_id).unwrap();
+    configuration.global.port_base = 65522;
+    configuration.global.d1 = 11;
+    configuration.global.participant_gain = 2;
+    let IpAddr::V4(ip) = local_ip_address::local_ip().unwrap() else {
+        panic!("no local IPv4 address")
+    };
+    let _socket = UdpSocket::bind(SocketAddr::from((ip, u16::MAX))).unwrap();
+
+    let res = DomainParticipantBuilder::new()
+        .with_config(configuration.clone())
+        .build()
+        .await;
+    let Err(DdsError::Transport { locator, .. }) = res else {
+        panic!("unexpected result {res:?}")
+    };
+    assert_eq!(locator.port, u32::from(u16::MAX));
+
+    // retrying with another port base succeeds
+    configuration.global.port_base = 7400;
+    let res = DomainParticipantBuilder::new()
+        .with_config(configuration)
+        .build()
+        .await;
+    assert!(res.is_ok());
+}
@@ -9,8 +9,8 @@ use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitEx
 use troc::{
     Configuration, DataReader, DataWriter, DeadlineQosPolicy, DomainParticipant,
     DomainParticipantBuilder, DomainTag, DurabilityQosPolicy, DurationKind, EntityId, Guid,
-    GuidPrefix, HistoryQosPolicy, LifespanQosPolicy, LivelinessQosPolicy, Publisher, QosPolicy,
-    QosPolicyBuilder, ReliabilityQosPolicy, Subscriber, TopicKind, VendorId,
+    GuidPrefix, HistoryQosPolicy, LifespanQosPolicy, LivelinessQosPolicy, LocatorList, Publisher,
+    QosPolicy, QosPolicyBuilder, ReliabilityQosPolicy, Subscriber, TopicKind, VendorId,
 };
 
 pub struct TwoParticipantsBundle {
@@ -92,13 +92,13 @@ pub async fn two_participants(
     #[default(QosPolicy::default())] writer_qos: QosPolicy,
     #[default(0)] alpha_domain_id: u32,
     #[default(0)] beta_domain_id: u32,
+    #[default(Configuration::default())] mut configuration: Configuration,
     #[from(get_guid)] alpha_guid: Guid,
     #[from(get_guid)]
     #[with(1)]
     beta_guid: Guid,
     #[from(get_unique_id)] unique_id: String,
 ) -> TwoParticipantsBundle {
-    let mut configuration = Configuration::default();
     configuration.global.domain_tag = DomainTag::from_str(&unique_id).unwrap();
     configuration.discovery.announcement_period = Duration::from_secs(1);
     configuration.discovery.lease_duration = Duration::from_secs(3);
@@ -108,14 +108,16 @@ pub async fn two_participants(
         .with_domain(alpha_domain_id)
         .with_config(configuration.clone())
         .build()
-        .await;
+        .await
+        .unwrap();
 
     let mut beta_domain_participant = DomainParticipantBuilder::new()
         .with_guid(beta_guid)
         .with_domain(beta_domain_id)
         .with_config(configuration.clone())
         .build()
-        .await;
+        .await
+        .unwrap();
 
     let topic_name = build_test_topic(topic_name.as_ref());
     let topic =
@@ -165,7 +167,8 @@ pub async fn three_participants(
         .with_domain(domain_id)
         .with_config(configuration.clone())
         .build()
-        .await;
+        .await
+        .unwrap();
 
     let qos = alpha_domain_participant
         .create_qos_builder()
@@ -204,7 +207,8 @@ pub async fn three_participants(
         .with_domain(domain_id)
         .with_config(configuration.clone())
         .build()
-        .await;
+        .await
+        .unwrap();
 
     let mut beta_subscriber = beta_domain_participant
         .create_subscriber(&qos)
@@ -231,7 +235,8 @@ pub async fn three_participants(
         .with_domain(domain_id)
         .with_config(configuration)
         .build()
-        .await;
+        .await
+        .unwrap();
 
     let mut gamma_subscriber = gamma_domain_participant
         .create_subscriber(&qos)
@@ -360,6 +365,43 @@ pub fn build_payload(#[default(4)] size: u32) -> Vec<u8> {
     std::iter::repeat_n(7u8, size as usize).collect::<Vec<u8>>()
 }
 
+/// Reliable, keeping the last 10 samples of an instance
+pub fn reliable_qos() -> QosPolicy {
+    QosPolicyBuilder::new()
+        .reliability(ReliabilityQosPolicy::Reliable {
+            max_blocking_time: Default::default(),
+        })
+        .history(HistoryQosPolicy::KeepLast { depth: 10 })
+        .build()
+}
+
+/// Write `count` samples of `payload` once the endpoints of the bundle match, each one is read before the next
+///
+/// Return the locators of the matched reader, the samples went through one of them
+pub async fn exchange(
+    bundle: &mut TwoParticipantsBundle,
+    payload: &[u8],
+    count: u8,
+) -> LocatorList {
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    let reader_proxy = writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    for i in 0..count {
+        let expected_msg = DummyStruct::new(i, payload);
+        bundle
+            .beta_writer
+            .write(expected_msg.clone())
+            .await
+            .unwrap();
+        let sample = bundle.alpha_reader.read_next_sample().await.unwrap();
+        assert_eq!(sample.take_data().unwrap(), expected_msg);
+    }
+    reader_proxy.get_locators()
+}
+
 // /// Test with multiple writers and a single reader
 // #[fixture]
 // pub async fn multiple_writers_single_reader(
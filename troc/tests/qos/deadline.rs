@@ -0,0 +1,213 @@
+use std::time::Duration;
+
+use chrono::DateTime;
+use rstest::*;
+use troc::{DeadlineQosPolicy, DurationKind, QosPolicy, Timestamp, TopicKind};
+
+use crate::fixture::{
+    DummyStruct, TwoParticipantsBundle, build_payload, build_qos, setup_log, two_participants,
+};
+
+fn deadline(period: Duration) -> DeadlineQosPolicy {
+    DeadlineQosPolicy {
+        period: Timestamp::from_datetime(
+            DateTime::from_timestamp_millis(period.as_millis() as i64).unwrap(),
+        ),
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn deadline_missed(
+    #[from(setup_log)] _setup_log: (),
+    #[from(build_qos)]
+    #[with(
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        deadline(Duration::from_millis(500))
+    )]
+    _qos: QosPolicy,
+    #[with("qos/deadline/missed", TopicKind::NoKey, _qos.clone(), _qos.clone())]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    reader_listener
+        .wait_publication_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    bundle
+        .beta_writer
+        .write(DummyStruct::new(0, &payload))
+        .await
+        .unwrap();
+
+    let offered = writer_listener
+        .wait_offered_deadline_missed(DurationKind::Finite(Duration::from_secs(3)))
+        .await
+        .unwrap();
+    assert!(offered.total_count >= 1);
+    assert!(offered.total_count_change >= 1);
+
+    let requested = reader_listener
+        .wait_requested_deadline_missed(DurationKind::Finite(Duration::from_secs(3)))
+        .await
+        .unwrap();
+    assert!(requested.total_count >= 1);
+    assert!(requested.total_count_change >= 1);
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn deadline_kept_by_periodic_writes(
+    #[from(setup_log)] _setup_log: (),
+    #[from(build_qos)]
+    #[with(
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        deadline(Duration::from_millis(500))
+    )]
+    _qos: QosPolicy,
+    #[with("qos/deadline/kept", TopicKind::NoKey, _qos.clone(), _qos.clone())]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    // the deadline is tracked per instance, every write updates the same one
+    let expected_msg = DummyStruct::new(0, &payload);
+    for _ in 0..5 {
+        bundle
+            .beta_writer
+            .write(expected_msg.clone())
+            .await
+            .unwrap();
+        let sample = bundle.alpha_reader.read_next_sample().await.unwrap();
+        assert_eq!(sample.take_data().unwrap(), expected_msg);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let res = writer_listener
+        .wait_offered_deadline_missed(DurationKind::Finite(Duration::from_millis(200)))
+        .await;
+    assert!(res.is_err());
+
+    // after a miss, the samples keep being delivered
+    writer_listener
+        .wait_offered_deadline_missed(DurationKind::Finite(Duration::from_secs(3)))
+        .await
+        .unwrap();
+    bundle
+        .beta_writer
+        .write(expected_msg.clone())
+        .await
+        .unwrap();
+    let sample = bundle.alpha_reader.read_next_sample().await.unwrap();
+    assert_eq!(sample.take_data().unwrap(), expected_msg);
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn infinite_deadline_is_never_missed(
+    #[from(setup_log)] _setup_log: (),
+    #[with("qos/deadline/infinite")]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    bundle
+        .beta_writer
+        .write(DummyStruct::new(0, &payload))
+        .await
+        .unwrap();
+    bundle.alpha_reader.read_next_sample().await.unwrap();
+
+    let res = writer_listener
+        .wait_offered_deadline_missed(DurationKind::Finite(Duration::from_secs(1)))
+        .await;
+    assert!(res.is_err());
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn shorter_deadline_applies_at_once(
+    #[from(setup_log)] _setup_log: (),
+    #[from(build_qos)]
+    #[with(
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        deadline(Duration::from_secs(60))
+    )]
+    _qos: QosPolicy,
+    #[with("qos/deadline/shorter", TopicKind::NoKey, _qos.clone(), _qos.clone())]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    bundle
+        .beta_writer
+        .write(DummyStruct::new(0, &payload))
+        .await
+        .unwrap();
+
+    // the check pending for the former period would only report the miss a minute later
+    let qos = build_qos(
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        deadline(Duration::from_millis(200)),
+    );
+    bundle.beta_writer.set_qos(&qos).await.unwrap();
+    writer_listener
+        .wait_offered_deadline_missed(DurationKind::Finite(Duration::from_secs(3)))
+        .await
+        .unwrap();
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use rstest::*;
+use troc::{DestinationOrderQosPolicy, DurationKind, QosPolicy, QosPolicyBuilder, TopicKind};
+
+use crate::fixture::{
+    DummyStruct, TwoParticipantsBundle, build_payload, setup_log, two_participants,
+};
+
+fn ordered_qos(destination_order: DestinationOrderQosPolicy) -> QosPolicy {
+    QosPolicyBuilder::new()
+        .destination_order(destination_order)
+        .build()
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn by_source_timestamp_is_delivered(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "qos/destination_order/by_source",
+        TopicKind::NoKey,
+        ordered_qos(DestinationOrderQosPolicy::BySourceTimestamp),
+        ordered_qos(DestinationOrderQosPolicy::BySourceTimestamp)
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    bundle
+        .beta_writer
+        .write(DummyStruct::new(0, &payload))
+        .await
+        .unwrap();
+
+    let sample = bundle
+        .alpha_reader
+        .read_next_sample_timeout(Duration::from_secs(3))
+        .await
+        .unwrap();
+    assert_eq!(sample.data.unwrap(), DummyStruct::new(0, &payload));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn by_reception_offered_to_by_source_does_not_match(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "qos/destination_order/mismatch",
+        TopicKind::NoKey,
+        ordered_qos(DestinationOrderQosPolicy::BySourceTimestamp),
+        ordered_qos(DestinationOrderQosPolicy::ByReceptionTimestamp)
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+) {
+    let bundle = two_participants.await;
+
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    let res = reader_listener
+        .wait_publication_matched(DurationKind::Finite(Duration::from_secs(3)))
+        .await;
+    assert!(res.is_err());
+}
@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use rstest::*;
+use troc::{
+    DurabilityQosPolicy, DurationKind, HistoryQosPolicy, QosPolicy, ReadCondition,
+    ReliabilityQosPolicy, TopicKind,
+};
+
+use crate::fixture::{
+    DummyStruct, TwoParticipantsBundle, build_payload, build_qos, build_test_topic, setup_log,
+    two_participants,
+};
+
+async fn late_joining_reader_receives_history(
+    mut bundle: TwoParticipantsBundle,
+    topic_name: &str,
+    qos: QosPolicy,
+    payload: Vec<u8>,
+) {
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    for i in 0..3 {
+        bundle
+            .beta_writer
+            .write(DummyStruct::new(i, &payload))
+            .await
+            .unwrap();
+    }
+
+    let topic = bundle.alpha_domain_participant.create_topic(
+        build_test_topic(topic_name),
+        "DummyStruct",
+        &qos,
+        TopicKind::NoKey,
+    );
+    let mut late_reader = bundle
+        .alpha_subscriber
+        .create_datareader::<DummyStruct>(&topic, &qos)
+        .await
+        .unwrap();
+
+    let mut reader_listener = late_reader.get_listener().await.unwrap();
+    reader_listener
+        .wait_publication_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    // only the samples retained by the writer HISTORY are delivered
+    let mut received = Vec::new();
+    while received.len() < 2 {
+        if let Ok(samples) = late_reader.take(10, ReadCondition::any()).await {
+            received.extend(samples.into_iter().filter_map(|s| s.data().cloned()));
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(
+        received,
+        vec![DummyStruct::new(1, &payload), DummyStruct::new(2, &payload)]
+    );
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn reliable_late_joiner(
+    #[from(setup_log)] _setup_log: (),
+    #[from(build_qos)]
+    #[with(
+        Default::default(),
+        HistoryQosPolicy::KeepLast { depth: 2 },
+        DurabilityQosPolicy::TransientLocal
+    )]
+    qos: QosPolicy,
+    #[with("qos/durability/reliable", TopicKind::NoKey, qos.clone(), qos.clone())]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    late_joining_reader_receives_history(
+        two_participants.await,
+        "qos/durability/reliable",
+        qos,
+        payload,
+    )
+    .await;
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn best_effort_late_joiner(
+    #[from(setup_log)] _setup_log: (),
+    #[from(build_qos)]
+    #[with(
+        ReliabilityQosPolicy::BestEffort,
+        HistoryQosPolicy::KeepLast { depth: 2 },
+        DurabilityQosPolicy::TransientLocal
+    )]
+    qos: QosPolicy,
+    #[with("qos/durability/best_effort", TopicKind::NoKey, qos.clone(), qos.clone())]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    late_joining_reader_receives_history(
+        two_participants.await,
+        "qos/durability/best_effort",
+        qos,
+        payload,
+    )
+    .await;
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn volatile_late_joiner(
+    #[from(setup_log)] _setup_log: (),
+    #[from(build_qos)]
+    #[with(
+        Default::default(),
+        HistoryQosPolicy::KeepLast { depth: 2 },
+        DurabilityQosPolicy::TransientLocal
+    )]
+    _qos: QosPolicy,
+    #[from(build_qos)]
+    #[with(Default::default(), HistoryQosPolicy::KeepLast { depth: 2 })]
+    volatile_qos: QosPolicy,
+    #[with("qos/durability/volatile", TopicKind::NoKey, _qos.clone(), _qos.clone())]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    for i in 0..3 {
+        bundle
+            .beta_writer
+            .write(DummyStruct::new(i, &payload))
+            .await
+            .unwrap();
+    }
+
+    let topic = bundle.alpha_domain_participant.create_topic(
+        build_test_topic("qos/durability/volatile"),
+        "DummyStruct",
+        &volatile_qos,
+        TopicKind::NoKey,
+    );
+    let mut late_reader = bundle
+        .alpha_subscriber
+        .create_datareader::<DummyStruct>(&topic, &volatile_qos)
+        .await
+        .unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    // the VOLATILE reader only gets the samples written once it is matched
+    bundle
+        .beta_writer
+        .write(DummyStruct::new(3, &payload))
+        .await
+        .unwrap();
+    let sample = late_reader.read_next_sample().await.unwrap();
+    assert_eq!(sample.data(), Some(&DummyStruct::new(3, &payload)));
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(
+        late_reader
+            .read(10, ReadCondition::any())
+            .await
+            .is_ok_and(|samples| samples.len() == 1)
+    );
+}
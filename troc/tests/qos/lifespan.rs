@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use chrono::DateTime;
+use rstest::*;
+use troc::{
+    DdsError, DurationKind, LifespanQosPolicy, QosPolicy, ReadCondition, Timestamp, TopicKind,
+};
+
+use crate::fixture::{
+    DummyStruct, TwoParticipantsBundle, build_payload, build_qos, setup_log, two_participants,
+};
+
+fn lifespan(duration: Duration) -> LifespanQosPolicy {
+    LifespanQosPolicy {
+        duration: Timestamp::from_datetime(
+            DateTime::from_timestamp_millis(duration.as_millis() as i64).unwrap(),
+        ),
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn expired_samples_are_not_delivered(
+    #[from(setup_log)] _setup_log: (),
+    #[from(build_qos)]
+    #[with(
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        lifespan(Duration::from_millis(500))
+    )]
+    _qos: QosPolicy,
+    #[with("qos/lifespan/expired", TopicKind::NoKey, _qos.clone(), _qos.clone())]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    reader_listener
+        .wait_publication_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    bundle
+        .beta_writer
+        .write(DummyStruct::new(0, &payload))
+        .await
+        .unwrap();
+
+    let sample = bundle.alpha_reader.read_next_sample().await.unwrap();
+    assert_eq!(sample.data().unwrap(), &DummyStruct::new(0, &payload));
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    match bundle.alpha_reader.read(10, ReadCondition::any()).await {
+        Err(DdsError::NoData) => (),
+        other => panic!("expired sample still available: {other:?}"),
+    }
+
+    // a sample written after the expiration is delivered
+    bundle
+        .beta_writer
+        .write(DummyStruct::new(1, &payload))
+        .await
+        .unwrap();
+    let sample = bundle.alpha_reader.read_next_sample().await.unwrap();
+    assert_eq!(sample.data().unwrap(), &DummyStruct::new(1, &payload));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn infinite_lifespan_keeps_samples(
+    #[from(setup_log)] _setup_log: (),
+    #[with("qos/lifespan/infinite")]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    bundle
+        .beta_writer
+        .write(DummyStruct::new(0, &payload))
+        .await
+        .unwrap();
+    let sample = bundle.alpha_reader.read_next_sample().await.unwrap();
+    assert_eq!(sample.data().unwrap(), &DummyStruct::new(0, &payload));
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let samples = bundle
+        .alpha_reader
+        .read(10, ReadCondition::any())
+        .await
+        .unwrap();
+    assert_eq!(samples.len(), 1);
+}
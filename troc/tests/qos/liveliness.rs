@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use chrono::DateTime;
+use rstest::*;
+use troc::{
+    DataReaderListener, DurationKind, LivelinessKind, LivelinessQosPolicy, QosPolicy, Timestamp,
+    TopicKind,
+};
+
+use crate::fixture::{
+    DummyStruct, TwoParticipantsBundle, build_payload, build_qos, setup_log, two_participants,
+};
+
+fn liveliness(kind: LivelinessKind, lease_duration: Duration) -> LivelinessQosPolicy {
+    LivelinessQosPolicy {
+        kind,
+        lease_duration: Timestamp::from_datetime(
+            DateTime::from_timestamp_millis(lease_duration.as_millis() as i64).unwrap(),
+        ),
+    }
+}
+
+async fn wait_not_alive_count(listener: &mut DataReaderListener, not_alive_count: i32) {
+    loop {
+        let status = listener
+            .wait_liveliness_changed(DurationKind::Finite(Duration::from_secs(3)))
+            .await
+            .unwrap();
+        if status.not_alive_count == not_alive_count {
+            break;
+        }
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn manual_by_topic_liveliness(
+    #[from(setup_log)] _setup_log: (),
+    #[from(build_qos)]
+    #[with(
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        liveliness(LivelinessKind::ManualByTopic, Duration::from_millis(500))
+    )]
+    _qos: QosPolicy,
+    #[with("qos/liveliness/manual_by_topic", TopicKind::NoKey, _qos.clone(), _qos.clone())]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    reader_listener
+        .wait_publication_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    bundle
+        .beta_writer
+        .write(DummyStruct::new(0, &payload))
+        .await
+        .unwrap();
+
+    // nothing asserts the liveliness after the write
+    let lost = writer_listener
+        .wait_liveliness_lost(DurationKind::Finite(Duration::from_secs(3)))
+        .await
+        .unwrap();
+    assert_eq!(lost.total_count, 1);
+    wait_not_alive_count(&mut reader_listener, 1).await;
+
+    bundle.beta_writer.assert_liveliness().await.unwrap();
+    wait_not_alive_count(&mut reader_listener, 0).await;
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn manual_by_participant_liveliness(
+    #[from(setup_log)] _setup_log: (),
+    #[from(build_qos)]
+    #[with(
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        liveliness(LivelinessKind::ManualByParticipant, Duration::from_millis(500))
+    )]
+    _qos: QosPolicy,
+    #[with("qos/liveliness/manual_by_participant", TopicKind::NoKey, _qos.clone(), _qos.clone())]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+) {
+    let bundle = two_participants.await;
+
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    reader_listener
+        .wait_publication_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    wait_not_alive_count(&mut reader_listener, 1).await;
+
+    bundle
+        .beta_domain_participant
+        .assert_liveliness()
+        .await
+        .unwrap();
+    wait_not_alive_count(&mut reader_listener, 0).await;
+}
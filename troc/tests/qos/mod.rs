@@ -0,0 +1,13 @@
+mod deadline;
+mod destination_order;
+mod durability;
+mod lifespan;
+mod liveliness;
+mod ownership;
+mod partition;
+mod presentation;
+mod profile;
+mod resource_limits;
+mod set_qos;
+mod time_based_filter;
+mod user_data;
@@ -0,0 +1,109 @@
+use std::time::{Duration, Instant};
+
+use chrono::DateTime;
+use rstest::*;
+use troc::{
+    DataReader, DurationKind, LivelinessKind, LivelinessQosPolicy, OwnershipQosPolicy,
+    OwnershipStrengthQosPolicy, QosPolicy, QosPolicyBuilder, ReadCondition, ReliabilityQosPolicy,
+    Timestamp, TopicKind,
+};
+
+use crate::fixture::{
+    DummyStruct, TwoParticipantsBundle, build_test_topic, setup_log, two_participants,
+};
+
+fn exclusive_qos(strength: i32) -> QosPolicy {
+    QosPolicyBuilder::new()
+        .reliability(ReliabilityQosPolicy::Reliable {
+            max_blocking_time: Default::default(),
+        })
+        .liveness(LivelinessQosPolicy {
+            kind: LivelinessKind::ManualByTopic,
+            lease_duration: Timestamp::from_datetime(DateTime::from_timestamp_millis(500).unwrap()),
+        })
+        .ownership(OwnershipQosPolicy::Exclusive)
+        .ownership_strength(OwnershipStrengthQosPolicy { value: strength })
+        .build()
+}
+
+/// Take the contents of the samples received within `period`, as soon as there is at least one
+async fn take_contents(reader: &mut DataReader<DummyStruct>, period: Duration) -> Vec<Vec<u8>> {
+    let start = Instant::now();
+    loop {
+        let samples = reader
+            .take(10, ReadCondition::any())
+            .await
+            .unwrap_or_default();
+        if !samples.is_empty() || start.elapsed() >= period {
+            return samples
+                .into_iter()
+                .filter_map(|s| s.data().map(|data| data.content.clone()))
+                .collect();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn exclusive_owner_fail_over(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "qos/ownership/fail_over",
+        TopicKind::NoKey,
+        exclusive_qos(0),
+        exclusive_qos(10)
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+) {
+    let mut bundle = two_participants.await;
+
+    let weak_qos = exclusive_qos(1);
+    let topic = bundle.beta_domain_participant.create_topic::<DummyStruct>(
+        build_test_topic("qos/ownership/fail_over"),
+        "DummyStruct",
+        &weak_qos,
+        TopicKind::NoKey,
+    );
+    let mut weak_writer = bundle
+        .beta_publisher
+        .create_datawriter::<DummyStruct>(&topic, &weak_qos)
+        .await
+        .unwrap();
+
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    for _ in 0..2 {
+        reader_listener
+            .wait_publication_matched(DurationKind::Infinite)
+            .await
+            .unwrap();
+    }
+
+    // every sample belongs to the same instance
+    bundle
+        .beta_writer
+        .write(DummyStruct::new(0, b"strong"))
+        .await
+        .unwrap();
+    let contents = take_contents(&mut bundle.alpha_reader, Duration::from_secs(3)).await;
+    assert_eq!(contents, vec![b"strong".to_vec()]);
+
+    // the stronger Writer owns the instance
+    weak_writer
+        .write(DummyStruct::new(0, b"weak"))
+        .await
+        .unwrap();
+    let contents = take_contents(&mut bundle.alpha_reader, Duration::from_millis(300)).await;
+    assert!(contents.is_empty());
+
+    // the stronger Writer no longer asserts its liveliness
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    weak_writer
+        .write(DummyStruct::new(0, b"weak"))
+        .await
+        .unwrap();
+    let contents = take_contents(&mut bundle.alpha_reader, Duration::from_secs(3)).await;
+    assert_eq!(contents, vec![b"weak".to_vec()]);
+}
@@ -0,0 +1,191 @@
+use std::time::{Duration, Instant};
+
+use rstest::*;
+use troc::{
+    DataReader, DdsError, DurationKind, HistoryQosPolicy, PresentationAccessScope,
+    PresentationQosPolicy, QosPolicy, QosPolicyBuilder, ReadCondition, ReliabilityQosPolicy,
+    TopicKind,
+};
+
+use crate::fixture::{
+    DummyStruct, TwoParticipantsBundle, build_payload, build_test_topic, setup_log,
+    two_participants,
+};
+
+fn coherent_qos(access_scope: PresentationAccessScope, coherent_access: bool) -> QosPolicy {
+    QosPolicyBuilder::new()
+        .reliability(ReliabilityQosPolicy::Reliable {
+            max_blocking_time: Default::default(),
+        })
+        .history(HistoryQosPolicy::KeepLast { depth: 10 })
+        .presentation(PresentationQosPolicy {
+            access_scope,
+            coherent_access,
+            ordered_access: false,
+        })
+        .build()
+}
+
+/// Take the samples received within `period`, as soon as there is at least one
+async fn take_samples(reader: &mut DataReader<DummyStruct>, period: Duration) -> Vec<DummyStruct> {
+    let start = Instant::now();
+    loop {
+        let samples = reader
+            .take(10, ReadCondition::any())
+            .await
+            .unwrap_or_default();
+        if !samples.is_empty() || start.elapsed() >= period {
+            return samples
+                .into_iter()
+                .filter_map(|s| s.data().cloned())
+                .collect();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn coherent_set_is_delivered_once_ended(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "qos/presentation/coherent_set",
+        TopicKind::NoKey,
+        coherent_qos(PresentationAccessScope::Topic, true),
+        coherent_qos(PresentationAccessScope::Topic, true)
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    bundle
+        .beta_publisher
+        .begin_coherent_changes()
+        .await
+        .unwrap();
+    for i in 0..2 {
+        bundle
+            .beta_writer
+            .write(DummyStruct::new(i, &payload))
+            .await
+            .unwrap();
+    }
+
+    // the set is held back until it is ended
+    let samples = take_samples(&mut bundle.alpha_reader, Duration::from_millis(500)).await;
+    assert!(samples.is_empty());
+
+    bundle.beta_publisher.end_coherent_changes().await.unwrap();
+    let samples = take_samples(&mut bundle.alpha_reader, Duration::from_secs(3)).await;
+    assert_eq!(
+        samples,
+        vec![DummyStruct::new(0, &payload), DummyStruct::new(1, &payload)]
+    );
+
+    let res = bundle.beta_publisher.end_coherent_changes().await;
+    assert!(matches!(res, Err(DdsError::PreconditionNotMet)));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn coherent_access_not_offered_does_not_match(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "qos/presentation/not_offered",
+        TopicKind::NoKey,
+        coherent_qos(PresentationAccessScope::Topic, true),
+        coherent_qos(PresentationAccessScope::Topic, false)
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+) {
+    let bundle = two_participants.await;
+
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    let res = reader_listener
+        .wait_publication_matched(DurationKind::Finite(Duration::from_secs(3)))
+        .await;
+    assert!(res.is_err());
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(20))]
+#[tokio::test]
+async fn group_coherent_set_is_delivered_once_ended(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "qos/presentation/group_coherent_set",
+        TopicKind::NoKey,
+        coherent_qos(PresentationAccessScope::Group, true),
+        coherent_qos(PresentationAccessScope::Group, true)
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    // a second Topic written by the same Publisher and read by the same Subscriber
+    let qos = coherent_qos(PresentationAccessScope::Group, true);
+    let topic = bundle.alpha_domain_participant.create_topic(
+        build_test_topic("qos/presentation/group_coherent_set/other"),
+        "DummyStruct",
+        &qos,
+        TopicKind::NoKey,
+    );
+    let mut other_reader = bundle
+        .alpha_subscriber
+        .create_datareader::<DummyStruct>(&topic, &qos)
+        .await
+        .unwrap();
+    let mut other_writer = bundle
+        .beta_publisher
+        .create_datawriter::<DummyStruct>(&topic, &qos)
+        .await
+        .unwrap();
+
+    for writer in [&bundle.beta_writer, &other_writer] {
+        let mut writer_listener = writer.get_listener().await.unwrap();
+        writer_listener
+            .wait_subscription_matched(DurationKind::Infinite)
+            .await
+            .unwrap();
+    }
+
+    bundle
+        .beta_publisher
+        .begin_coherent_changes()
+        .await
+        .unwrap();
+    bundle
+        .beta_writer
+        .write(DummyStruct::new(0, &payload))
+        .await
+        .unwrap();
+    other_writer
+        .write(DummyStruct::new(1, &payload))
+        .await
+        .unwrap();
+
+    // the set is held back on both Topics until it is ended
+    let samples = take_samples(&mut bundle.alpha_reader, Duration::from_millis(500)).await;
+    assert!(samples.is_empty());
+    let samples = take_samples(&mut other_reader, Duration::from_millis(100)).await;
+    assert!(samples.is_empty());
+
+    bundle.beta_publisher.end_coherent_changes().await.unwrap();
+    let samples = take_samples(&mut bundle.alpha_reader, Duration::from_secs(3)).await;
+    assert_eq!(samples, vec![DummyStruct::new(0, &payload)]);
+    let samples = take_samples(&mut other_reader, Duration::from_secs(3)).await;
+    assert_eq!(samples, vec![DummyStruct::new(1, &payload)]);
+}
@@ -0,0 +1,183 @@
+use std::{str::FromStr, time::Duration};
+
+use rstest::*;
+use troc::{
+    Configuration, DomainParticipant, DomainParticipantBuilder, DomainTag, DurationKind, Guid,
+    QosProfiles, TopicKind,
+};
+
+use crate::fixture::{
+    DummyStruct, build_payload, build_test_topic, get_guid, get_unique_id, setup_log,
+};
+
+const PROFILES: &str = r#"
+[test.reliable]
+reliability = { Reliable = { max_blocking_time = { secs = 1, nanos = 0 } } }
+history = { KeepLast = { depth = 10 } }
+partition = ["ops"]
+
+[test.sensor]
+base_name = "reliable"
+
+[test.sensor.topics."*/best_effort"]
+reliability = "BestEffort"
+"#;
+
+/// Two participants sharing the profiles of [`PROFILES`], loaded from a file
+async fn profile_participants(
+    file_name: &str,
+    alpha_guid: Guid,
+    beta_guid: Guid,
+    unique_id: &str,
+) -> (DomainParticipant, DomainParticipant) {
+    let path = std::env::temp_dir().join(format!("{}-{file_name}", std::process::id()));
+    std::fs::write(&path, PROFILES).unwrap();
+    let qos_profiles = QosProfiles::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let mut configuration = Configuration::default();
+    configuration.global.domain_tag = DomainTag::from_str(unique_id).unwrap();
+    configuration.discovery.announcement_period = Duration::from_secs(1);
+
+    let alpha_domain_participant = DomainParticipantBuilder::new()
+        .with_guid(alpha_guid)
+        .with_qos_profiles(qos_profiles.clone())
+        .with_config(configuration.clone())
+        .build()
+        .await
+        .unwrap();
+    let beta_domain_participant = DomainParticipantBuilder::new()
+        .with_guid(beta_guid)
+        .with_qos_profiles(qos_profiles)
+        .with_config(configuration)
+        .build()
+        .await
+        .unwrap();
+    (alpha_domain_participant, beta_domain_participant)
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn endpoints_created_with_profiles_communicate(
+    #[from(setup_log)] _setup_log: (),
+    #[from(get_guid)] alpha_guid: Guid,
+    #[from(get_guid)]
+    #[with(1)]
+    beta_guid: Guid,
+    #[from(get_unique_id)] unique_id: String,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let (mut alpha_domain_participant, mut beta_domain_participant) =
+        profile_participants("communicate.toml", alpha_guid, beta_guid, &unique_id).await;
+    let topic_name = build_test_topic("qos/profile/communicate");
+
+    let topic = alpha_domain_participant
+        .create_topic_with_profile::<DummyStruct>(
+            &topic_name,
+            "DummyStruct",
+            "test::reliable",
+            TopicKind::NoKey,
+        )
+        .unwrap();
+    let mut subscriber = alpha_domain_participant
+        .create_subscriber_with_profile("test::reliable")
+        .await
+        .unwrap();
+    let mut reader = subscriber
+        .create_datareader_with_profile(&topic, "test::reliable")
+        .await
+        .unwrap();
+
+    let topic = beta_domain_participant
+        .create_topic_with_profile::<DummyStruct>(
+            &topic_name,
+            "DummyStruct",
+            "test::sensor",
+            TopicKind::NoKey,
+        )
+        .unwrap();
+    let mut publisher = beta_domain_participant
+        .create_publisher_with_profile("test::sensor")
+        .await
+        .unwrap();
+    let mut writer = publisher
+        .create_datawriter_with_profile(&topic, "test::sensor")
+        .await
+        .unwrap();
+
+    let mut writer_listener = writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    writer.write(DummyStruct::new(0, &payload)).await.unwrap();
+    let sample = reader
+        .read_next_sample_timeout(Duration::from_secs(3))
+        .await
+        .unwrap();
+    assert_eq!(sample.data.unwrap(), DummyStruct::new(0, &payload));
+
+    let res = alpha_domain_participant
+        .create_subscriber_with_profile("test::unknown")
+        .await;
+    assert!(res.is_err());
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn topic_pattern_overrides_profile(
+    #[from(setup_log)] _setup_log: (),
+    #[from(get_guid)] alpha_guid: Guid,
+    #[from(get_guid)]
+    #[with(1)]
+    beta_guid: Guid,
+    #[from(get_unique_id)] unique_id: String,
+) {
+    let (mut alpha_domain_participant, mut beta_domain_participant) =
+        profile_participants("best_effort.toml", alpha_guid, beta_guid, &unique_id).await;
+    let topic_name = build_test_topic("qos/profile/best_effort");
+
+    let topic = alpha_domain_participant
+        .create_topic_with_profile::<DummyStruct>(
+            &topic_name,
+            "DummyStruct",
+            "test::reliable",
+            TopicKind::NoKey,
+        )
+        .unwrap();
+    let mut subscriber = alpha_domain_participant
+        .create_subscriber_with_profile("test::reliable")
+        .await
+        .unwrap();
+    let reader = subscriber
+        .create_datareader_with_profile(&topic, "test::reliable")
+        .await
+        .unwrap();
+
+    // the DataWriter of this topic is BEST_EFFORT, it cannot serve a RELIABLE DataReader
+    let topic = beta_domain_participant
+        .create_topic_with_profile::<DummyStruct>(
+            &topic_name,
+            "DummyStruct",
+            "test::sensor",
+            TopicKind::NoKey,
+        )
+        .unwrap();
+    let mut publisher = beta_domain_participant
+        .create_publisher_with_profile("test::sensor")
+        .await
+        .unwrap();
+    let _writer = publisher
+        .create_datawriter_with_profile(&topic, "test::sensor")
+        .await
+        .unwrap();
+
+    let mut reader_listener = reader.get_listener().await.unwrap();
+    let res = reader_listener
+        .wait_publication_matched(DurationKind::Finite(Duration::from_secs(3)))
+        .await;
+    assert!(res.is_err());
+}
@@ -0,0 +1,157 @@
+use std::time::{Duration, Instant};
+
+use chrono::DateTime;
+use rstest::*;
+use troc::{
+    DdsError, DurationKind, HistoryQosPolicy, QosPolicy, QosPolicyBuilder, ReliabilityQosPolicy,
+    ResourceLimitsQosPolicy, SampleRejectedStatusKind, Timestamp, TopicKind,
+};
+
+use crate::fixture::{
+    DummyStruct, TwoParticipantsBundle, build_payload, build_test_topic, setup_log,
+    two_participants,
+};
+
+fn limited_qos(history: HistoryQosPolicy, max_samples: u32) -> QosPolicy {
+    QosPolicyBuilder::new()
+        .reliability(ReliabilityQosPolicy::Reliable {
+            max_blocking_time: Timestamp::from_datetime(
+                DateTime::from_timestamp_millis(200).unwrap(),
+            ),
+        })
+        .history(history)
+        .resource_limits(ResourceLimitsQosPolicy {
+            max_samples,
+            max_instances: 1,
+            max_samples_per_instance: max_samples,
+        })
+        .build()
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn limits_lower_than_history_depth_are_inconsistent(
+    #[from(setup_log)] _setup_log: (),
+    #[with("qos/resource_limits/inconsistent")]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+) {
+    let mut bundle = two_participants.await;
+    let qos = limited_qos(HistoryQosPolicy::KeepLast { depth: 5 }, 2);
+    let topic = bundle.beta_domain_participant.create_topic::<DummyStruct>(
+        build_test_topic("qos/resource_limits/inconsistent"),
+        "DummyStruct",
+        &qos,
+        TopicKind::NoKey,
+    );
+
+    let res = bundle
+        .beta_publisher
+        .create_datawriter::<DummyStruct>(&topic, &qos)
+        .await;
+    assert!(matches!(res, Err(DdsError::InconsistentPolicy)));
+
+    // a KEEP_LAST history keeps at least one sample
+    let qos = limited_qos(HistoryQosPolicy::KeepLast { depth: 0 }, 2);
+    let res = bundle
+        .beta_publisher
+        .create_datawriter::<DummyStruct>(&topic, &qos)
+        .await;
+    assert!(matches!(res, Err(DdsError::InconsistentPolicy)));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn full_writer_times_out(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "qos/resource_limits/full_writer",
+        TopicKind::NoKey,
+        limited_qos(HistoryQosPolicy::KeepAll, 1),
+        limited_qos(HistoryQosPolicy::KeepAll, 2)
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    // the DataReader is never read, once full it no longer acknowledges the samples
+    // and the DataWriter can't remove them from its history
+    let mut res = Ok(());
+    let mut start = Instant::now();
+    for _ in 0..5 {
+        start = Instant::now();
+        res = bundle
+            .beta_writer
+            .write(DummyStruct::new(0, &payload))
+            .await;
+        if res.is_err() {
+            break;
+        }
+    }
+    assert!(matches!(res, Err(DdsError::Timeout { .. })));
+    assert!(start.elapsed() >= Duration::from_millis(200));
+
+    let res = bundle
+        .beta_writer
+        .wait_for_acknowledgments(DurationKind::Finite(Duration::from_millis(200)))
+        .await;
+    assert!(matches!(res, Err(DdsError::Timeout { .. })));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn full_reader_rejects_samples(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "qos/resource_limits/full_reader",
+        TopicKind::NoKey,
+        limited_qos(HistoryQosPolicy::KeepAll, 1),
+        limited_qos(HistoryQosPolicy::KeepAll, 10)
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    reader_listener
+        .wait_publication_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    // every sample belongs to the same instance
+    for _ in 0..2 {
+        bundle
+            .beta_writer
+            .write(DummyStruct::new(0, &payload))
+            .await
+            .unwrap();
+    }
+
+    let reason = reader_listener
+        .wait_sample_rejected(DurationKind::Finite(Duration::from_secs(5)))
+        .await
+        .unwrap();
+    assert_eq!(
+        reason,
+        SampleRejectedStatusKind::RejectedBySamplesPerInstanceLimit
+    );
+}
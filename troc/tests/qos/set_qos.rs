@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use rstest::*;
+use troc::{
+    DdsError, DurationKind, HistoryQosPolicy, PartitionQosPolicy, PresentationAccessScope,
+    PresentationQosPolicy, QosPolicy, QosPolicyBuilder, TopicKind, UserDataQosPolicy,
+};
+
+use crate::fixture::{
+    DummyStruct, TwoParticipantsBundle, build_payload, setup_log, two_participants,
+};
+
+fn partition_qos(name: &str) -> QosPolicy {
+    QosPolicyBuilder::new()
+        .partition(PartitionQosPolicy::new([name]))
+        .build()
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn immutable_policies_are_rejected(
+    #[from(setup_log)] _setup_log: (),
+    #[with("qos/set_qos/immutable")]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+) {
+    let mut bundle = two_participants.await;
+
+    let res = bundle
+        .beta_writer
+        .set_qos(
+            &QosPolicyBuilder::new()
+                .history(HistoryQosPolicy::KeepAll)
+                .build(),
+        )
+        .await;
+    assert!(matches!(res, Err(DdsError::ImmutablePolicy)));
+
+    let res = bundle
+        .beta_publisher
+        .set_qos(
+            &QosPolicyBuilder::new()
+                .presentation(PresentationQosPolicy {
+                    access_scope: PresentationAccessScope::Topic,
+                    coherent_access: true,
+                    ordered_access: false,
+                })
+                .build(),
+        )
+        .await;
+    assert!(matches!(res, Err(DdsError::ImmutablePolicy)));
+
+    // the USER_DATA can change at any time
+    let res = bundle
+        .alpha_reader
+        .set_qos(
+            &QosPolicyBuilder::new()
+                .user_data(UserDataQosPolicy::new(b"v2".as_slice()))
+                .build(),
+        )
+        .await;
+    assert!(res.is_ok());
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(20))]
+#[tokio::test]
+async fn partition_change_rematches(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "qos/set_qos/partition",
+        TopicKind::NoKey,
+        partition_qos("tenant-a"),
+        partition_qos("tenant-a")
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    // the DataWriter leaves the partition of the DataReader
+    bundle
+        .beta_publisher
+        .set_qos(&partition_qos("tenant-b"))
+        .await
+        .unwrap();
+    let writer_guid = reader_listener
+        .wait_publication_stopped(DurationKind::Finite(Duration::from_secs(5)))
+        .await
+        .unwrap();
+    assert_eq!(writer_guid, bundle.beta_writer.get_guid());
+    writer_listener
+        .wait_subscription_stopped(DurationKind::Finite(Duration::from_secs(5)))
+        .await
+        .unwrap();
+
+    // then the DataReader joins it
+    bundle
+        .alpha_subscriber
+        .set_qos(&partition_qos("tenant-b"))
+        .await
+        .unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Finite(Duration::from_secs(5)))
+        .await
+        .unwrap();
+
+    bundle
+        .beta_writer
+        .write(DummyStruct::new(0, &payload))
+        .await
+        .unwrap();
+    let sample = bundle
+        .alpha_reader
+        .read_next_sample_timeout(Duration::from_secs(3))
+        .await
+        .unwrap();
+    assert_eq!(sample.data.unwrap(), DummyStruct::new(0, &payload));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn participant_user_data_change_is_announced(
+    #[from(setup_log)] _setup_log: (),
+    #[with("qos/set_qos/user_data")]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut listener = bundle
+        .alpha_domain_participant
+        .get_listener()
+        .await
+        .unwrap();
+    let mut reader_listener = bundle.alpha_reader.get_listener().await.unwrap();
+    reader_listener
+        .wait_publication_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    bundle
+        .beta_domain_participant
+        .set_qos(
+            &QosPolicyBuilder::new()
+                .user_data(UserDataQosPolicy::new(b"build 2".as_slice()))
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    let participant_proxy = listener
+        .wait_participant_update(DurationKind::Finite(Duration::from_secs(5)))
+        .await
+        .unwrap();
+    assert_eq!(participant_proxy.user_data.value, b"build 2");
+}
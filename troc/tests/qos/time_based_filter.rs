@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use chrono::DateTime;
+use rstest::*;
+use troc::{
+    DdsError, DeadlineQosPolicy, DurationKind, HistoryQosPolicy, QosPolicy, QosPolicyBuilder,
+    ReadCondition, ReliabilityQosPolicy, TimeBasedFilterQosPolicy, Timestamp, TopicKind,
+};
+
+use crate::fixture::{
+    DummyStruct, TwoParticipantsBundle, build_payload, build_test_topic, reliable_qos, setup_log,
+    two_participants,
+};
+
+fn filtered_qos(minimum_separation_ms: i64) -> QosPolicy {
+    QosPolicyBuilder::new()
+        .reliability(ReliabilityQosPolicy::Reliable {
+            max_blocking_time: Default::default(),
+        })
+        .history(HistoryQosPolicy::KeepLast { depth: 10 })
+        .time_based_filter(TimeBasedFilterQosPolicy {
+            minimum_separation: Timestamp::from_datetime(
+                DateTime::from_timestamp_millis(minimum_separation_ms).unwrap(),
+            ),
+        })
+        .build()
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn samples_within_minimum_separation_are_filtered(
+    #[from(setup_log)] _setup_log: (),
+    #[with(
+        "qos/time_based_filter/filtered",
+        TopicKind::NoKey,
+        filtered_qos(2_000),
+        reliable_qos()
+    )]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+    #[from(build_payload)] payload: Vec<u8>,
+) {
+    let mut bundle = two_participants.await;
+
+    let mut writer_listener = bundle.beta_writer.get_listener().await.unwrap();
+    writer_listener
+        .wait_subscription_matched(DurationKind::Infinite)
+        .await
+        .unwrap();
+
+    // every sample belongs to the same instance
+    for _ in 0..5 {
+        bundle
+            .beta_writer
+            .write(DummyStruct::new(0, &payload))
+            .await
+            .unwrap();
+    }
+
+    let mut filtered = 0;
+    for _ in 0..60 {
+        filtered = bundle
+            .alpha_reader
+            .get_filtered_sample_count()
+            .await
+            .unwrap();
+        if filtered == 4 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(filtered, 4);
+
+    let samples = bundle
+        .alpha_reader
+        .take(10, ReadCondition::any())
+        .await
+        .unwrap_or_default();
+    assert_eq!(samples.len(), 1);
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn minimum_separation_longer_than_deadline_is_inconsistent(
+    #[from(setup_log)] _setup_log: (),
+    #[with("qos/time_based_filter/inconsistent")]
+    #[future]
+    two_participants: TwoParticipantsBundle,
+) {
+    let mut bundle = two_participants.await;
+    let qos = QosPolicyBuilder::new()
+        .deadline(DeadlineQosPolicy {
+            period: Timestamp::from_datetime(DateTime::from_timestamp_millis(100).unwrap()),
+        })
+        .time_based_filter(TimeBasedFilterQosPolicy {
+            minimum_separation: Timestamp::from_datetime(
+                DateTime::from_timestamp_millis(500).unwrap(),
+            ),
+        })
+        .build();
+    let topic = bundle.alpha_domain_participant.create_topic::<DummyStruct>(
+        build_test_topic("qos/time_based_filter/inconsistent"),
+        "DummyStruct",
+        &qos,
+        TopicKind::NoKey,
+    );
+
+    let res = bundle
+        .alpha_subscriber
+        .create_datareader::<DummyStruct>(&topic, &qos)
+        .await;
+    assert!(matches!(res, Err(DdsError::InconsistentPolicy)));
+}
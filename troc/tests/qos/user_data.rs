@@ -0,0 +1,99 @@
+use std::{str::FromStr, time::Duration};
+
+use rstest::*;
+use troc::{
+    Configuration, DomainParticipantBuilder, DomainTag, DurationKind, GroupDataQosPolicy, Guid,
+    QosPolicy, QosPolicyBuilder, TopicDataQosPolicy, TopicKind, UserDataQosPolicy,
+};
+
+use crate::fixture::{DummyStruct, build_test_topic, get_guid, get_unique_id, setup_log};
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[tokio::test]
+async fn octet_policies_are_discovered(
+    #[from(setup_log)] _setup_log: (),
+    #[from(get_guid)] alpha_guid: Guid,
+    #[from(get_guid)]
+    #[with(1)]
+    beta_guid: Guid,
+    #[from(get_unique_id)] unique_id: String,
+) {
+    let mut configuration = Configuration::default();
+    configuration.global.domain_tag = DomainTag::from_str(&unique_id).unwrap();
+    configuration.discovery.announcement_period = Duration::from_secs(1);
+
+    let mut alpha_domain_participant = DomainParticipantBuilder::new()
+        .with_guid(alpha_guid)
+        .with_config(configuration.clone())
+        .build()
+        .await
+        .unwrap();
+    let mut listener = alpha_domain_participant.get_listener().await.unwrap();
+
+    let mut beta_domain_participant = DomainParticipantBuilder::new()
+        .with_guid(beta_guid)
+        .with_qos(
+            QosPolicyBuilder::new()
+                .user_data(UserDataQosPolicy::new(b"build 1.2.3".as_slice()))
+                .build(),
+        )
+        .with_config(configuration)
+        .build()
+        .await
+        .unwrap();
+
+    let participant_proxy = listener
+        .wait_participant_discovered(DurationKind::Infinite)
+        .await
+        .unwrap();
+    assert_eq!(participant_proxy.user_data.value, b"build 1.2.3");
+
+    let topic_qos = QosPolicyBuilder::new()
+        .topic_data(TopicDataQosPolicy::new(b"topic".as_slice()))
+        .build();
+    let topic = beta_domain_participant.create_topic::<DummyStruct>(
+        build_test_topic("qos/user_data/discovered"),
+        "DummyStruct",
+        &topic_qos,
+        TopicKind::NoKey,
+    );
+    let publisher_qos = QosPolicyBuilder::new()
+        .group_data(GroupDataQosPolicy::new(b"group".as_slice()))
+        .build();
+    let mut publisher = beta_domain_participant
+        .create_publisher(&publisher_qos)
+        .await
+        .unwrap();
+    let writer_qos = QosPolicyBuilder::new()
+        .user_data(UserDataQosPolicy::new(b"token".as_slice()))
+        .build();
+    let _writer = publisher
+        .create_datawriter::<DummyStruct>(&topic, &writer_qos)
+        .await
+        .unwrap();
+
+    // the remote DataWriter is discovered by a local DataReader of its Topic
+    let topic = alpha_domain_participant.create_topic::<DummyStruct>(
+        build_test_topic("qos/user_data/discovered"),
+        "DummyStruct",
+        &QosPolicy::default(),
+        TopicKind::NoKey,
+    );
+    let mut subscriber = alpha_domain_participant
+        .create_subscriber(&QosPolicy::default())
+        .await
+        .unwrap();
+    let _reader = subscriber
+        .create_datareader::<DummyStruct>(&topic, &QosPolicy::default())
+        .await
+        .unwrap();
+
+    let writer_data = listener
+        .wait_writer_discovered(DurationKind::Infinite)
+        .await
+        .unwrap();
+    assert_eq!(writer_data.params.user_data.value, b"token");
+    assert_eq!(writer_data.params.topic_data.value, b"topic");
+    assert_eq!(writer_data.params.group_data.value, b"group");
+}
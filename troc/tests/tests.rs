@@ -4,3 +4,4 @@
 mod communication;
 mod discovery;
 mod fixture;
+mod qos;